@@ -0,0 +1,136 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Benchmarks of TextEditor over a large (100k line) document
+//!
+//! Lines are laid out and drawn using [`FlatTheme`] over an [`SvgDraw`].
+//!
+//! Run with `cargo +nightly bench --features nightly,editor`.
+#![cfg_attr(feature = "nightly", feature(test))]
+
+extern crate test;
+
+use std::cell::RefCell;
+
+use kas::document::TextDocument;
+use kas::draw::SvgDraw;
+use kas::event::ManagerState;
+use kas::geom::{Coord, Rect, Size};
+use kas::layout::SolveCache;
+use kas::widget::TextEditor;
+use kas::Layout;
+use kas_theme::{FlatTheme, Theme, Window};
+use test::Bencher;
+
+const LINES: usize = 100_000;
+
+const SIZE: Size = Size(800, 1000);
+
+thread_local! {
+    // The theme loads its font into the first draw only, thus we share this
+    static DRAW: RefCell<SvgDraw> = RefCell::new(SvgDraw::new(SIZE));
+}
+
+fn document() -> TextDocument {
+    let mut text = String::new();
+    for i in 0..LINES {
+        text.push_str(&format!(
+            "Line {}: the quick brown fox jumps over the lazy dog\n",
+            i
+        ));
+    }
+    TextDocument::new(&text)
+}
+
+/// A view of the start of a document
+struct View {
+    theme: FlatTheme,
+    window: <FlatTheme as Theme<SvgDraw>>::Window,
+    mgr: ManagerState,
+    editor: TextEditor,
+}
+
+impl View {
+    fn new(doc: &TextDocument) -> Self {
+        DRAW.with(|draw| {
+            let draw = &mut *draw.borrow_mut();
+            let mut theme = FlatTheme::new();
+            theme.init(draw);
+            let mut window = Theme::<SvgDraw>::new_window(&theme, draw, 1.0);
+            let mut editor = TextEditor::new(doc.clone());
+            let rect = Rect::new(Coord::ZERO, SIZE);
+            let mut size_handle = unsafe { window.size_handle(draw) };
+            let mut solve = SolveCache::find_constraints(&mut editor, &mut size_handle);
+            solve.apply_rect(&mut editor, &mut size_handle, rect, true);
+            drop(size_handle);
+
+            View {
+                theme,
+                window,
+                mgr: ManagerState::new(1.0),
+                editor,
+            }
+        })
+    }
+
+    /// Draw, laying out lines where not cached
+    fn draw(&mut self) {
+        DRAW.with(|draw| {
+            let draw = &mut *draw.borrow_mut();
+            draw.clear(SIZE);
+            let rect = Rect::new(Coord::ZERO, SIZE);
+            let theme = &self.theme;
+            let mut draw_handle =
+                unsafe { Theme::<SvgDraw>::draw_handle(theme, draw, &mut self.window, rect) };
+            self.editor.draw(&mut draw_handle, &self.mgr, false);
+        });
+    }
+}
+
+#[bench]
+fn load(b: &mut Bencher) {
+    let doc = document();
+    let text = doc.text();
+    b.iter(|| TextDocument::new(&text));
+}
+
+#[bench]
+fn draw_unchanged(b: &mut Bencher) {
+    let doc = document();
+    let mut view = View::new(&doc);
+    b.iter(|| view.draw());
+}
+
+#[bench]
+fn edit_off_screen(b: &mut Bencher) {
+    let doc = document();
+    let mut view = View::new(&doc);
+    let pos = doc.line_start(LINES / 2);
+    b.iter(|| {
+        doc.insert(pos, "x");
+        view.draw();
+    });
+}
+
+#[bench]
+fn edit_first_line(b: &mut Bencher) {
+    let doc = document();
+    let mut view = View::new(&doc);
+    b.iter(|| {
+        doc.insert(0, "x");
+        view.draw();
+    });
+}
+
+#[bench]
+fn insert_line(b: &mut Bencher) {
+    let doc = document();
+    let mut view = View::new(&doc);
+    let pos = doc.line_start(10);
+    b.iter(|| {
+        doc.insert(pos, "\n");
+        view.draw();
+    });
+}
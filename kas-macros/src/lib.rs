@@ -261,13 +261,13 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             for child in args.children.iter() {
                 let ident = &child.ident;
                 let handler = if let Some(ref h) = child.args.handler {
-                    quote! { r.try_into().unwrap_or_else(|msg| self.#h(mgr, msg)) }
+                    quote! { r.try_into().unwrap_or_else(|(msg, _)| self.#h(mgr, msg)) }
                 } else {
                     quote! { r.into() }
                 };
                 ev_to_num.append_all(quote! {
                     if id <= self.#ident.id() {
-                        let r = self.#ident.send(mgr, id, event);
+                        let r = self.#ident.send(mgr, id, event).apply_action(mgr);
                         #handler
                     } else
                 });
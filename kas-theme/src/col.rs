@@ -6,11 +6,27 @@
 //! Colour schemes
 
 use log::warn;
+#[cfg(feature = "config")]
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[cfg(feature = "config")]
+use crate::config::{read_file, ConfigError};
 
 use kas::draw::{Colour, InputState};
 
 /// Provides standard theme colours
-#[derive(Clone, Debug)]
+///
+/// With feature `config`, a colour scheme may be loaded from a file; see
+/// [`ThemeColours::from_file`]. Colours are specified as linear RGBA. Example
+/// (TOML):
+/// ```toml
+/// background = { r = 0.2, g = 0.2, b = 0.2, a = 1.0 }
+/// frame = { r = 0.4, g = 0.4, b = 0.4, a = 1.0 }
+/// # ... (all fields are required)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
 pub struct ThemeColours {
     /// Background colour
     pub background: Colour,
@@ -38,7 +54,7 @@ pub struct ThemeColours {
     pub button_highlighted: Colour,
     /// Colour of a `TextButton` when depressed
     pub button_depressed: Colour,
-    /// Colour of mark within a `CheckBox` or `RadioBox`
+    /// Colour of mark within a `CheckBox` or `RadioBox` and of progress bars
     pub checkbox: Colour,
 }
 
@@ -59,6 +75,46 @@ impl ThemeColours {
         })
     }
 
+    /// Load a scheme from a file
+    ///
+    /// The file format is selected by extension: `.json` (requires feature
+    /// `json`), `.toml` (requires feature `toml`), otherwise
+    /// [RON](https://github.com/ron-rs/ron).
+    ///
+    /// **Feature gated**: this is only available with feature `config`.
+    #[cfg(feature = "config")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        read_file(path.as_ref())
+    }
+
+    /// Load the scheme from a file, if possible
+    ///
+    /// This is a wrapper around [`ThemeColours::from_file`] which logs a
+    /// warning on failure. Without feature `config`, it always fails.
+    pub fn load(path: &Path) -> Option<Self> {
+        #[cfg(feature = "config")]
+        match Self::from_file(path) {
+            Ok(scheme) => Some(scheme),
+            Err(e) => {
+                warn!(
+                    "ThemeColours::load: failed to load {}: {}",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+
+        #[cfg(not(feature = "config"))]
+        {
+            warn!(
+                "ThemeColours::load: unable to load {}: requires feature \"config\"",
+                path.display()
+            );
+            None
+        }
+    }
+
     /// Default theme: grey with blue activable items
     pub fn new() -> Self {
         ThemeColours {
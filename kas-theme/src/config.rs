@@ -0,0 +1,219 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Theme configuration
+
+#[cfg(feature = "config")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "config")]
+use std::path::Path;
+
+use crate::{DimensionsParams, ThemeColours};
+use kas::draw::TextRendering;
+use kas::ThemeAction;
+
+/// Parameterisation of element shapes
+///
+/// Radii are relative: each is the inner radius of a rounded frame as a
+/// fraction of its outer radius. Normals are `(outer, inner)` pairs describing
+/// the slope of shaded frames; see `kas::draw::DrawShaded`.
+///
+/// Themes use only those parameters applicable to their drawing style.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub struct ShapeParams {
+    /// Inner radius of frames and separators
+    pub frame_radius: f32,
+    /// Inner radius of edit-box frames
+    pub edit_radius: f32,
+    /// Inner radius of buttons, handles and tracks
+    pub button_radius: f32,
+    /// Inner radius of navigation-focus highlights
+    pub nav_radius: f32,
+    /// Normals of outer frames
+    pub frame_norm: (f32, f32),
+    /// Normals of raised elements (buttons and handles)
+    pub raised_norm: (f32, f32),
+    /// Normals of sunken elements (separators and tracks)
+    pub sunken_norm: (f32, f32),
+    /// Normals of edit-box frames
+    pub edit_norm: (f32, f32),
+}
+
+/// A theme parameterisation
+///
+/// All fields are optional; themes use their own values for omitted fields.
+/// A config may be applied to a theme via [`crate::Theme::apply_config`].
+///
+/// With feature `config`, this type may be loaded from (and saved to) a
+/// [RON](https://github.com/ron-rs/ron) file; JSON and TOML files may also
+/// be loaded (see [`ThemeConfig::from_file`]). Example:
+/// ```ron
+/// (
+///     font_size: Some(16.0),
+///     dimensions: Some((
+///         margin: 2.0,
+///         frame_size: 4.0,
+///         button_frame: 6.0,
+///         scrollbar_size: (8.0, 8.0),
+///         slider_size: (12.0, 25.0),
+///         scroll_lines: 3.0,
+///     )),
+///     text_rendering: Some((hinting: true)),
+/// )
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub struct ThemeConfig {
+    /// Font size
+    #[cfg_attr(feature = "config", serde(default))]
+    pub font_size: Option<f32>,
+    /// Colour scheme
+    #[cfg_attr(feature = "config", serde(default))]
+    pub colours: Option<ThemeColours>,
+    /// Element dimensions
+    #[cfg_attr(feature = "config", serde(default))]
+    pub dimensions: Option<DimensionsParams>,
+    /// Element shapes
+    #[cfg_attr(feature = "config", serde(default))]
+    pub shapes: Option<ShapeParams>,
+    /// Text rendering options
+    #[cfg_attr(feature = "config", serde(default))]
+    pub text_rendering: Option<TextRendering>,
+}
+
+/// Error loading or saving a [`ThemeConfig`]
+///
+/// **Feature gated**: this is only available with feature `config`.
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub enum ConfigError {
+    /// File IO error
+    Io(std::io::Error),
+    /// RON parse or serialisation error
+    Ron(ron::Error),
+    /// JSON parse error
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// TOML parse error
+    #[cfg(feature = "toml")]
+    Toml(toml_::de::Error),
+    /// The file format (given by its extension) is not supported
+    ///
+    /// This is reported for `.json` and `.toml` files when the corresponding
+    /// feature is not enabled.
+    Unsupported(String),
+}
+
+#[cfg(feature = "config")]
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ConfigError::Io(e) => write!(f, "ThemeConfig: IO error: {}", e),
+            ConfigError::Ron(e) => write!(f, "ThemeConfig: RON error: {}", e),
+            #[cfg(feature = "json")]
+            ConfigError::Json(e) => write!(f, "ThemeConfig: JSON error: {}", e),
+            #[cfg(feature = "toml")]
+            ConfigError::Toml(e) => write!(f, "ThemeConfig: TOML error: {}", e),
+            ConfigError::Unsupported(ext) => {
+                write!(
+                    f,
+                    "ThemeConfig: unsupported format: {} (feature required)",
+                    ext
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl std::error::Error for ConfigError {}
+
+/// Read a file, with format selected by extension
+///
+/// Extensions `json` and `toml` select JSON and TOML (each requiring the
+/// feature of the same name); anything else is read as RON.
+#[cfg(feature = "config")]
+pub(crate) fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T, ConfigError> {
+    let text = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "json")]
+        Some("json") => serde_json::from_str(&text).map_err(ConfigError::Json),
+        #[cfg(not(feature = "json"))]
+        Some("json") => Err(ConfigError::Unsupported("json".to_string())),
+        #[cfg(feature = "toml")]
+        Some("toml") => toml_::from_str(&text).map_err(ConfigError::Toml),
+        #[cfg(not(feature = "toml"))]
+        Some("toml") => Err(ConfigError::Unsupported("toml".to_string())),
+        _ => ron::de::from_str(&text).map_err(ConfigError::Ron),
+    }
+}
+
+#[cfg(feature = "config")]
+impl ThemeConfig {
+    /// Load from a file
+    ///
+    /// The file format is selected by extension: `.json` (requires feature
+    /// `json`), `.toml` (requires feature `toml`), otherwise RON.
+    ///
+    /// **Feature gated**: this is only available with feature `config`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        read_file(path.as_ref())
+    }
+
+    /// Save to a RON file
+    ///
+    /// **Feature gated**: this is only available with feature `config`.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ConfigError> {
+        let pretty = ron::ser::PrettyConfig::default();
+        let text = ron::ser::to_string_pretty(self, pretty).map_err(ConfigError::Ron)?;
+        std::fs::write(path, text).map_err(ConfigError::Io)
+    }
+}
+
+impl ThemeConfig {
+    /// Apply to theme properties, returning the required action
+    pub(crate) fn apply_to(
+        &self,
+        font_size: &mut f32,
+        cols: &mut ThemeColours,
+        dims: &mut DimensionsParams,
+        shapes: &mut ShapeParams,
+        text_rendering: &mut TextRendering,
+    ) -> ThemeAction {
+        let mut action = ThemeAction::None;
+        if let Some(size) = self.font_size {
+            if size != *font_size {
+                *font_size = size;
+                action = ThemeAction::ThemeResize;
+            }
+        }
+        if let Some(ref d) = self.dimensions {
+            if *d != *dims {
+                *dims = d.clone();
+                action = ThemeAction::ThemeResize;
+            }
+        }
+        if let Some(ref c) = self.colours {
+            if *c != *cols {
+                *cols = c.clone();
+                action = action.max(ThemeAction::RedrawAll);
+            }
+        }
+        if let Some(ref s) = self.shapes {
+            if *s != *shapes {
+                *shapes = s.clone();
+                action = action.max(ThemeAction::RedrawAll);
+            }
+        }
+        if let Some(r) = self.text_rendering {
+            if r != *text_rendering {
+                *text_rendering = r;
+                action = action.max(ThemeAction::RedrawAll);
+            }
+        }
+        action
+    }
+}
@@ -0,0 +1,680 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Custom theme: a base theme with per-element overrides
+use std::any::Any;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::{Theme, ThemeConfig};
+use kas::draw::{
+    self, ClipRegion, Colour, Draw, DrawShared, ImageId, InputState, Pass, SizeHandle, TextClass,
+    TextOrientation,
+};
+use kas::geom::{Coord, Rect, Size};
+use kas::layout::Margins;
+use kas::{Align, Direction, ThemeAction, ThemeApi};
+
+type Hook<F> = Option<Box<F>>;
+type RectHook = dyn Fn(&mut dyn draw::DrawHandle, Rect);
+type StateHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, InputState);
+type CheckHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, bool, InputState);
+type HandleHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, Rect, Direction, InputState);
+type TextHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, (Align, Align));
+type MarkerHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, (Align, Align), usize);
+type ScaledTextHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, f32, (Align, Align));
+type ScaledMarkerHook =
+    dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, f32, (Align, Align), usize);
+type OrientedTextHook =
+    dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, TextOrientation, (Align, Align));
+type SelectionHook =
+    dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, (Align, Align), Range<usize>);
+type ScaledSelectionHook =
+    dyn Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, f32, (Align, Align), Range<usize>);
+type DirectionHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, Direction, f32);
+type ImageHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, ImageId);
+type NinePatchHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, ImageId, Size, Margins);
+type DimHook = dyn Fn(&mut dyn draw::DrawHandle, Rect, Option<Rect>);
+
+/// Per-element drawing overrides used by [`CustomTheme`]
+#[derive(Default)]
+struct Hooks {
+    outer_frame: Hook<RectHook>,
+    menu_frame: Hook<RectHook>,
+    separator: Hook<RectHook>,
+    text: Hook<TextHook>,
+    edit_marker: Hook<MarkerHook>,
+    text_scaled: Hook<ScaledTextHook>,
+    edit_marker_scaled: Hook<ScaledMarkerHook>,
+    text_oriented: Hook<OrientedTextHook>,
+    text_selection: Hook<SelectionHook>,
+    text_selection_scaled: Hook<ScaledSelectionHook>,
+    overscroll: Hook<DirectionHook>,
+    image: Hook<ImageHook>,
+    ninepatch: Hook<NinePatchHook>,
+    dim: Hook<DimHook>,
+    menu_entry: Hook<StateHook>,
+    button: Hook<StateHook>,
+    tab: Hook<CheckHook>,
+    edit_box: Hook<StateHook>,
+    checkbox: Hook<CheckHook>,
+    radiobox: Hook<CheckHook>,
+    scrollbar: Hook<HandleHook>,
+    slider: Hook<HandleHook>,
+    progress_bar: Hook<DirectionHook>,
+    progress_bar_indeterminate: Hook<DirectionHook>,
+}
+
+/// A theme built from a base theme with per-element overrides
+///
+/// All sizing and drawing is delegated to the base theme `T` except for those
+/// elements with an installed hook. Each hook receives the base theme's
+/// [`DrawHandle`](draw::DrawHandle) followed by the arguments of the
+/// corresponding [`DrawHandle`](draw::DrawHandle) method; it may draw
+/// directly (via [`DrawHandle::draw_device`](draw::DrawHandle::draw_device))
+/// and/or call the base implementation, for example to decorate it.
+///
+/// Hooks also apply within clip regions (scroll regions and pop-ups).
+///
+/// Example:
+/// ```notest
+/// let theme = CustomTheme::new(FlatTheme::new())
+///     .on_draw_separator(|_, _| ())
+///     .on_draw_button(|handle, rect, mut state| {
+///         state.hover = false;
+///         handle.button(rect, state);
+///     });
+/// ```
+pub struct CustomTheme<T> {
+    base: T,
+    clear_colour: Option<Colour>,
+    hooks: Hooks,
+}
+
+impl<T> CustomTheme<T> {
+    /// Construct from a base theme, with no overrides
+    pub fn new(base: T) -> Self {
+        CustomTheme {
+            base,
+            clear_colour: None,
+            hooks: Default::default(),
+        }
+    }
+
+    /// Access the base theme
+    pub fn base(&self) -> &T {
+        &self.base
+    }
+
+    /// Access the base theme, mutably
+    pub fn base_mut(&mut self) -> &mut T {
+        &mut self.base
+    }
+
+    /// Override the background colour
+    pub fn with_clear_colour(mut self, colour: Colour) -> Self {
+        self.clear_colour = Some(colour);
+        self
+    }
+
+    /// Override [`DrawHandle::outer_frame`](draw::DrawHandle::outer_frame)
+    pub fn on_draw_outer_frame<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect) + 'static,
+    {
+        self.hooks.outer_frame = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::menu_frame`](draw::DrawHandle::menu_frame)
+    pub fn on_draw_menu_frame<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect) + 'static,
+    {
+        self.hooks.menu_frame = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::separator`](draw::DrawHandle::separator)
+    pub fn on_draw_separator<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect) + 'static,
+    {
+        self.hooks.separator = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::text`](draw::DrawHandle::text)
+    ///
+    /// The hook does not apply to scaled text; see
+    /// [`CustomTheme::on_draw_text_scaled`].
+    pub fn on_draw_text<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, (Align, Align)) + 'static,
+    {
+        self.hooks.text = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::edit_marker`](draw::DrawHandle::edit_marker)
+    pub fn on_draw_edit_marker<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, (Align, Align), usize) + 'static,
+    {
+        self.hooks.edit_marker = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::text_scaled`](draw::DrawHandle::text_scaled)
+    pub fn on_draw_text_scaled<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, f32, (Align, Align)) + 'static,
+    {
+        self.hooks.text_scaled = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::edit_marker_scaled`](draw::DrawHandle::edit_marker_scaled)
+    pub fn on_draw_edit_marker_scaled<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, f32, (Align, Align), usize)
+            + 'static,
+    {
+        self.hooks.edit_marker_scaled = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::text_oriented`](draw::DrawHandle::text_oriented)
+    pub fn on_draw_text_oriented<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, TextOrientation, (Align, Align))
+            + 'static,
+    {
+        self.hooks.text_oriented = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::text_selection`](draw::DrawHandle::text_selection)
+    pub fn on_draw_text_selection<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, (Align, Align), Range<usize>)
+            + 'static,
+    {
+        self.hooks.text_selection = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::text_selection_scaled`](draw::DrawHandle::text_selection_scaled)
+    pub fn on_draw_text_selection_scaled<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, &str, TextClass, f32, (Align, Align), Range<usize>)
+            + 'static,
+    {
+        self.hooks.text_selection_scaled = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::overscroll`](draw::DrawHandle::overscroll)
+    pub fn on_draw_overscroll<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, Direction, f32) + 'static,
+    {
+        self.hooks.overscroll = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::image`](draw::DrawHandle::image)
+    pub fn on_draw_image<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, ImageId) + 'static,
+    {
+        self.hooks.image = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::ninepatch`](draw::DrawHandle::ninepatch)
+    pub fn on_draw_ninepatch<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, ImageId, Size, Margins) + 'static,
+    {
+        self.hooks.ninepatch = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::dim`](draw::DrawHandle::dim)
+    pub fn on_draw_dim<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, Option<Rect>) + 'static,
+    {
+        self.hooks.dim = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::menu_entry`](draw::DrawHandle::menu_entry)
+    pub fn on_draw_menu_entry<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, InputState) + 'static,
+    {
+        self.hooks.menu_entry = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::button`](draw::DrawHandle::button)
+    pub fn on_draw_button<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, InputState) + 'static,
+    {
+        self.hooks.button = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::edit_box`](draw::DrawHandle::edit_box)
+    pub fn on_draw_edit_box<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, InputState) + 'static,
+    {
+        self.hooks.edit_box = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::tab`](draw::DrawHandle::tab)
+    pub fn on_draw_tab<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, bool, InputState) + 'static,
+    {
+        self.hooks.tab = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::checkbox`](draw::DrawHandle::checkbox)
+    pub fn on_draw_checkbox<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, bool, InputState) + 'static,
+    {
+        self.hooks.checkbox = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::radiobox`](draw::DrawHandle::radiobox)
+    pub fn on_draw_radiobox<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, bool, InputState) + 'static,
+    {
+        self.hooks.radiobox = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::scrollbar`](draw::DrawHandle::scrollbar)
+    pub fn on_draw_scrollbar<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, Rect, Direction, InputState) + 'static,
+    {
+        self.hooks.scrollbar = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::slider`](draw::DrawHandle::slider)
+    pub fn on_draw_slider<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, Rect, Direction, InputState) + 'static,
+    {
+        self.hooks.slider = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::progress_bar`](draw::DrawHandle::progress_bar)
+    pub fn on_draw_progress_bar<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, Direction, f32) + 'static,
+    {
+        self.hooks.progress_bar = Some(Box::new(f));
+        self
+    }
+
+    /// Override [`DrawHandle::progress_bar_indeterminate`](draw::DrawHandle::progress_bar_indeterminate)
+    pub fn on_draw_progress_bar_indeterminate<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut dyn draw::DrawHandle, Rect, Direction, f32) + 'static,
+    {
+        self.hooks.progress_bar_indeterminate = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for CustomTheme<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        f.debug_struct("CustomTheme")
+            .field("base", &self.base)
+            .field("clear_colour", &self.clear_colour)
+            .finish()
+    }
+}
+
+/// [`DrawHandle`](draw::DrawHandle) implementation of [`CustomTheme`]
+pub struct DrawHandle<'a, H> {
+    base: H,
+    hooks: &'a Hooks,
+}
+
+impl<D: DrawShared, T: Theme<D>> Theme<D> for CustomTheme<T> {
+    type Window = T::Window;
+
+    #[cfg(not(feature = "gat"))]
+    type DrawHandle = DrawHandle<'static, T::DrawHandle>;
+    #[cfg(feature = "gat")]
+    type DrawHandle<'a> = DrawHandle<'a, T::DrawHandle<'a>>;
+
+    fn init(&mut self, draw: &mut D) {
+        self.base.init(draw);
+    }
+
+    fn new_window(&self, draw: &mut D::Draw, dpi_factor: f32) -> Self::Window {
+        self.base.new_window(draw, dpi_factor)
+    }
+
+    fn update_window(&self, draw: &mut D::Draw, window: &mut Self::Window, dpi_factor: f32) {
+        self.base.update_window(draw, window, dpi_factor);
+    }
+
+    #[cfg(not(feature = "gat"))]
+    unsafe fn draw_handle<'a>(
+        &'a self,
+        draw: &'a mut D::Draw,
+        window: &'a mut Self::Window,
+        rect: Rect,
+    ) -> Self::DrawHandle {
+        // We extend lifetimes (unsafe) due to the lack of associated type generics.
+        use std::mem::transmute;
+        DrawHandle {
+            base: self.base.draw_handle(draw, window, rect),
+            hooks: transmute::<&'a Hooks, &'static Hooks>(&self.hooks),
+        }
+    }
+    #[cfg(feature = "gat")]
+    fn draw_handle<'a>(
+        &'a self,
+        draw: &'a mut D::Draw,
+        window: &'a mut Self::Window,
+        rect: Rect,
+    ) -> Self::DrawHandle<'a> {
+        DrawHandle {
+            base: self.base.draw_handle(draw, window, rect),
+            hooks: &self.hooks,
+        }
+    }
+
+    fn clear_colour(&self) -> Colour {
+        self.clear_colour
+            .unwrap_or_else(|| self.base.clear_colour())
+    }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        self.base.apply_config(config)
+    }
+}
+
+impl<T: ThemeApi> ThemeApi for CustomTheme<T> {
+    fn set_font_size(&mut self, size: f32) -> ThemeAction {
+        self.base.set_font_size(size)
+    }
+    fn set_colours(&mut self, scheme: &str) -> ThemeAction {
+        self.base.set_colours(scheme)
+    }
+    fn load_colour_scheme(&mut self, path: &Path) -> ThemeAction {
+        self.base.load_colour_scheme(path)
+    }
+    fn set_theme(&mut self, theme: &str) -> ThemeAction {
+        self.base.set_theme(theme)
+    }
+    fn list_themes(&self) -> Vec<String> {
+        self.base.list_themes()
+    }
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        self.base.as_any_mut()
+    }
+}
+
+impl<'a, H: draw::DrawHandle> draw::DrawHandle for DrawHandle<'a, H> {
+    fn size_handle_dyn(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        self.base.size_handle_dyn(f)
+    }
+
+    fn draw_device(&mut self) -> (Pass, Coord, &mut dyn Draw) {
+        self.base.draw_device()
+    }
+
+    fn clip_region(
+        &mut self,
+        rect: Rect,
+        offset: Coord,
+        class: ClipRegion,
+        f: &mut dyn FnMut(&mut dyn draw::DrawHandle),
+    ) {
+        let hooks = self.hooks;
+        self.base.clip_region(rect, offset, class, &mut |base| {
+            f(&mut DrawHandle { base, hooks });
+        });
+    }
+
+    fn target_rect(&self) -> Rect {
+        self.base.target_rect()
+    }
+
+    fn outer_frame(&mut self, rect: Rect) {
+        match self.hooks.outer_frame {
+            Some(ref f) => f(&mut self.base, rect),
+            None => self.base.outer_frame(rect),
+        }
+    }
+
+    fn menu_frame(&mut self, rect: Rect) {
+        match self.hooks.menu_frame {
+            Some(ref f) => f(&mut self.base, rect),
+            None => self.base.menu_frame(rect),
+        }
+    }
+
+    fn separator(&mut self, rect: Rect) {
+        match self.hooks.separator {
+            Some(ref f) => f(&mut self.base, rect),
+            None => self.base.separator(rect),
+        }
+    }
+
+    fn text(&mut self, rect: Rect, text: &str, class: TextClass, align: (Align, Align)) {
+        match self.hooks.text {
+            Some(ref f) => f(&mut self.base, rect, text, class, align),
+            None => self.base.text(rect, text, class, align),
+        }
+    }
+
+    fn edit_marker(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        match self.hooks.edit_marker {
+            Some(ref f) => f(&mut self.base, rect, text, class, align, byte),
+            None => self.base.edit_marker(rect, text, class, align, byte),
+        }
+    }
+
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        match self.hooks.text_scaled {
+            Some(ref f) => f(&mut self.base, rect, text, class, scale, align),
+            None => self.base.text_scaled(rect, text, class, scale, align),
+        }
+    }
+
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        let base = &mut self.base;
+        match self.hooks.edit_marker_scaled {
+            Some(ref f) => f(base, rect, text, class, scale, align, byte),
+            None => base.edit_marker_scaled(rect, text, class, scale, align, byte),
+        }
+    }
+
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        let base = &mut self.base;
+        match self.hooks.text_oriented {
+            Some(ref f) => f(base, rect, text, class, orientation, align),
+            None => base.text_oriented(rect, text, class, orientation, align),
+        }
+    }
+
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        let base = &mut self.base;
+        match self.hooks.text_selection {
+            Some(ref f) => f(base, rect, text, class, align, range),
+            None => base.text_selection(rect, text, class, align, range),
+        }
+    }
+
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        let base = &mut self.base;
+        match self.hooks.text_selection_scaled {
+            Some(ref f) => f(base, rect, text, class, scale, align, range),
+            None => base.text_selection_scaled(rect, text, class, scale, align, range),
+        }
+    }
+
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        match self.hooks.overscroll {
+            Some(ref f) => f(&mut self.base, rect, direction, amount),
+            None => self.base.overscroll(rect, direction, amount),
+        }
+    }
+
+    fn image(&mut self, rect: Rect, id: ImageId) {
+        match self.hooks.image {
+            Some(ref f) => f(&mut self.base, rect, id),
+            None => self.base.image(rect, id),
+        }
+    }
+
+    fn ninepatch(&mut self, rect: Rect, id: ImageId, size: Size, insets: Margins) {
+        match self.hooks.ninepatch {
+            Some(ref f) => f(&mut self.base, rect, id, size, insets),
+            None => self.base.ninepatch(rect, id, size, insets),
+        }
+    }
+
+    fn dim(&mut self, rect: Rect, spotlight: Option<Rect>) {
+        match self.hooks.dim {
+            Some(ref f) => f(&mut self.base, rect, spotlight),
+            None => self.base.dim(rect, spotlight),
+        }
+    }
+
+    fn menu_entry(&mut self, rect: Rect, state: InputState) {
+        match self.hooks.menu_entry {
+            Some(ref f) => f(&mut self.base, rect, state),
+            None => self.base.menu_entry(rect, state),
+        }
+    }
+
+    fn button(&mut self, rect: Rect, state: InputState) {
+        match self.hooks.button {
+            Some(ref f) => f(&mut self.base, rect, state),
+            None => self.base.button(rect, state),
+        }
+    }
+
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState) {
+        match self.hooks.tab {
+            Some(ref f) => f(&mut self.base, rect, active, state),
+            None => self.base.tab(rect, active, state),
+        }
+    }
+
+    fn edit_box(&mut self, rect: Rect, state: InputState) {
+        match self.hooks.edit_box {
+            Some(ref f) => f(&mut self.base, rect, state),
+            None => self.base.edit_box(rect, state),
+        }
+    }
+
+    fn checkbox(&mut self, rect: Rect, checked: bool, state: InputState) {
+        match self.hooks.checkbox {
+            Some(ref f) => f(&mut self.base, rect, checked, state),
+            None => self.base.checkbox(rect, checked, state),
+        }
+    }
+
+    fn radiobox(&mut self, rect: Rect, checked: bool, state: InputState) {
+        match self.hooks.radiobox {
+            Some(ref f) => f(&mut self.base, rect, checked, state),
+            None => self.base.radiobox(rect, checked, state),
+        }
+    }
+
+    fn scrollbar(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
+        match self.hooks.scrollbar {
+            Some(ref f) => f(&mut self.base, rect, h_rect, dir, state),
+            None => self.base.scrollbar(rect, h_rect, dir, state),
+        }
+    }
+
+    fn slider(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
+        match self.hooks.slider {
+            Some(ref f) => f(&mut self.base, rect, h_rect, dir, state),
+            None => self.base.slider(rect, h_rect, dir, state),
+        }
+    }
+
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32) {
+        match self.hooks.progress_bar {
+            Some(ref f) => f(&mut self.base, rect, dir, fraction),
+            None => self.base.progress_bar(rect, dir, fraction),
+        }
+    }
+
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        match self.hooks.progress_bar_indeterminate {
+            Some(ref f) => f(&mut self.base, rect, dir, phase),
+            None => self.base.progress_bar_indeterminate(rect, dir, phase),
+        }
+    }
+}
@@ -7,10 +7,13 @@
 //!
 //! Widget size and appearance can be modified through themes.
 
+#[cfg(feature = "config")]
+use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::collections::HashMap;
 use std::f32;
 
-use kas::draw::{self, DrawText, FontId, TextClass, TextProperties};
+use kas::draw::{self, DrawText, FontId, TextClass, TextLine, TextProperties};
 use kas::geom::{Rect, Size, Vec2};
 use kas::layout::{AxisInfo, Margins, SizeRules, StretchPolicy};
 use kas::Align;
@@ -19,7 +22,8 @@ use kas::Align;
 ///
 /// All dimensions are multiplied by the DPI factor, then rounded to the
 /// nearest integer. Example: `(2.0 * 1.25).round() = 3.0`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
 pub struct DimensionsParams {
     /// Inner margin
     pub margin: f32,
@@ -31,9 +35,34 @@ pub struct DimensionsParams {
     pub scrollbar_size: Vec2,
     /// Slider minimum handle size
     pub slider_size: Vec2,
+    /// Scroll step, in lines (of label text)
+    #[cfg_attr(feature = "config", serde(default = "default_scroll_lines"))]
+    pub scroll_lines: f32,
+}
+
+#[cfg(feature = "config")]
+fn default_scroll_lines() -> f32 {
+    3.0
+}
+
+/// Scale `value` by `scale_factor` and snap to whole pixels
+///
+/// Positive values are at least one pixel, thus frames do not vanish at small
+/// scale factors.
+fn snap(value: f32, scale_factor: f32) -> u32 {
+    let scaled = (value * scale_factor).round();
+    match value > 0.0 {
+        true => scaled.max(1.0) as u32,
+        false => scaled as u32,
+    }
 }
 
 /// Dimensions available within [`DimensionsWindow`]
+///
+/// Dimensions are calculated from [`DimensionsParams`] in `f32` and snapped to
+/// whole pixels, thus frames are drawn crisply for any scale factor. Values
+/// derived from other dimensions (e.g. `checkbox`) are calculated from the
+/// snapped values, thus are consistent with these.
 #[derive(Clone, Debug)]
 pub struct Dimensions {
     pub font_id: FontId,
@@ -49,6 +78,7 @@ pub struct Dimensions {
     pub checkbox: u32,
     pub scrollbar: Size,
     pub slider: Size,
+    pub scroll_step: u32,
 }
 
 impl Dimensions {
@@ -59,13 +89,15 @@ impl Dimensions {
         scale_factor: f32,
     ) -> Self {
         let font_scale = font_size * scale_factor;
-        let line_height = font_scale.round() as u32;
-        let margin = (params.margin * scale_factor).round() as u32;
-        let frame = (params.frame_size * scale_factor).round() as u32;
+        let line_height = snap(font_size, scale_factor);
+        let margin = snap(params.margin, scale_factor);
+        let frame = snap(params.frame_size, scale_factor);
+        let scrollbar = params.scrollbar_size;
+        let slider = params.slider_size;
         Dimensions {
             font_id,
             font_scale,
-            font_marker_width: (2.0 * scale_factor).round(),
+            font_marker_width: snap(2.0, scale_factor) as f32,
             scale_factor,
             line_height,
             // We appear to average about 2 characters per line_height
@@ -74,10 +106,14 @@ impl Dimensions {
             ideal_line_length: line_height * 15,
             margin,
             frame,
-            button_frame: (params.button_frame * scale_factor).round() as u32,
-            checkbox: (font_scale * 0.7).round() as u32 + 2 * (margin + frame),
-            scrollbar: Size::from(params.scrollbar_size * scale_factor),
-            slider: Size::from(params.slider_size * scale_factor),
+            button_frame: snap(params.button_frame, scale_factor),
+            checkbox: snap(font_size * 0.7, scale_factor) + 2 * (margin + frame),
+            scrollbar: Size(
+                snap(scrollbar.0, scale_factor),
+                snap(scrollbar.1, scale_factor),
+            ),
+            slider: Size(snap(slider.0, scale_factor), snap(slider.1, scale_factor)),
+            scroll_step: (line_height as f32 * params.scroll_lines).round() as u32,
         }
     }
 
@@ -86,17 +122,112 @@ impl Dimensions {
     }
 }
 
+/// Maximum number of texts in a [`TextMap`] before it is cleared
+const MAX_CACHED_TEXTS: usize = 4096;
+
+/// Parameters of a text layout, other than the text itself
+///
+/// Font and scale factor are not included: [`TextCache`] is cleared when
+/// these change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TextKey {
+    class: TextClass,
+    scale: f32,
+    bounds: (f32, f32),
+    align: (Align, Align),
+}
+
+/// Results of type `V` keyed by text and [`TextKey`]
+#[derive(Debug)]
+struct TextMap<V> {
+    map: HashMap<String, Vec<(TextKey, V)>>,
+}
+
+impl<V> Default for TextMap<V> {
+    fn default() -> Self {
+        TextMap {
+            map: Default::default(),
+        }
+    }
+}
+
+impl<V> TextMap<V> {
+    fn get(&self, text: &str, key: &TextKey) -> Option<&V> {
+        let entries = self.map.get(text)?;
+        entries.iter().find(|e| e.0 == *key).map(|e| &e.1)
+    }
+
+    fn insert(&mut self, text: &str, key: TextKey, value: V) {
+        if self.map.len() >= MAX_CACHED_TEXTS && !self.map.contains_key(text) {
+            self.map.clear();
+        }
+        let entries = self.map.entry(text.to_string()).or_default();
+        // Labels are measured with few distinct widths; avoid unbounded growth
+        // of entries when a pane is resized continuously.
+        if entries.len() >= 8 {
+            entries.remove(0);
+        }
+        entries.push((key, value));
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+/// Cache of text bounds and line breaks
+///
+/// Results are keyed by text, class, scale and bounds (the wrap width), thus
+/// when only some widgets are resized, others need not re-wrap their text.
+/// The cache is cleared when the window's font or scale factor changes and
+/// when the theme updates the window's dimensions.
+#[derive(Debug, Default)]
+struct TextCache {
+    font: Option<(FontId, f32)>,
+    bounds: TextMap<(f32, f32)>,
+    // Lines as laid out within a rect at the origin
+    lines: TextMap<Vec<TextLine>>,
+}
+
+impl TextCache {
+    /// Clear if the font or scale changed
+    fn check_font(&mut self, dims: &Dimensions) {
+        let font = Some((dims.font_id, dims.font_scale));
+        if self.font != font {
+            self.clear();
+            self.font = font;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.bounds.clear();
+        self.lines.clear();
+    }
+}
+
 /// A convenient implementation of [`crate::Window`]
+///
+/// This includes a cache of text bounds and line breaks (see
+/// [`kas::draw::SizeHandle::text_bound`] and
+/// [`kas::draw::SizeHandle::text_lines`]).
 pub struct DimensionsWindow {
     pub dims: Dimensions,
+    text_cache: TextCache,
 }
 
 impl DimensionsWindow {
     pub fn new(dims: DimensionsParams, font_id: FontId, font_size: f32, scale_factor: f32) -> Self {
         DimensionsWindow {
             dims: Dimensions::new(dims, font_id, font_size, scale_factor),
+            text_cache: Default::default(),
         }
     }
+
+    /// Replace the dimensions, clearing cached text layout
+    pub fn update(&mut self, dims: Dimensions) {
+        self.dims = dims;
+        self.text_cache.clear();
+    }
 }
 
 impl<Draw: DrawText + 'static> crate::Window<Draw> for DimensionsWindow {
@@ -108,12 +239,22 @@ impl<Draw: DrawText + 'static> crate::Window<Draw> for DimensionsWindow {
     #[cfg(not(feature = "gat"))]
     unsafe fn size_handle<'a>(&'a mut self, draw: &'a mut Draw) -> Self::SizeHandle {
         // We extend lifetimes (unsafe) due to the lack of associated type generics.
-        let h: SizeHandle<'a, Draw> = SizeHandle::new(draw, &self.dims);
+        self.text_cache.check_font(&self.dims);
+        let h: SizeHandle<'a, Draw> = SizeHandle {
+            draw,
+            dims: &self.dims,
+            cache: Some(&mut self.text_cache),
+        };
         std::mem::transmute(h)
     }
     #[cfg(feature = "gat")]
     fn size_handle<'a>(&'a mut self, draw: &'a mut Draw) -> Self::SizeHandle<'a> {
-        SizeHandle::new(draw, &self.dims)
+        self.text_cache.check_font(&self.dims);
+        SizeHandle {
+            draw,
+            dims: &self.dims,
+            cache: Some(&mut self.text_cache),
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
@@ -124,11 +265,30 @@ impl<Draw: DrawText + 'static> crate::Window<Draw> for DimensionsWindow {
 pub struct SizeHandle<'a, Draw> {
     draw: &'a mut Draw,
     dims: &'a Dimensions,
+    cache: Option<&'a mut TextCache>,
 }
 
 impl<'a, Draw> SizeHandle<'a, Draw> {
+    /// Construct, without a text bound cache
     pub fn new(draw: &'a mut Draw, dims: &'a Dimensions) -> Self {
-        SizeHandle { draw, dims }
+        SizeHandle {
+            draw,
+            dims,
+            cache: None,
+        }
+    }
+
+    fn text_props(&self, class: TextClass, scale: f32, align: (Align, Align)) -> TextProperties {
+        TextProperties {
+            font: self.dims.font_id,
+            scale: (self.dims.font_scale * scale).into(),
+            align,
+            line_wrap: match class {
+                TextClass::Label | TextClass::EditMulti => true,
+                TextClass::Button | TextClass::Edit => false,
+            },
+            ..Default::default()
+        }
     }
 }
 
@@ -159,9 +319,30 @@ impl<'a, Draw: DrawText> draw::SizeHandle for SizeHandle<'a, Draw> {
     }
 
     fn text_bound(&mut self, text: &str, class: TextClass, axis: AxisInfo) -> SizeRules {
+        self.text_bound_scaled(text, class, 1.0, axis)
+    }
+
+    fn text_index_nearest(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        pos: Vec2,
+    ) -> usize {
+        self.text_index_nearest_scaled(rect, text, class, 1.0, align, pos)
+    }
+
+    fn text_bound_scaled(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        axis: AxisInfo,
+    ) -> SizeRules {
         let font_id = self.dims.font_id;
-        let font_scale = self.dims.font_scale;
-        let line_height = self.dims.line_height;
+        let font_scale = self.dims.font_scale * scale;
+        let line_height = (self.dims.line_height as f32 * scale).round() as u32;
         let mut bounds = (f32::INFINITY, f32::INFINITY);
         if let Some(size) = axis.size_other_if_fixed(false) {
             bounds.1 = size as f32;
@@ -172,20 +353,37 @@ impl<'a, Draw: DrawText> draw::SizeHandle for SizeHandle<'a, Draw> {
             TextClass::Label | TextClass::EditMulti => true,
             TextClass::Button | TextClass::Edit => false,
         };
-        let bounds = self
-            .draw
-            .text_bound(text, font_id, font_scale, bounds, line_wrap);
+        let key = TextKey {
+            class,
+            scale,
+            bounds,
+            align: Default::default(),
+        };
+        let cached = self.cache.as_ref();
+        let bounds = match cached.and_then(|cache| cache.bounds.get(text, &key)) {
+            Some(bounds) => *bounds,
+            None => {
+                let bounds = self
+                    .draw
+                    .text_bound(text, font_id, font_scale, bounds, line_wrap);
+                if let Some(cache) = self.cache.as_mut() {
+                    cache.bounds.insert(text, key, bounds);
+                }
+                bounds
+            }
+        };
 
         let margins = (self.dims.margin as u16, self.dims.margin as u16);
         if axis.is_horizontal() {
             let bound = bounds.0 as u32;
-            let min = self.dims.min_line_length;
-            let ideal = self.dims.ideal_line_length;
+            let min = (self.dims.min_line_length as f32 * scale).round() as u32;
+            let ideal = (self.dims.ideal_line_length as f32 * scale).round() as u32;
             let (min, ideal) = match class {
                 TextClass::Edit | TextClass::EditMulti => (min, ideal),
                 _ => (bound.min(min), bound.min(ideal)),
             };
-            SizeRules::new(min, ideal, margins, StretchPolicy::LowUtility)
+            // Wrapped text grows taller when narrower
+            SizeRules::new(min, ideal, margins, StretchPolicy::LowUtility).with_trade_off(line_wrap)
         } else {
             let min = match class {
                 TextClass::EditMulti => line_height * 3,
@@ -200,30 +398,92 @@ impl<'a, Draw: DrawText> draw::SizeHandle for SizeHandle<'a, Draw> {
         }
     }
 
-    fn text_index_nearest(
+    fn text_index_nearest_scaled(
         &mut self,
         rect: Rect,
         text: &str,
         class: TextClass,
+        scale: f32,
         align: (Align, Align),
         pos: Vec2,
     ) -> usize {
-        let props = TextProperties {
-            font: self.dims.font_id,
-            scale: self.dims.font_scale.into(),
-            align,
-            line_wrap: match class {
-                TextClass::Label | TextClass::EditMulti => true,
-                TextClass::Button | TextClass::Edit => false,
-            },
-            ..Default::default()
-        };
+        let props = self.text_props(class, scale, align);
 
         // Note: we don't add offset here since it was already subtracted from
         // pos (e.g. via ScrollRegion::send)
         self.draw.text_index_nearest(rect, text, props, pos)
     }
 
+    fn text_lines(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        self.text_lines_scaled(rect, text, class, 1.0, align)
+    }
+
+    fn text_lines_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        // Lines are cached relative to the rect's position
+        let key = TextKey {
+            class,
+            scale,
+            bounds: (rect.size.0 as f32, rect.size.1 as f32),
+            align,
+        };
+        let top = rect.pos.1 as f32;
+        let offset = |mut line: TextLine| {
+            line.top += top;
+            line.bottom += top;
+            line
+        };
+        if let Some(lines) = self.cache.as_ref().and_then(|c| c.lines.get(text, &key)) {
+            return lines.iter().cloned().map(offset).collect();
+        }
+
+        let props = self.text_props(class, scale, align);
+        let origin = Rect::new(Default::default(), rect.size);
+        let lines = self.draw.text_lines(origin, text, props);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.lines.insert(text, key, lines.clone());
+        }
+        lines.into_iter().map(offset).collect()
+    }
+
+    fn edit_marker_rect(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        self.edit_marker_rect_scaled(rect, text, class, 1.0, align, byte)
+    }
+
+    fn edit_marker_rect_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        let props = self.text_props(class, scale, align);
+        let pos = self.draw.text_glyph_pos(rect, text, props, byte);
+        let size = self.dims.edit_marker_size() * scale;
+        Rect::new(pos.into(), Size::from(size).max(Size(1, 1)))
+    }
+
     fn button_surround(&self) -> (Size, Size) {
         let s = Size::uniform(self.dims.button_frame);
         (s, s)
@@ -252,4 +512,73 @@ impl<'a, Draw: DrawText> draw::SizeHandle for SizeHandle<'a, Draw> {
         let size = self.dims.slider;
         (size, 2 * size.0)
     }
+
+    fn scroll_step(&self) -> u32 {
+        self.dims.scroll_step
+    }
+}
+
+#[test]
+fn dimensions_scale_factors() {
+    let params = DimensionsParams {
+        margin: 2.0,
+        frame_size: 4.0,
+        button_frame: 6.0,
+        scrollbar_size: Vec2::splat(8.0),
+        slider_size: Vec2(12.0, 25.0),
+        scroll_lines: 3.0,
+    };
+    for &scale_factor in &[0.25, 1.0, 1.25, 1.5, 1.75, 2.0, 2.25] {
+        let dims = Dimensions::new(params.clone(), FontId(0), 18.0, scale_factor);
+        assert!(dims.margin >= 1 && dims.frame >= 1 && dims.button_frame >= 1);
+        if scale_factor >= 1.0 {
+            assert_eq!(dims.frame, (4.0 * scale_factor).round() as u32);
+        }
+        assert_eq!(dims.font_marker_width.fract(), 0.0);
+        // Derived dimensions are consistent with snapped dimensions
+        let check = dims.checkbox - 2 * (dims.margin + dims.frame);
+        assert_eq!(check, (18.0 * 0.7 * scale_factor).round().max(1.0) as u32);
+    }
+}
+
+#[test]
+fn text_cache_invalidation() {
+    let params = DimensionsParams {
+        margin: 2.0,
+        frame_size: 4.0,
+        button_frame: 6.0,
+        scrollbar_size: Vec2::splat(8.0),
+        slider_size: Vec2(12.0, 25.0),
+        scroll_lines: 3.0,
+    };
+    let key = TextKey {
+        class: TextClass::Label,
+        scale: 1.0,
+        bounds: (100.0, f32::INFINITY),
+        align: Default::default(),
+    };
+    let mut window = DimensionsWindow::new(params.clone(), FontId(0), 18.0, 1.0);
+    let cache = &mut window.text_cache;
+    cache.check_font(&window.dims);
+    cache.bounds.insert("text", key, (30.0, 18.0));
+    assert_eq!(cache.bounds.get("text", &key), Some(&(30.0, 18.0)));
+    let other = TextKey {
+        bounds: (20.0, f32::INFINITY),
+        ..key
+    };
+    assert_eq!(cache.bounds.get("text", &other), None);
+
+    // Unchanged font: entries are kept
+    cache.check_font(&window.dims);
+    assert!(cache.bounds.get("text", &key).is_some());
+
+    // Scale factor change
+    window.dims = Dimensions::new(params.clone(), FontId(0), 18.0, 2.0);
+    window.text_cache.check_font(&window.dims);
+    assert!(window.text_cache.bounds.get("text", &key).is_none());
+
+    // Dimensions replaced by the theme
+    window.text_cache.bounds.insert("text", key, (30.0, 18.0));
+    window.update(Dimensions::new(params, FontId(0), 18.0, 2.0));
+    assert!(window.text_cache.bounds.get("text", &key).is_none());
 }
@@ -7,22 +7,94 @@
 //!
 //! Widget size and appearance can be modified through themes.
 
+use std::any::Any;
 use std::f32;
+use std::ops::Range;
+use std::path::Path;
 
 use crate::{Dimensions, DimensionsParams, DimensionsWindow, Theme, ThemeColours, Window};
+use crate::{ShapeParams, ThemeConfig};
 use kas::draw::{
     self, ClipRegion, Colour, Draw, DrawRounded, DrawShared, DrawText, DrawTextShared, FontId,
-    InputState, Pass, SizeHandle, TextClass, TextProperties,
+    InputState, Pass, SizeHandle, TextClass, TextOrientation, TextProperties, TextRendering,
 };
 use kas::geom::*;
 use kas::{Align, Direction, Directional, ThemeAction, ThemeApi};
 
+/// Quads covering a text selection from glyph position `a` to `b`
+///
+/// Positions are the top-left of glyphs, as returned by
+/// [`DrawText::text_glyph_pos`]. Where the selection spans multiple lines, all
+/// lines except the first and last are highlighted across the whole `rect`.
+pub(crate) fn selection_quads(rect: Rect, a: Vec2, b: Vec2, line_height: f32) -> Vec<Quad> {
+    if (b.1 - a.1).abs() < 0.5 * line_height {
+        return vec![Quad::with_coords(a, Vec2(b.0, a.1 + line_height))];
+    }
+    let (x0, x1) = (rect.pos.0 as f32, (rect.pos.0 + rect.size.0 as i32) as f32);
+    let mut quads = vec![Quad::with_coords(a, Vec2(x1, a.1 + line_height))];
+    if b.1 > a.1 + line_height {
+        quads.push(Quad::with_coords(
+            Vec2(x0, a.1 + line_height),
+            Vec2(x1, b.1),
+        ));
+    }
+    quads.push(Quad::with_coords(
+        Vec2(x0, b.1),
+        Vec2(b.0, b.1 + line_height),
+    ));
+    quads
+}
+
+/// Quad along the edge of `rect` in `direction`, of the given `thickness`
+pub(crate) fn overscroll_quad(rect: Rect, direction: Direction, thickness: f32) -> Quad {
+    let mut quad = Quad::from(rect);
+    match direction {
+        Direction::Right => quad.a.0 = quad.b.0 - thickness,
+        Direction::Down => quad.a.1 = quad.b.1 - thickness,
+        Direction::Left => quad.b.0 = quad.a.0 + thickness,
+        Direction::Up => quad.b.1 = quad.a.1 + thickness,
+    }
+    quad
+}
+
+/// Quad covering fractions `a..b` of the length of `rect`, measured from the
+/// start in `direction`
+///
+/// Fractions are clamped to the range `0.0..=1.0`.
+pub(crate) fn progress_quad(rect: Rect, direction: Direction, a: f32, b: f32) -> Quad {
+    let (a, b) = (a.max(0.0).min(1.0), b.max(0.0).min(1.0));
+    let mut quad = Quad::from(rect);
+    let size = quad.size();
+    match direction {
+        Direction::Right => {
+            quad.b.0 = quad.a.0 + size.0 * b;
+            quad.a.0 += size.0 * a;
+        }
+        Direction::Down => {
+            quad.b.1 = quad.a.1 + size.1 * b;
+            quad.a.1 += size.1 * a;
+        }
+        Direction::Left => {
+            quad.a.0 = quad.b.0 - size.0 * b;
+            quad.b.0 -= size.0 * a;
+        }
+        Direction::Up => {
+            quad.a.1 = quad.b.1 - size.1 * b;
+            quad.b.1 -= size.1 * a;
+        }
+    }
+    quad
+}
+
 /// A theme with flat (unshaded) rendering
 #[derive(Clone, Debug)]
 pub struct FlatTheme {
     font_id: FontId,
     font_size: f32,
     cols: ThemeColours,
+    dims: DimensionsParams,
+    shapes: ShapeParams,
+    text_rendering: TextRendering,
 }
 
 impl FlatTheme {
@@ -32,6 +104,58 @@ impl FlatTheme {
             font_id: Default::default(),
             font_size: 18.0,
             cols: ThemeColours::new(),
+            dims: DIMS,
+            shapes: SHAPES,
+            text_rendering: Default::default(),
+        }
+    }
+
+    /// Construct with the given configuration
+    ///
+    /// Fields omitted from `config` take default values.
+    pub fn with_config(mut self, config: &ThemeConfig) -> Self {
+        let _ = config.apply_to(
+            &mut self.font_size,
+            &mut self.cols,
+            &mut self.dims,
+            &mut self.shapes,
+            &mut self.text_rendering,
+        );
+        self
+    }
+
+    /// Set dimensions (chain style)
+    ///
+    /// These replace the theme's default margins, frame and handle sizes.
+    pub fn with_dimensions(mut self, dims: DimensionsParams) -> Self {
+        self.dims = dims;
+        self
+    }
+
+    /// Get dimensions
+    pub fn dimensions(&self) -> &DimensionsParams {
+        &self.dims
+    }
+
+    /// Set dimensions
+    ///
+    /// At run-time, the theme may be accessed via [`ThemeApi::as_any_mut`]
+    /// within [`kas::event::Manager::adjust_theme`]:
+    /// ```ignore
+    /// mgr.adjust_theme(|theme| match theme
+    ///     .as_any_mut()
+    ///     .and_then(|any| any.downcast_mut::<FlatTheme>())
+    /// {
+    ///     Some(theme) => theme.set_dimensions(dims.clone()),
+    ///     None => ThemeAction::None,
+    /// });
+    /// ```
+    pub fn set_dimensions(&mut self, dims: DimensionsParams) -> ThemeAction {
+        if dims != self.dims {
+            self.dims = dims;
+            ThemeAction::ThemeResize
+        } else {
+            ThemeAction::None
         }
     }
 }
@@ -42,12 +166,26 @@ const DIMS: DimensionsParams = DimensionsParams {
     button_frame: 6.0,
     scrollbar_size: Vec2::splat(8.0),
     slider_size: Vec2(12.0, 25.0),
+    scroll_lines: 3.0,
+};
+
+const SHAPES: ShapeParams = ShapeParams {
+    frame_radius: 0.5,
+    edit_radius: 0.333,
+    button_radius: 0.0,
+    nav_radius: 0.5,
+    frame_norm: (0.7, -0.7),
+    raised_norm: (0.0, 0.6),
+    sunken_norm: (0.0, -0.7),
+    edit_norm: (-0.8, 0.0),
 };
 
 pub struct DrawHandle<'a, D: Draw> {
     draw: &'a mut D,
     window: &'a mut DimensionsWindow,
     cols: &'a ThemeColours,
+    shapes: &'a ShapeParams,
+    text_rendering: TextRendering,
     rect: Rect,
     offset: Coord,
     pass: Pass,
@@ -69,11 +207,12 @@ where
     }
 
     fn new_window(&self, _draw: &mut D::Draw, dpi_factor: f32) -> Self::Window {
-        DimensionsWindow::new(DIMS, self.font_id, self.font_size, dpi_factor)
+        DimensionsWindow::new(self.dims.clone(), self.font_id, self.font_size, dpi_factor)
     }
 
-    fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        window.dims = Dimensions::new(DIMS, self.font_id, self.font_size, dpi_factor);
+    fn update_window(&self, _draw: &mut D::Draw, window: &mut Self::Window, dpi_factor: f32) {
+        let dims = Dimensions::new(self.dims.clone(), self.font_id, self.font_size, dpi_factor);
+        window.update(dims);
     }
 
     #[cfg(not(feature = "gat"))]
@@ -89,6 +228,8 @@ where
             draw: transmute::<&'a mut D::Draw, &'static mut D::Draw>(draw),
             window: transmute::<&'a mut Self::Window, &'static mut Self::Window>(window),
             cols: transmute::<&'a ThemeColours, &'static ThemeColours>(&self.cols),
+            shapes: transmute::<&'a ShapeParams, &'static ShapeParams>(&self.shapes),
+            text_rendering: self.text_rendering,
             rect,
             offset: Coord::ZERO,
             pass: super::START_PASS,
@@ -105,6 +246,8 @@ where
             draw,
             window,
             cols: &self.cols,
+            shapes: &self.shapes,
+            text_rendering: self.text_rendering,
             rect,
             offset: Coord::ZERO,
             pass: super::START_PASS,
@@ -114,6 +257,16 @@ where
     fn clear_colour(&self) -> Colour {
         self.cols.background
     }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        config.apply_to(
+            &mut self.font_size,
+            &mut self.cols,
+            &mut self.dims,
+            &mut self.shapes,
+            &mut self.text_rendering,
+        )
+    }
 }
 
 impl ThemeApi for FlatTheme {
@@ -130,13 +283,26 @@ impl ThemeApi for FlatTheme {
             ThemeAction::None
         }
     }
+
+    fn load_colour_scheme(&mut self, path: &Path) -> ThemeAction {
+        if let Some(scheme) = ThemeColours::load(path) {
+            self.cols = scheme;
+            ThemeAction::RedrawAll
+        } else {
+            ThemeAction::None
+        }
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 impl<'a, D: Draw + DrawRounded> DrawHandle<'a, D> {
-    fn text_props(&self, class: TextClass, align: (Align, Align)) -> TextProperties {
+    fn text_props(&self, class: TextClass, scale: f32, align: (Align, Align)) -> TextProperties {
         TextProperties {
             font: self.window.dims.font_id,
-            scale: self.window.dims.font_scale.into(),
+            scale: (self.window.dims.font_scale * scale).into(),
             col: match class {
                 TextClass::Label => self.cols.label_text,
                 TextClass::Button => self.cols.button_text,
@@ -147,6 +313,8 @@ impl<'a, D: Draw + DrawRounded> DrawHandle<'a, D> {
                 TextClass::Label | TextClass::EditMulti => true,
                 TextClass::Button | TextClass::Edit => false,
             },
+            rendering: self.text_rendering,
+            ..Default::default()
         }
     }
 
@@ -158,15 +326,16 @@ impl<'a, D: Draw + DrawRounded> DrawHandle<'a, D> {
     /// - `nav_col`: colour of navigation highlight, if visible
     fn draw_edit_box(&mut self, outer: Rect, bg_col: Colour, nav_col: Option<Colour>) -> Quad {
         let outer = Quad::from(outer);
-        let inner1 = outer.shrink(self.window.dims.frame as f32 / 2.0);
+        let inner1 = outer.shrink((self.window.dims.frame as f32 / 2.0).round());
         let inner2 = outer.shrink(self.window.dims.frame as f32);
 
         self.draw.rect(self.pass, inner1, bg_col);
 
         // We draw over the inner rect, taking advantage of the fact that
         // rounded frames get drawn after flat rects.
+        let radius = self.shapes.edit_radius;
         self.draw
-            .rounded_frame(self.pass, outer, inner2, 0.333, self.cols.frame);
+            .rounded_frame(self.pass, outer, inner2, radius, self.cols.frame);
 
         if let Some(col) = nav_col {
             self.draw.rounded_frame(self.pass, inner1, inner2, 0.0, col);
@@ -181,14 +350,36 @@ impl<'a, D: Draw + DrawRounded> DrawHandle<'a, D> {
         let thickness = outer.size().min_comp() / 2.0;
         let inner = outer.shrink(thickness);
         let col = self.cols.scrollbar_state(state);
-        self.draw.rounded_frame(self.pass, outer, inner, 0.0, col);
+        let radius = self.shapes.button_radius;
+        self.draw
+            .rounded_frame(self.pass, outer, inner, radius, col);
 
         if let Some(col) = self.cols.nav_region(state) {
-            let outer = outer.shrink(thickness / 4.0);
+            let outer = outer.shrink((thickness / 4.0).round());
             self.draw
                 .rounded_frame(self.pass, outer, inner, 2.0 / 3.0, col);
         }
     }
+
+    /// Draw a progress bar, filled over fractions `a..b` of its length
+    fn draw_progress(&mut self, rect: Rect, dir: Direction, a: f32, b: f32) {
+        // track
+        let rect = rect + self.offset;
+        let outer = Quad::from(rect);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let radius = self.shapes.button_radius;
+        self.draw
+            .rounded_frame(self.pass, outer, inner, radius, self.cols.frame);
+
+        // bar
+        let outer = progress_quad(rect, dir, a, b);
+        let thickness = outer.size().min_comp() / 2.0;
+        if thickness > 0.0 {
+            let inner = outer.shrink(thickness);
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, self.cols.checkbox);
+        }
+    }
 }
 
 impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D> {
@@ -222,6 +413,8 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
             draw: self.draw,
             window: self.window,
             cols: self.cols,
+            shapes: self.shapes,
+            text_rendering: self.text_rendering,
             rect,
             offset: self.offset - offset,
             pass,
@@ -237,29 +430,31 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
     fn outer_frame(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(self.window.dims.frame as f32);
+        let radius = self.shapes.frame_radius;
         self.draw
-            .rounded_frame(self.pass, outer, inner, 0.5, self.cols.frame);
+            .rounded_frame(self.pass, outer, inner, radius, self.cols.frame);
     }
 
     fn menu_frame(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(self.window.dims.frame as f32);
+        let radius = self.shapes.frame_radius;
         self.draw
-            .rounded_frame(self.pass, outer, inner, 0.5, self.cols.frame);
-        let inner = outer.shrink(self.window.dims.frame as f32 / 3.0);
+            .rounded_frame(self.pass, outer, inner, radius, self.cols.frame);
+        let inner = outer.shrink((self.window.dims.frame as f32 / 3.0).round());
         self.draw.rect(self.pass, inner, self.cols.background);
     }
 
     fn separator(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let radius = self.shapes.frame_radius;
         self.draw
-            .rounded_frame(self.pass, outer, inner, 0.5, self.cols.frame);
+            .rounded_frame(self.pass, outer, inner, radius, self.cols.frame);
     }
 
     fn text(&mut self, rect: Rect, text: &str, class: TextClass, align: (Align, Align)) {
-        let props = self.text_props(class, align);
-        self.draw.text(self.pass, rect + self.offset, text, props);
+        self.text_scaled(rect, text, class, 1.0, align);
     }
 
     fn edit_marker(
@@ -270,15 +465,89 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         align: (Align, Align),
         byte: usize,
     ) {
-        let props = self.text_props(class, align);
+        self.edit_marker_scaled(rect, text, class, 1.0, align, byte);
+    }
+
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        let props = self.text_props(class, scale, align);
+        self.draw.text(self.pass, rect + self.offset, text, props);
+    }
+
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        let mut props = self.text_props(class, 1.0, align);
+        props.orientation = orientation;
+        self.draw.text(self.pass, rect + self.offset, text, props);
+    }
+
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        let props = self.text_props(class, scale, align);
         let pos = self
             .draw
             .text_glyph_pos(rect + self.offset, text, props, byte);
-        let size = self.window.dims.edit_marker_size();
+        let size = self.window.dims.edit_marker_size() * scale;
         let quad = Quad::with_pos_and_size(pos, size);
         self.draw.rect(self.pass, quad, props.col);
     }
 
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.text_selection_scaled(rect, text, class, 1.0, align, range);
+    }
+
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        let props = self.text_props(class, scale, align);
+        let rect = rect + self.offset;
+        let a = self.draw.text_glyph_pos(rect, text, props, range.start);
+        let b = self.draw.text_glyph_pos(rect, text, props, range.end);
+        let line_height = self.window.dims.line_height as f32 * scale;
+        let col = self.cols.nav_focus;
+        for quad in selection_quads(rect, a, b, line_height) {
+            self.draw.rect(self.pass, quad, col);
+        }
+    }
+
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        let thickness = amount.max(0.0).min(1.0) * self.window.dims.line_height as f32 / 2.0;
+        let quad = overscroll_quad(rect + self.offset, direction, thickness);
+        self.draw.rect(self.pass, quad, self.cols.nav_focus);
+    }
+
     fn menu_entry(&mut self, rect: Rect, state: InputState) {
         if let Some(col) = self.cols.menu_entry(state) {
             let quad = Quad::from(rect + self.offset);
@@ -291,12 +560,43 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         let col = self.cols.button_state(state);
 
         let inner = outer.shrink(self.window.dims.button_frame as f32);
-        self.draw.rounded_frame(self.pass, outer, inner, 0.0, col);
+        let radius = self.shapes.button_radius;
+        self.draw
+            .rounded_frame(self.pass, outer, inner, radius, col);
         self.draw.rect(self.pass, inner, col);
 
         if let Some(col) = self.cols.nav_region(state) {
-            let outer = outer.shrink(self.window.dims.button_frame as f32 / 3.0);
-            self.draw.rounded_frame(self.pass, outer, inner, 0.5, col);
+            let outer = outer.shrink((self.window.dims.button_frame as f32 / 3.0).round());
+            let radius = self.shapes.nav_radius;
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, col);
+        }
+    }
+
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState) {
+        let outer = Quad::from(rect + self.offset);
+        let inner = outer.shrink(self.window.dims.button_frame as f32);
+        let radius = self.shapes.button_radius;
+
+        // The active tab is drawn as a button; others as an outline
+        if active {
+            let col = self.cols.button_state(state);
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, col);
+            self.draw.rect(self.pass, inner, col);
+        } else {
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, self.cols.frame);
+            if let Some(col) = self.cols.menu_entry(state) {
+                self.draw.rect(self.pass, inner, col);
+            }
+        }
+
+        if let Some(col) = self.cols.nav_region(state) {
+            let outer = outer.shrink((self.window.dims.button_frame as f32 / 3.0).round());
+            let radius = self.shapes.nav_radius;
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, col);
         }
     }
 
@@ -339,7 +639,9 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
         let col = self.cols.frame;
-        self.draw.rounded_frame(self.pass, outer, inner, 0.0, col);
+        let radius = self.shapes.button_radius;
+        self.draw
+            .rounded_frame(self.pass, outer, inner, radius, col);
 
         // handle
         self.draw_handle(h_rect, state);
@@ -349,14 +651,26 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         // track
         let mut outer = Quad::from(rect + self.offset);
         outer = match dir.is_horizontal() {
-            true => outer.shrink_vec(Vec2(0.0, outer.size().1 * (3.0 / 8.0))),
-            false => outer.shrink_vec(Vec2(outer.size().0 * (3.0 / 8.0), 0.0)),
+            true => outer.shrink_vec(Vec2(0.0, outer.size().1 * (3.0 / 8.0)).round()),
+            false => outer.shrink_vec(Vec2(outer.size().0 * (3.0 / 8.0), 0.0).round()),
         };
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
         let col = self.cols.frame;
-        self.draw.rounded_frame(self.pass, outer, inner, 0.0, col);
+        let radius = self.shapes.button_radius;
+        self.draw
+            .rounded_frame(self.pass, outer, inner, radius, col);
 
         // handle
         self.draw_handle(h_rect, state);
     }
+
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32) {
+        self.draw_progress(rect, dir, 0.0, fraction);
+    }
+
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        // A quarter-length bar sweeps across the track
+        let a = phase * 1.25 - 0.25;
+        self.draw_progress(rect, dir, a, a + 0.25);
+    }
 }
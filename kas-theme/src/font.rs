@@ -14,6 +14,7 @@ use font_kit::{
 };
 
 use lazy_static::lazy_static;
+use std::fmt;
 use std::sync::Once;
 // use wgpu_glyph::rusttype::FontCollection;
 
@@ -22,31 +23,61 @@ use kas::draw::{DrawTextShared, FontArc, FontId};
 #[cfg(feature = "font-kit")]
 use std::{fs::File, io::Read, sync::Arc};
 
+/// Error loading the default font
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FontError {
+    /// No suitable system font found
+    NotFound,
+    /// Error reading the font file (with message)
+    Io(String),
+    /// Font collections are not supported
+    Collection,
+    /// The font data is invalid
+    Invalid,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            FontError::NotFound => write!(f, "no suitable font found"),
+            FontError::Io(e) => write!(f, "error reading font: {}", e),
+            FontError::Collection => write!(f, "font collections are not supported"),
+            FontError::Invalid => write!(f, "invalid font data"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
 #[cfg(feature = "font-kit")]
-fn load_font() -> FontArc {
+fn load_font() -> Result<FontArc, FontError> {
     let handle = SystemSource::new()
         .select_best_match(&[FamilyName::SansSerif], &Properties::new())
-        .unwrap();
+        .map_err(|_| FontError::NotFound)?;
 
     let (bytes, index) = match handle {
         Handle::Path { path, font_index } => {
             let mut bytes = vec![];
-            File::open(path).unwrap().read_to_end(&mut bytes).unwrap();
+            File::open(&path)
+                .and_then(|mut file| file.read_to_end(&mut bytes))
+                .map_err(|e| FontError::Io(format!("{}: {}", path.display(), e)))?;
             (bytes, font_index)
         }
         Handle::Memory { bytes, font_index } => {
-            let bytes = Arc::try_unwrap(bytes).unwrap();
+            let bytes = Arc::try_unwrap(bytes).unwrap_or_else(|bytes| (*bytes).clone());
             (bytes, font_index)
         }
     };
 
-    assert!(index == 0, "Font collections not yet supported");
-    FontArc::try_from_vec(bytes).unwrap()
+    if index != 0 {
+        return Err(FontError::Collection);
+    }
+    FontArc::try_from_vec(bytes).map_err(|_| FontError::Invalid)
 }
 
 #[cfg(feature = "font-kit")]
 lazy_static! {
-    static ref FONT: FontArc = load_font();
+    static ref FONT: Result<FontArc, FontError> = load_font();
 }
 
 #[cfg(not(feature = "font-kit"))]
@@ -54,14 +85,29 @@ const BYTES: &'static [u8] = include_bytes!("/usr/share/fonts/dejavu/DejaVuSerif
 
 #[cfg(not(feature = "font-kit"))]
 lazy_static! {
-    static ref FONT: FontArc = FontArc::try_from_slice(BYTES).unwrap();
+    static ref FONT: Result<FontArc, FontError> =
+        FontArc::try_from_slice(BYTES).map_err(|_| FontError::Invalid);
+}
+
+/// Check that the default font can be loaded
+///
+/// Themes load this font on initialisation (and panic on failure). Toolkits
+/// may call this beforehand in order to report failure as an error.
+pub fn check_fonts() -> Result<(), FontError> {
+    FONT.as_ref().map(|_| ()).map_err(|e| e.clone())
 }
 
 /// Load fonts
+///
+/// Panics if loading the default font fails; see [`check_fonts`].
 pub(crate) fn load_fonts<D: DrawTextShared>(draw: &mut D) -> FontId {
     static LOAD_FONTS: Once = Once::new();
     LOAD_FONTS.call_once(|| {
-        let font_id = draw.load_font(FONT.clone());
+        let font = match &*FONT {
+            Ok(font) => font.clone(),
+            Err(e) => panic!("failed to load font: {}", e),
+        };
+        let font_id = draw.load_font(font);
         debug_assert_eq!(font_id, FontId::default());
     });
     FontId::default()
@@ -13,12 +13,18 @@
 //!
 //! Two themes are provided by this trait: [`FlatTheme`] and [`ShadedTheme`].
 //! Additionally, a meta-theme, [`MultiTheme`], allows run-time switching
-//! between themes.
+//! between themes, and [`CustomTheme`] allows overriding the drawing of
+//! individual elements of another theme.
+//!
+//! Themes may be parameterised via a [`ThemeConfig`], which (with feature
+//! `config`) may be loaded from a file.
 
 #![cfg_attr(feature = "gat", feature(generic_associated_types))]
 #![cfg_attr(feature = "unsize", feature(unsize))]
 
 mod col;
+mod config;
+mod custom;
 mod dim;
 mod flat_theme;
 mod font;
@@ -33,9 +39,14 @@ pub use kas;
 use kas::draw::{ClipRegion, Pass};
 
 pub use col::ThemeColours;
+#[cfg(feature = "config")]
+pub use config::ConfigError;
+pub use config::{ShapeParams, ThemeConfig};
+pub use custom::CustomTheme;
 pub use dim::{Dimensions, DimensionsParams, DimensionsWindow};
 pub use flat_theme::FlatTheme;
 pub(crate) use font::load_fonts;
+pub use font::{check_fonts, FontError};
 #[cfg(feature = "stack_dst")]
 pub use multi::{MultiTheme, MultiThemeBuilder};
 pub use shaded_theme::ShadedTheme;
@@ -60,6 +71,8 @@ pub const START_PASS: Pass = Pass::new_pass_with_depth(0, 0.01);
 fn relative_region_depth(class: ClipRegion) -> f32 {
     match class {
         ClipRegion::Popup => 0.01,
+        ClipRegion::Overlay => 1e-3,
         ClipRegion::Scroll => -1e-5,
+        ClipRegion::Layer => 1e-4,
     }
 }
@@ -5,11 +5,13 @@
 
 //! Wrapper around mutliple themes, supporting run-time switching
 
+use std::any::Any;
 use std::collections::HashMap;
 #[cfg(feature = "unsize")]
 use std::marker::Unsize;
+use std::path::Path;
 
-use crate::{StackDst, Theme, ThemeDst, WindowDst};
+use crate::{StackDst, Theme, ThemeConfig, ThemeDst, WindowDst};
 use kas::draw::{Colour, DrawHandle, DrawShared};
 use kas::geom::Rect;
 use kas::{string::CowString, ThemeAction, ThemeApi};
@@ -120,8 +122,9 @@ impl<D: DrawShared + 'static> Theme<D> for MultiTheme<D> {
         self.themes[self.active].new_window(draw, dpi_factor)
     }
 
-    fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        self.themes[self.active].update_window(window, dpi_factor);
+    fn update_window(&self, draw: &mut D::Draw, window: &mut Self::Window, dpi_factor: f32) {
+        // After switching themes, window may be of a different type
+        self.themes[self.active].update_window(draw, window, dpi_factor);
     }
 
     #[cfg(not(feature = "gat"))]
@@ -147,6 +150,18 @@ impl<D: DrawShared + 'static> Theme<D> for MultiTheme<D> {
     fn clear_colour(&self) -> Colour {
         self.themes[self.active].clear_colour()
     }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        // Apply to all themes, but only the active theme's action is needed
+        let mut action = ThemeAction::None;
+        for (i, theme) in self.themes.iter_mut().enumerate() {
+            let a = theme.apply_config(config);
+            if i == self.active {
+                action = a;
+            }
+        }
+        action
+    }
 }
 
 impl<Draw> ThemeApi for MultiTheme<Draw> {
@@ -170,6 +185,14 @@ impl<Draw> ThemeApi for MultiTheme<Draw> {
         action
     }
 
+    fn load_colour_scheme(&mut self, path: &Path) -> ThemeAction {
+        let mut action = ThemeAction::None;
+        for theme in &mut self.themes {
+            action = action.max(theme.load_colour_scheme(path));
+        }
+        action
+    }
+
     fn set_theme(&mut self, theme: &str) -> ThemeAction {
         if let Some(index) = self.names.get(theme).cloned() {
             if index != self.active {
@@ -179,4 +202,17 @@ impl<Draw> ThemeApi for MultiTheme<Draw> {
         }
         ThemeAction::None
     }
+
+    fn list_themes(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.names.iter().collect();
+        names.sort_by_key(|(_, index)| **index);
+        names
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        self.themes[self.active].as_any_mut()
+    }
 }
@@ -5,12 +5,17 @@
 
 //! Shaded theme
 
+use std::any::Any;
 use std::f32;
+use std::ops::Range;
+use std::path::Path;
 
 use crate::{Dimensions, DimensionsParams, DimensionsWindow, Theme, ThemeColours, Window};
+use crate::{ShapeParams, ThemeConfig};
 use kas::draw::{
     self, ClipRegion, Colour, Draw, DrawRounded, DrawShaded, DrawShared, DrawText, DrawTextShared,
-    FontId, InputState, Pass, SizeHandle, TextClass, TextProperties,
+    FontId, InputState, Pass, SizeHandle, TextClass, TextOrientation, TextProperties,
+    TextRendering,
 };
 use kas::geom::*;
 use kas::{Align, Direction, Directional, ThemeAction, ThemeApi};
@@ -21,6 +26,9 @@ pub struct ShadedTheme {
     font_id: FontId,
     font_size: f32,
     cols: ThemeColours,
+    dims: DimensionsParams,
+    shapes: ShapeParams,
+    text_rendering: TextRendering,
 }
 
 impl ShadedTheme {
@@ -30,6 +38,58 @@ impl ShadedTheme {
             font_id: Default::default(),
             font_size: 18.0,
             cols: ThemeColours::new(),
+            dims: DIMS,
+            shapes: SHAPES,
+            text_rendering: Default::default(),
+        }
+    }
+
+    /// Construct with the given configuration
+    ///
+    /// Fields omitted from `config` take default values.
+    pub fn with_config(mut self, config: &ThemeConfig) -> Self {
+        let _ = config.apply_to(
+            &mut self.font_size,
+            &mut self.cols,
+            &mut self.dims,
+            &mut self.shapes,
+            &mut self.text_rendering,
+        );
+        self
+    }
+
+    /// Set dimensions (chain style)
+    ///
+    /// These replace the theme's default margins, frame and handle sizes.
+    pub fn with_dimensions(mut self, dims: DimensionsParams) -> Self {
+        self.dims = dims;
+        self
+    }
+
+    /// Get dimensions
+    pub fn dimensions(&self) -> &DimensionsParams {
+        &self.dims
+    }
+
+    /// Set dimensions
+    ///
+    /// At run-time, the theme may be accessed via [`ThemeApi::as_any_mut`]
+    /// within [`kas::event::Manager::adjust_theme`]:
+    /// ```ignore
+    /// mgr.adjust_theme(|theme| match theme
+    ///     .as_any_mut()
+    ///     .and_then(|any| any.downcast_mut::<ShadedTheme>())
+    /// {
+    ///     Some(theme) => theme.set_dimensions(dims.clone()),
+    ///     None => ThemeAction::None,
+    /// });
+    /// ```
+    pub fn set_dimensions(&mut self, dims: DimensionsParams) -> ThemeAction {
+        if dims != self.dims {
+            self.dims = dims;
+            ThemeAction::ThemeResize
+        } else {
+            ThemeAction::None
         }
     }
 }
@@ -40,12 +100,26 @@ const DIMS: DimensionsParams = DimensionsParams {
     button_frame: 5.0,
     scrollbar_size: Vec2::splat(8.0),
     slider_size: Vec2(12.0, 25.0),
+    scroll_lines: 3.0,
+};
+
+const SHAPES: ShapeParams = ShapeParams {
+    frame_radius: 0.5,
+    edit_radius: 0.333,
+    button_radius: 0.0,
+    nav_radius: 0.5,
+    frame_norm: (0.7, -0.7),
+    raised_norm: (0.0, 0.6),
+    sunken_norm: (0.0, -0.7),
+    edit_norm: (-0.8, 0.0),
 };
 
 pub struct DrawHandle<'a, D: Draw> {
     draw: &'a mut D,
     window: &'a mut DimensionsWindow,
     cols: &'a ThemeColours,
+    shapes: &'a ShapeParams,
+    text_rendering: TextRendering,
     rect: Rect,
     offset: Coord,
     pass: Pass,
@@ -67,11 +141,12 @@ where
     }
 
     fn new_window(&self, _draw: &mut D::Draw, dpi_factor: f32) -> Self::Window {
-        DimensionsWindow::new(DIMS, self.font_id, self.font_size, dpi_factor)
+        DimensionsWindow::new(self.dims.clone(), self.font_id, self.font_size, dpi_factor)
     }
 
-    fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        window.dims = Dimensions::new(DIMS, self.font_id, self.font_size, dpi_factor);
+    fn update_window(&self, _draw: &mut D::Draw, window: &mut Self::Window, dpi_factor: f32) {
+        let dims = Dimensions::new(self.dims.clone(), self.font_id, self.font_size, dpi_factor);
+        window.update(dims);
     }
 
     #[cfg(not(feature = "gat"))]
@@ -87,6 +162,8 @@ where
             draw: transmute::<&'a mut D::Draw, &'static mut D::Draw>(draw),
             window: transmute::<&'a mut Self::Window, &'static mut Self::Window>(window),
             cols: transmute::<&'a ThemeColours, &'static ThemeColours>(&self.cols),
+            shapes: transmute::<&'a ShapeParams, &'static ShapeParams>(&self.shapes),
+            text_rendering: self.text_rendering,
             rect,
             offset: Coord::ZERO,
             pass: super::START_PASS,
@@ -103,6 +180,8 @@ where
             draw,
             window,
             cols: &self.cols,
+            shapes: &self.shapes,
+            text_rendering: self.text_rendering,
             rect,
             offset: Coord::ZERO,
             pass: super::START_PASS,
@@ -112,6 +191,16 @@ where
     fn clear_colour(&self) -> Colour {
         self.cols.background
     }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        config.apply_to(
+            &mut self.font_size,
+            &mut self.cols,
+            &mut self.dims,
+            &mut self.shapes,
+            &mut self.text_rendering,
+        )
+    }
 }
 
 impl ThemeApi for ShadedTheme {
@@ -128,13 +217,26 @@ impl ThemeApi for ShadedTheme {
             ThemeAction::None
         }
     }
+
+    fn load_colour_scheme(&mut self, path: &Path) -> ThemeAction {
+        if let Some(scheme) = ThemeColours::load(path) {
+            self.cols = scheme;
+            ThemeAction::RedrawAll
+        } else {
+            ThemeAction::None
+        }
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 impl<'a, D: Draw + DrawRounded + DrawShaded> DrawHandle<'a, D> {
-    fn text_props(&self, class: TextClass, align: (Align, Align)) -> TextProperties {
+    fn text_props(&self, class: TextClass, scale: f32, align: (Align, Align)) -> TextProperties {
         TextProperties {
             font: self.window.dims.font_id,
-            scale: self.window.dims.font_scale.into(),
+            scale: (self.window.dims.font_scale * scale).into(),
             col: match class {
                 TextClass::Label => self.cols.label_text,
                 TextClass::Button => self.cols.button_text,
@@ -145,6 +247,8 @@ impl<'a, D: Draw + DrawRounded + DrawShaded> DrawHandle<'a, D> {
                 TextClass::Label | TextClass::EditMulti => true,
                 TextClass::Button | TextClass::Edit => false,
             },
+            rendering: self.text_rendering,
+            ..Default::default()
         }
     }
 
@@ -158,8 +262,9 @@ impl<'a, D: Draw + DrawRounded + DrawShaded> DrawHandle<'a, D> {
         let mut outer = Quad::from(outer);
         let mut inner = outer.shrink(self.window.dims.frame as f32);
 
+        let norm = self.shapes.edit_norm;
         self.draw
-            .shaded_square_frame(self.pass, outer, inner, (-0.8, 0.0), self.cols.background);
+            .shaded_square_frame(self.pass, outer, inner, norm, self.cols.background);
 
         if let Some(col) = nav_col {
             outer = inner;
@@ -178,14 +283,35 @@ impl<'a, D: Draw + DrawRounded + DrawShaded> DrawHandle<'a, D> {
         let inner = outer.shrink(thickness);
         let col = self.cols.scrollbar_state(state);
         self.draw
-            .shaded_round_frame(self.pass, outer, inner, (0.0, 0.6), col);
+            .shaded_round_frame(self.pass, outer, inner, self.shapes.raised_norm, col);
 
         if let Some(col) = self.cols.nav_region(state) {
-            let outer = outer.shrink(thickness / 4.0);
+            let outer = outer.shrink((thickness / 4.0).round());
             self.draw
                 .rounded_frame(self.pass, outer, inner, 2.0 / 3.0, col);
         }
     }
+
+    /// Draw a progress bar, filled over fractions `a..b` of its length
+    fn draw_progress(&mut self, rect: Rect, dir: Direction, a: f32, b: f32) {
+        // track
+        let rect = rect + self.offset;
+        let outer = Quad::from(rect);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let norm = self.shapes.sunken_norm;
+        self.draw
+            .shaded_round_frame(self.pass, outer, inner, norm, self.cols.background);
+
+        // bar
+        let outer = crate::flat_theme::progress_quad(rect, dir, a, b);
+        let thickness = outer.size().min_comp() / 2.0;
+        if thickness > 0.0 {
+            let inner = outer.shrink(thickness);
+            let norm = self.shapes.raised_norm;
+            self.draw
+                .shaded_round_frame(self.pass, outer, inner, norm, self.cols.checkbox);
+        }
+    }
 }
 
 impl<'a, D> draw::DrawHandle for DrawHandle<'a, D>
@@ -222,6 +348,8 @@ where
             draw: self.draw,
             window: self.window,
             cols: self.cols,
+            shapes: self.shapes,
+            text_rendering: self.text_rendering,
             rect,
             offset: self.offset - offset,
             pass,
@@ -237,7 +365,7 @@ where
     fn outer_frame(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(self.window.dims.frame as f32);
-        let norm = (0.7, -0.7);
+        let norm = self.shapes.frame_norm;
         let col = self.cols.background;
         self.draw
             .shaded_round_frame(self.pass, outer, inner, norm, col);
@@ -256,15 +384,14 @@ where
     fn separator(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
-        let norm = (0.0, -0.7);
+        let norm = self.shapes.sunken_norm;
         let col = self.cols.background;
         self.draw
             .shaded_round_frame(self.pass, outer, inner, norm, col);
     }
 
     fn text(&mut self, rect: Rect, text: &str, class: TextClass, align: (Align, Align)) {
-        let props = self.text_props(class, align);
-        self.draw.text(self.pass, rect + self.offset, text, props);
+        self.text_scaled(rect, text, class, 1.0, align);
     }
 
     fn edit_marker(
@@ -275,15 +402,89 @@ where
         align: (Align, Align),
         byte: usize,
     ) {
-        let props = self.text_props(class, align);
+        self.edit_marker_scaled(rect, text, class, 1.0, align, byte);
+    }
+
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        let props = self.text_props(class, scale, align);
+        self.draw.text(self.pass, rect + self.offset, text, props);
+    }
+
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        let mut props = self.text_props(class, 1.0, align);
+        props.orientation = orientation;
+        self.draw.text(self.pass, rect + self.offset, text, props);
+    }
+
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        let props = self.text_props(class, scale, align);
         let pos = self
             .draw
             .text_glyph_pos(rect + self.offset, text, props, byte);
-        let size = self.window.dims.edit_marker_size();
+        let size = self.window.dims.edit_marker_size() * scale;
         let quad = Quad::with_pos_and_size(pos, size);
         self.draw.rect(self.pass, quad, props.col);
     }
 
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.text_selection_scaled(rect, text, class, 1.0, align, range);
+    }
+
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        let props = self.text_props(class, scale, align);
+        let rect = rect + self.offset;
+        let a = self.draw.text_glyph_pos(rect, text, props, range.start);
+        let b = self.draw.text_glyph_pos(rect, text, props, range.end);
+        let line_height = self.window.dims.line_height as f32 * scale;
+        let col = self.cols.nav_focus;
+        for quad in crate::flat_theme::selection_quads(rect, a, b, line_height) {
+            self.draw.rect(self.pass, quad, col);
+        }
+    }
+
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        let thickness = amount.max(0.0).min(1.0) * self.window.dims.line_height as f32 / 2.0;
+        let quad = crate::flat_theme::overscroll_quad(rect + self.offset, direction, thickness);
+        self.draw.rect(self.pass, quad, self.cols.nav_focus);
+    }
+
     fn menu_entry(&mut self, rect: Rect, state: InputState) {
         if let Some(col) = self.cols.menu_entry(state) {
             let quad = Quad::from(rect + self.offset);
@@ -297,12 +498,39 @@ where
         let col = self.cols.button_state(state);
 
         self.draw
-            .shaded_round_frame(self.pass, outer, inner, (0.0, 0.6), col);
+            .shaded_round_frame(self.pass, outer, inner, self.shapes.raised_norm, col);
         self.draw.rect(self.pass, inner, col);
 
         if let Some(col) = self.cols.nav_region(state) {
-            let outer = outer.shrink(self.window.dims.button_frame as f32 / 3.0);
-            self.draw.rounded_frame(self.pass, outer, inner, 0.5, col);
+            let outer = outer.shrink((self.window.dims.button_frame as f32 / 3.0).round());
+            let radius = self.shapes.nav_radius;
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, col);
+        }
+    }
+
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState) {
+        let outer = Quad::from(rect + self.offset);
+        let inner = outer.shrink(self.window.dims.button_frame as f32);
+
+        // The active tab is raised like a button; others are sunken
+        if active {
+            let col = self.cols.button_state(state);
+            self.draw
+                .shaded_round_frame(self.pass, outer, inner, self.shapes.raised_norm, col);
+            self.draw.rect(self.pass, inner, col);
+        } else {
+            let col = self.cols.menu_entry(state).unwrap_or(self.cols.background);
+            self.draw
+                .shaded_round_frame(self.pass, outer, inner, self.shapes.sunken_norm, col);
+            self.draw.rect(self.pass, inner, col);
+        }
+
+        if let Some(col) = self.cols.nav_region(state) {
+            let outer = outer.shrink((self.window.dims.button_frame as f32 / 3.0).round());
+            let radius = self.shapes.nav_radius;
+            self.draw
+                .rounded_frame(self.pass, outer, inner, radius, col);
         }
     }
 
@@ -337,7 +565,7 @@ where
         // track
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
-        let norm = (0.0, -0.7);
+        let norm = self.shapes.sunken_norm;
         let col = self.cols.background;
         self.draw
             .shaded_round_frame(self.pass, outer, inner, norm, col);
@@ -350,11 +578,11 @@ where
         // track
         let mut outer = Quad::from(rect + self.offset);
         outer = match dir.is_horizontal() {
-            true => outer.shrink_vec(Vec2(0.0, outer.size().1 * (3.0 / 8.0))),
-            false => outer.shrink_vec(Vec2(outer.size().0 * (3.0 / 8.0), 0.0)),
+            true => outer.shrink_vec(Vec2(0.0, outer.size().1 * (3.0 / 8.0)).round()),
+            false => outer.shrink_vec(Vec2(outer.size().0 * (3.0 / 8.0), 0.0).round()),
         };
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
-        let norm = (0.0, -0.7);
+        let norm = self.shapes.sunken_norm;
         let col = self.cols.background;
         self.draw
             .shaded_round_frame(self.pass, outer, inner, norm, col);
@@ -362,4 +590,14 @@ where
         // handle
         self.draw_handle(h_rect, state);
     }
+
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32) {
+        self.draw_progress(rect, dir, 0.0, fraction);
+    }
+
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        // A quarter-length bar sweeps across the track
+        let a = phase * 1.25 - 0.25;
+        self.draw_progress(rect, dir, a, a + 0.25);
+    }
 }
@@ -8,10 +8,10 @@
 use std::any::Any;
 use std::ops::DerefMut;
 
-use super::{StackDst, Theme, Window};
+use super::{StackDst, Theme, ThemeConfig, Window};
 use kas::draw::{Colour, DrawHandle, DrawShared, SizeHandle};
 use kas::geom::Rect;
-use kas::ThemeApi;
+use kas::{ThemeAction, ThemeApi};
 
 /// As [`Theme`], but without associated types
 ///
@@ -35,8 +35,16 @@ pub trait ThemeDst<D: DrawShared>: ThemeApi {
 
     /// Update a window created by [`Theme::new_window`]
     ///
+    /// If `window` was created by a different theme (e.g. before switching
+    /// themes within a [`crate::MultiTheme`]), it is replaced by a new window.
+    ///
     /// See also [`Theme::update_window`].
-    fn update_window(&self, window: &mut dyn WindowDst<D::Draw>, dpi_factor: f32);
+    fn update_window(
+        &self,
+        draw: &mut D::Draw,
+        window: &mut StackDst<dyn WindowDst<D::Draw>>,
+        dpi_factor: f32,
+    );
 
     /// Construct a [`DrawHandle`] object
     ///
@@ -71,6 +79,11 @@ pub trait ThemeDst<D: DrawShared>: ThemeApi {
     ///
     /// See also [`Theme::clear_colour`].
     fn clear_colour(&self) -> Colour;
+
+    /// Apply a configuration
+    ///
+    /// See also [`Theme::apply_config`].
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction;
 }
 
 #[cfg(not(feature = "gat"))]
@@ -102,9 +115,17 @@ where
         }
     }
 
-    fn update_window(&self, window: &mut dyn WindowDst<D::Draw>, dpi_factor: f32) {
-        let window = window.as_any_mut().downcast_mut().unwrap();
-        self.update_window(window, dpi_factor);
+    fn update_window(
+        &self,
+        draw: &mut D::Draw,
+        window: &mut StackDst<dyn WindowDst<D::Draw>>,
+        dpi_factor: f32,
+    ) {
+        if let Some(w) = window.as_any_mut().downcast_mut() {
+            <T as Theme<D>>::update_window(self, draw, w, dpi_factor);
+        } else {
+            *window = <T as ThemeDst<D>>::new_window(self, draw, dpi_factor);
+        }
     }
 
     unsafe fn draw_handle(
@@ -130,6 +151,10 @@ where
     fn clear_colour(&self) -> Colour {
         self.clear_colour()
     }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        self.apply_config(config)
+    }
 }
 
 #[cfg(feature = "gat")]
@@ -143,9 +168,17 @@ impl<'a, D: DrawShared + 'static, T: Theme<D>> ThemeDst<D> for T {
         StackDst::new_or_boxed(window)
     }
 
-    fn update_window(&self, window: &mut dyn WindowDst<D::Draw>, dpi_factor: f32) {
-        let window = window.as_any_mut().downcast_mut().unwrap();
-        self.update_window(window, dpi_factor);
+    fn update_window(
+        &self,
+        draw: &mut D::Draw,
+        window: &mut StackDst<dyn WindowDst<D::Draw>>,
+        dpi_factor: f32,
+    ) {
+        if let Some(w) = window.as_any_mut().downcast_mut() {
+            <T as Theme<D>>::update_window(self, draw, w, dpi_factor);
+        } else {
+            *window = <T as ThemeDst<D>>::new_window(self, draw, dpi_factor);
+        }
     }
 
     fn draw_handle<'b>(
@@ -162,6 +195,10 @@ impl<'a, D: DrawShared + 'static, T: Theme<D>> ThemeDst<D> for T {
     fn clear_colour(&self) -> Colour {
         self.clear_colour()
     }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        self.apply_config(config)
+    }
 }
 
 /// As [`Window`], but without associated types
@@ -10,7 +10,9 @@ use std::ops::{Deref, DerefMut};
 
 use kas::draw::{Colour, DrawHandle, DrawShared, SizeHandle};
 use kas::geom::Rect;
-use kas::ThemeApi;
+use kas::{ThemeAction, ThemeApi};
+
+use crate::ThemeConfig;
 
 /// A *theme* provides widget sizing and drawing implementations.
 ///
@@ -55,7 +57,9 @@ pub trait Theme<D: DrawShared>: ThemeApi {
     /// Update a window created by [`Theme::new_window`]
     ///
     /// This is called when the DPI factor changes or theme dimensions change.
-    fn update_window(&self, window: &mut Self::Window, dpi_factor: f32);
+    /// The `draw` reference is identical to that passed to
+    /// [`Theme::new_window`].
+    fn update_window(&self, draw: &mut D::Draw, window: &mut Self::Window, dpi_factor: f32);
 
     /// Construct a [`DrawHandle`] object
     ///
@@ -81,6 +85,15 @@ pub trait Theme<D: DrawShared>: ThemeApi {
 
     /// Background colour
     fn clear_colour(&self) -> Colour;
+
+    /// Apply a configuration
+    ///
+    /// Themes should apply all applicable fields present in `config` and
+    /// return the action required to make the changes visible.
+    /// The default implementation does nothing.
+    fn apply_config(&mut self, _config: &ThemeConfig) -> ThemeAction {
+        ThemeAction::None
+    }
 }
 
 /// Per-window storage for the theme
@@ -123,8 +136,8 @@ impl<T: Theme<D>, D: DrawShared> Theme<D> for Box<T> {
     fn new_window(&self, draw: &mut D::Draw, dpi_factor: f32) -> Self::Window {
         self.deref().new_window(draw, dpi_factor)
     }
-    fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        self.deref().update_window(window, dpi_factor);
+    fn update_window(&self, draw: &mut D::Draw, window: &mut Self::Window, dpi_factor: f32) {
+        self.deref().update_window(draw, window, dpi_factor);
     }
 
     #[cfg(not(feature = "gat"))]
@@ -149,6 +162,10 @@ impl<T: Theme<D>, D: DrawShared> Theme<D> for Box<T> {
     fn clear_colour(&self) -> Colour {
         self.deref().clear_colour()
     }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        self.deref_mut().apply_config(config)
+    }
 }
 
 impl<W: Window<Draw>, Draw> Window<Draw> for Box<W> {
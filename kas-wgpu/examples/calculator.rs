@@ -14,7 +14,7 @@ use kas::event::VirtualKeyCode as VK;
 use kas::event::{Manager, Response, VoidMsg};
 use kas::macros::{make_widget, VoidMsg};
 use kas::widget::{EditBox, TextButton, Window};
-use kas::ThemeApi;
+use kas::{ThemeApi, Widget};
 
 #[derive(Clone, Debug, VoidMsg)]
 enum Key {
@@ -27,9 +27,7 @@ enum Key {
     Char(char),
 }
 
-fn main() -> Result<(), kas_wgpu::Error> {
-    env_logger::init();
-
+fn calculator() -> Window<impl Widget<Msg = VoidMsg>> {
     let buttons = make_widget! {
         #[layout(grid)]
         #[handler(msg = Key)]
@@ -97,7 +95,13 @@ fn main() -> Result<(), kas_wgpu::Error> {
             }
         }
     };
-    let window = Window::new("Calculator", content);
+    Window::new("Calculator", content)
+}
+
+fn main() -> Result<(), kas_wgpu::Error> {
+    env_logger::init();
+
+    let window = calculator();
 
     let mut theme = kas_theme::ShadedTheme::new();
     theme.set_font_size(24.0);
@@ -212,3 +216,40 @@ impl Calculator {
         true
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kas::test::{Script, Selector};
+    use kas::widget::EditBoxVoid;
+
+    fn button(label: &'static str) -> Selector {
+        Selector::new(move |w| match w.as_any().downcast_ref::<TextButton<Key>>() {
+            Some(button) => button.get_text() == label,
+            None => false,
+        })
+    }
+
+    fn press(script: &mut Script<impl Widget<Msg = VoidMsg>>, labels: &[&'static str]) {
+        for label in labels {
+            script.activate(&button(label));
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let display = Selector::nth("EditBox", 0);
+        let shows = |s: &'static str| move |w: &EditBoxVoid| w.get_text() == s;
+        let mut script = Script::new(calculator());
+        script.expect(&display, shows("0"));
+
+        press(&mut script, &["1", "2", "+", "3"]);
+        script.expect(&display, shows("12\n+\n3"));
+        press(&mut script, &["0", "="]);
+        script.expect(&display, shows("42\n\n"));
+        press(&mut script, &["÷", "8", "="]);
+        script.expect(&display, shows("5.25\n\n"));
+        press(&mut script, &["clear"]);
+        script.expect(&display, shows("0\n\n"));
+    }
+}
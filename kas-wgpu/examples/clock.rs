@@ -112,7 +112,7 @@ impl Layout for Clock {
 
 impl WidgetConfig for Clock {
     fn configure(&mut self, mgr: &mut Manager) {
-        mgr.update_on_timer(Duration::new(0, 0), self.id());
+        mgr.update_after(Duration::new(0, 0), self.id());
     }
 }
 
@@ -129,7 +129,7 @@ impl Handler for Clock {
                 self.time = self.now.format("%H:%M:%S").to_string();
                 let ns = 1_000_000_000 - (self.now.time().nanosecond() % 1_000_000_000);
                 info!("Requesting update in {}ns", ns);
-                mgr.update_on_timer(Duration::new(0, ns), self.id());
+                mgr.update_after(Duration::new(0, ns), self.id());
                 Response::None
             }
             event => Response::Unhandled(event),
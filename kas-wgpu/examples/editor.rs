@@ -0,0 +1,42 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Editor example: two views of one document
+#![feature(proc_macro_hygiene)]
+
+use kas::document::TextDocument;
+use kas::event::VoidMsg;
+use kas::macros::make_widget;
+use kas::widget::{TextEditor, Window};
+
+fn main() -> Result<(), kas_wgpu::Error> {
+    env_logger::init();
+
+    // Usage: editor [FILE]
+    let doc = match std::env::args().nth(1) {
+        Some(path) => TextDocument::from_file(path).expect("failed to read file"),
+        None => TextDocument::new(
+            "Edit this text in either view.\n\nBoth views share one document, \
+            but each has its own edit cursor.\n",
+        ),
+    };
+
+    let window = Window::new(
+        "Editor",
+        make_widget! {
+            #[layout(row)]
+            #[handler(msg = VoidMsg)]
+            struct {
+                #[widget] _ = TextEditor::new(doc.clone()),
+                #[widget] _ = TextEditor::new(doc),
+            }
+        },
+    );
+
+    let theme = kas_theme::ShadedTheme::new();
+    let mut toolkit = kas_wgpu::Toolkit::new(theme)?;
+    toolkit.add(window)?;
+    toolkit.run()
+}
@@ -47,7 +47,7 @@ fn make_window() -> Box<dyn kas::Window> {
                             self.start = None;
                         } else {
                             self.start = Some(Instant::now());
-                            mgr.update_on_timer(Duration::new(0, 0), self.id());
+                            mgr.update_after(Duration::new(0, 0), self.id());
                         }
                     }
                 }
@@ -71,7 +71,7 @@ fn make_window() -> Box<dyn kas::Window> {
                                 dur.as_secs(),
                                 dur.subsec_millis()
                             ));
-                            mgr.update_on_timer(Duration::new(0, 1), self.id());
+                            mgr.update_after(Duration::new(0, 1), self.id());
                         }
                         Response::None
                     }
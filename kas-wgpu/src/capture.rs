@@ -0,0 +1,201 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Screenshot capture
+
+use kas::geom::Size;
+
+use crate::draw::TEX_FORMAT;
+
+/// Row pitch of buffers used in texture-to-buffer copies must be a multiple of
+/// this value (bytes)
+const ROW_ALIGNMENT: u32 = 256;
+
+/// An RGBA image, as captured from a window
+///
+/// See [`crate::ToolkitProxy::capture`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbaImage {
+    /// Size in pixels
+    pub size: Size,
+    /// Pixel data
+    ///
+    /// Rows are stored top-to-bottom without padding; each pixel is four
+    /// bytes, in the order red, green, blue, alpha (sRGB encoded).
+    pub data: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Get the pixel at `(x, y)`
+    ///
+    /// Panics if the coordinates are out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(x < self.size.0 && y < self.size.1);
+        let i = 4 * (y as usize * self.size.0 as usize + x as usize);
+        [
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ]
+    }
+
+    /// Count pixels differing from `other`
+    ///
+    /// Pixels are considered different when any channel differs by more than
+    /// `tolerance`. Returns `None` if image sizes differ.
+    pub fn count_differing(&self, other: &RgbaImage, tolerance: u8) -> Option<usize> {
+        if self.size != other.size {
+            return None;
+        }
+        let count = self
+            .data
+            .chunks(4)
+            .zip(other.data.chunks(4))
+            .filter(|(a, b)| differs(a, b, tolerance))
+            .count();
+        Some(count)
+    }
+
+    /// Construct an image highlighting differences from `other`
+    ///
+    /// Differing pixels (as in [`RgbaImage::count_differing`]) are drawn in
+    /// opaque red, others as a faded version of `self`. Returns `None` if
+    /// image sizes differ.
+    pub fn diff_image(&self, other: &RgbaImage, tolerance: u8) -> Option<RgbaImage> {
+        if self.size != other.size {
+            return None;
+        }
+        let mut data = Vec::with_capacity(self.data.len());
+        for (a, b) in self.data.chunks(4).zip(other.data.chunks(4)) {
+            if differs(a, b, tolerance) {
+                data.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                data.extend(a[0..3].iter().map(|x| 192 + x / 4));
+                data.push(255);
+            }
+        }
+        Some(RgbaImage {
+            size: self.size,
+            data,
+        })
+    }
+}
+
+/// True if any channel of pixels `a` and `b` differs by more than `tolerance`
+fn differs(a: &[u8], b: &[u8], tolerance: u8) -> bool {
+    let diff = |(x, y): (&u8, &u8)| if x > y { x - y } else { y - x };
+    a.iter().zip(b.iter()).map(diff).any(|d| d > tolerance)
+}
+
+/// An off-screen render target whose contents may be read back
+pub(crate) struct Capture {
+    size: Size,
+    bytes_per_row: u32,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    buffer: wgpu::Buffer,
+}
+
+impl Capture {
+    /// Construct a render target of the given size
+    ///
+    /// The size must not be zero.
+    pub fn new(device: &wgpu::Device, size: Size) -> Self {
+        let unaligned = 4 * size.0;
+        let bytes_per_row = (unaligned + ROW_ALIGNMENT - 1) / ROW_ALIGNMENT * ROW_ALIGNMENT;
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth: 1,
+            },
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEX_FORMAT,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+            label: Some("capture"),
+        });
+        let view = texture.create_default_view();
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("capture"),
+            size: (bytes_per_row * size.1) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+        });
+
+        Capture {
+            size,
+            bytes_per_row,
+            texture,
+            view,
+            buffer,
+        }
+    }
+
+    /// The view to render to
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Copy the rendered texture to the read-back buffer
+    pub fn copy(&self, device: &wgpu::Device) -> wgpu::CommandBuffer {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("capture"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &self.texture,
+                mip_level: 0,
+                array_layer: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::BufferCopyView {
+                buffer: &self.buffer,
+                offset: 0,
+                bytes_per_row: self.bytes_per_row,
+                rows_per_image: self.size.1,
+            },
+            wgpu::Extent3d {
+                width: self.size.0,
+                height: self.size.1,
+                depth: 1,
+            },
+        );
+        encoder.finish()
+    }
+
+    /// Read back the image
+    ///
+    /// The command buffer from [`Capture::copy`] must have been submitted.
+    /// This blocks until the GPU has finished.
+    pub fn read(self, device: &wgpu::Device) -> Option<RgbaImage> {
+        let len = (self.bytes_per_row * self.size.1) as wgpu::BufferAddress;
+        let req = self.buffer.map_read(0, len);
+        device.poll(wgpu::Maintain::Wait);
+        let mapping = match futures::executor::block_on(req) {
+            Ok(mapping) => mapping,
+            Err(_) => return None,
+        };
+        let src = mapping.as_slice();
+
+        let row_len = 4 * self.size.0 as usize;
+        let mut data = Vec::with_capacity(row_len * self.size.1 as usize);
+        for row in src.chunks(self.bytes_per_row as usize) {
+            // TEX_FORMAT is BGRA; swap to RGBA
+            for px in row[..row_len].chunks(4) {
+                data.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+            }
+        }
+
+        Some(RgbaImage {
+            size: self.size,
+            data,
+        })
+    }
+}
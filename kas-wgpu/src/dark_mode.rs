@@ -0,0 +1,102 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Operating-system light/dark preference
+
+/// Query whether the operating system prefers a dark colour scheme
+///
+/// Returns `None` where the preference cannot be determined. This is a
+/// best-effort query of platform settings:
+///
+/// -   Windows: the `AppsUseLightTheme` registry value
+/// -   macOS: the `AppleInterfaceStyle` user default
+/// -   other Unix: the `GTK_THEME` environment variable or the GNOME
+///     `color-scheme` and `gtk-theme` settings (via `gsettings`)
+///
+/// Changes are reported by the windowing system only on some platforms
+/// (currently Windows and web); see [`crate::Options::system_colours`].
+pub fn system_prefers_dark() -> Option<bool> {
+    query()
+}
+
+#[cfg(target_os = "windows")]
+fn query() -> Option<bool> {
+    let out = command_output(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+            "/v",
+            "AppsUseLightTheme",
+        ],
+    )?;
+    if out.contains("0x0") {
+        Some(true)
+    } else if out.contains("0x1") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn query() -> Option<bool> {
+    // The key is only present when the dark style is used
+    let out = std::process::Command::new("defaults")
+        .args(&["read", "-g", "AppleInterfaceStyle"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    Some(out.status.success() && text.trim().eq_ignore_ascii_case("dark"))
+}
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+fn query() -> Option<bool> {
+    if let Ok(theme) = std::env::var("GTK_THEME") {
+        return Some(is_dark_name(&theme));
+    }
+    let gsettings =
+        |key: &str| command_output("gsettings", &["get", "org.gnome.desktop.interface", key]);
+    if let Some(scheme) = gsettings("color-scheme") {
+        match scheme.trim().trim_matches('\'') {
+            "prefer-dark" => return Some(true),
+            "prefer-light" => return Some(false),
+            _ => (),
+        }
+    }
+    gsettings("gtk-theme").map(|theme| is_dark_name(&theme))
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(unix, not(target_os = "android"))
+)))]
+fn query() -> Option<bool> {
+    None
+}
+
+// Theme names such as "Adwaita:dark" or "Arc-Dark"
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+fn is_dark_name(name: &str) -> bool {
+    name.to_lowercase().contains("dark")
+}
+
+// Run a command, returning its output on success
+#[cfg(any(
+    target_os = "windows",
+    all(unix, not(any(target_os = "macos", target_os = "android")))
+))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let out = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    if out.status.success() {
+        Some(String::from_utf8_lossy(&out.stdout).into_owned())
+    } else {
+        None
+    }
+}
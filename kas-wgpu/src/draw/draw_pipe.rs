@@ -7,14 +7,17 @@
 
 use std::any::Any;
 use std::f32::consts::FRAC_PI_2;
-use wgpu::TextureView;
-use wgpu_glyph::GlyphBrushBuilder;
+use wgpu::{DepthStencilStateDescriptor, TextureView};
+use wgpu_glyph::{GlyphBrush, GlyphBrushBuilder};
 
+use super::draw_text::rotated_projection;
 use super::{
-    flat_round, shaded_round, shaded_square, CustomPipe, CustomPipeBuilder, CustomWindow, DrawPipe,
-    DrawWindow, ShaderManager, TEX_FORMAT,
+    flat_round, images, shaded_round, shaded_square, CustomPipe, CustomPipeBuilder, CustomWindow,
+    DrawPipe, DrawWindow, ShaderManager, TEX_FORMAT,
+};
+use kas::draw::{
+    Colour, Draw, DrawRounded, DrawShaded, DrawShared, ImageId, Pass, TextOrientation,
 };
-use kas::draw::{Colour, Draw, DrawRounded, DrawShaded, DrawShared, Pass};
 use kas::geom::{Coord, Quad, Rect, Size, Vec2};
 
 fn make_depth_texture(device: &wgpu::Device, size: Size) -> Option<TextureView> {
@@ -52,6 +55,7 @@ impl<C: CustomPipe> DrawPipe<C> {
         let shaded_square = shaded_square::Pipeline::new(device, shaders);
         let shaded_round = shaded_round::Pipeline::new(device, shaders);
         let flat_round = flat_round::Pipeline::new(device, shaders);
+        let images = images::Pipeline::new(device, shaders);
         let custom = custom.build(&device, TEX_FORMAT, super::DEPTH_FORMAT);
 
         DrawPipe {
@@ -59,6 +63,7 @@ impl<C: CustomPipe> DrawPipe<C> {
             shaded_square,
             shaded_round,
             flat_round,
+            images,
             custom,
         }
     }
@@ -85,11 +90,11 @@ impl<C: CustomPipe> DrawPipe<C> {
         let shaded_square = self.shaded_square.new_window(device, size, norm);
         let shaded_round = self.shaded_round.new_window(device, size, norm);
         let flat_round = self.flat_round.new_window(device, size);
+        let images = self.images.new_window(device, size);
         let custom = self.custom.new_window(device, size);
 
-        let glyph_brush = GlyphBrushBuilder::using_fonts(self.fonts.clone())
-            .depth_stencil_state(super::GLPYH_DEPTH_DESC)
-            .build(device, TEX_FORMAT);
+        let glyph_brush = self.new_glyph_brush(device);
+        let rotated_glyph_brushes = [self.new_glyph_brush(device), self.new_glyph_brush(device)];
 
         DrawWindow {
             depth: make_depth_texture(device, size),
@@ -97,11 +102,23 @@ impl<C: CustomPipe> DrawPipe<C> {
             shaded_square,
             shaded_round,
             flat_round,
+            images,
             custom,
             glyph_brush,
+            rotated_glyph_brushes,
+            glyph_usage: Default::default(),
         }
     }
 
+    /// Construct a glyph brush
+    ///
+    /// The brush's glyph cache texture grows as required.
+    fn new_glyph_brush(&self, device: &wgpu::Device) -> GlyphBrush<DepthStencilStateDescriptor> {
+        GlyphBrushBuilder::using_fonts(self.fonts.clone())
+            .depth_stencil_state(super::GLPYH_DEPTH_DESC)
+            .build(device, TEX_FORMAT)
+    }
+
     /// Process window resize
     pub fn resize(
         &self,
@@ -119,29 +136,38 @@ impl<C: CustomPipe> DrawPipe<C> {
         self.custom
             .resize(&mut window.custom, device, &mut encoder, size);
         window.flat_round.resize(device, &mut encoder, size);
+        window.images.resize(device, &mut encoder, size);
         encoder.finish()
     }
 
     /// Render batched draw instructions via `rpass`
+    ///
+    /// If `clear_color` is `None`, existing contents of `frame_view` are drawn
+    /// over instead of being cleared.
     pub fn render(
-        &self,
+        &mut self,
         window: &mut DrawWindow<C::Window>,
         device: &mut wgpu::Device,
         frame_view: &wgpu::TextureView,
-        clear_color: wgpu::Color,
+        clear_color: Option<wgpu::Color>,
     ) -> wgpu::CommandBuffer {
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("render"),
         });
 
+        self.images.prepare(device, &mut encoder);
         self.custom.update(&mut window.custom, device, &mut encoder);
 
+        let load_op = match clear_color {
+            Some(_) => wgpu::LoadOp::Clear,
+            None => wgpu::LoadOp::Load,
+        };
         let mut color_attachments = [wgpu::RenderPassColorAttachmentDescriptor {
             attachment: frame_view,
             resolve_target: None,
-            load_op: wgpu::LoadOp::Clear,
+            load_op,
             store_op: wgpu::StoreOp::Store,
-            clear_color,
+            clear_color: clear_color.unwrap_or(wgpu::Color::TRANSPARENT),
         }];
         let mut depth_stencil_attachment = wgpu::RenderPassDepthStencilAttachmentDescriptor {
             attachment: window.depth.as_ref().unwrap(),
@@ -164,6 +190,7 @@ impl<C: CustomPipe> DrawPipe<C> {
             let fr = self
                 .flat_round
                 .render_buf(&mut window.flat_round, device, pass);
+            let im = self.images.render_buf(&mut window.images, device, pass);
 
             {
                 let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -180,6 +207,7 @@ impl<C: CustomPipe> DrawPipe<C> {
                 ss.as_ref().map(|buf| buf.render(&mut rpass));
                 sr.as_ref().map(|buf| buf.render(&mut rpass));
                 fr.as_ref().map(|buf| buf.render(&mut rpass));
+                im.as_ref().map(|buf| buf.render(&mut rpass));
                 self.custom
                     .render_pass(&mut window.custom, device, pass, &mut rpass);
             }
@@ -201,6 +229,19 @@ impl<C: CustomPipe> DrawPipe<C> {
             size,
         );
 
+        let orientations = [TextOrientation::RotatedCw, TextOrientation::RotatedCcw];
+        for (brush, orientation) in window.rotated_glyph_brushes.iter_mut().zip(&orientations) {
+            brush
+                .draw_queued_with_transform(
+                    device,
+                    &mut encoder,
+                    frame_view,
+                    depth_stencil_attachment.clone(),
+                    rotated_projection(*orientation, size),
+                )
+                .expect("glyph_brush.draw_queued_with_transform");
+        }
+
         window
             .glyph_brush
             .draw_queued(
@@ -213,6 +254,13 @@ impl<C: CustomPipe> DrawPipe<C> {
             )
             .expect("glyph_brush.draw_queued");
 
+        if window.glyph_usage.end_frame() {
+            // Rebuild brushes to release cached glyphs of unused font sizes
+            window.glyph_brush = self.new_glyph_brush(device);
+            window.rotated_glyph_brushes =
+                [self.new_glyph_brush(device), self.new_glyph_brush(device)];
+        }
+
         // Keep only first clip region (which is the entire window)
         window.clip_regions.truncate(1);
 
@@ -222,6 +270,26 @@ impl<C: CustomPipe> DrawPipe<C> {
 
 impl<C: CustomPipe> DrawShared for DrawPipe<C> {
     type Draw = DrawWindow<C::Window>;
+
+    #[inline]
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        self.images.load_image(size, data)
+    }
+
+    #[inline]
+    fn remove_image(&mut self, id: ImageId) {
+        self.images.remove_image(id);
+    }
+
+    #[inline]
+    fn memory_usage(&self) -> u64 {
+        self.images.memory_usage()
+    }
+
+    #[inline]
+    fn set_memory_limit(&mut self, limit: Option<u64>) {
+        self.images.set_memory_limit(limit);
+    }
 }
 
 impl<CW: CustomWindow + 'static> Draw for DrawWindow<CW> {
@@ -238,13 +306,23 @@ impl<CW: CustomWindow + 'static> Draw for DrawWindow<CW> {
 
     #[inline]
     fn rect(&mut self, pass: Pass, rect: Quad, col: Colour) {
-        self.shaded_square.rect(pass, rect, col);
+        if col.a < 1.0 {
+            // Only the flat pipeline supports translucency
+            self.flat_round.rect(pass, rect, col);
+        } else {
+            self.shaded_square.rect(pass, rect, col);
+        }
     }
 
     #[inline]
     fn frame(&mut self, pass: Pass, outer: Quad, inner: Quad, col: Colour) {
         self.shaded_square.frame(pass, outer, inner, col);
     }
+
+    #[inline]
+    fn image_region(&mut self, pass: Pass, rect: Quad, id: ImageId, region: Quad) {
+        self.images.image_region(pass, rect, id, region);
+    }
 }
 
 impl<CW: CustomWindow + 'static> DrawRounded for DrawWindow<CW> {
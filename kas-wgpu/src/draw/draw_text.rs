@@ -5,15 +5,19 @@
 
 //! Text drawing API for `kas_wgpu`
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::f32;
-use wgpu_glyph::ab_glyph::{Glyph, PxScale, PxScaleFont, ScaleFont};
+use std::ops::Range;
+use wgpu_glyph::ab_glyph::{self, point, Glyph, PxScale, PxScaleFont, ScaleFont};
 use wgpu_glyph::{
     Extra, GlyphCruncher, HorizontalAlign, Layout, Section, SectionGlyph, Text, VerticalAlign,
 };
 
 use super::{CustomPipe, CustomWindow, DrawPipe, DrawWindow};
-use kas::draw::{DrawText, DrawTextShared, FontArc, FontId, Pass, TextProperties};
-use kas::geom::{Coord, Rect, Vec2};
+use kas::draw::{measure_text, TextLine, TextMeasure, TextOrientation, TextProperties};
+use kas::draw::{vertical_text, DrawText, DrawTextShared, FontArc, FontId, Pass};
+use kas::geom::{Coord, Rect, Size, Vec2};
 use kas::Align;
 
 impl<C: CustomPipe + 'static> DrawTextShared for DrawPipe<C> {
@@ -22,13 +26,109 @@ impl<C: CustomPipe + 'static> DrawTextShared for DrawPipe<C> {
         self.fonts.push(font);
         id
     }
+
+    fn measure_text(
+        &self,
+        text: &str,
+        font: FontId,
+        scale: f32,
+        wrap_width: Option<f32>,
+    ) -> TextMeasure {
+        measure_text(&self.fonts[font.0], scale, text, wrap_width)
+    }
+}
+
+/// Number of distinct font sizes used before unused sizes are evicted
+const MAX_FONT_SIZES: usize = 32;
+
+/// Number of frames after which an unused font size may be evicted
+const FONT_SIZE_FRAMES: u64 = 120;
+
+/// Usage of font sizes by a window's glyph brushes
+///
+/// Glyph brushes grow their cache texture as required, but never shrink it
+/// (glyphs are only replaced when the cache is full). When many font sizes
+/// have been used (e.g. while zooming a document view) but few remain in use,
+/// the brushes should be rebuilt to release memory.
+#[derive(Debug, Default)]
+pub(super) struct GlyphUsage {
+    frame: u64,
+    /// Frame of last use for each `(font, scale)` pair
+    last_used: HashMap<(usize, u32), u64>,
+}
+
+impl GlyphUsage {
+    fn record(&mut self, font: FontId, scale: f32) {
+        self.last_used.insert((font.0, scale.to_bits()), self.frame);
+    }
+
+    /// End the frame
+    ///
+    /// Returns true when glyph brushes should be rebuilt.
+    pub(super) fn end_frame(&mut self) -> bool {
+        self.frame += 1;
+        if self.last_used.len() <= MAX_FONT_SIZES {
+            return false;
+        }
+        let frame = self.frame;
+        self.last_used
+            .retain(|_, last| frame - *last <= FONT_SIZE_FRAMES);
+        // Rebuilding is only worthwhile if most sizes are no longer used
+        self.last_used.len() <= MAX_FONT_SIZES / 2
+    }
+}
+
+/// Projection used by the brush for text with the given rotated `orientation`
+///
+/// This maps text space (see [`TextOrientation::to_text_space`]) to device
+/// coordinates on a target of the given `size`.
+pub(super) fn rotated_projection(orientation: TextOrientation, size: Size) -> [f32; 16] {
+    let (sx, sy) = (2.0 / size.0 as f32, 2.0 / size.1 as f32);
+    // Column-major. Clockwise: screen (x, y) = (-v, u) for text (u, v).
+    let (u, v) = match orientation {
+        TextOrientation::RotatedCw => ([0.0, -sy], [-sx, 0.0]),
+        TextOrientation::RotatedCcw => ([0.0, sy], [sx, 0.0]),
+        _ => ([sx, 0.0], [0.0, -sy]),
+    };
+    [
+        u[0], u[1], 0.0, 0.0, v[0], v[1], 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, -1.0, 1.0, 0.0, 1.0,
+    ]
+}
+
+/// Map `rect` and `text` to the text space and text used for layout
+fn prepare<'a>(rect: Rect, text: &'a str, props: &TextProperties) -> (Rect, Cow<'a, str>) {
+    let rect = props.orientation.to_text_space(rect);
+    let text = match props.orientation {
+        TextOrientation::Vertical => Cow::Owned(vertical_text(text)),
+        _ => Cow::Borrowed(text),
+    };
+    (rect, text)
+}
+
+/// Map a byte index of `text` to the corresponding index of [`vertical_text`]
+fn to_vertical_index(text: &str, byte: usize) -> usize {
+    // Each char except the last is followed by an inserted line break
+    let len = text.len() + text.chars().count().saturating_sub(1);
+    (byte + text[..byte].chars().count()).min(len)
+}
+
+/// Map a byte index of [`vertical_text`] to the corresponding index of `text`
+fn from_vertical_index(text: &str, index: usize) -> usize {
+    for (n, (byte, _)) in text.char_indices().enumerate() {
+        if byte + n >= index {
+            return byte;
+        }
+    }
+    text.len()
 }
 
 fn make_section(pass: Pass, rect: Rect, text: &str, props: TextProperties) -> Section {
     let bounds = Coord::from(rect.size);
+    let vertical = props.orientation == TextOrientation::Vertical;
 
     // TODO: support justified alignment
     let (h_align, h_offset) = match props.align.0 {
+        _ if vertical => (HorizontalAlign::Center, bounds.0 / 2),
         Align::Begin | Align::Stretch => (HorizontalAlign::Left, 0),
         Align::Centre => (HorizontalAlign::Center, bounds.0 / 2),
         Align::End => (HorizontalAlign::Right, bounds.0),
@@ -39,9 +139,10 @@ fn make_section(pass: Pass, rect: Rect, text: &str, props: TextProperties) -> Se
         Align::End => (VerticalAlign::Bottom, bounds.1),
     };
 
-    let text_pos = rect.pos + Coord(h_offset, v_offset);
+    let mut text_pos = Vec2::from(rect.pos + Coord(h_offset, v_offset));
+    text_pos.1 += props.baseline;
 
-    let layout = match props.line_wrap {
+    let layout = match props.line_wrap && !vertical {
         true => Layout::default_wrap(),
         false => Layout::default_single_line(),
     };
@@ -58,7 +159,7 @@ fn make_section(pass: Pass, rect: Rect, text: &str, props: TextProperties) -> Se
     }];
 
     Section {
-        screen_position: Vec2::from(text_pos).into(),
+        screen_position: text_pos.into(),
         bounds: Vec2::from(bounds).into(),
         layout,
         text,
@@ -67,8 +168,36 @@ fn make_section(pass: Pass, rect: Rect, text: &str, props: TextProperties) -> Se
 
 impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
     fn text(&mut self, pass: Pass, rect: Rect, text: &str, props: TextProperties) {
-        self.glyph_brush
-            .queue(make_section(pass, rect, text, props));
+        self.glyph_usage.record(props.font, props.scale.y);
+        let (rect, text) = prepare(rect, text, &props);
+        let section = make_section(pass, rect, &text, props);
+        let brush = match props.orientation {
+            TextOrientation::RotatedCw => &mut self.rotated_glyph_brushes[0],
+            TextOrientation::RotatedCcw => &mut self.rotated_glyph_brushes[1],
+            _ => &mut self.glyph_brush,
+        };
+
+        if props.rendering.hinting {
+            // Lay out as usual, then snap glyph origins to whole pixels
+            let glyphs = brush
+                .glyphs(&section)
+                .map(|glyph| {
+                    let mut glyph = glyph.clone();
+                    let pos = &mut glyph.glyph.position;
+                    *pos = point(pos.x.round(), pos.y.round());
+                    glyph
+                })
+                .collect();
+            let extra = section.text.iter().map(|text| text.extra.clone()).collect();
+            let (min, max) = (Vec2::from(rect.pos), Vec2::from(rect.pos_end()));
+            let bounds = ab_glyph::Rect {
+                min: point(min.0, min.1),
+                max: point(max.0, max.1),
+            };
+            brush.queue_pre_positioned(glyphs, extra, bounds);
+        } else {
+            brush.queue(section);
+        }
     }
 
     #[inline]
@@ -112,14 +241,20 @@ impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
         props: TextProperties,
         byte: usize,
     ) -> Vec2 {
+        let orientation = props.orientation;
+        let byte = match orientation {
+            TextOrientation::Vertical => to_vertical_index(text, byte),
+            _ => byte,
+        };
+        let (rect, text) = prepare(rect, text, &props);
         if byte == 0 {
             // Short-cut. We also cannot iterate since there may be no glyphs.
-            return rect.pos.into();
+            return orientation.vec_from_text_space(rect.pos.into());
         }
         let pass = Pass::new_pass_with_depth(0, 0.0); // values are unimportant
         let mut iter = self
             .glyph_brush
-            .glyphs(make_section(pass, rect, text, props));
+            .glyphs(make_section(pass, rect, &text, props));
 
         let mut advance = false;
         let mut glyph;
@@ -147,7 +282,7 @@ impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
             pos.x += scale_font.h_advance(glyph.glyph.id);
         }
         pos.y -= scale_font.ascent();
-        return Vec2(pos.x, pos.y);
+        orientation.vec_from_text_space(Vec2(pos.x, pos.y))
     }
 
     fn text_index_nearest(
@@ -160,6 +295,10 @@ impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
         if text.len() == 0 {
             return 0; // short-cut
         }
+        let orientation = props.orientation;
+        let pos = orientation.vec_to_text_space(pos);
+        let (rect, layout_text) = prepare(rect, text, &props);
+
         let scale_font = PxScaleFont {
             font: self.glyph_brush.fonts()[props.font.0].clone(),
             scale: props.scale,
@@ -169,7 +308,7 @@ impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
         let pass = Pass::new_pass_with_depth(0, 0.0); // values are unimportant
         let mut iter = self
             .glyph_brush
-            .glyphs(make_section(pass, rect, text, props));
+            .glyphs(make_section(pass, rect, &layout_text, props));
 
         // Find the (horiz, vert) distance between pos and the glyph.
         let dist = |glyph: &Glyph| {
@@ -200,9 +339,88 @@ impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
         }
 
         assert!(
-            best.0 <= text.len(),
+            best.0 <= layout_text.len(),
             "text_index_nearest: index beyond text length!"
         );
-        best.0
+        match orientation {
+            TextOrientation::Vertical => from_vertical_index(text, best.0),
+            _ => best.0,
+        }
+    }
+
+    fn text_lines(&mut self, rect: Rect, text: &str, props: TextProperties) -> Vec<TextLine> {
+        let orientation = props.orientation;
+        let (rect, layout_text) = prepare(rect, text, &props);
+
+        let scale_font = PxScaleFont {
+            font: self.glyph_brush.fonts()[props.font.0].clone(),
+            scale: props.scale,
+        };
+        let (ascent, descent) = (scale_font.ascent(), scale_font.descent());
+        let line_advance = ascent - descent + scale_font.line_gap();
+
+        // Start index and baseline of each visual line with glyphs
+        let pass = Pass::new_pass_with_depth(0, 0.0); // values are unimportant
+        let mut starts: Vec<(usize, f32)> = vec![];
+        for glyph in self
+            .glyph_brush
+            .glyphs(make_section(pass, rect, &layout_text, props))
+        {
+            let y = glyph.glyph.position.y;
+            match starts.last() {
+                Some(last) if (y - last.1).abs() < 0.5 => (),
+                _ => starts.push((glyph.byte_index, y)),
+            }
+        }
+
+        // Hard line breaks are not glyphs, thus empty lines have no start;
+        // we assign these a baseline after that of the previous line.
+        let mut lines: Vec<(Range<usize>, Option<f32>)> = vec![];
+        let mut next = starts.iter().peekable();
+        let mut start = 0;
+        for para in layout_text.split('\n') {
+            let end = start + para.len();
+            let mut line_start = start;
+            let mut baseline = None;
+            while let Some((index, y)) = next.peek().cloned() {
+                if *index >= end {
+                    break;
+                }
+                next.next();
+                if baseline.is_some() {
+                    // Wrapped: exclude the white-space at which we wrapped
+                    let line_end = line_start + layout_text[line_start..*index].trim_end().len();
+                    lines.push((line_start..line_end, baseline));
+                    line_start = *index;
+                }
+                baseline = Some(*y);
+            }
+            lines.push((line_start..end, baseline));
+            start = end + 1;
+        }
+
+        let first_known = lines.iter().position(|line| line.1.is_some());
+        let mut y = match first_known {
+            Some(i) => lines[i].1.unwrap() - i as f32 * line_advance,
+            None => rect.pos.1 as f32 + props.baseline + ascent,
+        };
+        lines
+            .into_iter()
+            .map(|(range, baseline)| {
+                let baseline = baseline.unwrap_or(y);
+                y = baseline + line_advance;
+                let range = match orientation {
+                    TextOrientation::Vertical => {
+                        from_vertical_index(text, range.start)..from_vertical_index(text, range.end)
+                    }
+                    _ => range,
+                };
+                TextLine {
+                    range,
+                    top: baseline - ascent,
+                    bottom: baseline - descent,
+                }
+            })
+            .collect()
     }
 }
@@ -7,7 +7,7 @@
 
 use std::mem::size_of;
 
-use crate::draw::{Rgb, ShaderManager};
+use crate::draw::{Rgba, ShaderManager};
 use kas::draw::{Colour, Pass};
 use kas::geom::{Quad, Size, Vec2, Vec3};
 
@@ -17,12 +17,12 @@ const OFFSET: f32 = 0.125;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
-struct Vertex(Vec3, Rgb, f32, Vec2, Vec2);
+struct Vertex(Vec3, Rgba, f32, Vec2, Vec2);
 unsafe impl bytemuck::Zeroable for Vertex {}
 unsafe impl bytemuck::Pod for Vertex {}
 
 impl Vertex {
-    fn new2(v: Vec2, d: f32, col: Rgb, inner: f32, n: Vec2, p: Vec2) -> Self {
+    fn new2(v: Vec2, d: f32, col: Rgba, inner: f32, n: Vec2, p: Vec2) -> Self {
         let v = Vec3::from2(v, d);
         Vertex(v, col, inner, n, p)
     }
@@ -124,7 +124,7 @@ impl Pipeline {
                     step_mode: wgpu::InputStepMode::Vertex,
                     attributes: &wgpu::vertex_attr_array![
                         0 => Float3,
-                        1 => Float3,
+                        1 => Float4,
                         2 => Float,
                         3 => Float2,
                         4 => Float2
@@ -264,6 +264,37 @@ impl Window {
         ]);
     }
 
+    /// Draw a plain rectangle
+    ///
+    /// Unlike the shaded pipelines, this respects the colour's alpha component.
+    pub fn rect(&mut self, pass: Pass, rect: Quad, col: Colour) {
+        let aa = rect.a;
+        let bb = rect.b;
+
+        if !aa.lt(bb) {
+            // zero / negative size: nothing to draw
+            return;
+        }
+
+        let col = col.into();
+        let ab = Vec2(aa.0, bb.1);
+        let ba = Vec2(bb.0, aa.1);
+
+        // All dir values are zero, thus the whole area is filled
+        let n0 = Vec2::splat(0.0);
+        let depth = pass.depth();
+        let aa = Vertex::new2(aa, depth, col, 0.0, n0, n0);
+        let ab = Vertex::new2(ab, depth, col, 0.0, n0, n0);
+        let ba = Vertex::new2(ba, depth, col, 0.0, n0, n0);
+        let bb = Vertex::new2(bb, depth, col, 0.0, n0, n0);
+
+        #[rustfmt::skip]
+        self.add_vertices(pass.pass(), &[
+            ba, aa, ab,
+            ab, bb, ba,
+        ]);
+    }
+
     /// Bounds on input: `0 ≤ inner_radius ≤ 1`.
     pub fn circle(&mut self, pass: Pass, rect: Quad, inner_radius: f32, col: Colour) {
         let aa = rect.a;
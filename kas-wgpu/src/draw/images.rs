@@ -0,0 +1,440 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Image pipeline
+
+use log::warn;
+use std::mem::size_of;
+
+use crate::draw::ShaderManager;
+use kas::draw::{ImageId, Pass};
+use kas::geom::{Quad, Size, Vec2, Vec3};
+
+/// Alignment of rows in buffer-to-texture copies
+const ROW_ALIGN: u32 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Vertex(Vec3, Vec2);
+unsafe impl bytemuck::Zeroable for Vertex {}
+unsafe impl bytemuck::Pod for Vertex {}
+
+/// An image resident on the GPU
+struct Image {
+    _texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+}
+
+/// A pipeline for rendering images
+///
+/// This also owns the (shared) image resources.
+pub struct Pipeline {
+    bind_group_layout: wgpu::BindGroupLayout,
+    image_bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    render_pipeline: wgpu::RenderPipeline,
+    images: Vec<Option<Image>>,
+    /// Memory used by each image (including those pending), in bytes
+    memory: Vec<u64>,
+    free: Vec<u32>,
+    /// Images awaiting upload
+    pending: Vec<(ImageId, Size, Vec<u8>)>,
+    /// Total of `memory`
+    memory_usage: u64,
+    memory_limit: Option<u64>,
+}
+
+/// Per-window state
+pub struct Window {
+    bind_group: wgpu::BindGroup,
+    scale_buf: wgpu::Buffer,
+    /// Per pass: vertices (six per image) and the image drawn by each six
+    passes: Vec<(Vec<Vertex>, Vec<ImageId>)>,
+}
+
+/// Buffer used during render pass
+///
+/// This buffer must not be dropped before the render pass.
+pub struct RenderBuffer<'a> {
+    pipe: &'a wgpu::RenderPipeline,
+    images: &'a [Option<Image>],
+    vertices: &'a mut Vec<Vertex>,
+    ids: &'a mut Vec<ImageId>,
+    bind_group: &'a wgpu::BindGroup,
+    buffer: wgpu::Buffer,
+}
+
+impl<'a> RenderBuffer<'a> {
+    /// Do the render
+    pub fn render(&'a self, rpass: &mut wgpu::RenderPass<'a>) {
+        rpass.set_pipeline(self.pipe);
+        rpass.set_bind_group(0, self.bind_group, &[]);
+        rpass.set_vertex_buffer(0, &self.buffer, 0, 0);
+        for (i, id) in self.ids.iter().enumerate() {
+            if let Some(Some(image)) = self.images.get(id.0 as usize) {
+                rpass.set_bind_group(1, &image.bind_group, &[]);
+                let start = 6 * i as u32;
+                rpass.draw(start..(start + 6), 0..1);
+            }
+        }
+    }
+}
+
+impl<'a> Drop for RenderBuffer<'a> {
+    fn drop(&mut self) {
+        self.vertices.clear();
+        self.ids.clear();
+    }
+}
+
+impl Pipeline {
+    /// Construct
+    pub fn new(device: &wgpu::Device, shaders: &ShaderManager) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            bindings: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::VERTEX,
+                ty: wgpu::BindingType::UniformBuffer { dynamic: false },
+            }],
+            label: None,
+        });
+
+        let image_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                bindings: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::SampledTexture {
+                            multisampled: false,
+                            dimension: wgpu::TextureViewDimension::D2,
+                            component_type: wgpu::TextureComponentType::Float,
+                        },
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler { comparison: false },
+                    },
+                ],
+                label: None,
+            });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 0.0,
+            compare: wgpu::CompareFunction::Always,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            bind_group_layouts: &[&bind_group_layout, &image_bind_group_layout],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            layout: &pipeline_layout,
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &shaders.vert_2,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &shaders.frag_image,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: wgpu::CullMode::None,
+                depth_bias: 0,
+                depth_bias_slope_scale: 0.0,
+                depth_bias_clamp: 0.0,
+            }),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format: wgpu::TextureFormat::Bgra8UnormSrgb,
+                color_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: Some(super::DEPTH_DESC),
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint16,
+                vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                    stride: size_of::<Vertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::InputStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float3, 1 => Float2],
+                }],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+
+        Pipeline {
+            bind_group_layout,
+            image_bind_group_layout,
+            sampler,
+            render_pipeline,
+            images: vec![],
+            memory: vec![],
+            free: vec![],
+            pending: vec![],
+            memory_usage: 0,
+            memory_limit: None,
+        }
+    }
+
+    /// Approximate memory used by images, in bytes
+    ///
+    /// This is the size of texture data, excluding any padding.
+    pub fn memory_usage(&self) -> u64 {
+        self.memory_usage
+    }
+
+    /// Limit memory used by images
+    pub fn set_memory_limit(&mut self, limit: Option<u64>) {
+        self.memory_limit = limit;
+    }
+
+    /// Load an image
+    ///
+    /// The image is uploaded on the next call to [`Pipeline::prepare`].
+    /// Returns `None` if the memory limit would be exceeded.
+    pub fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        assert_eq!(
+            data.len(),
+            4 * size.0 as usize * size.1 as usize,
+            "load_image: data length does not match size"
+        );
+        let memory = data.len() as u64;
+        if let Some(limit) = self.memory_limit {
+            if self.memory_usage + memory > limit {
+                warn!(
+                    "load_image: memory limit exceeded ({} + {} > {} bytes)",
+                    self.memory_usage, memory, limit
+                );
+                return None;
+            }
+        }
+
+        let id = match self.free.pop() {
+            Some(n) => ImageId(n),
+            None => {
+                self.images.push(None);
+                self.memory.push(0);
+                ImageId(self.images.len() as u32 - 1)
+            }
+        };
+        self.memory[id.0 as usize] = memory;
+        self.memory_usage += memory;
+        self.pending.push((id, size, data.to_vec()));
+        Some(id)
+    }
+
+    /// Free an image
+    pub fn remove_image(&mut self, id: ImageId) {
+        let index = id.0 as usize;
+        if index < self.images.len() && !self.free.contains(&id.0) {
+            self.images[index] = None;
+            self.memory_usage -= self.memory[index];
+            self.memory[index] = 0;
+            self.pending.retain(|pending| pending.0 != id);
+            self.free.push(id.0);
+        }
+    }
+
+    /// Upload pending images
+    pub fn prepare(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        for (id, size, data) in self.pending.drain(..) {
+            if size.0 * size.1 == 0 {
+                // Wgpu does not support zero-sized textures; draw nothing
+                continue;
+            }
+
+            // Rows of the source buffer must be aligned
+            let row_len = 4 * size.0;
+            let bytes_per_row = (row_len + ROW_ALIGN - 1) / ROW_ALIGN * ROW_ALIGN;
+            let mut padded = vec![0u8; (bytes_per_row * size.1) as usize];
+            for (src, dst) in data
+                .chunks(row_len as usize)
+                .zip(padded.chunks_mut(bytes_per_row as usize))
+            {
+                dst[..src.len()].copy_from_slice(src);
+            }
+            let buffer = device.create_buffer_with_data(&padded, wgpu::BufferUsage::COPY_SRC);
+
+            let extent = wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth: 1,
+            };
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                size: extent,
+                array_layer_count: 1,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+                label: None,
+            });
+            encoder.copy_buffer_to_texture(
+                wgpu::BufferCopyView {
+                    buffer: &buffer,
+                    offset: 0,
+                    bytes_per_row,
+                    rows_per_image: size.1,
+                },
+                wgpu::TextureCopyView {
+                    texture: &texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin: wgpu::Origin3d { x: 0, y: 0, z: 0 },
+                },
+                extent,
+            );
+
+            let view = texture.create_default_view();
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.image_bind_group_layout,
+                bindings: &[
+                    wgpu::Binding {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::Binding {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+                label: None,
+            });
+
+            self.images[id.0 as usize] = Some(Image {
+                _texture: texture,
+                bind_group,
+            });
+        }
+    }
+
+    /// Construct per-window state
+    pub fn new_window(&self, device: &wgpu::Device, size: Size) -> Window {
+        let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+
+        type Scale = [f32; 2];
+        let scale_factor: Scale = [2.0 / size.0 as f32, -2.0 / size.1 as f32];
+        let scale_buf = device.create_buffer_with_data(bytemuck::cast_slice(&scale_factor), usage);
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.bind_group_layout,
+            bindings: &[wgpu::Binding {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer {
+                    buffer: &scale_buf,
+                    range: 0..(size_of::<Scale>() as u64),
+                },
+            }],
+            label: None,
+        });
+
+        Window {
+            bind_group,
+            scale_buf,
+            passes: vec![],
+        }
+    }
+
+    /// Construct a render buffer
+    pub fn render_buf<'a>(
+        &'a self,
+        window: &'a mut Window,
+        device: &wgpu::Device,
+        pass: usize,
+    ) -> Option<RenderBuffer<'a>> {
+        if pass >= window.passes.len() || window.passes[pass].0.len() == 0 {
+            return None;
+        }
+
+        let (vertices, ids) = &mut window.passes[pass];
+        let buffer = device
+            .create_buffer_with_data(bytemuck::cast_slice(&vertices), wgpu::BufferUsage::VERTEX);
+
+        Some(RenderBuffer {
+            pipe: &self.render_pipeline,
+            images: &self.images,
+            vertices,
+            ids,
+            bind_group: &window.bind_group,
+            buffer,
+        })
+    }
+}
+
+impl Window {
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        size: Size,
+    ) {
+        type Scale = [f32; 2];
+        let scale_factor: Scale = [2.0 / size.0 as f32, -2.0 / size.1 as f32];
+        let scale_buf = device.create_buffer_with_data(
+            bytemuck::cast_slice(&scale_factor),
+            wgpu::BufferUsage::COPY_SRC,
+        );
+        let byte_len = size_of::<Scale>() as u64;
+
+        encoder.copy_buffer_to_buffer(&scale_buf, 0, &self.scale_buf, 0, byte_len);
+    }
+
+    /// Add an image region to the buffer
+    ///
+    /// The `region` is in texture coordinates.
+    pub fn image_region(&mut self, pass: Pass, rect: Quad, id: ImageId, region: Quad) {
+        let aa = rect.a;
+        let bb = rect.b;
+
+        if !aa.lt(bb) {
+            // zero / negative size: nothing to draw
+            return;
+        }
+
+        let depth = pass.depth();
+        let ab = Vec3(aa.0, bb.1, depth);
+        let ba = Vec3(bb.0, aa.1, depth);
+        let aa = Vec3::from2(aa, depth);
+        let bb = Vec3::from2(bb, depth);
+
+        // Texture coordinates: (0, 0) is the top-left corner
+        let (taa, tbb) = (region.a, region.b);
+        let (tab, tba) = (region.ab(), region.ba());
+
+        let pass = pass.pass();
+        if self.passes.len() <= pass {
+            // We only need one more, but no harm in adding extra
+            self.passes.resize(pass + 8, Default::default());
+        }
+
+        #[rustfmt::skip]
+        self.passes[pass].0.extend_from_slice(&[
+            Vertex(aa, taa), Vertex(ba, tba), Vertex(ab, tab),
+            Vertex(ab, tab), Vertex(ba, tba), Vertex(bb, tbb),
+        ]);
+        self.passes[pass].1.push(id);
+    }
+}
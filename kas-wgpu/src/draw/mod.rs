@@ -11,6 +11,7 @@ mod custom;
 mod draw_pipe;
 mod draw_text;
 mod flat_round;
+mod images;
 mod shaded_round;
 mod shaded_square;
 mod shaders;
@@ -60,12 +61,34 @@ impl From<kas::draw::Colour> for Rgb {
     }
 }
 
+/// 4-part colour data (with alpha)
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Rgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl From<kas::draw::Colour> for Rgba {
+    fn from(c: kas::draw::Colour) -> Self {
+        Rgba {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+            a: c.a,
+        }
+    }
+}
+
 /// Shared pipeline data
 pub struct DrawPipe<C> {
     fonts: Vec<FontArc>,
     shaded_square: shaded_square::Pipeline,
     shaded_round: shaded_round::Pipeline,
     flat_round: flat_round::Pipeline,
+    images: images::Pipeline,
     custom: C,
 }
 
@@ -76,6 +99,10 @@ pub struct DrawWindow<CW: CustomWindow> {
     shaded_square: shaded_square::Window,
     shaded_round: shaded_round::Window,
     flat_round: flat_round::Window,
+    images: images::Window,
     custom: CW,
     glyph_brush: GlyphBrush<DepthStencilStateDescriptor>, // TODO: should be in DrawPipe
+    /// Brushes for text rotated clockwise and anti-clockwise respectively
+    rotated_glyph_brushes: [GlyphBrush<DepthStencilStateDescriptor>; 2],
+    glyph_usage: draw_text::GlyphUsage,
 }
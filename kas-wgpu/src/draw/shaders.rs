@@ -15,6 +15,7 @@ use wgpu::ShaderModule;
 /// Not really optimal (we could embed SPIR-V directly or load shaders from
 /// external resources), but simple to set up and use.
 pub struct ShaderManager {
+    pub vert_2: ShaderModule,
     pub vert_3122: ShaderModule,
     pub vert_32: ShaderModule,
     pub vert_322: ShaderModule,
@@ -22,12 +23,18 @@ pub struct ShaderManager {
     pub frag_flat_round: ShaderModule,
     pub frag_shaded_square: ShaderModule,
     pub frag_shaded_round: ShaderModule,
+    pub frag_image: ShaderModule,
 }
 
 impl ShaderManager {
     pub fn new(device: &wgpu::Device) -> Result<Self, Error> {
         let mut compiler = Compiler::new().unwrap();
 
+        let fname = "shaders/scaled2.vert";
+        let source = include_str!("shaders/scaled2.vert");
+        let artifact = compiler.compile_into_spirv(source, Vertex, fname, "main", None)?;
+        let vert_2 = device.create_shader_module(&artifact.as_binary());
+
         let fname = "shaders/scaled3122.vert";
         let source = include_str!("shaders/scaled3122.vert");
         let artifact = compiler.compile_into_spirv(source, Vertex, fname, "main", None)?;
@@ -63,7 +70,13 @@ impl ShaderManager {
         let artifact = compiler.compile_into_spirv(source, Fragment, fname, "main", None)?;
         let frag_shaded_round = device.create_shader_module(&artifact.as_binary());
 
+        let fname = "shaders/image.frag";
+        let source = include_str!("shaders/image.frag");
+        let artifact = compiler.compile_into_spirv(source, Fragment, fname, "main", None)?;
+        let frag_image = device.create_shader_module(&artifact.as_binary());
+
         Ok(ShaderManager {
+            vert_2,
             vert_3122,
             vert_32,
             vert_322,
@@ -71,6 +84,7 @@ impl ShaderManager {
             frag_flat_round,
             frag_shaded_square,
             frag_shaded_round,
+            frag_image,
         })
     }
 }
@@ -0,0 +1,504 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Embedding within an external renderer
+
+use log::{debug, warn};
+use std::any::Any;
+use std::num::NonZeroU32;
+use std::time::Instant;
+
+use kas::draw::{DrawShared, ImageId, SizeHandle};
+use kas::event::{CursorIcon, FrameInfo, ManagerState, UpdateData, UpdateHandle};
+use kas::geom::{Coord, Rect, Size};
+use kas::layout::SolveCache;
+use kas::string::{CowString, CowStringL};
+use kas::{ThemeAction, ThemeApi, TkAction, WindowId};
+use kas_theme::Theme;
+use winit::event::WindowEvent;
+
+use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow, ShaderManager};
+use crate::Error;
+
+/// Actions deferred until the current event has been handled
+enum Pending {
+    AddPopup(WindowId, kas::Popup),
+    Close(WindowId),
+    Action(TkAction),
+    Update(UpdateHandle, u64, Option<UpdateData>),
+}
+
+/// Toolkit state of an [`Embedded`] UI
+struct TkState {
+    window_id: u32,
+    pending: Vec<Pending>,
+    theme_action: ThemeAction,
+    cursor_icon: CursorIcon,
+}
+
+impl TkState {
+    fn next_window_id(&mut self) -> WindowId {
+        self.window_id += 1;
+        WindowId::new(NonZeroU32::new(self.window_id).unwrap())
+    }
+}
+
+/// A KAS UI embedded within an external renderer
+///
+/// Where [`crate::Toolkit`] owns the graphics device, windows and event loop,
+/// this type does none of these: the host application passes in its own
+/// `wgpu::Device` and `wgpu::Queue`, forwards input events and asks the UI to
+/// draw itself over a `wgpu::TextureView` of its choosing. This allows use of
+/// a KAS UI as an overlay within a game or other application.
+///
+/// The host is responsible for:
+///
+/// -   forwarding input events via [`Embedded::handle_event`], translating
+///     coordinates to be relative to the UI's region
+/// -   reporting size and scale factor changes via [`Embedded::resize`] and
+///     [`Embedded::set_scale_factor`]
+/// -   calling [`Embedded::update`] after each batch of events and redrawing
+///     when this reports [`TkAction::REDRAW`] or higher
+/// -   calling [`Embedded::update_timer`] at the time reported by
+///     [`Embedded::next_resume`]
+/// -   calling [`Embedded::update_frame`] before rendering, when
+///     [`Embedded::frame_requested`] (for animations)
+///
+/// Pop-ups are drawn within the UI's region. The UI cannot open new windows
+/// and has no clipboard access.
+pub struct Embedded<C: CustomPipe, T: Theme<DrawPipe<C>>>
+where
+    T::Window: kas_theme::Window<DrawWindow<C::Window>>,
+{
+    widget: Box<dyn kas::Window>,
+    window_id: WindowId,
+    mgr: ManagerState,
+    solve_cache: SolveCache,
+    size: Size,
+    scale_factor: f64,
+    pipe: DrawPipe<C>,
+    draw: DrawWindow<C::Window>,
+    theme: T,
+    theme_window: T::Window,
+    state: TkState,
+}
+
+impl<T: Theme<DrawPipe<()>>> Embedded<(), T>
+where
+    T::Window: kas_theme::Window<DrawWindow<()>>,
+{
+    /// Construct
+    ///
+    /// See [`Embedded::new_custom`].
+    pub fn new(
+        device: &wgpu::Device,
+        theme: T,
+        widget: Box<dyn kas::Window>,
+        size: Size,
+        scale_factor: f64,
+    ) -> Result<Self, Error> {
+        Self::new_custom((), device, theme, widget, size, scale_factor)
+    }
+}
+
+impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Embedded<C, T>
+where
+    T::Window: kas_theme::Window<DrawWindow<C::Window>>,
+{
+    /// Construct with a custom draw pipe
+    ///
+    /// The UI occupies a region of the given `size` (in physical pixels).
+    /// Render targets passed to [`Embedded::render`] must use texture format
+    /// `Bgra8UnormSrgb`.
+    pub fn new_custom<CB: CustomPipeBuilder<Pipe = C>>(
+        custom: CB,
+        device: &wgpu::Device,
+        mut theme: T,
+        mut widget: Box<dyn kas::Window>,
+        size: Size,
+        scale_factor: f64,
+    ) -> Result<Self, Error> {
+        use kas_theme::Window;
+
+        let shaders = ShaderManager::new(device)?;
+        let mut pipe = DrawPipe::new(custom, device, &shaders);
+        theme.init(&mut pipe);
+
+        let mut draw = pipe.new_window(device, size);
+        let mut theme_window = theme.new_window(&mut draw, scale_factor as f32);
+
+        let mut size_handle = unsafe { theme_window.size_handle(&mut draw) };
+        let solve_cache = SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle);
+        drop(size_handle);
+
+        let mut state = TkState {
+            window_id: 0,
+            pending: vec![],
+            theme_action: ThemeAction::None,
+            cursor_icon: CursorIcon::Default,
+        };
+        let window_id = state.next_window_id();
+
+        let mut mgr = ManagerState::new(scale_factor);
+        let mut tkw = TkWindow::<C, T> {
+            state: &mut state,
+            theme: &mut theme,
+            draw: &mut draw,
+            theme_window: &mut theme_window,
+            pipe: &mut pipe,
+        };
+        mgr.configure(&mut tkw, &mut *widget);
+
+        let mut r = Embedded {
+            widget,
+            window_id,
+            mgr,
+            solve_cache,
+            size,
+            scale_factor,
+            pipe,
+            draw,
+            theme,
+            theme_window,
+            state,
+        };
+        r.apply_size();
+        r.process_pending();
+        Ok(r)
+    }
+
+    /// Get the ideal size of the UI
+    pub fn ideal_size(&self) -> Size {
+        self.solve_cache.ideal(true)
+    }
+
+    /// Get the cursor icon requested by the UI
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.state.cursor_icon
+    }
+
+    /// Set the size of the UI's region
+    pub fn resize(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, size: Size) {
+        if size == self.size {
+            return;
+        }
+        let buf = self.pipe.resize(&mut self.draw, device, size);
+        queue.submit(&[buf]);
+        self.size = size;
+        self.apply_size();
+    }
+
+    /// Set the scale factor
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.theme
+            .update_window(&mut self.draw, &mut self.theme_window, scale_factor as f32);
+        self.mgr.set_dpi_factor(scale_factor);
+        self.solve_cache.invalidate_rule_cache();
+        self.apply_size();
+    }
+
+    /// Handle an input event
+    ///
+    /// Coordinates of pointer events must be relative to the UI's region.
+    /// Window events other than input events (e.g. `Resized`) are ignored.
+    pub fn handle_event(&mut self, event: WindowEvent) {
+        let widget = &mut *self.widget;
+        let mut tkw = TkWindow::<C, T> {
+            state: &mut self.state,
+            theme: &mut self.theme,
+            draw: &mut self.draw,
+            theme_window: &mut self.theme_window,
+            pipe: &mut self.pipe,
+        };
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.handle_winit(widget, event);
+        });
+        self.process_pending();
+    }
+
+    /// Trigger an update handle
+    pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
+        self.state
+            .pending
+            .push(Pending::Update(handle, payload, None));
+        self.process_pending();
+    }
+
+    /// Trigger an update handle, passing typed data
+    ///
+    /// See [`kas::event::Manager::trigger_update_data`].
+    pub fn trigger_update_data<T: Any + Send + Sync>(
+        &mut self,
+        handle: UpdateHandle,
+        payload: u64,
+        data: T,
+    ) {
+        let data = Some(UpdateData::new(data));
+        self.state
+            .pending
+            .push(Pending::Update(handle, payload, data));
+        self.process_pending();
+    }
+
+    /// Update, after handling all events
+    ///
+    /// Returns the action required of the host: if this is not empty, the host
+    /// should redraw (via [`Embedded::render`]); if it contains
+    /// [`TkAction::CLOSE`] or [`TkAction::CLOSE_ALL`], the UI requests that it
+    /// be closed.
+    pub fn update(&mut self) -> TkAction {
+        let mut tkw = TkWindow::<C, T> {
+            state: &mut self.state,
+            theme: &mut self.theme,
+            draw: &mut self.draw,
+            theme_window: &mut self.theme_window,
+            pipe: &mut self.pipe,
+        };
+        let action = self.mgr.update(&mut tkw, &mut *self.widget);
+
+        // Handle only the most comprehensive action (see TkAction docs)
+        if action.intersects(TkAction::CLOSE | TkAction::CLOSE_ALL) {
+            // Closing is left to the caller
+        } else if action.contains(TkAction::RECONFIGURE) {
+            debug!("Embedded: reconfigure");
+            self.mgr.configure(&mut tkw, &mut *self.widget);
+            self.solve_cache.invalidate_rule_cache();
+            self.apply_size();
+        } else if action.contains(TkAction::POPUP) {
+            use kas_theme::Window;
+            let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw) };
+            self.widget.resize_popups(&mut size_handle);
+            drop(size_handle);
+
+            let mut tkw = TkWindow::<C, T> {
+                state: &mut self.state,
+                theme: &mut self.theme,
+                draw: &mut self.draw,
+                theme_window: &mut self.theme_window,
+                pipe: &mut self.pipe,
+            };
+            self.mgr.region_moved(&mut tkw, &mut *self.widget);
+        } else if action.contains(TkAction::REGION_MOVED) {
+            self.mgr.region_moved(&mut tkw, &mut *self.widget);
+        }
+        self.process_pending();
+
+        match std::mem::replace(&mut self.state.theme_action, ThemeAction::None) {
+            ThemeAction::None => action,
+            ThemeAction::RedrawAll => action | TkAction::REDRAW,
+            ThemeAction::ThemeResize => {
+                self.theme.update_window(
+                    &mut self.draw,
+                    &mut self.theme_window,
+                    self.scale_factor as f32,
+                );
+                self.solve_cache.invalidate_rule_cache();
+                self.apply_size();
+                action | TkAction::REDRAW
+            }
+        }
+    }
+
+    /// Get the next time at which [`Embedded::update_timer`] should be called
+    pub fn next_resume(&self) -> Option<Instant> {
+        self.mgr.next_resume()
+    }
+
+    /// Handle timer updates
+    ///
+    /// Call at (or after) the time reported by [`Embedded::next_resume`].
+    pub fn update_timer(&mut self) {
+        let widget = &mut *self.widget;
+        let mut tkw = TkWindow::<C, T> {
+            state: &mut self.state,
+            theme: &mut self.theme,
+            draw: &mut self.draw,
+            theme_window: &mut self.theme_window,
+            pipe: &mut self.pipe,
+        };
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.update_timer(widget);
+        });
+        self.process_pending();
+    }
+
+    /// True if any widget requested [`kas::event::Event::Frame`]
+    pub fn frame_requested(&self) -> bool {
+        self.mgr.frame_requested()
+    }
+
+    /// Deliver [`kas::event::Event::Frame`]
+    ///
+    /// Call immediately before [`Embedded::render`], passing the host's
+    /// estimate of the frame's presentation time.
+    pub fn update_frame(&mut self, info: FrameInfo) {
+        let widget = &mut *self.widget;
+        let mut tkw = TkWindow::<C, T> {
+            state: &mut self.state,
+            theme: &mut self.theme,
+            draw: &mut self.draw,
+            theme_window: &mut self.theme_window,
+            pipe: &mut self.pipe,
+        };
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.update_frame(widget, info);
+        });
+        self.process_pending();
+    }
+
+    /// Draw the UI over `view`
+    ///
+    /// Existing contents of `view` are not cleared; the UI's region must be
+    /// the size of `view`. The view must use texture format `Bgra8UnormSrgb`.
+    pub fn render(
+        &mut self,
+        device: &mut wgpu::Device,
+        queue: &wgpu::Queue,
+        view: &wgpu::TextureView,
+    ) {
+        if self.size.0 * self.size.1 == 0 {
+            return;
+        }
+
+        let rect = Rect::new(Coord::ZERO, self.size);
+        let mut draw_handle = unsafe {
+            self.theme
+                .draw_handle(&mut self.draw, &mut self.theme_window, rect)
+        };
+        self.widget.draw(&mut draw_handle, &self.mgr, false);
+        drop(draw_handle);
+
+        let buf = self.pipe.render(&mut self.draw, device, view, None);
+        queue.submit(&[buf]);
+    }
+}
+
+// Internal functions
+impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Embedded<C, T>
+where
+    T::Window: kas_theme::Window<DrawWindow<C::Window>>,
+{
+    fn apply_size(&mut self) {
+        use kas_theme::Window;
+        let rect = Rect::new(Coord::ZERO, self.size);
+        debug!("Embedded: resizing to rect = {:?}", rect);
+
+        let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw) };
+        self.solve_cache
+            .apply_rect(self.widget.as_widget_mut(), &mut size_handle, rect, true);
+        self.widget.resize_popups(&mut size_handle);
+    }
+
+    fn process_pending(&mut self) {
+        while let Some(pending) = self.state.pending.pop() {
+            let widget = &mut *self.widget;
+            let window_id = self.window_id;
+            let mut tkw = TkWindow::<C, T> {
+                state: &mut self.state,
+                theme: &mut self.theme,
+                draw: &mut self.draw,
+                theme_window: &mut self.theme_window,
+                pipe: &mut self.pipe,
+            };
+            match pending {
+                Pending::AddPopup(id, popup) => self.mgr.with(&mut tkw, |mgr| {
+                    kas::Window::add_popup(widget, mgr, id, popup);
+                }),
+                Pending::Close(id) if id == window_id => {
+                    self.mgr.send_action(TkAction::CLOSE);
+                }
+                Pending::Close(id) => self.mgr.with(&mut tkw, |mgr| {
+                    widget.remove_popup(mgr, id);
+                }),
+                Pending::Action(action) => self.mgr.send_action(action),
+                Pending::Update(handle, payload, data) => self.mgr.with(&mut tkw, |mgr| {
+                    mgr.update_handle(widget, handle, payload, data);
+                }),
+            }
+        }
+    }
+}
+
+/// Implementation of [`kas::TkWindow`] for [`Embedded`]
+struct TkWindow<'a, C: CustomPipe, T: Theme<DrawPipe<C>>>
+where
+    T::Window: kas_theme::Window<DrawWindow<C::Window>>,
+{
+    state: &'a mut TkState,
+    theme: &'a mut T,
+    draw: &'a mut DrawWindow<C::Window>,
+    theme_window: &'a mut T::Window,
+    pipe: &'a mut DrawPipe<C>,
+}
+
+impl<'a, C, T> kas::TkWindow for TkWindow<'a, C, T>
+where
+    C: CustomPipe,
+    T: Theme<DrawPipe<C>>,
+    T::Window: kas_theme::Window<DrawWindow<C::Window>>,
+{
+    fn add_popup(&mut self, popup: kas::Popup) -> WindowId {
+        let id = self.state.next_window_id();
+        self.state.pending.push(Pending::AddPopup(id, popup));
+        id
+    }
+
+    fn add_window(&mut self, widget: Box<dyn kas::Window>) -> WindowId {
+        warn!("Embedded: unable to add window \"{}\"", widget.title());
+        self.state.next_window_id()
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        self.state.pending.push(Pending::Close(id));
+    }
+
+    fn send_action_to(&mut self, _: Option<WindowId>, action: TkAction) {
+        // Pop-ups are drawn within the single embedded window
+        self.state.pending.push(Pending::Action(action));
+    }
+
+    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64, data: Option<UpdateData>) {
+        self.state
+            .pending
+            .push(Pending::Update(handle, payload, data));
+    }
+
+    #[inline]
+    fn get_clipboard(&mut self) -> Option<CowString> {
+        None
+    }
+
+    #[inline]
+    fn set_clipboard<'c>(&mut self, _: CowStringL<'c>) {}
+
+    fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
+        let action = f(self.theme);
+        self.state.theme_action = self.state.theme_action.max(action);
+    }
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        use kas_theme::Window;
+        let mut size_handle = unsafe { self.theme_window.size_handle(self.draw) };
+        f(&mut size_handle);
+    }
+
+    #[inline]
+    fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.state.cursor_icon = icon;
+    }
+
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        self.pipe.load_image(size, data)
+    }
+
+    fn remove_image(&mut self, id: ImageId) {
+        self.pipe.remove_image(id);
+    }
+
+    fn memory_usage(&self) -> u64 {
+        self.pipe.memory_usage()
+    }
+}
@@ -14,6 +14,8 @@ use winit::event::{Event, StartCause};
 use winit::event_loop::{ControlFlow, EventLoopWindowTarget};
 use winit::window as ww;
 
+use kas::dialog::ErrorReport;
+use kas::session::Session;
 use kas::TkAction;
 use kas_theme::Theme;
 
@@ -34,6 +36,14 @@ where
     shared: SharedState<C, T>,
     /// Timer resumes: (time, window index)
     resumes: Vec<(Instant, ww::WindowId)>,
+    /// Called with the session on exit
+    on_exit: Option<Box<dyn FnOnce(Session)>>,
+    /// True while suspended (surfaces may be invalid)
+    suspended: bool,
+    /// Keep running without windows
+    persistent: bool,
+    /// Exit requested via the proxy
+    exit_requested: bool,
 }
 
 impl<C: CustomPipe + 'static, T: Theme<DrawPipe<C>>> Loop<C, T>
@@ -43,6 +53,8 @@ where
     pub(crate) fn new(
         mut windows: Vec<Window<C::Window, T::Window>>,
         shared: SharedState<C, T>,
+        on_exit: Option<Box<dyn FnOnce(Session)>>,
+        persistent: bool,
     ) -> Self {
         let id_map = windows
             .iter()
@@ -53,6 +65,10 @@ where
             id_map,
             shared,
             resumes: vec![],
+            on_exit,
+            suspended: false,
+            persistent,
+            exit_requested: false,
         }
     }
 
@@ -71,24 +87,66 @@ where
                 }
             }
 
+            DeviceEvent {
+                event: winit::event::DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                // Raw motion is only used by windows with an active mouse grab
+                for window in self.windows.values_mut() {
+                    window.handle_pointer_motion(&mut self.shared, delta);
+                }
+            }
             DeviceEvent { .. } => return, // windows handle local input; we do not handle global input
             UserEvent(action) => match action {
                 ProxyAction::Close(id) => {
                     if let Some(id) = self.id_map.get(&id) {
                         if let Some(window) = self.windows.get_mut(&id) {
-                            window.send_action(TkAction::Close);
+                            window.send_action(TkAction::CLOSE);
                         }
                     }
                 }
                 ProxyAction::CloseAll => {
+                    self.exit_requested = true;
                     for window in self.windows.values_mut() {
-                        window.send_action(TkAction::Close);
+                        window.send_action(TkAction::CLOSE);
                     }
                 }
-                ProxyAction::Update(handle, payload) => {
+                ProxyAction::AddWindow(factory) => {
+                    let id = self.shared.next_window_id();
+                    let widget = (factory.0)();
+                    self.shared
+                        .pending
+                        .push(PendingAction::AddWindow(id, widget));
+                }
+                ProxyAction::Update(handle, payload, data) => {
+                    self.shared
+                        .pending
+                        .push(PendingAction::Update(handle, payload, data));
+                }
+                ProxyAction::Error(report) => {
+                    let id = self.shared.next_window_id();
+                    let dialog = Box::new(kas::dialog::error(report));
                     self.shared
                         .pending
-                        .push(PendingAction::Update(handle, payload));
+                        .push(PendingAction::AddWindow(id, dialog));
+                }
+                #[cfg(feature = "gamepad")]
+                ProxyAction::Gamepad(gamepad, input) => {
+                    for window in self.windows.values_mut() {
+                        if window.focused {
+                            window.handle_gamepad(&mut self.shared, gamepad, input);
+                        }
+                    }
+                }
+                ProxyAction::TrackFocus(sender) => {
+                    self.shared.focus_trackers.push(sender);
+                }
+                ProxyAction::Capture(id, sender) => {
+                    if let Some(id) = self.id_map.get(&id) {
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            window.request_capture(sender);
+                        }
+                    }
                 }
             },
 
@@ -102,25 +160,32 @@ where
                     } => {
                         debug!("Wakeup: timer (requested: {:?})", requested_resume);
 
-                        let item = self
-                            .resumes
-                            .first()
-                            .cloned()
-                            .unwrap_or_else(|| panic!("timer wakeup without resume"));
-                        assert_eq!(item.0, requested_resume);
+                        // The theme config (if due) is polled on
+                        // MainEventsCleared. Window timers may also be due at
+                        // the same time, or none may be (e.g. if the window
+                        // was closed); neither is an error.
 
-                        let resume = if let Some(w) = self.windows.get_mut(&item.1) {
-                            w.update_timer(&mut self.shared)
-                        } else {
-                            // presumably, some window with active timers was removed
-                            None
-                        };
+                        // Windows are paced independently (e.g. on monitors
+                        // with differing refresh rates), thus several may be
+                        // due; update each of these.
+                        let mut i = 0;
+                        while i < self.resumes.len() && self.resumes[i].0 <= requested_resume {
+                            let window_id = self.resumes[i].1;
+                            let resume = if let Some(w) = self.windows.get_mut(&window_id) {
+                                w.update_timer(&mut self.shared)
+                            } else {
+                                // presumably, some window with active timers was removed
+                                None
+                            };
 
-                        if let Some(instant) = resume {
-                            self.resumes[0].0 = instant;
-                        } else {
-                            self.resumes.remove(0);
+                            if let Some(instant) = resume {
+                                self.resumes[i].0 = instant;
+                                i += 1;
+                            } else {
+                                self.resumes.remove(i);
+                            }
                         }
+                        self.resumes.sort_by_key(|item| item.0);
                     }
                     StartCause::WaitCancelled { .. } => {
                         // This event serves no purpose?
@@ -134,18 +199,16 @@ where
             }
 
             MainEventsCleared => {
+                self.shared.poll_theme_config();
+
                 let mut close_all = false;
                 let mut to_close = SmallVec::<[ww::WindowId; 4]>::new();
                 for (window_id, window) in self.windows.iter_mut() {
                     let (action, resume) = window.update(&mut self.shared);
-                    match action {
-                        TkAction::None
-                        | TkAction::Redraw
-                        | TkAction::RegionMoved
-                        | TkAction::Popup
-                        | TkAction::Reconfigure => (),
-                        TkAction::Close => to_close.push(*window_id),
-                        TkAction::CloseAll => close_all = true,
+                    if action.contains(TkAction::CLOSE_ALL) {
+                        close_all = true;
+                    } else if action.contains(TkAction::CLOSE) {
+                        to_close.push(*window_id);
                     }
                     if let Some(instant) = resume {
                         if let Some((i, _)) = self
@@ -161,10 +224,19 @@ where
                     }
                 }
 
+                let last_closed = !to_close.is_empty() && to_close.len() == self.windows.len();
+                let exiting = close_all || self.exit_requested || (last_closed && !self.persistent);
+                if exiting {
+                    self.save_session();
+                }
+
                 for window_id in &to_close {
                     if let Some(window) = self.windows.remove(window_id) {
                         self.id_map.remove(&window.window_id);
-                        if window.handle_closure(&mut self.shared) == TkAction::CloseAll {
+                        if window
+                            .handle_closure(&mut self.shared)
+                            .contains(TkAction::CLOSE_ALL)
+                        {
                             close_all = true;
                         }
                         // Wake immediately in order to close remaining windows:
@@ -178,26 +250,50 @@ where
                 }
 
                 self.resumes.sort_by_key(|item| item.0);
+                let resume = match (self.resumes.first(), self.shared.theme_config_resume()) {
+                    (Some((a, _)), Some(b)) => Some((*a).min(b)),
+                    (a, b) => a.map(|item| item.0).or(b),
+                };
 
-                *control_flow = if *control_flow == ControlFlow::Exit || self.windows.is_empty() {
+                let exit = self.windows.is_empty() && (exiting || !self.persistent);
+                *control_flow = if *control_flow == ControlFlow::Exit || exit {
                     ControlFlow::Exit
                 } else if *control_flow == ControlFlow::Poll {
                     ControlFlow::Poll
-                } else if let Some((instant, _)) = self.resumes.first() {
-                    trace!("Requesting resume at {:?}", *instant);
-                    ControlFlow::WaitUntil(*instant)
+                } else if let Some(instant) = resume {
+                    trace!("Requesting resume at {:?}", instant);
+                    ControlFlow::WaitUntil(instant)
                 } else {
                     ControlFlow::Wait
                 };
             }
 
             RedrawRequested(id) => {
+                if self.suspended {
+                    return;
+                }
                 if let Some(window) = self.windows.get_mut(&id) {
                     window.do_draw(&mut self.shared);
                 }
             }
 
-            RedrawEventsCleared | LoopDestroyed | Suspended | Resumed => return,
+            Suspended => {
+                debug!("Suspended");
+                self.suspended = true;
+                return;
+            }
+            Resumed => {
+                if self.suspended {
+                    debug!("Resumed");
+                    self.suspended = false;
+                    for window in self.windows.values_mut() {
+                        window.resume(&mut self.shared);
+                    }
+                }
+                return;
+            }
+
+            RedrawEventsCleared | LoopDestroyed => return,
         };
 
         // Create and init() any new windows.
@@ -215,7 +311,7 @@ where
                 }
                 PendingAction::AddWindow(id, widget) => {
                     debug!("Adding window {}", widget.title());
-                    match Window::new(&mut self.shared, elwt, id, widget) {
+                    match Window::new(&mut self.shared, elwt, id, widget, None) {
                         Ok(window) => {
                             let wid = window.window.id();
                             self.id_map.insert(id, wid);
@@ -234,6 +330,18 @@ where
                         self.id_map.remove(&id);
                     }
                 }
+                PendingAction::Action(Some(id), action) => {
+                    if let Some(wwid) = self.id_map.get(&id) {
+                        if let Some(window) = self.windows.get_mut(&wwid) {
+                            window.send_action(action);
+                        }
+                    }
+                }
+                PendingAction::Action(None, action) => {
+                    for window in self.windows.values_mut() {
+                        window.send_action(action);
+                    }
+                }
                 PendingAction::ThemeResize => {
                     for (_, window) in self.windows.iter_mut() {
                         window.theme_resize(&self.shared);
@@ -244,12 +352,59 @@ where
                         window.window.request_redraw();
                     }
                 }
-                PendingAction::Update(handle, payload) => {
+                PendingAction::Update(handle, payload, data) => {
                     for window in self.windows.values_mut() {
-                        window.update_handle(&mut self.shared, handle, payload);
+                        let data = data.clone();
+                        window.update_handle(&mut self.shared, handle, payload, data);
                     }
                 }
             }
         }
     }
+
+    /// Replace all windows with an error dialog after a panic
+    pub(crate) fn handle_panic(
+        &mut self,
+        report: ErrorReport,
+        elwt: &EventLoopWindowTarget<ProxyAction>,
+        control_flow: &mut ControlFlow,
+    ) {
+        error!("Panic during event handling; closing all windows");
+        // Exit once the error dialog is closed
+        self.persistent = false;
+        // Window state may be inconsistent, thus we do not call handle_closure
+        self.windows.clear();
+        self.id_map.clear();
+        self.resumes.clear();
+        self.shared.pending.clear();
+
+        let id = self.shared.next_window_id();
+        let widget = Box::new(kas::dialog::error(report));
+        match Window::new(&mut self.shared, elwt, id, widget, None) {
+            Ok(window) => {
+                let wid = window.window.id();
+                self.id_map.insert(id, wid);
+                self.windows.insert(wid, window);
+                *control_flow = ControlFlow::Wait;
+            }
+            Err(e) => {
+                error!("Unable to create window: {}", e);
+                *control_flow = ControlFlow::Exit;
+            }
+        }
+    }
+
+    /// Pass the session (all open windows) to the exit handler, if any
+    fn save_session(&mut self) {
+        if let Some(on_exit) = self.on_exit.take() {
+            let mut windows: Vec<_> = self.windows.values_mut().collect();
+            windows.sort_by_key(|window| window.window_id);
+            let shared = &mut self.shared;
+            let windows = windows
+                .into_iter()
+                .map(|window| window.session_state(shared))
+                .collect();
+            on_exit(Session { windows });
+        }
+    }
 }
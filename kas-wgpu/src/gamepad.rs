@@ -0,0 +1,99 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Gamepad input via gilrs
+
+use gilrs::{Axis, Button, EventType, Gilrs};
+use log::warn;
+use std::thread;
+use std::time::Duration;
+use winit::event_loop::EventLoopProxy;
+
+use kas::event::{GamepadAxis, GamepadButton, GamepadInput};
+
+use crate::ProxyAction;
+
+/// Interval at which gamepads are polled
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Spawn a thread polling gamepad input
+///
+/// gilrs does not integrate with the winit event loop, thus we poll on
+/// another thread and forward input via the proxy. The thread exits when the
+/// event loop does.
+pub(crate) fn spawn(proxy: EventLoopProxy<ProxyAction>) {
+    let result = thread::Builder::new()
+        .name("gamepad".to_string())
+        .spawn(move || {
+            let mut gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(e) => {
+                    warn!("Gamepad input unavailable: {}", e);
+                    return;
+                }
+            };
+            loop {
+                while let Some(event) = gilrs.next_event() {
+                    let gamepad = usize::from(event.id) as u32;
+                    if let Some(input) = translate(event.event) {
+                        let action = ProxyAction::Gamepad(gamepad, input);
+                        if proxy.send_event(action).is_err() {
+                            return;
+                        }
+                    }
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+    if let Err(e) = result {
+        warn!("Failed to spawn gamepad thread: {}", e);
+    }
+}
+
+fn translate(event: EventType) -> Option<GamepadInput> {
+    Some(match event {
+        EventType::ButtonPressed(button, _) => GamepadInput::ButtonPressed(button_from(button)?),
+        EventType::ButtonReleased(button, _) => GamepadInput::ButtonReleased(button_from(button)?),
+        EventType::AxisChanged(axis, value, _) => {
+            GamepadInput::AxisChanged(axis_from(axis)?, value)
+        }
+        _ => return None,
+    })
+}
+
+fn button_from(button: Button) -> Option<GamepadButton> {
+    use GamepadButton as GB;
+    Some(match button {
+        Button::South => GB::South,
+        Button::East => GB::East,
+        Button::North => GB::North,
+        Button::West => GB::West,
+        Button::LeftTrigger | Button::LeftTrigger2 => GB::LeftTrigger,
+        Button::RightTrigger | Button::RightTrigger2 => GB::RightTrigger,
+        Button::Select => GB::Select,
+        Button::Start => GB::Start,
+        Button::Mode => GB::Mode,
+        Button::LeftThumb => GB::LeftThumb,
+        Button::RightThumb => GB::RightThumb,
+        Button::DPadUp => GB::DPadUp,
+        Button::DPadDown => GB::DPadDown,
+        Button::DPadLeft => GB::DPadLeft,
+        Button::DPadRight => GB::DPadRight,
+        _ => return None,
+    })
+}
+
+fn axis_from(axis: Axis) -> Option<GamepadAxis> {
+    use GamepadAxis as GA;
+    Some(match axis {
+        Axis::LeftStickX => GA::LeftStickX,
+        Axis::LeftStickY => GA::LeftStickY,
+        Axis::RightStickX => GA::RightStickX,
+        Axis::RightStickY => GA::RightStickY,
+        Axis::LeftZ => GA::LeftZ,
+        Axis::RightZ => GA::RightZ,
+        _ => return None,
+    })
+}
@@ -11,18 +11,35 @@
 //! Windowing is provided by [winit](https://github.com/rust-windowing/winit/).
 //! Clipboard functionality is (currently) provided by
 //! [clipboard](https://crates.io/crates/clipboard).
+//! Optional gamepad support is provided by
+//! [gilrs](https://crates.io/crates/gilrs).
 
 #![cfg_attr(feature = "gat", feature(generic_associated_types))]
 
+mod capture;
+mod dark_mode;
 pub mod draw;
+mod embed;
 mod event_loop;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 pub mod options;
+#[cfg(feature = "png")]
+pub mod reference;
 mod shared;
 mod window;
 
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
 use std::{error, fmt};
 
-use kas::event::UpdateHandle;
+use kas::dialog::ErrorReport;
+use kas::draw::{DrawTextShared, FontId, TextMeasure};
+
+use kas::event::{FocusRect, UpdateData, UpdateHandle};
+use kas::session::Session;
 use kas::WindowId;
 use kas_theme::Theme;
 use winit::error::OsError;
@@ -32,7 +49,11 @@ use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow};
 use crate::shared::SharedState;
 use window::Window;
 
+pub use capture::RgbaImage;
+pub use dark_mode::system_prefers_dark;
+pub use embed::Embedded;
 pub use options::Options;
+pub use wgpu::{AdapterInfo, Backend, DeviceType};
 
 pub use kas;
 pub use kas_theme as theme;
@@ -51,6 +72,12 @@ pub enum Error {
     /// This can be a driver/configuration issue or hardware limitation. Note
     /// that for now, `wgpu` only supports DX11, DX12, Vulkan and Metal.
     NoAdapter,
+    /// No graphics adapter matches [`Options::adapter`] (with the given name)
+    ///
+    /// See [`adapters`] for a list of available adapters.
+    AdapterNotFound(String),
+    /// Failed to load the default font
+    Font(kas_theme::FontError),
     #[doc(hidden)]
     /// Shaders failed to compile (likely internal issue)
     ShaderCompilation(shaderc::Error),
@@ -62,6 +89,8 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
             Error::NoAdapter => write!(f, "no suitable graphics adapter found"),
+            Error::AdapterNotFound(name) => write!(f, "graphics adapter not found: {}", name),
+            Error::Font(e) => write!(f, "font loading failed: {}", e),
             Error::ShaderCompilation(e) => write!(f, "shader compilation failed: {}", e),
             Error::Window(e) => write!(f, "window creation error: {}", e),
         }
@@ -76,12 +105,30 @@ impl From<OsError> for Error {
     }
 }
 
+impl From<kas_theme::FontError> for Error {
+    fn from(e: kas_theme::FontError) -> Self {
+        Error::Font(e)
+    }
+}
+
 impl From<shaderc::Error> for Error {
     fn from(e: shaderc::Error) -> Self {
         Error::ShaderCompilation(e)
     }
 }
 
+/// Enumerate available graphics adapters
+///
+/// Lists adapters supporting any of the given `backends` (usually
+/// [`Options::backends`]). An adapter may be selected by name via
+/// [`Options::adapter`].
+pub fn adapters(backends: options::BackendBit) -> Vec<AdapterInfo> {
+    wgpu::Adapter::enumerate(backends)
+        .iter()
+        .map(|adapter| adapter.get_info())
+        .collect()
+}
+
 /// Builds a toolkit over a `winit::event_loop::EventLoop`.
 pub struct Toolkit<C: CustomPipe, T: Theme<DrawPipe<C>>>
 where
@@ -92,6 +139,9 @@ where
     el: EventLoop<ProxyAction>,
     windows: Vec<Window<C::Window, T::Window>>,
     shared: SharedState<C, T>,
+    on_exit: Option<Box<dyn FnOnce(Session)>>,
+    panic_report: Option<Arc<Mutex<Option<ErrorReport>>>>,
+    persistent: bool,
 }
 
 impl<T: Theme<DrawPipe<()>> + 'static> Toolkit<(), T>
@@ -129,6 +179,9 @@ where
             el,
             windows: vec![],
             shared: SharedState::new(custom, theme, options, scale_factor)?,
+            on_exit: None,
+            panic_report: None,
+            persistent: false,
         })
     }
 
@@ -144,11 +197,118 @@ where
     /// Add a boxed window directly
     pub fn add_boxed(&mut self, widget: Box<dyn kas::Window>) -> Result<WindowId, Error> {
         let id = self.shared.next_window_id();
-        let win = Window::new(&mut self.shared, &self.el, id, widget)?;
+        let win = Window::new(&mut self.shared, &self.el, id, widget, None)?;
         self.windows.push(win);
         Ok(id)
     }
 
+    /// Restore windows from a saved [`Session`]
+    ///
+    /// For each saved window, `factory` is called with the window's
+    /// [`kas::Window::session_key`]; where it returns a window, this is
+    /// added with restored geometry and navigation focus. Windows for which
+    /// `factory` returns `None` are skipped.
+    ///
+    /// Returns the identifiers of restored windows; if empty, the application
+    /// should add its default windows.
+    pub fn restore_session<F>(
+        &mut self,
+        session: &Session,
+        mut factory: F,
+    ) -> Result<Vec<WindowId>, Error>
+    where
+        F: FnMut(&str) -> Option<Box<dyn kas::Window>>,
+    {
+        let mut ids = vec![];
+        for state in &session.windows {
+            if let Some(widget) = factory(&state.key) {
+                let id = self.shared.next_window_id();
+                let win = Window::new(&mut self.shared, &self.el, id, widget, Some(state))?;
+                self.windows.push(win);
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Save the session on exit
+    ///
+    /// When the last windows are closed (including via
+    /// [`TkAction::CLOSE_ALL`](kas::TkAction::CLOSE_ALL)), `f` is called with a
+    /// [`Session`] describing these windows. This may be passed to
+    /// [`Toolkit::restore_session`] on next launch.
+    pub fn save_session_on_exit<F: FnOnce(Session) + 'static>(&mut self, f: F) {
+        self.on_exit = Some(Box::new(f));
+    }
+
+    /// Scale factor for new windows
+    ///
+    /// This is the scale factor of the primary monitor, unless overridden by
+    /// [`Options::scale_factor`].
+    pub fn scale_factor(&self) -> f64 {
+        self.shared.scale_factor
+    }
+
+    /// Measure text
+    ///
+    /// This allows text to be measured without a window or widget, e.g. to
+    /// choose the size of a window to fit a message. `FontId(0)` is the
+    /// theme's default font. Sizes are in physical pixels: multiply a logical
+    /// `scale` by [`Toolkit::scale_factor`] and divide results by the same.
+    /// See [`kas::draw::measure_text`].
+    pub fn measure_text(
+        &self,
+        text: &str,
+        font: FontId,
+        scale: f32,
+        wrap_width: Option<f32>,
+    ) -> TextMeasure {
+        self.shared.draw.measure_text(text, font, scale, wrap_width)
+    }
+
+    /// Keep running without windows
+    ///
+    /// By default, the toolkit exits once all windows are closed. If
+    /// `persistent`, it instead keeps running with zero windows open (e.g. for
+    /// a utility which starts hidden or lives in a system tray), waiting for
+    /// windows to be added via [`ToolkitProxy::add_window`] or
+    /// [`kas::event::Manager::add_window`]. The toolkit then exits only via
+    /// [`ToolkitProxy::exit`], [`ToolkitProxy::close_all`] or
+    /// [`TkAction::CLOSE_ALL`](kas::TkAction::CLOSE_ALL); the session (see
+    /// [`Toolkit::save_session_on_exit`]) describes the windows open at this
+    /// time.
+    pub fn set_persistent(&mut self, persistent: bool) {
+        self.persistent = persistent;
+    }
+
+    /// Report panics via an error dialog
+    ///
+    /// This installs a panic hook (see [`kas::dialog::set_panic_hook`]); it
+    /// should be called from the thread which calls [`Toolkit::run`].
+    ///
+    /// A panic on another thread opens a new
+    /// [`ErrorDialog`](kas::dialog::ErrorDialog). A panic during event
+    /// handling is caught: since the state of open windows may be
+    /// inconsistent, these are closed (without calling
+    /// [`kas::WidgetConfig::destroy`] or the session handler) and replaced by
+    /// an error dialog; the application exits once this is closed.
+    pub fn report_panics(&mut self) {
+        let gui_thread = std::thread::current().id();
+        let report = Arc::new(Mutex::new(None));
+        let local = report.clone();
+        let proxy = Mutex::new(self.el.create_proxy());
+        kas::dialog::set_panic_hook(move |r| {
+            if std::thread::current().id() == gui_thread {
+                if let Ok(mut local) = local.lock() {
+                    *local = Some(r);
+                }
+            } else if let Ok(proxy) = proxy.lock() {
+                let _ = proxy.send_event(ProxyAction::Error(r));
+            }
+        });
+        self.panic_report = Some(report);
+    }
+
     /// Create a proxy which can be used to update the UI from another thread
     pub fn create_proxy(&self) -> ToolkitProxy {
         ToolkitProxy {
@@ -157,10 +317,31 @@ where
     }
 
     /// Run the main loop.
+    ///
+    /// The loop exits when all windows are closed (unless persistent; see
+    /// [`Toolkit::set_persistent`]).
+    ///
+    /// With the `gamepad` feature, this also starts a thread polling for
+    /// gamepad input (see [`kas::event::Manager::handle_gamepad`]).
     pub fn run(self) -> ! {
-        let mut el = event_loop::Loop::new(self.windows, self.shared);
-        self.el
-            .run(move |event, elwt, control_flow| el.handle(event, elwt, control_flow))
+        #[cfg(feature = "gamepad")]
+        gamepad::spawn(self.el.create_proxy());
+        let mut el =
+            event_loop::Loop::new(self.windows, self.shared, self.on_exit, self.persistent);
+        let panic_report = self.panic_report;
+        self.el.run(move |event, elwt, control_flow| {
+            if let Some(ref report) = panic_report {
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| el.handle(event, elwt, control_flow)));
+                if result.is_err() {
+                    let report = report.lock().ok().and_then(|mut r| r.take());
+                    let report = report.unwrap_or_else(|| ErrorReport::new("Panic"));
+                    el.handle_panic(report, elwt, control_flow);
+                }
+            } else {
+                el.handle(event, elwt, control_flow)
+            }
+        })
     }
 }
 
@@ -191,17 +372,122 @@ impl ToolkitProxy {
             .map_err(|_| ClosedError)
     }
 
+    /// Terminate the UI
+    ///
+    /// This is equivalent to [`ToolkitProxy::close_all`], and is the usual way
+    /// to end a persistent toolkit (see [`Toolkit::set_persistent`]).
+    #[inline]
+    pub fn exit(&self) -> Result<(), ClosedError> {
+        self.close_all()
+    }
+
+    /// Add a window
+    ///
+    /// Since windows are not usually [`Send`], the window is constructed by
+    /// calling `factory` on the UI thread. This is equivalent to
+    /// [`kas::event::Manager::add_window`], but may be used without any open
+    /// windows (see [`Toolkit::set_persistent`]) and from other threads.
+    pub fn add_window<F>(&self, factory: F) -> Result<(), ClosedError>
+    where
+        F: FnOnce() -> Box<dyn kas::Window> + Send + 'static,
+    {
+        self.proxy
+            .send_event(ProxyAction::AddWindow(WindowFactory(Box::new(factory))))
+            .map_err(|_| ClosedError)
+    }
+
     /// Trigger an update handle
     pub fn trigger_update(&self, handle: UpdateHandle, payload: u64) -> Result<(), ClosedError> {
         self.proxy
-            .send_event(ProxyAction::Update(handle, payload))
+            .send_event(ProxyAction::Update(handle, payload, None))
             .map_err(|_| ClosedError)
     }
+
+    /// Trigger an update handle, passing typed data
+    ///
+    /// This allows e.g. a worker thread to pass results to subscribed
+    /// widgets; see [`kas::event::Manager::trigger_update_data`].
+    pub fn trigger_update_data<T: Any + Send + Sync>(
+        &self,
+        handle: UpdateHandle,
+        payload: u64,
+        data: T,
+    ) -> Result<(), ClosedError> {
+        let data = Some(UpdateData::new(data));
+        self.proxy
+            .send_event(ProxyAction::Update(handle, payload, data))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Capture the contents of a window
+    ///
+    /// The window is redrawn and the rendered frame read back; the image is
+    /// delivered via the returned channel. If the window does not exist (or
+    /// read-back fails), the channel is closed without delivering an image.
+    ///
+    /// Pop-ups are drawn as part of their parent window; capturing a pop-up's
+    /// identifier captures its parent.
+    ///
+    /// Example:
+    /// ```notest
+    /// let image = proxy.capture(id)?.recv().expect("capture failed");
+    /// ```
+    pub fn capture(&self, id: WindowId) -> Result<Receiver<RgbaImage>, ClosedError> {
+        let (sender, receiver) = channel();
+        self.proxy
+            .send_event(ProxyAction::Capture(id, sender))
+            .map_err(|_| ClosedError)?;
+        Ok(receiver)
+    }
+
+    /// Track the focused widget and edit marker (caret)
+    ///
+    /// A [`FocusNotification`] is delivered via the returned channel whenever
+    /// the focused widget of some window changes or moves, or (for text
+    /// widgets) the edit marker moves. This is intended for assistive tools
+    /// such as screen magnifiers. See also [`kas::event::ManagerState::focus_rect`].
+    pub fn track_focus(&self) -> Result<Receiver<FocusNotification>, ClosedError> {
+        let (sender, receiver) = channel();
+        self.proxy
+            .send_event(ProxyAction::TrackFocus(sender))
+            .map_err(|_| ClosedError)?;
+        Ok(receiver)
+    }
+}
+
+/// Notification of a change in focus
+///
+/// See [`ToolkitProxy::track_focus`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusNotification {
+    /// The window
+    pub window: WindowId,
+    /// The focused widget, if any
+    ///
+    /// Rects are in screen coordinates, except where the platform does not
+    /// report the window's position (e.g. Wayland), in which case they are in
+    /// window coordinates.
+    pub focus: Option<FocusRect>,
+}
+
+// Constructs a window on the UI thread (see ToolkitProxy::add_window)
+struct WindowFactory(Box<dyn FnOnce() -> Box<dyn kas::Window> + Send>);
+
+impl fmt::Debug for WindowFactory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WindowFactory")
+    }
 }
 
 #[derive(Debug)]
 enum ProxyAction {
     CloseAll,
     Close(WindowId),
-    Update(UpdateHandle, u64),
+    AddWindow(WindowFactory),
+    Update(UpdateHandle, u64, Option<UpdateData>),
+    Capture(WindowId, Sender<RgbaImage>),
+    TrackFocus(Sender<FocusNotification>),
+    Error(ErrorReport),
+    #[cfg(feature = "gamepad")]
+    Gamepad(u32, kas::event::GamepadInput),
 }
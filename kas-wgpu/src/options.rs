@@ -5,17 +5,104 @@
 
 //! Options
 
+use crate::Error;
+use kas::ThemeApi;
 use log::warn;
 use std::env::var;
+use std::path::PathBuf;
+use std::time::Duration;
 pub use wgpu::{BackendBit, PowerPreference};
 
 /// Toolkit options
-#[derive(Clone, PartialEq, Hash)]
+///
+/// Theme properties are resolved in the following order, with later sources
+/// taking precedence:
+///
+/// 1.  defaults, as set on the theme passed to the toolkit
+/// 2.  the theme configuration file, [`Options::theme_config`]
+/// 3.  application overrides: fields of [`Options`] such as
+///     [`Options::font_size`], set before calling [`Options::load_env`]
+/// 4.  environment variables, read by [`Options::load_env`]
+///
+/// Example:
+/// ```ignore
+/// let mut options = Options::default();
+/// options.font_size = Some(16.0);
+/// options.load_env();
+/// let toolkit = Toolkit::new_custom((), theme, options)?;
+/// ```
+#[derive(Clone, PartialEq)]
 pub struct Options {
     /// Adapter power preference. Default value: low power.
     pub power_preference: PowerPreference,
     /// Adapter backend. Default value: PRIMARY (Vulkan/Metal/DX12).
     pub backends: BackendBit,
+    /// Adapter name. Default value: none.
+    ///
+    /// If set, the first adapter (see [`crate::adapters`]) whose name contains
+    /// this string (ignoring case) is used in place of
+    /// [`Options::power_preference`]; if no such adapter is found, toolkit
+    /// construction fails with [`crate::Error::AdapterNotFound`].
+    pub adapter: Option<String>,
+    /// Theme configuration file. Default value: none.
+    ///
+    /// If set, a [`kas_theme::ThemeConfig`] is loaded from this file and
+    /// applied to the theme on start-up. Requires feature `config`.
+    pub theme_config: Option<PathBuf>,
+    /// Reload the theme configuration file whenever it changes. Default
+    /// value: false.
+    ///
+    /// This is intended for use during development.
+    pub theme_config_reload: bool,
+    /// Theme name, applied via [`ThemeApi::set_theme`]. Default value: none.
+    pub theme: Option<String>,
+    /// Colour scheme, applied via [`ThemeApi::set_colours`]. Default value:
+    /// none.
+    pub colours: Option<String>,
+    /// Follow the operating system's light/dark preference. Default value:
+    /// false.
+    ///
+    /// If true, the `"light"` or `"dark"` colour scheme is applied on
+    /// start-up according to [`crate::system_prefers_dark`] (taking
+    /// precedence over [`Options::colours`] unless the preference is
+    /// unknown), and again whenever the windowing system reports a change
+    /// (currently only on Windows and web).
+    pub system_colours: bool,
+    /// Font size, applied via [`ThemeApi::set_font_size`]. Default value:
+    /// none.
+    pub font_size: Option<f32>,
+    /// Scale factor override. Default value: none.
+    ///
+    /// If set, this is used in place of the scale factor reported by the
+    /// platform for each window.
+    pub scale_factor: Option<f64>,
+    /// Event handling configuration. Default value: platform dependent.
+    ///
+    /// See [`kas::event::Config`].
+    pub event_config: kas::event::Config,
+    /// Kiosk mode. Default value: false.
+    ///
+    /// If true, windows are made full-screen (borderless, on the current
+    /// monitor) and have no decorations. Interaction may additionally be
+    /// restricted to part of the UI via [`kas::event::Manager::set_input_lock`].
+    ///
+    /// Note that operating-system shortcuts (e.g. Alt+Tab or the Super key)
+    /// cannot be intercepted via winit; a locked-down kiosk therefore requires
+    /// platform configuration (for example, running under a dedicated session
+    /// without a window manager).
+    pub kiosk: bool,
+    /// Hide the mouse cursor after this period of inactivity. Default value:
+    /// none.
+    ///
+    /// The cursor is shown again when the mouse is moved or used.
+    pub hide_cursor: Option<Duration>,
+    /// Limit on memory used by shared resources such as images, in bytes.
+    /// Default value: none.
+    ///
+    /// When the limit would be exceeded, [`kas::event::Manager::load_image`]
+    /// fails (returns `None`). Usage may be queried via
+    /// [`kas::event::Manager::memory_usage`].
+    pub memory_limit: Option<u64>,
 }
 
 impl Default for Options {
@@ -23,6 +110,18 @@ impl Default for Options {
         Options {
             power_preference: PowerPreference::LowPower,
             backends: BackendBit::PRIMARY,
+            adapter: None,
+            theme_config: None,
+            theme_config_reload: false,
+            theme: None,
+            colours: None,
+            system_colours: false,
+            font_size: None,
+            scale_factor: None,
+            event_config: Default::default(),
+            kiosk: false,
+            hide_cursor: None,
+            memory_limit: None,
         }
     }
 }
@@ -30,6 +129,16 @@ impl Default for Options {
 impl Options {
     /// Construct a new instance, reading from environment variables
     ///
+    /// This is equivalent to calling [`Options::load_env`] on the default
+    /// instance.
+    pub fn from_env() -> Self {
+        let mut options = Options::default();
+        options.load_env();
+        options
+    }
+
+    /// Read environment variables, overriding existing values
+    ///
     /// The following environment variables are read, in case-insensitive mode.
     ///
     /// ### Power preference
@@ -51,12 +160,48 @@ impl Options {
     /// -   `DX12`
     /// -   `PRIMARY`: any of Vulkan, Metal or DX12
     /// -   `SECONDARY`: any of GL or DX11
-    pub fn from_env() -> Self {
-        let mut options = Options::default();
-
+    ///
+    /// ### Adapter
+    ///
+    /// The `KAS_ADAPTER` variable selects an adapter by name (see
+    /// [`Options::adapter`]).
+    ///
+    /// ### Theme configuration
+    ///
+    /// The `KAS_THEME_CONFIG` variable specifies the path to a theme
+    /// configuration file. If `KAS_THEME_CONFIG_RELOAD` is set to `1` or
+    /// `true`, this file is reloaded when modified.
+    ///
+    /// ### Theme overrides
+    ///
+    /// -   `KAS_THEME`: theme name (see [`Options::theme`])
+    /// -   `KAS_COLOURS`: colour scheme (see [`Options::colours`])
+    /// -   `KAS_SYSTEM_COLOURS`: if `1` or `true`, follow the system's
+    ///     light/dark preference (see [`Options::system_colours`])
+    /// -   `KAS_FONT_SIZE`: font size (see [`Options::font_size`])
+    /// -   `KAS_SCALE`: scale factor (see [`Options::scale_factor`])
+    ///
+    /// ### Primary selection
+    ///
+    /// If `KAS_PRIMARY_SELECTION` is set to `0` or `false`, the primary
+    /// selection (copy-on-select and middle-click paste) is disabled; if set to
+    /// `1` or `true`, it is enabled.
+    ///
+    /// ### Kiosk mode
+    ///
+    /// If `KAS_KIOSK` is set to `1` or `true`, kiosk mode is enabled (see
+    /// [`Options::kiosk`]). The `KAS_HIDE_CURSOR` variable specifies the
+    /// inactivity period (in seconds) after which the mouse cursor is hidden
+    /// (see [`Options::hide_cursor`]).
+    ///
+    /// ### Memory limit
+    ///
+    /// The `KAS_MEMORY_LIMIT` variable specifies a limit on memory used by
+    /// shared resources, in MiB (see [`Options::memory_limit`]).
+    pub fn load_env(&mut self) {
         if let Ok(mut v) = var("KAS_POWER_PREFERENCE") {
             v.make_ascii_uppercase();
-            options.power_preference = match v.as_str() {
+            self.power_preference = match v.as_str() {
                 "DEFAULT" => PowerPreference::Default,
                 "LOWPOWER" => PowerPreference::LowPower,
                 "HIGHPERFORMANCE" => PowerPreference::HighPerformance,
@@ -65,14 +210,14 @@ impl Options {
                         "Unexpected environment value: KAS_POWER_PREFERENCE={}",
                         other
                     );
-                    options.power_preference
+                    self.power_preference
                 }
             }
         }
 
         if let Ok(mut v) = var("KAS_BACKENDS") {
             v.make_ascii_uppercase();
-            options.backends = match v.as_str() {
+            self.backends = match v.as_str() {
                 "VULKAN" => BackendBit::VULKAN,
                 "GL" => BackendBit::GL,
                 "METAL" => BackendBit::METAL,
@@ -82,12 +227,128 @@ impl Options {
                 "SECONDARY" => BackendBit::SECONDARY,
                 other => {
                     warn!("Unexpected environment value: KAS_BACKENDS={}", other);
-                    options.backends
+                    self.backends
                 }
             }
         }
 
-        options
+        if let Ok(v) = var("KAS_ADAPTER") {
+            self.adapter = Some(v);
+        }
+
+        if let Ok(v) = var("KAS_THEME_CONFIG") {
+            self.theme_config = Some(v.into());
+        }
+
+        if let Ok(mut v) = var("KAS_THEME_CONFIG_RELOAD") {
+            v.make_ascii_uppercase();
+            self.theme_config_reload = match v.as_str() {
+                "0" | "FALSE" => false,
+                "1" | "TRUE" => true,
+                other => {
+                    warn!(
+                        "Unexpected environment value: KAS_THEME_CONFIG_RELOAD={}",
+                        other
+                    );
+                    self.theme_config_reload
+                }
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_PRIMARY_SELECTION") {
+            v.make_ascii_uppercase();
+            self.event_config.primary_selection = match v.as_str() {
+                "0" | "FALSE" => false,
+                "1" | "TRUE" => true,
+                other => {
+                    warn!(
+                        "Unexpected environment value: KAS_PRIMARY_SELECTION={}",
+                        other
+                    );
+                    self.event_config.primary_selection
+                }
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_KIOSK") {
+            v.make_ascii_uppercase();
+            self.kiosk = match v.as_str() {
+                "0" | "FALSE" => false,
+                "1" | "TRUE" => true,
+                other => {
+                    warn!("Unexpected environment value: KAS_KIOSK={}", other);
+                    self.kiosk
+                }
+            }
+        }
+
+        if let Ok(v) = var("KAS_HIDE_CURSOR") {
+            match v.parse::<f64>() {
+                Ok(secs) if secs >= 0.0 => {
+                    self.hide_cursor = Some(Duration::from_secs_f64(secs));
+                }
+                _ => warn!("Unexpected environment value: KAS_HIDE_CURSOR={}", v),
+            }
+        }
+
+        if let Ok(v) = var("KAS_MEMORY_LIMIT") {
+            match v.parse::<u64>() {
+                Ok(mib) => self.memory_limit = Some(mib << 20),
+                Err(_) => warn!("Unexpected environment value: KAS_MEMORY_LIMIT={}", v),
+            }
+        }
+
+        if let Ok(v) = var("KAS_THEME") {
+            self.theme = Some(v);
+        }
+
+        if let Ok(v) = var("KAS_COLOURS") {
+            self.colours = Some(v);
+        }
+
+        if let Ok(mut v) = var("KAS_SYSTEM_COLOURS") {
+            v.make_ascii_uppercase();
+            self.system_colours = match v.as_str() {
+                "1" | "TRUE" => true,
+                "0" | "FALSE" => false,
+                other => {
+                    warn!("Unexpected environment value: KAS_SYSTEM_COLOURS={}", other);
+                    self.system_colours
+                }
+            };
+        }
+
+        if let Ok(v) = var("KAS_FONT_SIZE") {
+            match v.parse::<f32>() {
+                Ok(size) if size > 0.0 => self.font_size = Some(size),
+                _ => warn!("Unexpected environment value: KAS_FONT_SIZE={}", v),
+            }
+        }
+
+        if let Ok(v) = var("KAS_SCALE") {
+            match v.parse::<f64>() {
+                Ok(factor) if factor > 0.0 => self.scale_factor = Some(factor),
+                _ => warn!("Unexpected environment value: KAS_SCALE={}", v),
+            }
+        }
+    }
+
+    /// Apply theme overrides
+    pub(crate) fn apply_to_theme(&self, theme: &mut dyn ThemeApi) {
+        if let Some(ref name) = self.theme {
+            let _ = theme.set_theme(name);
+        }
+        if let Some(ref scheme) = self.colours {
+            let _ = theme.set_colours(scheme);
+        }
+        if self.system_colours {
+            if let Some(dark) = crate::system_prefers_dark() {
+                let _ = theme.set_colours(if dark { "dark" } else { "light" });
+            }
+        }
+        if let Some(size) = self.font_size {
+            let _ = theme.set_font_size(size);
+        }
     }
 
     pub(crate) fn adapter_options(&self) -> wgpu::RequestAdapterOptions {
@@ -100,4 +361,17 @@ impl Options {
     pub(crate) fn backend(&self) -> BackendBit {
         self.backends
     }
+
+    /// Select the adapter named by [`Options::adapter`], if any
+    pub(crate) fn select_adapter(&self) -> Result<Option<wgpu::Adapter>, Error> {
+        let name = match self.adapter {
+            Some(ref name) => name.to_lowercase(),
+            None => return Ok(None),
+        };
+        wgpu::Adapter::enumerate(self.backends)
+            .into_iter()
+            .find(|adapter| adapter.get_info().name.to_lowercase().contains(&name))
+            .map(Some)
+            .ok_or_else(|| Error::AdapterNotFound(self.adapter.clone().unwrap()))
+    }
 }
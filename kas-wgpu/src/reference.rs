@@ -0,0 +1,239 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Visual regression testing
+//!
+//! A [`Reference`] compares captured window contents (see
+//! [`ToolkitProxy::capture`](crate::ToolkitProxy::capture)) against reference
+//! images stored as PNG files. On mismatch, the captured image and an image
+//! highlighting differing pixels are written next to the reference, allowing
+//! review of changes (e.g. to a theme).
+//!
+//! ### Blessing
+//!
+//! Reference images are created or updated by running tests with environment
+//! variable `KAS_BLESS` set to `1` or `true`; in this mode, [`Reference::check`]
+//! writes each captured image as the new reference and succeeds. Changes to
+//! reference images may then be reviewed via the usual version-control diff.
+//!
+//! Example (run from a test; requires a graphics adapter):
+//! ```notest
+//! let mut toolkit = kas_wgpu::Toolkit::new(theme)?;
+//! let id = toolkit.add(window)?;
+//! let proxy = toolkit.create_proxy();
+//! std::thread::spawn(move || {
+//!     let image = proxy.capture(id).unwrap().recv().expect("capture failed");
+//!     let result = Reference::new("tests/reference").check("window", &image);
+//!     proxy.close_all().unwrap();
+//!     result.unwrap();
+//! });
+//! toolkit.run();
+//! ```
+//!
+//! Note that rendering may differ slightly between graphics adapters and
+//! drivers (e.g. anti-aliasing); see [`Reference::with_tolerance`].
+//!
+//! **Feature gated**: this is only available with feature `png`.
+
+use log::info;
+use std::env::var;
+use std::fmt;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use crate::RgbaImage;
+use kas::geom::Size;
+
+/// Error reading or writing a PNG file
+#[derive(Debug)]
+pub enum PngError {
+    /// File IO error
+    Io(std::io::Error),
+    /// PNG decoding error
+    Decoding(png::DecodingError),
+    /// PNG encoding error
+    Encoding(png::EncodingError),
+    /// Unsupported format: only 8-bit RGBA images are supported
+    Format,
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            PngError::Io(e) => write!(f, "PNG: IO error: {}", e),
+            PngError::Decoding(e) => write!(f, "PNG: decoding error: {}", e),
+            PngError::Encoding(e) => write!(f, "PNG: encoding error: {}", e),
+            PngError::Format => write!(f, "PNG: unsupported format (expected 8-bit RGBA)"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+impl RgbaImage {
+    /// Read from a PNG file
+    ///
+    /// Only 8-bit RGBA images are supported.
+    ///
+    /// **Feature gated**: this is only available with feature `png`.
+    pub fn from_png<P: AsRef<Path>>(path: P) -> Result<Self, PngError> {
+        let file = File::open(path).map_err(PngError::Io)?;
+        let decoder = png::Decoder::new(file);
+        let (info, mut reader) = decoder.read_info().map_err(PngError::Decoding)?;
+        if info.color_type != png::ColorType::RGBA || info.bit_depth != png::BitDepth::Eight {
+            return Err(PngError::Format);
+        }
+        let mut data = vec![0; info.buffer_size()];
+        reader.next_frame(&mut data).map_err(PngError::Decoding)?;
+        Ok(RgbaImage {
+            size: Size(info.width, info.height),
+            data,
+        })
+    }
+
+    /// Write to a PNG file
+    ///
+    /// **Feature gated**: this is only available with feature `png`.
+    pub fn write_png<P: AsRef<Path>>(&self, path: P) -> Result<(), PngError> {
+        let file = File::create(path).map_err(PngError::Io)?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.size.0, self.size.1);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(PngError::Encoding)?;
+        writer
+            .write_image_data(&self.data)
+            .map_err(PngError::Encoding)
+    }
+}
+
+/// Failure of [`Reference::check`]
+#[derive(Debug)]
+pub enum CheckError {
+    /// No reference image exists (run with `KAS_BLESS=1` to create)
+    Missing(PathBuf),
+    /// Error reading or writing an image
+    Png(PathBuf, PngError),
+    /// Image size differs from the reference: `(path, expected, actual)`
+    Size(PathBuf, Size, Size),
+    /// Too many pixels differ: `(path, count)`
+    Mismatch(PathBuf, usize),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            CheckError::Missing(path) => write!(
+                f,
+                "reference image {} not found (set KAS_BLESS=1 to create)",
+                path.display()
+            ),
+            CheckError::Png(path, e) => write!(f, "{}: {}", path.display(), e),
+            CheckError::Size(path, expected, actual) => write!(
+                f,
+                "{}: size mismatch: expected {:?}, found {:?}",
+                path.display(),
+                expected,
+                actual
+            ),
+            CheckError::Mismatch(path, count) => {
+                write!(f, "{}: {} pixels differ", path.display(), count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+/// A set of reference images
+///
+/// Reference images are stored in a directory as `<name>.png`. On mismatch,
+/// [`Reference::check`] writes the captured image as `<name>.actual.png` and a
+/// difference image (see [`RgbaImage::diff_image`]) as `<name>.diff.png`.
+///
+/// See the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct Reference {
+    dir: PathBuf,
+    tolerance: u8,
+    max_differing: usize,
+    bless: bool,
+}
+
+impl Reference {
+    /// Construct, using reference images in directory `dir`
+    ///
+    /// By default, images must match exactly. Blessing mode is enabled when
+    /// the `KAS_BLESS` environment variable is set to `1` or `true`.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        let bless = match var("KAS_BLESS") {
+            Ok(v) => v == "1" || v.eq_ignore_ascii_case("true"),
+            Err(_) => false,
+        };
+        Reference {
+            dir: dir.into(),
+            tolerance: 0,
+            max_differing: 0,
+            bless,
+        }
+    }
+
+    /// Set the per-channel tolerance
+    ///
+    /// Pixels are considered equal when no channel differs by more than this
+    /// value.
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the number of differing pixels allowed
+    pub fn with_max_differing(mut self, count: usize) -> Self {
+        self.max_differing = count;
+        self
+    }
+
+    /// Enable or disable blessing mode, overriding `KAS_BLESS`
+    pub fn with_bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Compare `image` against the reference `name`
+    ///
+    /// In blessing mode, `image` is written as the new reference instead.
+    pub fn check(&self, name: &str, image: &RgbaImage) -> Result<(), CheckError> {
+        let path = self.dir.join(format!("{}.png", name));
+
+        if self.bless {
+            info!("Reference: writing {}", path.display());
+            return image.write_png(&path).map_err(|e| CheckError::Png(path, e));
+        }
+
+        if !path.exists() {
+            return Err(CheckError::Missing(path));
+        }
+        let reference = RgbaImage::from_png(&path).map_err(|e| CheckError::Png(path.clone(), e))?;
+
+        let result = match image.count_differing(&reference, self.tolerance) {
+            None => Err(CheckError::Size(path.clone(), reference.size, image.size)),
+            Some(count) if count > self.max_differing => {
+                Err(CheckError::Mismatch(path.clone(), count))
+            }
+            Some(_) => return Ok(()),
+        };
+
+        let actual = self.dir.join(format!("{}.actual.png", name));
+        image
+            .write_png(&actual)
+            .map_err(|e| CheckError::Png(actual, e))?;
+        if let Some(diff) = image.diff_image(&reference, self.tolerance) {
+            let diff_path = self.dir.join(format!("{}.diff.png", name));
+            diff.write_png(&diff_path)
+                .map_err(|e| CheckError::Png(diff_path, e))?;
+        }
+        result
+    }
+}
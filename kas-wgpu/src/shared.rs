@@ -7,30 +7,101 @@
 
 use log::{info, warn};
 use std::num::NonZeroU32;
+#[cfg(feature = "config")]
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+#[cfg(feature = "config")]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
+use crate::capture::{Capture, RgbaImage};
 use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow, ShaderManager};
-use crate::{Error, Options, WindowId};
-use kas::event::UpdateHandle;
+use crate::{Error, FocusNotification, Options, WindowId};
+use kas::draw::DrawShared;
+use kas::event::{UpdateData, UpdateHandle};
+use kas::geom::Size;
 use kas::string::{CowString, CowStringL};
+use kas::{ThemeAction, TkAction};
 use kas_theme::Theme;
+#[cfg(feature = "config")]
+use kas_theme::ThemeConfig;
 
+#[cfg(all(
+    feature = "clipboard",
+    unix,
+    not(any(target_os = "macos", target_os = "android"))
+))]
+use clipboard::x11_clipboard::{Primary, X11ClipboardContext};
 #[cfg(feature = "clipboard")]
 use clipboard::{ClipboardContext, ClipboardProvider};
 
+/// Interval between checks for modification of the theme config file
+#[cfg(feature = "config")]
+const THEME_CONFIG_POLL: Duration = Duration::from_secs(1);
+
+/// A theme config file, optionally watched for changes
+#[cfg(feature = "config")]
+struct ThemeConfigFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    reload: bool,
+    next_check: Instant,
+}
+
+#[cfg(feature = "config")]
+impl ThemeConfigFile {
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn load(&mut self) -> Option<ThemeConfig> {
+        self.modified = Self::modified_time(&self.path);
+        match ThemeConfig::from_file(&self.path) {
+            Ok(config) => {
+                info!("Loaded theme config: {}", self.path.display());
+                Some(config)
+            }
+            Err(e) => {
+                warn!("Failed to load {}: {}", self.path.display(), e);
+                None
+            }
+        }
+    }
+}
+
 /// State shared between windows
 pub struct SharedState<C: CustomPipe, T> {
     #[cfg(feature = "clipboard")]
     clipboard: Option<ClipboardContext>,
+    #[cfg(all(
+        feature = "clipboard",
+        unix,
+        not(any(target_os = "macos", target_os = "android"))
+    ))]
+    primary: Option<X11ClipboardContext<Primary>>,
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
     pub shaders: ShaderManager,
     pub draw: DrawPipe<C>,
     pub theme: T,
+    pub event_config: kas::event::Config,
     pub pending: Vec<PendingAction>,
     /// Newly created windows need to know the scale_factor *before* they are
     /// created. This is used to estimate ideal window size.
     pub scale_factor: f64,
+    /// Scale factor override (see [`Options::scale_factor`])
+    pub scale_override: Option<f64>,
+    /// Kiosk mode (see [`Options::kiosk`])
+    pub kiosk: bool,
+    /// Follow the system light/dark preference (see [`Options::system_colours`])
+    pub system_colours: bool,
+    /// Cursor hiding delay (see [`Options::hide_cursor`])
+    pub hide_cursor: Option<Duration>,
+    /// Receivers of focus notifications (see [`ToolkitProxy::track_focus`])
+    pub focus_trackers: Vec<Sender<FocusNotification>>,
     window_id: u32,
+    #[cfg(feature = "config")]
+    theme_config: Option<ThemeConfigFile>,
 }
 
 impl<C: CustomPipe, T: Theme<DrawPipe<C>>> SharedState<C, T>
@@ -52,14 +123,31 @@ where
                 None
             }
         };
+        #[cfg(all(
+            feature = "clipboard",
+            unix,
+            not(any(target_os = "macos", target_os = "android"))
+        ))]
+        let primary = match options.event_config.primary_selection {
+            true => X11ClipboardContext::new()
+                .map_err(|e| warn!("Unable to open primary selection: {:?}", e))
+                .ok(),
+            false => None,
+        };
+        let event_config = options.event_config.clone();
 
         let adapter_options = options.adapter_options();
         let backend = options.backend();
 
-        let req = wgpu::Adapter::request(&adapter_options, backend);
-        let adapter = match futures::executor::block_on(req) {
+        let adapter = match options.select_adapter()? {
             Some(a) => a,
-            None => return Err(Error::NoAdapter),
+            None => {
+                let req = wgpu::Adapter::request(&adapter_options, backend);
+                match futures::executor::block_on(req) {
+                    Some(a) => a,
+                    None => return Err(Error::NoAdapter),
+                }
+            }
         };
         info!("Using graphics adapter: {}", adapter.get_info().name);
 
@@ -74,23 +162,75 @@ where
 
         let shaders = ShaderManager::new(&device)?;
         let mut draw = DrawPipe::new(custom, &device, &shaders);
+        draw.set_memory_limit(options.memory_limit);
 
+        kas_theme::check_fonts()?;
         theme.init(&mut draw);
 
+        #[cfg(feature = "config")]
+        let reload = options.theme_config_reload;
+        #[cfg(feature = "config")]
+        let theme_config = options.theme_config.clone().map(|path| {
+            let mut file = ThemeConfigFile {
+                path,
+                modified: None,
+                reload,
+                next_check: Instant::now() + THEME_CONFIG_POLL,
+            };
+            if let Some(config) = file.load() {
+                // No windows exist yet, thus the action may be ignored
+                let _ = theme.apply_config(&config);
+            }
+            file
+        });
+        #[cfg(not(feature = "config"))]
+        {
+            if options.theme_config.is_some() {
+                warn!("Ignoring theme config: this requires feature \"config\"");
+            }
+        }
+        // Application and environment overrides take precedence over config
+        options.apply_to_theme(&mut theme);
+        let scale_override = options.scale_factor;
+
         Ok(SharedState {
             #[cfg(feature = "clipboard")]
             clipboard,
+            #[cfg(all(
+                feature = "clipboard",
+                unix,
+                not(any(target_os = "macos", target_os = "android"))
+            ))]
+            primary,
             device,
             queue,
             shaders,
             draw,
             theme,
+            event_config,
             pending: vec![],
-            scale_factor,
+            scale_factor: scale_override.unwrap_or(scale_factor),
+            scale_override,
+            kiosk: options.kiosk,
+            system_colours: options.system_colours,
+            hide_cursor: options.hide_cursor,
+            focus_trackers: vec![],
             window_id: 0,
+            #[cfg(feature = "config")]
+            theme_config,
         })
     }
 
+    /// Scale factor of `window`, unless overridden
+    pub fn window_scale_factor(&self, window: &winit::window::Window) -> f64 {
+        self.scale_override.unwrap_or_else(|| window.scale_factor())
+    }
+
+    /// Send a focus notification, dropping closed channels
+    pub fn notify_focus(&mut self, notification: FocusNotification) {
+        (self.focus_trackers).retain(|sender| sender.send(notification).is_ok());
+    }
+
     pub fn next_window_id(&mut self) -> WindowId {
         self.window_id += 1;
         WindowId::new(NonZeroU32::new(self.window_id).unwrap())
@@ -104,10 +244,88 @@ where
     ) {
         let buf = self
             .draw
-            .render(window, &mut self.device, frame_view, clear_color);
+            .render(window, &mut self.device, frame_view, Some(clear_color));
         self.queue.submit(&[buf]);
     }
 
+    /// Render to an off-screen target and read back the result
+    ///
+    /// This blocks until rendering completes.
+    pub fn render_capture(
+        &mut self,
+        window: &mut DrawWindow<C::Window>,
+        size: Size,
+        clear_color: wgpu::Color,
+    ) -> Option<RgbaImage> {
+        let capture = Capture::new(&self.device, size);
+        let buf = self
+            .draw
+            .render(window, &mut self.device, capture.view(), Some(clear_color));
+        let copy = capture.copy(&self.device);
+        self.queue.submit(&[buf, copy]);
+        capture.read(&self.device)
+    }
+
+    /// Time at which the theme config file should next be checked, if any
+    #[cfg(not(feature = "config"))]
+    #[inline]
+    pub fn theme_config_resume(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Time at which the theme config file should next be checked, if any
+    #[cfg(feature = "config")]
+    pub fn theme_config_resume(&self) -> Option<Instant> {
+        self.theme_config
+            .as_ref()
+            .filter(|file| file.reload)
+            .map(|file| file.next_check)
+    }
+
+    /// Reload the theme config file if due and modified
+    #[cfg(not(feature = "config"))]
+    #[inline]
+    pub fn poll_theme_config(&mut self) {}
+
+    /// Reload the theme config file if due and modified
+    #[cfg(feature = "config")]
+    pub fn poll_theme_config(&mut self) {
+        let file = match self.theme_config.as_mut() {
+            Some(file) if file.reload => file,
+            _ => return,
+        };
+        let now = Instant::now();
+        if now < file.next_check {
+            return;
+        }
+        file.next_check = now + THEME_CONFIG_POLL;
+        if ThemeConfigFile::modified_time(&file.path) == file.modified {
+            return;
+        }
+        if let Some(config) = file.load() {
+            let action = self.theme.apply_config(&config);
+            self.theme_action(action);
+        }
+    }
+
+    /// Apply a change in the system's light/dark preference
+    ///
+    /// Does nothing unless [`Options::system_colours`] is set.
+    pub fn system_theme_changed(&mut self, dark: bool) {
+        if self.system_colours {
+            let action = self.theme.set_colours(if dark { "dark" } else { "light" });
+            self.theme_action(action);
+        }
+    }
+
+    fn theme_action(&mut self, action: ThemeAction) {
+        match action {
+            ThemeAction::None => (),
+            ThemeAction::RedrawAll => self.pending.push(PendingAction::RedrawAll),
+            ThemeAction::ThemeResize => self.pending.push(PendingAction::ThemeResize),
+        }
+    }
+
     #[cfg(not(feature = "clipboard"))]
     #[inline]
     pub fn get_clipboard(&mut self) -> Option<CowString> {
@@ -138,13 +356,61 @@ where
                 .unwrap_or_else(|e| warn!("Failed to set clipboard contents: {:?}", e))
         });
     }
+
+    #[cfg(not(all(
+        feature = "clipboard",
+        unix,
+        not(any(target_os = "macos", target_os = "android"))
+    )))]
+    #[inline]
+    pub fn get_primary(&mut self) -> Option<CowString> {
+        None
+    }
+
+    #[cfg(all(
+        feature = "clipboard",
+        unix,
+        not(any(target_os = "macos", target_os = "android"))
+    ))]
+    pub fn get_primary(&mut self) -> Option<CowString> {
+        self.primary
+            .as_mut()
+            .and_then(|cb| match cb.get_contents() {
+                Ok(c) => Some(c.into()),
+                Err(e) => {
+                    warn!("Failed to get primary selection contents: {:?}", e);
+                    None
+                }
+            })
+    }
+
+    #[cfg(not(all(
+        feature = "clipboard",
+        unix,
+        not(any(target_os = "macos", target_os = "android"))
+    )))]
+    #[inline]
+    pub fn set_primary<'c>(&mut self, _: CowStringL<'c>) {}
+
+    #[cfg(all(
+        feature = "clipboard",
+        unix,
+        not(any(target_os = "macos", target_os = "android"))
+    ))]
+    pub fn set_primary<'c>(&mut self, content: CowStringL<'c>) {
+        self.primary.as_mut().map(|cb| {
+            cb.set_contents(content.into())
+                .unwrap_or_else(|e| warn!("Failed to set primary selection contents: {:?}", e))
+        });
+    }
 }
 
 pub enum PendingAction {
     AddPopup(winit::window::WindowId, WindowId, kas::Popup),
     AddWindow(WindowId, Box<dyn kas::Window>),
     CloseWindow(WindowId),
+    Action(Option<WindowId>, TkAction),
     ThemeResize,
     RedrawAll,
-    Update(UpdateHandle, u64),
+    Update(UpdateHandle, u64, Option<UpdateData>),
 }
@@ -5,25 +5,29 @@
 
 //! `Window` and `WindowList` types
 
-use log::{debug, info, trace};
-use std::time::Instant;
-
-use kas::draw::SizeHandle;
-use kas::event::{CursorIcon, ManagerState, UpdateHandle};
-use kas::geom::{Coord, Rect, Size};
+use log::{debug, info, trace, warn};
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use kas::draw::{DrawShared, ImageId, SizeHandle};
+use kas::event::{CursorIcon, FocusRect, FrameInfo, ManagerState, UpdateData, UpdateHandle};
+use kas::geom::{Coord, Rect, Size, Vec2};
 use kas::layout::SolveCache;
+use kas::session::WindowState;
 use kas::string::{CowString, CowStringL};
-use kas::{ThemeAction, ThemeApi, TkAction, WindowId};
+use kas::{ThemeAction, ThemeApi, TkAction, WidgetChildren, WidgetConfig, WidgetCore, WindowId};
 use kas_theme::Theme;
-use winit::dpi::PhysicalSize;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::error::OsError;
 use winit::event::WindowEvent;
 use winit::event_loop::EventLoopWindowTarget;
-use winit::window::WindowBuilder;
+use winit::window::{Fullscreen, WindowBuilder};
 
+use crate::capture::RgbaImage;
 use crate::draw::{CustomPipe, CustomWindow, DrawPipe, DrawWindow, TEX_FORMAT};
 use crate::shared::{PendingAction, SharedState};
-use crate::ProxyAction;
+use crate::{FocusNotification, ProxyAction};
 
 /// Per-window data
 pub(crate) struct Window<CW: CustomWindow, TW> {
@@ -38,6 +42,23 @@ pub(crate) struct Window<CW: CustomWindow, TW> {
     swap_chain: wgpu::SwapChain,
     draw: DrawWindow<CW>,
     theme_window: TW,
+    /// Pending screenshot requests, served on the next draw
+    captures: Vec<Sender<RgbaImage>>,
+    /// Display refresh interval
+    frame_interval: Duration,
+    /// Time at which the last frame was presented
+    last_present: Option<Instant>,
+    /// Time of a deferred redraw, if any (see `Window::request_redraw`)
+    redraw_at: Option<Instant>,
+    /// Time of the last mouse input (for cursor hiding)
+    last_mouse_input: Instant,
+    /// True if the cursor has been hidden due to inactivity
+    cursor_hidden: bool,
+    /// Last focus reported to focus trackers
+    focus: Option<FocusRect>,
+    /// True if the window has keyboard focus
+    #[cfg(feature = "gamepad")]
+    pub(crate) focused: bool,
 }
 
 // Public functions, for use by the toolkit
@@ -47,11 +68,14 @@ where
     TW: kas_theme::Window<DrawWindow<CW>> + 'static,
 {
     /// Construct a window
+    ///
+    /// If `state` is given, the window's geometry and focus are restored.
     pub fn new<C, T>(
         shared: &mut SharedState<C, T>,
         elwt: &EventLoopWindowTarget<ProxyAction>,
         window_id: WindowId,
         mut widget: Box<dyn kas::Window>,
+        state: Option<&WindowState>,
     ) -> Result<Self, OsError>
     where
         C: CustomPipe<Window = CW>,
@@ -67,7 +91,13 @@ where
         let ideal = solve_cache.ideal(true);
         drop(size_handle);
 
-        let mut builder = WindowBuilder::new().with_inner_size(ideal);
+        let mut builder = match state {
+            Some(state) => {
+                let size = PhysicalSize::new(state.size.0, state.size.1);
+                WindowBuilder::new().with_inner_size(size)
+            }
+            None => WindowBuilder::new().with_inner_size(ideal),
+        };
         let restrict_dimensions = widget.restrict_dimensions();
         if restrict_dimensions.0 {
             builder = builder.with_min_inner_size(solve_cache.min(true));
@@ -75,9 +105,19 @@ where
         if restrict_dimensions.1 {
             builder = builder.with_max_inner_size(ideal);
         }
+        if shared.kiosk {
+            builder = builder.with_decorations(false);
+        }
         let window = builder.with_title(widget.title()).build(elwt)?;
+        if let Some((x, y)) = state.and_then(|state| state.position) {
+            window.set_outer_position(PhysicalPosition::new(x, y));
+        }
+        if shared.kiosk {
+            let monitor = window.current_monitor();
+            window.set_fullscreen(Some(Fullscreen::Borderless(monitor)));
+        }
 
-        let scale_factor = window.scale_factor();
+        let scale_factor = shared.window_scale_factor(&window);
         shared.scale_factor = scale_factor;
         let size: Size = window.inner_size().into();
         info!("Constucted new window with size {:?}", size);
@@ -97,8 +137,14 @@ where
         let swap_chain = shared.device.create_swap_chain(&surface, &sc_desc);
 
         let mut mgr = ManagerState::new(scale_factor);
+        mgr.set_config(shared.event_config.clone());
         let mut tkw = TkWindow::new(shared, &window, &mut draw, &mut theme_window);
         mgr.configure(&mut tkw, &mut *widget);
+        if let Some(path) = state.and_then(|state| state.focus.as_ref()) {
+            if let Some(id) = widget.find_path(path).map(|w| w.id()) {
+                mgr.with(&mut tkw, |mgr| mgr.set_nav_focus(id));
+            }
+        }
 
         let mut r = Window {
             widget,
@@ -111,8 +157,28 @@ where
             swap_chain,
             draw,
             theme_window,
+            captures: vec![],
+            frame_interval: Duration::default(),
+            last_present: None,
+            redraw_at: None,
+            last_mouse_input: Instant::now(),
+            cursor_hidden: false,
+            focus: None,
+            #[cfg(feature = "gamepad")]
+            focused: true,
         };
+        r.frame_interval = r.refresh_interval();
         r.apply_size();
+        if let Some(state) = state {
+            // Scroll offsets are restored once sized (limiting the offset)
+            for (path, offset) in &state.scroll {
+                let id = r.widget.find_path(path).map(|w| w.id());
+                if let Some(w) = id.and_then(|id| r.widget.find_mut(id)) {
+                    let action = w.set_scroll_offset(Coord(offset.0, offset.1));
+                    r.mgr.send_action(action);
+                }
+            }
+        }
         Ok(r)
     }
 
@@ -137,10 +203,10 @@ where
         T: Theme<DrawPipe<C>, Window = TW>,
     {
         debug!("Window::theme_resize");
-        let scale_factor = self.window.scale_factor() as f32;
+        let scale_factor = shared.window_scale_factor(&self.window) as f32;
         shared
             .theme
-            .update_window(&mut self.theme_window, scale_factor);
+            .update_window(&mut self.draw, &mut self.theme_window, scale_factor);
         self.solve_cache.invalidate_rule_cache();
         self.apply_size();
     }
@@ -155,20 +221,38 @@ where
         match event {
             WindowEvent::Destroyed => (),
             WindowEvent::Resized(size) => self.do_resize(shared, size),
+            #[cfg(feature = "gamepad")]
+            WindowEvent::Focused(focused) => self.focused = focused,
+            WindowEvent::ThemeChanged(theme) => {
+                shared.system_theme_changed(theme == winit::window::Theme::Dark);
+            }
+            WindowEvent::Moved(_) => {
+                // The window may have moved to another monitor
+                self.frame_interval = self.refresh_interval();
+            }
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 new_inner_size,
             } => {
                 // Note: API allows us to set new window size here.
+                let scale_factor = shared.scale_override.unwrap_or(scale_factor);
                 shared.scale_factor = scale_factor;
-                shared
-                    .theme
-                    .update_window(&mut self.theme_window, scale_factor as f32);
+                shared.theme.update_window(
+                    &mut self.draw,
+                    &mut self.theme_window,
+                    scale_factor as f32,
+                );
                 self.mgr.set_dpi_factor(scale_factor);
                 self.solve_cache.invalidate_rule_cache();
                 self.do_resize(shared, *new_inner_size);
             }
             event @ _ => {
+                match event {
+                    WindowEvent::CursorMoved { .. }
+                    | WindowEvent::MouseWheel { .. }
+                    | WindowEvent::MouseInput { .. } => self.mouse_input(),
+                    _ => (),
+                }
                 let mut tkw =
                     TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
                 let widget = &mut *self.widget;
@@ -179,6 +263,38 @@ where
         }
     }
 
+    /// Handle raw relative mouse motion
+    pub fn handle_pointer_motion<C, T>(&mut self, shared: &mut SharedState<C, T>, delta: (f64, f64))
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let mut tkw = TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
+        let widget = &mut *self.widget;
+        let delta = Vec2(delta.0 as f32, delta.1 as f32);
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.handle_pointer_motion(widget, delta);
+        });
+    }
+
+    /// Handle gamepad input
+    #[cfg(feature = "gamepad")]
+    pub fn handle_gamepad<C, T>(
+        &mut self,
+        shared: &mut SharedState<C, T>,
+        gamepad: u32,
+        input: kas::event::GamepadInput,
+    ) where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let mut tkw = TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
+        let widget = &mut *self.widget;
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.handle_gamepad(widget, gamepad, input);
+        });
+    }
+
     /// Update, after receiving all events
     pub fn update<C, T>(&mut self, shared: &mut SharedState<C, T>) -> (TkAction, Option<Instant>)
     where
@@ -188,28 +304,32 @@ where
         let mut tkw = TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
         let action = self.mgr.update(&mut tkw, &mut *self.widget);
 
-        match action {
-            TkAction::None => (),
-            TkAction::Redraw => self.window.request_redraw(),
-            TkAction::RegionMoved => {
-                self.mgr.region_moved(&mut tkw, &mut *self.widget);
-                self.window.request_redraw();
-            }
-            TkAction::Popup => {
-                let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw) };
-                self.widget.resize_popups(&mut size_handle);
-                drop(size_handle);
+        // Handle only the most comprehensive action (see TkAction docs)
+        if action.intersects(TkAction::CLOSE | TkAction::CLOSE_ALL) {
+            // The window is closed by the caller
+        } else if action.contains(TkAction::RECONFIGURE) {
+            self.reconfigure(shared);
+        } else if action.contains(TkAction::POPUP) {
+            let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw) };
+            self.widget.resize_popups(&mut size_handle);
+            drop(size_handle);
 
-                let mut tkw =
-                    TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
-                self.mgr.region_moved(&mut tkw, &mut *self.widget);
-                self.window.request_redraw();
-            }
-            TkAction::Reconfigure => self.reconfigure(shared),
-            TkAction::Close | TkAction::CloseAll => (),
+            let mut tkw =
+                TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
+            self.mgr.region_moved(&mut tkw, &mut *self.widget);
+            self.request_redraw();
+        } else if action.contains(TkAction::REGION_MOVED) {
+            self.mgr.region_moved(&mut tkw, &mut *self.widget);
+            self.request_redraw();
+        } else if action.contains(TkAction::REDRAW) {
+            self.request_redraw();
         }
 
-        (action, self.mgr.next_resume())
+        if !shared.focus_trackers.is_empty() {
+            self.track_focus(shared);
+        }
+
+        (action, self.next_resume(shared.hide_cursor))
     }
 
     pub fn handle_closure<C, T>(mut self, shared: &mut SharedState<C, T>) -> TkAction
@@ -221,6 +341,7 @@ where
         let widget = &mut *self.widget;
         self.mgr.with(&mut tkw, |mut mgr| {
             widget.handle_closure(&mut mgr);
+            mgr.destroy(widget);
         });
         self.mgr.update(&mut tkw, &mut *self.widget)
     }
@@ -230,12 +351,27 @@ where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
     {
+        let now = Instant::now();
+        if let Some(time) = self.hide_cursor_time(shared.hide_cursor) {
+            if time <= now {
+                trace!("Window: hiding cursor due to inactivity");
+                self.window.set_cursor_visible(false);
+                self.cursor_hidden = true;
+            }
+        }
+        if let Some(time) = self.redraw_at {
+            if time <= now {
+                self.redraw_at = None;
+                self.window.request_redraw();
+            }
+        }
+
         let mut tkw = TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
         let widget = &mut *self.widget;
         self.mgr.with(&mut tkw, |mgr| {
             mgr.update_timer(widget);
         });
-        self.mgr.next_resume()
+        self.next_resume(shared.hide_cursor)
     }
 
     pub fn update_handle<C, T>(
@@ -243,6 +379,7 @@ where
         shared: &mut SharedState<C, T>,
         handle: UpdateHandle,
         payload: u64,
+        data: Option<UpdateData>,
     ) where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
@@ -250,7 +387,7 @@ where
         let mut tkw = TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
         let widget = &mut *self.widget;
         self.mgr.with(&mut tkw, |mgr| {
-            mgr.update_handle(widget, handle, payload);
+            mgr.update_handle(widget, handle, payload, data);
         });
     }
 
@@ -270,17 +407,54 @@ where
         });
     }
 
+    /// Get the window's state for a saved session
+    pub fn session_state<C, T>(&mut self, shared: &mut SharedState<C, T>) -> WindowState
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let mut tkw = TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
+        let mut focus = None;
+        self.mgr.with(&mut tkw, |mgr| focus = mgr.nav_focus());
+
+        let mut scrolled = vec![];
+        self.widget.walk_dyn(&mut |w| match w.scroll_offset() {
+            Some(offset) if offset != Coord::ZERO => scrolled.push((w.id(), offset)),
+            _ => (),
+        });
+        let widget = &self.widget;
+        let scroll = scrolled
+            .into_iter()
+            .filter_map(|(id, offset)| widget.path_of(id).map(|path| (path, (offset.0, offset.1))))
+            .collect();
+
+        let size = self.window.inner_size();
+        WindowState {
+            key: self.widget.session_key().to_string(),
+            position: self.window.outer_position().ok().map(|p| (p.x, p.y)),
+            size: (size.width, size.height),
+            focus: focus.and_then(|id| self.widget.path_of(id)),
+            scroll,
+        }
+    }
+
     pub fn send_action(&mut self, action: TkAction) {
         self.mgr.send_action(action);
     }
 
+    /// Capture the window contents on the next draw
+    pub fn request_capture(&mut self, sender: Sender<RgbaImage>) {
+        self.captures.push(sender);
+        self.window.request_redraw();
+    }
+
     pub fn send_close<C, T>(&mut self, shared: &mut SharedState<C, T>, id: WindowId)
     where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
     {
         if id == self.window_id {
-            self.mgr.send_action(TkAction::Close);
+            self.mgr.send_action(TkAction::CLOSE);
         } else {
             let mut tkw =
                 TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
@@ -298,6 +472,84 @@ where
     CW: CustomWindow + 'static,
     TW: kas_theme::Window<DrawWindow<CW>> + 'static,
 {
+    /// Estimate the refresh interval of the window's monitor
+    ///
+    /// Winit does not report the current video mode, thus we use the highest
+    /// refresh rate of modes matching the monitor's size, or 60Hz.
+    fn refresh_interval(&self) -> Duration {
+        let monitor = self.window.current_monitor();
+        let size = monitor.size();
+        let rate = monitor
+            .video_modes()
+            .filter(|mode| mode.size() == size)
+            .map(|mode| mode.refresh_rate())
+            .max()
+            .filter(|rate| *rate > 0)
+            .unwrap_or(60);
+        Duration::from_secs(1) / rate as u32
+    }
+
+    /// Request a redraw, paced to the monitor's refresh rate
+    ///
+    /// If a frame was presented within the last refresh interval, the redraw
+    /// is deferred until the next frame is due (resuming via the timer). With
+    /// a vsync-limited swap chain, drawing sooner would block on presentation,
+    /// and with it the event loop shared with all other windows.
+    fn request_redraw(&mut self) {
+        let due = self.last_present.map(|time| time + self.frame_interval);
+        match due {
+            Some(due) if Instant::now() < due => {
+                trace!("Window: deferring redraw until {:?}", due);
+                self.redraw_at = Some(self.redraw_at.map_or(due, |time| time.min(due)));
+            }
+            _ => self.window.request_redraw(),
+        }
+    }
+
+    /// Note mouse input, showing the cursor if hidden
+    fn mouse_input(&mut self) {
+        self.last_mouse_input = Instant::now();
+        if self.cursor_hidden {
+            self.window.set_cursor_visible(true);
+            self.cursor_hidden = false;
+        }
+    }
+
+    /// Time at which to hide the cursor, if pending
+    fn hide_cursor_time(&self, delay: Option<Duration>) -> Option<Instant> {
+        match self.cursor_hidden {
+            false => delay.map(|delay| self.last_mouse_input + delay),
+            true => None,
+        }
+    }
+
+    /// Time of the next timer update, deferred redraw or cursor hiding, if any
+    fn next_resume(&self, hide_cursor: Option<Duration>) -> Option<Instant> {
+        let times = [
+            self.mgr.next_resume(),
+            self.redraw_at,
+            self.hide_cursor_time(hide_cursor),
+        ];
+        times.iter().flatten().min().cloned()
+    }
+
+    /// Predict the presentation time of the next frame
+    fn frame_info(&self) -> FrameInfo {
+        let now = Instant::now();
+        let interval = self.frame_interval;
+        let mut presentation = match self.last_present {
+            Some(time) => time + interval,
+            None => now + interval,
+        };
+        while presentation < now {
+            presentation += interval;
+        }
+        FrameInfo {
+            presentation,
+            interval,
+        }
+    }
+
     fn apply_size(&mut self) {
         let size = Size(self.sc_desc.width, self.sc_desc.height);
         let rect = Rect::new(Coord::ZERO, size);
@@ -321,6 +573,25 @@ where
         self.window.request_redraw();
     }
 
+    /// Notify focus trackers if the focus has changed or moved
+    fn track_focus<C, T>(&mut self, shared: &mut SharedState<C, T>)
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let mut focus = self.mgr.focus_rect(self.widget.as_widget());
+        if let (Some(focus), Ok(pos)) = (focus.as_mut(), self.window.inner_position()) {
+            let offset = Coord::from(pos);
+            focus.rect = focus.rect + offset;
+            focus.caret = focus.caret.map(|rect| rect + offset);
+        }
+        if focus != self.focus {
+            self.focus = focus;
+            let window = self.window_id;
+            shared.notify_focus(FocusNotification { window, focus });
+        }
+    }
+
     fn do_resize<C, T>(&mut self, shared: &mut SharedState<C, T>, size: PhysicalSize<u32>)
     where
         C: CustomPipe<Window = CW>,
@@ -345,12 +616,39 @@ where
         self.apply_size();
     }
 
+    /// Re-create the surface after the application is resumed
+    ///
+    /// On some platforms (e.g. Android), surfaces are destroyed when the
+    /// application is suspended.
+    pub(crate) fn resume<C, T>(&mut self, shared: &mut SharedState<C, T>)
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        self.surface = wgpu::Surface::create(&self.window);
+        self.swap_chain = shared
+            .device
+            .create_swap_chain(&self.surface, &self.sc_desc);
+        self.window.request_redraw();
+    }
+
     pub(crate) fn do_draw<C, T>(&mut self, shared: &mut SharedState<C, T>)
     where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
     {
         trace!("Window::do_draw");
+        self.redraw_at = None;
+        if self.mgr.frame_requested() {
+            let info = self.frame_info();
+            let mut tkw =
+                TkWindow::new(shared, &self.window, &mut self.draw, &mut self.theme_window);
+            let widget = &mut *self.widget;
+            self.mgr.with(&mut tkw, |mgr| {
+                mgr.update_frame(widget, info);
+            });
+        }
+
         let size = Size(self.sc_desc.width, self.sc_desc.height);
         let rect = Rect {
             pos: Coord::ZERO,
@@ -364,9 +662,37 @@ where
         self.widget.draw(&mut draw_handle, &self.mgr, false);
         drop(draw_handle);
 
-        let frame = self.swap_chain.get_next_texture().unwrap();
         let clear_color = to_wgpu_color(shared.theme.clear_colour());
+
+        if !self.captures.is_empty() {
+            // Draw commands are consumed by rendering, thus we render this
+            // frame off-screen only and redraw the window afterwards.
+            let image = if size.0 * size.1 == 0 {
+                Some(RgbaImage { size, data: vec![] })
+            } else {
+                shared.render_capture(&mut self.draw, size, clear_color)
+            };
+            match image {
+                Some(image) => {
+                    for sender in self.captures.drain(..) {
+                        // The receiver may have been dropped; this is not an error
+                        let _ = sender.send(image.clone());
+                    }
+                }
+                None => {
+                    // Dropping senders notifies receivers of failure
+                    warn!("Window::do_draw: failed to read back captured frame");
+                    self.captures.clear();
+                }
+            }
+            self.window.request_redraw();
+            return;
+        }
+
+        let frame = self.swap_chain.get_next_texture().unwrap();
         shared.render(&mut self.draw, &frame.view, clear_color);
+        drop(frame);
+        self.last_present = Some(Instant::now());
     }
 }
 
@@ -444,10 +770,14 @@ where
         self.shared.pending.push(PendingAction::CloseWindow(id));
     }
 
-    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
+    fn send_action_to(&mut self, id: Option<WindowId>, action: TkAction) {
+        self.shared.pending.push(PendingAction::Action(id, action));
+    }
+
+    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64, data: Option<UpdateData>) {
         self.shared
             .pending
-            .push(PendingAction::Update(handle, payload));
+            .push(PendingAction::Update(handle, payload, data));
     }
 
     #[inline]
@@ -460,6 +790,16 @@ where
         self.shared.set_clipboard(content);
     }
 
+    #[inline]
+    fn get_primary(&mut self) -> Option<CowString> {
+        self.shared.get_primary()
+    }
+
+    #[inline]
+    fn set_primary<'c>(&mut self, content: CowStringL<'c>) {
+        self.shared.set_primary(content);
+    }
+
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
         match f(&mut self.shared.theme) {
             ThemeAction::None => (),
@@ -478,4 +818,30 @@ where
     fn set_cursor_icon(&mut self, icon: CursorIcon) {
         self.window.set_cursor_icon(icon);
     }
+
+    fn set_pointer_lock(&mut self, lock: bool) -> bool {
+        if let Err(e) = self.window.set_cursor_grab(lock) {
+            warn!("Failed to set cursor grab: {}", e);
+            return false;
+        }
+        self.window.set_cursor_visible(!lock);
+        true
+    }
+
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        self.shared.draw.load_image(size, data)
+    }
+
+    fn remove_image(&mut self, id: ImageId) {
+        self.shared.draw.remove_image(id);
+    }
+
+    fn memory_usage(&self) -> u64 {
+        self.shared.draw.memory_usage()
+    }
+
+    #[inline]
+    fn raw_window_handle(&self) -> Option<RawWindowHandle> {
+        Some(self.window.raw_window_handle())
+    }
 }
@@ -0,0 +1,321 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Actions
+//!
+//! An [`Action`] describes a user command: a label, an optional icon and
+//! keyboard shortcut, an enabled state and the message emitted on activation.
+//! One action may be bound to several widgets simultaneously, for example a
+//! [`kas::widget::MenuAction`] in a menu and a [`kas::widget::ActionButton`]
+//! in a toolbar; the shortcut is registered by bound widgets.
+//!
+//! Actions are shared state: clones refer to the same action. Changes (e.g.
+//! via [`Action::set_enabled`]) are propagated to all bound widgets via the
+//! action's [`UpdateHandle`].
+//!
+//! Actions may be *checkable*: a toggle action (see [`Action::with_checked`])
+//! flips its state on each activation, while actions of an [`ActionGroup`]
+//! are mutually exclusive (e.g. tools of a drawing application's palette).
+//! Bound widgets display the checked state.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+use kas::event::{Manager, Shortcut, UpdateHandle};
+use kas::string::{AccelString, CowString};
+
+#[derive(Debug, Default)]
+struct GroupState {
+    next: Cell<u32>,
+    selected: Cell<Option<u32>>,
+}
+
+/// A group of mutually exclusive actions
+///
+/// Actions are added via [`ActionGroup::action`]. Activating an action of the
+/// group checks it and unchecks all others. All actions of the group share
+/// the group's [`UpdateHandle`].
+#[derive(Clone, Debug)]
+pub struct ActionGroup {
+    state: Rc<GroupState>,
+    handle: UpdateHandle,
+}
+
+impl Default for ActionGroup {
+    fn default() -> Self {
+        ActionGroup::new()
+    }
+}
+
+impl ActionGroup {
+    /// Construct an empty group
+    pub fn new() -> Self {
+        ActionGroup {
+            state: Default::default(),
+            handle: UpdateHandle::new(),
+        }
+    }
+
+    /// Construct a new action in this group
+    ///
+    /// The action is initially unchecked; use [`Action::with_checked`] to
+    /// select it initially.
+    pub fn action<S: Into<AccelString>, M: Clone>(&self, label: S, msg: M) -> Action<M> {
+        let index = self.state.next.get();
+        self.state.next.set(index + 1);
+        let mut action = Action::new(label, msg);
+        action.handle = self.handle;
+        action.inner.borrow_mut().check = Check::Group(self.state.clone(), index);
+        action
+    }
+
+    /// Get the update handle
+    #[inline]
+    pub fn update_handle(&self) -> UpdateHandle {
+        self.handle
+    }
+
+    /// Uncheck all actions of the group
+    pub fn clear(&self, mgr: &mut Manager) {
+        if self.state.selected.replace(None).is_some() {
+            mgr.trigger_update(self.handle, 0);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Check {
+    None,
+    Toggle(bool),
+    Group(Rc<GroupState>, u32),
+}
+
+#[derive(Debug)]
+struct Inner<M> {
+    label: AccelString,
+    icon: Option<CowString>,
+    shortcut: Option<Shortcut>,
+    enabled: bool,
+    check: Check,
+    msg: M,
+}
+
+/// A shared user command
+///
+/// See the [module documentation](self).
+pub struct Action<M> {
+    inner: Rc<RefCell<Inner<M>>>,
+    handle: UpdateHandle,
+}
+
+// Manual impl: derive would require M: Clone
+impl<M> Clone for Action<M> {
+    fn clone(&self) -> Self {
+        Action {
+            inner: self.inner.clone(),
+            handle: self.handle,
+        }
+    }
+}
+
+impl<M: fmt::Debug> fmt::Debug for Action<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Action {{ handle: {:?}, inner: {:?} }}",
+            self.handle,
+            self.inner.borrow()
+        )
+    }
+}
+
+impl<M: Clone> Action<M> {
+    /// Construct with a given `label` and `msg`
+    ///
+    /// The message `msg` is emitted by bound widgets on activation.
+    /// Actions are initially enabled.
+    pub fn new<S: Into<AccelString>>(label: S, msg: M) -> Self {
+        Action {
+            inner: Rc::new(RefCell::new(Inner {
+                label: label.into(),
+                icon: None,
+                shortcut: None,
+                enabled: true,
+                check: Check::None,
+                msg,
+            })),
+            handle: UpdateHandle::new(),
+        }
+    }
+
+    /// Set an icon (chain style)
+    ///
+    /// The icon is identified by name. Widgets included in KAS do not
+    /// currently draw icons.
+    pub fn with_icon<S: Into<CowString>>(self, icon: S) -> Self {
+        self.inner.borrow_mut().icon = Some(icon.into());
+        self
+    }
+
+    /// Set a keyboard shortcut (chain style)
+    pub fn with_shortcut(self, shortcut: Shortcut) -> Self {
+        self.inner.borrow_mut().shortcut = Some(shortcut);
+        self
+    }
+
+    /// Set the checked state (chain style)
+    ///
+    /// For actions not in an [`ActionGroup`], this makes the action a toggle:
+    /// each activation flips the checked state. For actions in a group,
+    /// `true` selects this action.
+    pub fn with_checked(self, checked: bool) -> Self {
+        self.set_checked_(checked);
+        self
+    }
+
+    /// Get the update handle
+    ///
+    /// Bound widgets should subscribe to this via
+    /// [`Manager::update_on_handle`].
+    #[inline]
+    pub fn update_handle(&self) -> UpdateHandle {
+        self.handle
+    }
+
+    /// Get the label
+    pub fn label(&self) -> AccelString {
+        self.inner.borrow().label.clone()
+    }
+
+    /// Get the icon name, if any
+    pub fn icon(&self) -> Option<CowString> {
+        self.inner.borrow().icon.clone()
+    }
+
+    /// Get the shortcut, if any
+    pub fn shortcut(&self) -> Option<Shortcut> {
+        self.inner.borrow().shortcut
+    }
+
+    /// Get the message
+    pub fn msg(&self) -> M {
+        self.inner.borrow().msg.clone()
+    }
+
+    /// Check whether the action is checkable
+    ///
+    /// This is true for toggle actions and for actions in an [`ActionGroup`].
+    pub fn is_checkable(&self) -> bool {
+        match self.inner.borrow().check {
+            Check::None => false,
+            _ => true,
+        }
+    }
+
+    /// Check whether the action is in an [`ActionGroup`]
+    pub fn is_grouped(&self) -> bool {
+        match self.inner.borrow().check {
+            Check::Group(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Get the checked state
+    ///
+    /// This is false for actions which are not checkable.
+    pub fn is_checked(&self) -> bool {
+        match self.inner.borrow().check {
+            Check::None => false,
+            Check::Toggle(state) => state,
+            Check::Group(ref group, index) => group.selected.get() == Some(index),
+        }
+    }
+
+    /// Set the checked state
+    ///
+    /// See [`Action::with_checked`]. Bound widgets are updated.
+    pub fn set_checked(&self, mgr: &mut Manager, checked: bool) {
+        if self.set_checked_(checked) {
+            self.notify(mgr);
+        }
+    }
+
+    // Returns true when changed
+    fn set_checked_(&self, checked: bool) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        match inner.check {
+            Check::Group(ref group, index) => {
+                let selected = group.selected.get();
+                if checked {
+                    group.selected.set(Some(index));
+                } else if selected == Some(index) {
+                    group.selected.set(None);
+                }
+                selected != group.selected.get()
+            }
+            Check::Toggle(state) => {
+                inner.check = Check::Toggle(checked);
+                state != checked
+            }
+            Check::None => {
+                inner.check = Check::Toggle(checked);
+                true
+            }
+        }
+    }
+
+    /// Activate the action
+    ///
+    /// This should be called by bound widgets on activation. If the action is
+    /// disabled, nothing happens and `None` is returned. Otherwise, the
+    /// checked state is updated (where checkable) and the message is returned.
+    pub fn activate(&self, mgr: &mut Manager) -> Option<M> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let checked = match self.inner.borrow().check {
+            Check::None => None,
+            Check::Toggle(state) => Some(!state),
+            Check::Group(..) => Some(true),
+        };
+        if let Some(checked) = checked {
+            self.set_checked(mgr, checked);
+        }
+        Some(self.msg())
+    }
+
+    /// Check whether the action is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.inner.borrow().enabled
+    }
+
+    /// Enable or disable the action
+    ///
+    /// Bound widgets are updated.
+    pub fn set_enabled(&self, mgr: &mut Manager, enabled: bool) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.enabled != enabled {
+            inner.enabled = enabled;
+            drop(inner);
+            self.notify(mgr);
+        }
+    }
+
+    /// Set the label
+    ///
+    /// Bound widgets are updated. Since accelerator keys are registered during
+    /// configuration, bound widgets request [`kas::TkAction::RECONFIGURE`].
+    pub fn set_label<S: Into<AccelString>>(&self, mgr: &mut Manager, label: S) {
+        self.inner.borrow_mut().label = label.into();
+        self.notify(mgr);
+    }
+
+    /// Notify bound widgets of changes
+    #[inline]
+    fn notify(&self, mgr: &mut Manager) {
+        mgr.trigger_update(self.handle, 0);
+    }
+}
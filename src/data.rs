@@ -11,6 +11,7 @@ use std::num::NonZeroU32;
 use std::u32;
 
 use crate::geom::{Rect, Size};
+use crate::string::CowString;
 
 /// Widget identifier
 ///
@@ -21,7 +22,7 @@ use crate::geom::{Rect, Size};
 /// `Option<WidgetId>` is a free extension (requires no extra memory).
 ///
 /// Identifiers are assigned when configured and when re-configured
-/// (via [`kas::TkAction::Reconfigure`]). Since user-code is not notified of a
+/// (via [`kas::TkAction::RECONFIGURE`]). Since user-code is not notified of a
 /// re-configure, user-code should not store a `WidgetId`.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct WidgetId(NonZeroU32);
@@ -94,6 +95,7 @@ pub struct CoreData {
     pub rect: Rect,
     pub id: WidgetId,
     pub disabled: bool,
+    pub name: Option<CowString>,
 }
 
 /// Alignment of contents
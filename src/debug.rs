@@ -0,0 +1,598 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Debugging and development aids
+
+use log::{info, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use kas::action::Action;
+use kas::class::HasText;
+use kas::event::{Shortcut, UpdateHandle, VirtualKeyCode};
+use kas::prelude::*;
+use kas::widget::*;
+use kas::{Down, Right};
+
+#[derive(Clone, Debug, VoidMsg)]
+enum Item {
+    Button,
+    Check(bool),
+    Combo(i32),
+    Radio(WidgetId),
+    Edit(String),
+    Slider(i32),
+    Scroll(u32),
+    Popup,
+}
+
+#[derive(Clone, Debug, VoidMsg)]
+enum MenuItem {
+    Theme(String),
+    Colour(&'static str),
+    Disabled(bool),
+    Quit,
+}
+
+#[derive(Clone, Debug)]
+struct Guard;
+impl EditGuard for Guard {
+    type Msg = Item;
+
+    fn activate(edit: &mut EditBox<Self>) -> Option<Self::Msg> {
+        Some(Item::Edit(edit.get_text().to_string()))
+    }
+
+    fn edit(edit: &mut EditBox<Self>) -> Option<Self::Msg> {
+        // Exercise the error state: text of length 8 is an error
+        edit.set_error_state(edit.get_text().len() % (7 + 1) == 0);
+        None
+    }
+}
+
+#[layout(grid)]
+#[handler(msg = Item)]
+#[derive(Debug, Widget)]
+struct Widgets {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(row = 0, col = 0)]
+    l0: Label,
+    #[widget(row = 0, col = 1)]
+    label: Label,
+    #[widget(row = 1, col = 0)]
+    l1: Label,
+    #[widget(row = 1, col = 1)]
+    edit: EditBox<Guard>,
+    #[widget(row = 2, col = 0)]
+    l2: Label,
+    #[widget(row = 2, col = 1)]
+    button: TextButton<Item>,
+    #[widget(row = 3, col = 0)]
+    l3: Label,
+    #[widget(row = 3, col = 1)]
+    check: CheckBox<Item>,
+    #[widget(row = 4, col = 0)]
+    l4: Label,
+    #[widget(row = 4, col = 1)]
+    radio1: RadioBox<Item>,
+    #[widget(row = 5, col = 0)]
+    l5: Label,
+    #[widget(row = 5, col = 1)]
+    radio2: RadioBox<Item>,
+    #[widget(row = 6, col = 0)]
+    l6: Label,
+    #[widget(row = 6, col = 1, handler = handle_combo)]
+    combo: ComboBox<i32>,
+    #[widget(row = 7, col = 0)]
+    l7: Label,
+    #[widget(row = 7, col = 1, handler = handle_slider)]
+    slider: Slider<i32, Right>,
+    #[widget(row = 8, col = 0)]
+    l8: Label,
+    #[widget(row = 8, col = 1, handler = handle_scroll)]
+    scroll: ScrollBar<Right>,
+    #[widget(row = 9, col = 0)]
+    l9: Label,
+    #[widget(row = 9, col = 1)]
+    popup: TextButton<Item>,
+}
+
+impl Widgets {
+    fn new() -> Self {
+        let radio = UpdateHandle::new();
+        Widgets {
+            core: Default::default(),
+            layout_data: Default::default(),
+            l0: Label::new("Label"),
+            label: Label::new("Hello world"),
+            l1: Label::new("EditBox"),
+            edit: EditBox::new("edit me").with_guard(Guard),
+            l2: Label::new("TextButton"),
+            button: TextButton::new("&Press me", Item::Button),
+            l3: Label::new("CheckBox"),
+            check: CheckBox::new("&Check me")
+                .state(true)
+                .on_toggle(Item::Check),
+            l4: Label::new("RadioBox"),
+            radio1: RadioBox::new(radio, "radio box &1")
+                .state(false)
+                .on_activate(Item::Radio),
+            l5: Label::new("RadioBox"),
+            radio2: RadioBox::new(radio, "radio box &2")
+                .state(true)
+                .on_activate(Item::Radio),
+            l6: Label::new("ComboBox"),
+            combo: [("One", 1), ("Two", 2), ("Three", 3)].iter().collect(),
+            l7: Label::new("Slider"),
+            slider: Slider::new(-2, 2, 1).with_value(0),
+            l8: Label::new("ScrollBar"),
+            scroll: ScrollBar::new().with_limits(5, 2),
+            l9: Label::new("Child window"),
+            popup: TextButton::new("&Open", Item::Popup),
+        }
+    }
+
+    fn handle_combo(&mut self, _: &mut Manager, msg: i32) -> Response<Item> {
+        Response::Msg(Item::Combo(msg))
+    }
+    fn handle_slider(&mut self, _: &mut Manager, msg: i32) -> Response<Item> {
+        Response::Msg(Item::Slider(msg))
+    }
+    fn handle_scroll(&mut self, _: &mut Manager, msg: u32) -> Response<Item> {
+        Response::Msg(Item::Scroll(msg))
+    }
+}
+
+type Menus = MenuBar<Right, Box<dyn Menu<Msg = MenuItem>>>;
+
+/// Content of [`gallery_window`]
+///
+/// This type is opaque; it exists only to allow naming the result type.
+#[layout(column)]
+#[handler(msg = VoidMsg)]
+#[derive(Debug, Widget)]
+pub struct Gallery {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(handler = menu)]
+    menubar: Menus,
+    #[widget(halign = centre)]
+    title: Frame<Label>,
+    #[widget(handler = activations)]
+    gallery: ScrollRegion<Widgets>,
+}
+
+impl Gallery {
+    fn new(themes: Vec<String>) -> Self {
+        let quit =
+            Action::new("&Quit", MenuItem::Quit).with_shortcut(Shortcut::ctrl(VirtualKeyCode::Q));
+        let mut menus = vec![SubMenu::<Down, _>::new(
+            "&App",
+            vec![MenuAction::new(quit).boxed()],
+        )];
+        if !themes.is_empty() {
+            let themes = (themes.into_iter())
+                .map(|name| MenuEntry::new(name.clone(), MenuItem::Theme(name)).boxed())
+                .collect();
+            menus.push(SubMenu::new("&Theme", themes));
+        }
+        let colours = vec![
+            MenuEntry::new("&Default", MenuItem::Colour("default")),
+            MenuEntry::new("&Light", MenuItem::Colour("light")),
+            MenuEntry::new("Dar&k", MenuItem::Colour("dark")),
+        ];
+        menus.push(SubMenu::new(
+            "&Style",
+            vec![
+                SubMenu::right("&Colours", colours).boxed(),
+                Separator::infer().boxed(),
+                MenuToggle::new_on(MenuItem::Disabled, "&Disabled").boxed(),
+            ],
+        ));
+
+        Gallery {
+            core: Default::default(),
+            layout_data: Default::default(),
+            menubar: MenuBar::new(menus),
+            title: Frame::new(Label::new("Widget Gallery")),
+            gallery: ScrollRegion::new(Widgets::new()).with_auto_bars(true),
+        }
+    }
+
+    fn menu(&mut self, mgr: &mut Manager, msg: MenuItem) -> Response<VoidMsg> {
+        match msg {
+            MenuItem::Theme(name) => {
+                info!(target: "gallery", "theme = {:?}", name);
+                mgr.adjust_theme(|theme| theme.set_theme(&name));
+            }
+            MenuItem::Colour(name) => {
+                info!(target: "gallery", "colour scheme = {:?}", name);
+                mgr.adjust_theme(|theme| theme.set_colours(name));
+            }
+            MenuItem::Disabled(state) => {
+                *mgr += self.gallery.inner_mut().set_disabled(state);
+            }
+            MenuItem::Quit => {
+                *mgr += TkAction::CLOSE_ALL;
+            }
+        }
+        Response::None
+    }
+
+    fn activations(&mut self, mgr: &mut Manager, item: Item) -> Response<VoidMsg> {
+        match item {
+            Item::Button => info!(target: "gallery", "clicked!"),
+            Item::Check(b) => info!(target: "gallery", "CheckBox: {}", b),
+            Item::Combo(c) => info!(target: "gallery", "ComboBox: {}", c),
+            Item::Radio(id) => info!(target: "gallery", "RadioBox: {}", id),
+            Item::Edit(s) => info!(target: "gallery", "edited: {}", s),
+            Item::Slider(p) => info!(target: "gallery", "Slider: {}", p),
+            Item::Scroll(p) => info!(target: "gallery", "ScrollBar: {}", p),
+            Item::Popup => {
+                let window = MessageBox::new("Popup", "Hello!");
+                mgr.add_window(Box::new(window));
+            }
+        }
+        Response::None
+    }
+}
+
+/// Construct a window exercising all stock widgets
+///
+/// This is the widget gallery as a ready-made window, intended for debugging
+/// and for theme authors validating a theme against every drawn element.
+///
+/// The `theme` should be that passed to the toolkit. Where it offers
+/// multiple themes (see [`ThemeApi::list_themes`], e.g. via
+/// `kas_theme::MultiTheme`), a "Theme" menu is added with an entry for each;
+/// selecting an entry calls [`ThemeApi::set_theme`] with that name.
+/// Additionally, a "Style" menu allows switching between the standard colour
+/// schemes and disabling all widgets. "Quit" closes all windows. Activations
+/// are logged at `info` level.
+///
+/// Example:
+/// ```notest
+/// let window = kas::debug::gallery_window(&theme);
+/// let mut toolkit = kas_wgpu::Toolkit::new(theme)?;
+/// toolkit.add(window)?;
+/// toolkit.run()
+/// ```
+pub fn gallery_window(theme: &dyn ThemeApi) -> Window<Gallery> {
+    Window::new("Widget Gallery", Gallery::new(theme.list_themes()))
+}
+
+/// A captured log record
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    /// Severity
+    pub level: log::Level,
+    /// Target (usually the module path)
+    pub target: String,
+    /// Formatted message
+    pub message: String,
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{} {}] {}", self.level, self.target, self.message)
+    }
+}
+
+#[derive(Debug)]
+struct BufferState {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    generation: u64,
+}
+
+/// A ring buffer of [`LogRecord`]s
+///
+/// This is shared state: clones refer to the same buffer. When full, the
+/// oldest records are discarded.
+#[derive(Clone, Debug)]
+pub struct LogBuffer {
+    state: Arc<Mutex<BufferState>>,
+}
+
+impl LogBuffer {
+    /// Construct, holding at most `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        let state = BufferState {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+            generation: 0,
+        };
+        LogBuffer {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Append a record
+    pub fn push(&self, record: LogRecord) {
+        let mut state = self.state.lock().unwrap();
+        if state.records.len() >= state.capacity {
+            state.records.pop_front();
+        }
+        if state.capacity > 0 {
+            state.records.push_back(record);
+        }
+        state.generation += 1;
+    }
+
+    /// Get a copy of all records, oldest first
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.state.lock().unwrap().records.iter().cloned().collect()
+    }
+
+    /// Remove all records
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.records.clear();
+        state.generation += 1;
+    }
+
+    /// A counter incremented on each change
+    pub fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+}
+
+/// A logger capturing records to a [`LogBuffer`]
+///
+/// Records from the toolkit's own crates (`kas`, `kas_theme` and `kas_wgpu`)
+/// are not captured, since the toolkit logs while drawing and resizing the
+/// console itself. Records may additionally be forwarded to another logger (e.g. one writing
+/// to `stderr`) via [`LogCapture::with_inner`].
+///
+/// Example:
+/// ```notest
+/// let buffer = kas::debug::LogCapture::new(1000).install().unwrap();
+/// // ...
+/// mgr.add_window(Box::new(kas::debug::log_window(buffer)));
+/// ```
+pub struct LogCapture {
+    buffer: LogBuffer,
+    level: LevelFilter,
+    inner: Option<Box<dyn Log>>,
+}
+
+impl fmt::Debug for LogCapture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LogCapture {{ buffer: {:?}, level: {:?}, ... }}",
+            self.buffer, self.level
+        )
+    }
+}
+
+impl LogCapture {
+    /// Construct, holding at most `capacity` records
+    ///
+    /// By default, records up to level `Info` are captured.
+    pub fn new(capacity: usize) -> Self {
+        LogCapture {
+            buffer: LogBuffer::new(capacity),
+            level: LevelFilter::Info,
+            inner: None,
+        }
+    }
+
+    /// Set the maximum level captured (chain style)
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Forward all records to `inner` (chain style)
+    pub fn with_inner(mut self, inner: Box<dyn Log>) -> Self {
+        self.inner = Some(inner);
+        self
+    }
+
+    /// Get the buffer
+    pub fn buffer(&self) -> LogBuffer {
+        self.buffer.clone()
+    }
+
+    /// Install as the global logger
+    ///
+    /// This sets the maximum log level (see [`log::set_max_level`]) to that
+    /// captured; hence an inner logger does not receive records above this
+    /// level. Fails if a logger has already been installed.
+    pub fn install(self) -> Result<LogBuffer, log::SetLoggerError> {
+        let buffer = self.buffer.clone();
+        let level = self.level;
+        log::set_logger(Box::leak(Box::new(self)))?;
+        log::set_max_level(level);
+        Ok(buffer)
+    }
+}
+
+impl Log for LogCapture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+            || self.inner.as_ref().map(|inner| inner.enabled(metadata)) == Some(true)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= self.level && !is_toolkit_target(record.target()) {
+            self.buffer.push(LogRecord {
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+        if let Some(ref inner) = self.inner {
+            inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(ref inner) = self.inner {
+            inner.flush();
+        }
+    }
+}
+
+// True if target is within one of the toolkit's crates
+fn is_toolkit_target(target: &str) -> bool {
+    let krate = target.split("::").next().unwrap_or(target);
+    krate == "kas" || krate == "kas_theme" || krate == "kas_wgpu"
+}
+
+/// Interval between checks for new records in a [`LogConsole`]
+const CONSOLE_POLL: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Debug, VoidMsg)]
+enum ConsoleMsg {
+    Copy,
+    Clear,
+}
+
+/// A widget displaying the contents of a [`LogBuffer`]
+///
+/// Records are filtered by level; the "Copy" button copies displayed records
+/// to the clipboard. The buffer is polled for new records several times per
+/// second.
+#[layout(grid)]
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+#[derive(Debug, Widget)]
+pub struct LogConsole {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(row = 0, col = 0, handler = set_level)]
+    level: ComboBox<LevelFilter>,
+    #[widget(row = 0, col = 1, handler = handle_button)]
+    copy: TextButton<ConsoleMsg>,
+    #[widget(row = 0, col = 2, handler = handle_button)]
+    clear: TextButton<ConsoleMsg>,
+    #[widget(row = 1, col = 0, cspan = 3)]
+    text: ScrollRegion<SelectableLabel>,
+    buffer: LogBuffer,
+    filter: LevelFilter,
+    generation: u64,
+}
+
+impl LogConsole {
+    /// Construct, displaying records of `buffer`
+    ///
+    /// Initially, records up to level `Info` are displayed.
+    pub fn new(buffer: LogBuffer) -> Self {
+        let levels = [
+            ("Error", LevelFilter::Error),
+            ("Warn", LevelFilter::Warn),
+            ("Info", LevelFilter::Info),
+            ("Debug", LevelFilter::Debug),
+            ("Trace", LevelFilter::Trace),
+        ];
+        let mut level: ComboBox<LevelFilter> = levels.iter().collect();
+        level.set_active(2);
+        let mut console = LogConsole {
+            core: Default::default(),
+            layout_data: Default::default(),
+            level,
+            copy: TextButton::new("&Copy", ConsoleMsg::Copy),
+            clear: TextButton::new("C&lear", ConsoleMsg::Clear),
+            text: ScrollRegion::new(SelectableLabel::new("")).with_auto_bars(true),
+            buffer,
+            filter: LevelFilter::Info,
+            generation: 0,
+        };
+        let _ = console.update_text();
+        console
+    }
+
+    /// Access the buffer
+    pub fn buffer(&self) -> &LogBuffer {
+        &self.buffer
+    }
+
+    /// Get displayed records as text
+    pub fn text(&self) -> String {
+        let filter = self.filter;
+        let records = self.buffer.records();
+        let lines = records.iter().filter(|r| r.level <= filter);
+        lines.map(|r| format!("{}\n", r)).collect()
+    }
+
+    // Update displayed text
+    fn update_text(&mut self) -> TkAction {
+        self.generation = self.buffer.generation();
+        // Labels treat '&' as an accelerator-key prefix
+        let text = self.text().replace('&', "&&");
+        self.text.inner_mut().set_text(text)
+    }
+
+    // Update displayed text and resize the text region
+    fn refresh(&mut self, mgr: &mut Manager) {
+        *mgr += self.update_text();
+        // Text size may have changed. Rather than reconfigure the whole
+        // window, we solve the scroll region's layout within its current rect.
+        let rect = self.text.rect();
+        let text = &mut self.text;
+        mgr.size_handle(|size_handle| {
+            let _ = text.size_rules(size_handle, AxisInfo::new(false, None));
+            let _ = text.size_rules(size_handle, AxisInfo::new(true, Some(rect.size.0)));
+            text.set_rect(rect, AlignHints::NONE);
+        });
+        *mgr += TkAction::REGION_MOVED;
+    }
+
+    fn set_level(&mut self, mgr: &mut Manager, level: LevelFilter) -> Response<VoidMsg> {
+        self.filter = level;
+        self.refresh(mgr);
+        Response::None
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: ConsoleMsg) -> Response<VoidMsg> {
+        match msg {
+            ConsoleMsg::Copy => mgr.set_clipboard(self.text().into()),
+            ConsoleMsg::Clear => {
+                self.buffer.clear();
+                self.refresh(mgr);
+            }
+        }
+        Response::None
+    }
+}
+
+impl WidgetConfig for LogConsole {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.update_after(CONSOLE_POLL, self.id());
+    }
+}
+
+impl event::Handler for LogConsole {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<VoidMsg> {
+        match event {
+            Event::TimerUpdate => {
+                if self.buffer.generation() != self.generation {
+                    self.refresh(mgr);
+                }
+                mgr.update_after(CONSOLE_POLL, self.id());
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+/// Construct a window displaying a [`LogConsole`]
+pub fn log_window(buffer: LogBuffer) -> Window<LogConsole> {
+    Window::new("Log", LogConsole::new(buffer))
+}
@@ -0,0 +1,409 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Declarative UI descriptions
+//!
+//! This module allows constructing a widget tree at run-time from a
+//! [RON](https://github.com/ron-rs/ron) or JSON description (see [`Format`]).
+//! It is intended for
+//! prototyping: a [`UiFile`] widget may reload its description whenever the
+//! file changes, replacing its content. Production code should continue to use
+//! statically-typed widgets (e.g. via [`make_widget`]).
+//!
+//! Widgets built from a description emit `String` messages (see [`Node`]).
+//!
+//! Example description:
+//! ```ron
+//! Column([
+//!     Label("Hello world"),
+//!     Row([
+//!         Button("&Ok", "ok"),
+//!         Button("&Cancel", "cancel"),
+//!     ]),
+//! ])
+//! ```
+//!
+//! The equivalent JSON description:
+//! ```json
+//! {"Column": [
+//!     {"Label": "Hello world"},
+//!     {"Row": [
+//!         {"Button": ["&Ok", "ok"]},
+//!         {"Button": ["&Cancel", "cancel"]}
+//!     ]}
+//! ]}
+//! ```
+//!
+//! **Feature gated**: this is only available with feature `declarative`.
+//! Loading JSON additionally requires feature `json`.
+//!
+//! [`make_widget`]: ../macros/index.html#the-make_widget-macro
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use kas::prelude::*;
+use kas::widget::*;
+
+/// Interval between checks for modification of a [`UiFile`]
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Handler for messages emitted by the content of a [`UiFile`]
+type MsgHandler = Rc<dyn Fn(&mut Manager, &str)>;
+
+/// A node of a UI description
+///
+/// Messages emitted by the built widgets are strings: buttons emit their
+/// `msg`; check boxes emit `"msg=true"` or `"msg=false"` when toggled and edit
+/// boxes emit `"msg=text"` on activation (the Return key).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Node {
+    /// A [`Label`] with the given text
+    Label(String),
+    /// A [`TextButton`]: `(label, msg)`
+    Button(String, String),
+    /// A [`CheckBox`]: `(label, msg)`
+    CheckBox(String, String),
+    /// An [`EditBox`]: `(text, msg)`
+    EditBox(String, String),
+    /// A [`Separator`]
+    Separator,
+    /// A [`Filler`]
+    Filler,
+    /// A row of widgets
+    Row(Vec<Node>),
+    /// A column of widgets
+    Column(Vec<Node>),
+    /// A [`Frame`] around a widget
+    Frame(Box<Node>),
+    /// A [`ScrollRegion`] around a widget
+    Scroll(Box<Node>),
+}
+
+/// Format of a UI description
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// [RON](https://github.com/ron-rs/ron)
+    Ron,
+    /// JSON (requires feature `json`)
+    Json,
+}
+
+impl Format {
+    /// Select by the extension of `path`
+    ///
+    /// The extension `json` selects JSON; anything else selects RON.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Format::Json,
+            _ => Format::Ron,
+        }
+    }
+}
+
+/// Error loading a UI description
+#[derive(Debug)]
+pub enum LoadError {
+    /// File IO error
+    Io(std::io::Error),
+    /// RON parse error
+    Ron(ron::Error),
+    /// JSON parse error
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The format is not supported
+    ///
+    /// This is reported for [`Format::Json`] when feature `json` is not
+    /// enabled.
+    Unsupported(Format),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "UI description: IO error: {}", e),
+            LoadError::Ron(e) => write!(f, "UI description: RON error: {}", e),
+            #[cfg(feature = "json")]
+            LoadError::Json(e) => write!(f, "UI description: JSON error: {}", e),
+            LoadError::Unsupported(format) => write!(
+                f,
+                "UI description: unsupported format: {:?} (feature required)",
+                format
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl FromStr for Node {
+    type Err = LoadError;
+
+    /// Parse from a RON string
+    fn from_str(s: &str) -> Result<Self, LoadError> {
+        Node::parse(s, Format::Ron)
+    }
+}
+
+impl Node {
+    /// Parse from a string in the given `format`
+    pub fn parse(s: &str, format: Format) -> Result<Self, LoadError> {
+        match format {
+            Format::Ron => ron::de::from_str(s).map_err(LoadError::Ron),
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::from_str(s).map_err(LoadError::Json),
+            #[cfg(not(feature = "json"))]
+            Format::Json => Err(LoadError::Unsupported(format)),
+        }
+    }
+
+    /// Load from a file, with format selected by extension
+    ///
+    /// See [`Format::from_path`].
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let path = path.as_ref();
+        Node::from_file_as(path, Format::from_path(path))
+    }
+
+    /// Load from a file in the given `format`
+    pub fn from_file_as<P: AsRef<Path>>(path: P, format: Format) -> Result<Self, LoadError> {
+        let text = std::fs::read_to_string(path).map_err(LoadError::Io)?;
+        Node::parse(&text, format)
+    }
+
+    /// Build a widget tree
+    pub fn build(&self) -> Box<dyn Widget<Msg = String>> {
+        match self {
+            Node::Label(text) => Box::new(Static::new(Label::new(text.clone()))),
+            Node::Button(label, msg) => Box::new(TextButton::new(label.clone(), msg.clone())),
+            Node::CheckBox(label, msg) => {
+                let msg = msg.clone();
+                let check = CheckBox::new(label.clone());
+                Box::new(check.on_toggle(move |state| format!("{}={}", msg, state)))
+            }
+            Node::EditBox(text, msg) => {
+                let msg = msg.clone();
+                let edit = EditBox::new(text.clone());
+                Box::new(edit.on_activate(move |text| Some(format!("{}={}", msg, text))))
+            }
+            Node::Separator => Box::new(Separator::infer()),
+            Node::Filler => Box::new(Static::new(Filler::new())),
+            Node::Row(nodes) => Box::new(BoxRow::new(nodes.iter().map(Node::build).collect())),
+            Node::Column(nodes) => {
+                Box::new(BoxColumn::new(nodes.iter().map(Node::build).collect()))
+            }
+            Node::Frame(node) => Box::new(Frame::new(node.build())),
+            Node::Scroll(node) => Box::new(ScrollRegion::new(node.build()).with_auto_bars(true)),
+        }
+    }
+}
+
+/// Adapts a widget with `VoidMsg` to `String` messages
+#[layout(single)]
+#[handler(msg = String)]
+#[derive(Clone, Debug, Widget)]
+struct Static<W: Widget<Msg = VoidMsg>> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: W,
+}
+
+impl<W: Widget<Msg = VoidMsg>> Static<W> {
+    fn new(inner: W) -> Self {
+        Static {
+            core: Default::default(),
+            inner,
+        }
+    }
+}
+
+/// A widget built from a UI description file
+///
+/// The file is loaded on construction, with format selected by extension (see
+/// [`Format::from_path`]). If loading fails, an error message is displayed
+/// instead. With [`UiFile::with_reload`], the file is checked for
+/// modification periodically and reloaded, replacing the widget's content.
+///
+/// Messages from the content are logged at `info` level, then passed to the
+/// handler (see [`UiFile::on_msg`]), if any.
+///
+/// Example:
+/// ```notest
+/// let window = Window::new("Prototype", UiFile::new("ui.ron").with_reload(true));
+/// ```
+#[layout(single)]
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+#[derive(Widget)]
+pub struct UiFile {
+    #[widget_core]
+    core: CoreData,
+    #[widget(handler = handle_msg)]
+    root: Box<dyn Widget<Msg = String>>,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    reload: bool,
+    on_msg: Option<MsgHandler>,
+}
+
+impl Debug for UiFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "UiFile {{ core: {:?}, root: {:?}, path: {:?}, reload: {:?}, ... }}",
+            self.core, self.root, self.path, self.reload,
+        )
+    }
+}
+
+impl UiFile {
+    /// Construct, loading the description at `path`
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        let path = path.into();
+        let modified = Self::modified_time(&path);
+        let root = Self::load(&path);
+        UiFile {
+            core: Default::default(),
+            root,
+            path,
+            modified,
+            reload: false,
+            on_msg: None,
+        }
+    }
+
+    /// Enable or disable reloading when the file changes
+    pub fn with_reload(mut self, reload: bool) -> Self {
+        self.reload = reload;
+        self
+    }
+
+    /// Set a handler for messages emitted by the content
+    pub fn on_msg<F: Fn(&mut Manager, &str) + 'static>(mut self, f: F) -> Self {
+        self.on_msg = Some(Rc::new(f));
+        self
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn load(path: &Path) -> Box<dyn Widget<Msg = String>> {
+        match Node::from_file(path) {
+            Ok(node) => {
+                info!("UiFile: loaded {}", path.display());
+                node.build()
+            }
+            Err(e) => {
+                warn!("UiFile: failed to load {}: {}", path.display(), e);
+                Node::Label(e.to_string()).build()
+            }
+        }
+    }
+
+    fn handle_msg(&mut self, mgr: &mut Manager, msg: String) -> Response<VoidMsg> {
+        info!("UiFile: message: {}", msg);
+        if let Some(f) = self.on_msg.as_ref() {
+            f(mgr, &msg);
+        }
+        Response::None
+    }
+}
+
+impl WidgetConfig for UiFile {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if self.reload {
+            mgr.update_after(POLL_INTERVAL, self.id());
+        }
+    }
+}
+
+impl event::Handler for UiFile {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<VoidMsg> {
+        match event {
+            Event::TimerUpdate if self.reload => {
+                let modified = Self::modified_time(&self.path);
+                if modified != self.modified {
+                    self.modified = modified;
+                    self.root = Self::load(&self.path);
+                    // Reconfigure assigns identifiers and resizes the window
+                    *mgr += TkAction::RECONFIGURE;
+                } else {
+                    mgr.update_after(POLL_INTERVAL, self.id());
+                }
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expected() -> Node {
+        Node::Column(vec![
+            Node::Label("Hello world".into()),
+            Node::Row(vec![
+                Node::Button("&Ok".into(), "ok".into()),
+                Node::CheckBox("&Check".into(), "check".into()),
+            ]),
+            Node::Separator,
+        ])
+    }
+
+    const RON: &str = r#"Column([
+        Label("Hello world"),
+        Row([Button("&Ok", "ok"), CheckBox("&Check", "check")]),
+        Separator,
+    ])"#;
+
+    const JSON: &str = r#"{"Column": [
+        {"Label": "Hello world"},
+        {"Row": [{"Button": ["&Ok", "ok"]}, {"CheckBox": ["&Check", "check"]}]},
+        "Separator"
+    ]}"#;
+
+    #[test]
+    fn parse_formats() {
+        assert_eq!(RON.parse::<Node>().unwrap(), expected());
+        assert_eq!(Node::parse(RON, Format::Ron).unwrap(), expected());
+        match Node::parse(JSON, Format::Json) {
+            #[cfg(feature = "json")]
+            Ok(node) => assert_eq!(node, expected()),
+            #[cfg(not(feature = "json"))]
+            Err(LoadError::Unsupported(Format::Json)) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+        assert!(Node::parse(JSON, Format::Ron).is_err());
+    }
+
+    #[test]
+    fn file_format_by_extension() {
+        let dir = std::env::temp_dir().join(format!("kas-declarative-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (ron, json) = (dir.join("ui.ron"), dir.join("ui.json"));
+        std::fs::write(&ron, RON).unwrap();
+        std::fs::write(&json, JSON).unwrap();
+
+        assert_eq!(Format::from_path(&json), Format::Json);
+        assert_eq!(Format::from_path(Path::new("ui")), Format::Ron);
+        assert_eq!(Node::from_file(&ron).unwrap(), expected());
+        #[cfg(feature = "json")]
+        assert_eq!(Node::from_file(&json).unwrap(), expected());
+        assert!(Node::from_file_as(&json, Format::Ron).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
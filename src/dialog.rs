@@ -0,0 +1,733 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Stock dialogs
+//!
+//! Where the dialog boxes of [`kas::widget`] (e.g. [`MessageBox`]) only
+//! display content, the dialogs here additionally manage some operation on
+//! behalf of the caller.
+//!
+//! ### Long-running operations
+//!
+//! [`progress`] runs a worker function on a new thread while displaying a
+//! [`ProgressDialog`]. The worker is passed a [`Progress`] handle, over which
+//! it reports progress and checks for cancellation; the dialog closes once the
+//! worker returns. Workers driven some other way (e.g. as a future on an
+//! executor) may use [`ProgressDialog::new`] directly.
+//!
+//! Example:
+//! ```notest
+//! let dialog = kas::dialog::progress("Copying", "Copying files…", |progress| {
+//!     for i in 0..100 {
+//!         if progress.is_cancelled() {
+//!             return;
+//!         }
+//!         copy_file(i);
+//!         progress.set_fraction((i + 1) as f32 / 100.0);
+//!     }
+//! });
+//! mgr.add_window(Box::new(dialog.on_finish(|_, cancelled| {
+//!     println!("Finished (cancelled: {})", cancelled);
+//! })));
+//! ```
+//!
+//! ### About dialog
+//!
+//! [`about`] constructs a standard "about" window from an [`AppInfo`]. The
+//! [`app_info!`](crate::app_info) macro reads this metadata from the calling
+//! crate's Cargo manifest at compile time:
+//! ```notest
+//! let info = kas::app_info!().with_license(include_str!("../LICENSE"));
+//! mgr.add_window(Box::new(kas::dialog::about(info)));
+//! ```
+//!
+//! ### Error reporting
+//!
+//! An [`ErrorDialog`] displays an [`ErrorReport`], with the option of copying
+//! the report to the clipboard. [`set_panic_hook`] installs a panic hook
+//! generating such reports; toolkits use this to display a dialog instead of
+//! vanishing silently on panic (e.g. `kas_wgpu::Toolkit::report_panics`).
+//! Backtraces are only captured with the `nightly` feature.
+
+use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use kas::class::HasText;
+use kas::event::VirtualKeyCode;
+use kas::prelude::*;
+use kas::widget::{
+    BoxColumn, Label, ProgressBar, Row, ScrollRegion, SelectableLabel, TextButton, WidgetExt,
+};
+use kas::WindowId;
+
+pub use kas::widget::MessageBox;
+
+/// Interval between display updates of a [`ProgressDialog`]
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// State shared between a [`ProgressDialog`] and its worker
+#[derive(Debug, Default)]
+struct State {
+    /// Fraction complete, as bits of an `f32`
+    fraction: AtomicU32,
+    /// New message, if not yet displayed
+    message: Mutex<Option<String>>,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// A cancellation token
+///
+/// This may be cloned and passed to other threads; all clones observe
+/// cancellation of the corresponding [`ProgressDialog`].
+#[derive(Clone, Debug)]
+pub struct CancelToken {
+    state: Arc<State>,
+}
+
+impl CancelToken {
+    /// True if the user has requested cancellation
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// Worker handle of a [`ProgressDialog`]
+///
+/// The worker uses this to report progress and to check for cancellation.
+/// Cancellation is co-operative: the dialog remains open (with its "Cancel"
+/// button disabled) until the worker finishes.
+///
+/// The worker is considered finished when this handle is dropped (including
+/// when the worker panics).
+#[derive(Debug)]
+pub struct Progress {
+    state: Arc<State>,
+}
+
+impl Progress {
+    /// Set the fraction complete, in the range `0.0 ..= 1.0`
+    pub fn set_fraction(&self, fraction: f32) {
+        let fraction = fraction.max(0.0).min(1.0);
+        self.state
+            .fraction
+            .store(fraction.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Replace the displayed message
+    pub fn set_message<S: Into<String>>(&self, message: S) {
+        *self.state.message.lock().unwrap() = Some(message.into());
+    }
+
+    /// True if the user has requested cancellation
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Get a cancellation token
+    pub fn token(&self) -> CancelToken {
+        CancelToken {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.state.finished.store(true, Ordering::Release);
+    }
+}
+
+#[derive(Clone, Debug, VoidMsg)]
+enum DialogButton {
+    Cancel,
+}
+
+/// A progress dialog
+///
+/// This window displays a message, a progress bar and a "Cancel" button. It
+/// polls the state reported by its worker (see [`Progress`]) and closes itself
+/// once the worker finishes.
+///
+/// Usually this is constructed via [`progress`].
+#[layout(column)]
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+#[derive(Widget)]
+pub struct ProgressDialog {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: CowString,
+    #[widget]
+    label: Label,
+    #[widget]
+    bar: ProgressBar<kas::Right>,
+    #[widget(handler = handle_button)]
+    button: TextButton<DialogButton>,
+    state: Arc<State>,
+    on_finish: Option<Box<dyn FnOnce(&mut Manager, bool)>>,
+}
+
+impl Debug for ProgressDialog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ProgressDialog {{ core: {:?}, title: {:?}, label: {:?}, bar: {:?}, button: {:?}, state: {:?}, ... }}",
+            self.core, self.title, self.label, self.bar, self.button, self.state,
+        )
+    }
+}
+
+impl ProgressDialog {
+    /// Construct, returning the dialog and its worker handle
+    ///
+    /// The caller is responsible for running the worker.
+    pub fn new<T: Into<CowString>, M: Into<LabelString>>(title: T, message: M) -> (Self, Progress) {
+        let state = Arc::new(State::default());
+        let dialog = ProgressDialog {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: title.into(),
+            label: Label::new(message),
+            bar: ProgressBar::new(),
+            button: TextButton::new("&Cancel", DialogButton::Cancel)
+                .with_keys(&[VirtualKeyCode::Escape]),
+            state: state.clone(),
+            on_finish: None,
+        };
+        (dialog, Progress { state })
+    }
+
+    /// Set a handler called when the worker finishes
+    ///
+    /// The handler is passed `true` if the user requested cancellation.
+    pub fn on_finish<F: FnOnce(&mut Manager, bool) + 'static>(mut self, f: F) -> Self {
+        self.on_finish = Some(Box::new(f));
+        self
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
+        match msg {
+            DialogButton::Cancel => {
+                self.state.cancelled.store(true, Ordering::Release);
+                *mgr += self.label.set_text("Cancelling…") + self.button.set_disabled(true);
+            }
+        }
+        Response::None
+    }
+
+    fn poll(&mut self, mgr: &mut Manager) {
+        let fraction = f32::from_bits(self.state.fraction.load(Ordering::Relaxed));
+        *mgr += self.bar.set_value(fraction);
+        if let Some(message) = self.state.message.lock().unwrap().take() {
+            if !self.state.cancelled.load(Ordering::Acquire) {
+                *mgr += self.label.set_text(message);
+            }
+        }
+
+        if self.state.finished.load(Ordering::Acquire) {
+            if let Some(f) = self.on_finish.take() {
+                f(mgr, self.state.cancelled.load(Ordering::Acquire));
+            }
+            mgr.send_action(TkAction::CLOSE);
+        } else {
+            mgr.update_after(POLL_INTERVAL, self.id());
+        }
+    }
+}
+
+impl WidgetConfig for ProgressDialog {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.enable_alt_bypass(true);
+        mgr.update_after(POLL_INTERVAL, self.id());
+    }
+}
+
+impl event::Handler for ProgressDialog {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<VoidMsg> {
+        match event {
+            Event::TimerUpdate => {
+                self.poll(mgr);
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl kas::Window for ProgressDialog {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn restrict_dimensions(&self) -> (bool, bool) {
+        (false, true)
+    }
+
+    // do not support overlays (yet?)
+    fn add_popup(&mut self, _: &mut Manager, _: WindowId, _: kas::Popup) {
+        panic!("ProgressDialog does not (currently) support pop-ups");
+    }
+
+    fn remove_popup(&mut self, _: &mut Manager, _: WindowId) {}
+    fn resize_popups(&mut self, _: &mut dyn SizeHandle) {}
+}
+
+/// Run `f` on a new thread, returning a [`ProgressDialog`] displaying its
+/// progress
+///
+/// The dialog must be added to the toolkit (e.g. via
+/// [`Manager::add_window`]); it closes itself once `f` returns.
+pub fn progress<T, M, F>(title: T, message: M, f: F) -> ProgressDialog
+where
+    T: Into<CowString>,
+    M: Into<LabelString>,
+    F: FnOnce(Progress) + Send + 'static,
+{
+    let (dialog, progress) = ProgressDialog::new(title, message);
+    std::thread::spawn(move || f(progress));
+    dialog
+}
+
+/// Application metadata, as displayed by an [`AboutDialog`]
+///
+/// This may be constructed directly or read from the application's Cargo
+/// manifest via [`app_info!`](crate::app_info).
+#[derive(Clone, Debug, Default)]
+pub struct AppInfo {
+    /// Application name
+    pub name: CowString,
+    /// Version string
+    pub version: CowString,
+    /// A short description
+    pub description: Option<CowString>,
+    /// List of authors
+    pub authors: Vec<CowString>,
+    /// License text (or an SPDX license expression)
+    pub license: Option<CowString>,
+    /// Website URL
+    pub website: Option<CowString>,
+    /// Icon, identified by name
+    ///
+    /// Widgets included in KAS do not currently draw icons.
+    pub icon: Option<CowString>,
+}
+
+impl AppInfo {
+    /// Construct with a given `name` and `version`
+    pub fn new<N: Into<CowString>, V: Into<CowString>>(name: N, version: V) -> Self {
+        AppInfo {
+            name: name.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the description (chain style)
+    pub fn with_description<S: Into<CowString>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add an author (chain style)
+    pub fn with_author<S: Into<CowString>>(mut self, author: S) -> Self {
+        self.authors.push(author.into());
+        self
+    }
+
+    /// Set the license text (chain style)
+    pub fn with_license<S: Into<CowString>>(mut self, license: S) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+
+    /// Set the website URL (chain style)
+    pub fn with_website<S: Into<CowString>>(mut self, url: S) -> Self {
+        self.website = Some(url.into());
+        self
+    }
+
+    /// Set the icon name (chain style)
+    pub fn with_icon<S: Into<CowString>>(mut self, icon: S) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+/// Construct an [`AppInfo`] from the calling crate's Cargo metadata
+///
+/// This reads the package name, version, description, authors, license
+/// expression and homepage (or else repository) at compile time. Fields not
+/// specified in the manifest are left empty.
+#[macro_export]
+macro_rules! app_info {
+    () => {{
+        let mut info =
+            $crate::dialog::AppInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let non_empty = |s: Option<&'static str>| s.filter(|s| !s.is_empty());
+        info.description = non_empty(option_env!("CARGO_PKG_DESCRIPTION")).map(|s| s.into());
+        info.authors = env!("CARGO_PKG_AUTHORS")
+            .split(':')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.into())
+            .collect();
+        info.license = non_empty(option_env!("CARGO_PKG_LICENSE")).map(|s| s.into());
+        info.website = non_empty(option_env!("CARGO_PKG_HOMEPAGE"))
+            .or(non_empty(option_env!("CARGO_PKG_REPOSITORY")))
+            .map(|s| s.into());
+        info
+    }};
+}
+
+#[derive(Clone, Debug, VoidMsg)]
+enum AboutMsg {
+    Website,
+    Close,
+}
+
+// Labels treat '&' as an accelerator-key prefix
+fn escape(text: &str) -> String {
+    text.replace('&', "&&")
+}
+
+/// Open `url` with the platform's default handler
+fn open_url(url: &str) -> std::io::Result<()> {
+    use std::process::Command;
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "start", ""]);
+        cmd
+    };
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut cmd = Command::new("xdg-open");
+    cmd.arg(url).spawn().map(|_| ())
+}
+
+/// A standard "about" window
+///
+/// This displays the application's name, version, description and authors,
+/// the license in a scrollable region and a link to the website (each where
+/// available) followed by a "Close" button. Activating the link opens the
+/// website with the platform's default handler (falling back to copying the
+/// URL to the clipboard).
+///
+/// Usually this is constructed via [`about`].
+#[layout(column)]
+#[widget(config=noauto)]
+#[derive(Debug, Widget)]
+pub struct AboutDialog {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: CowString,
+    #[widget(handler = handle_msg)]
+    contents: BoxColumn<AboutMsg>,
+    #[widget(handler = handle_msg)]
+    button: TextButton<AboutMsg>,
+    info: AppInfo,
+}
+
+impl AboutDialog {
+    /// Construct from application metadata
+    pub fn new(info: AppInfo) -> Self {
+        let mut contents: Vec<Box<dyn Widget<Msg = AboutMsg>>> = vec![];
+        let heading = format!("{} {}", info.name, info.version);
+        contents.push(Box::new(Label::new(escape(&heading)).map_msg_into()));
+        if let Some(ref text) = info.description {
+            contents.push(Box::new(Label::new(escape(text)).map_msg_into()));
+        }
+        if !info.authors.is_empty() {
+            let authors = info.authors.join("\n");
+            contents.push(Box::new(Label::new(escape(&authors)).map_msg_into()));
+        }
+        if let Some(ref text) = info.license {
+            let label = SelectableLabel::new(escape(text));
+            contents.push(Box::new(ScrollRegion::new(label).map_msg_into()));
+        }
+        if let Some(ref url) = info.website {
+            contents.push(Box::new(TextButton::new(escape(url), AboutMsg::Website)));
+        }
+
+        AboutDialog {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: format!("About {}", info.name).into(),
+            contents: BoxColumn::new(contents),
+            button: TextButton::new("&Close", AboutMsg::Close).with_keys(&[
+                VirtualKeyCode::Escape,
+                VirtualKeyCode::Return,
+                VirtualKeyCode::NumpadEnter,
+            ]),
+            info,
+        }
+    }
+
+    /// Access the application metadata
+    pub fn info(&self) -> &AppInfo {
+        &self.info
+    }
+
+    fn handle_msg(&mut self, mgr: &mut Manager, msg: AboutMsg) -> Response<VoidMsg> {
+        match msg {
+            AboutMsg::Website => {
+                if let Some(ref url) = self.info.website {
+                    if let Err(e) = open_url(url) {
+                        log::warn!("Failed to open {}: {}", url, e);
+                        mgr.set_clipboard(url.clone());
+                    }
+                }
+            }
+            AboutMsg::Close => mgr.send_action(TkAction::CLOSE),
+        }
+        Response::None
+    }
+}
+
+impl WidgetConfig for AboutDialog {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.enable_alt_bypass(true);
+    }
+}
+
+impl kas::Window for AboutDialog {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn restrict_dimensions(&self) -> (bool, bool) {
+        (true, false)
+    }
+
+    // do not support overlays (yet?)
+    fn add_popup(&mut self, _: &mut Manager, _: WindowId, _: kas::Popup) {
+        panic!("AboutDialog does not (currently) support pop-ups");
+    }
+
+    fn remove_popup(&mut self, _: &mut Manager, _: WindowId) {}
+    fn resize_popups(&mut self, _: &mut dyn SizeHandle) {}
+}
+
+/// Construct a standard "about" window
+///
+/// The dialog must be added to the toolkit (e.g. via
+/// [`Manager::add_window`]). See also [`app_info!`](crate::app_info).
+pub fn about(info: AppInfo) -> AboutDialog {
+    AboutDialog::new(info)
+}
+
+/// A description of an error or panic, as displayed by an [`ErrorDialog`]
+#[derive(Clone, Debug, Default)]
+pub struct ErrorReport {
+    /// The error message
+    pub message: String,
+    /// Source location (for panics)
+    pub location: Option<String>,
+    /// Name of the thread which reported the error
+    pub thread: Option<String>,
+    /// Further details (e.g. the error's source chain)
+    pub details: Option<String>,
+    /// A formatted backtrace, if captured
+    pub backtrace: Option<String>,
+}
+
+impl ErrorReport {
+    /// Construct from a message
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        ErrorReport {
+            message: message.into(),
+            thread: std::thread::current().name().map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Construct from an error, including its chain of sources
+    pub fn from_error(error: &dyn std::error::Error) -> Self {
+        let mut report = ErrorReport::new(error.to_string());
+        let mut details = String::new();
+        let mut source = error.source();
+        while let Some(error) = source {
+            details.push_str(&format!("Caused by: {}\n", error));
+            source = error.source();
+        }
+        if !details.is_empty() {
+            report.details = Some(details);
+        }
+        report
+    }
+
+    /// Capture a backtrace (chain style)
+    ///
+    /// This requires the `nightly` feature; otherwise it does nothing.
+    #[cfg(feature = "nightly")]
+    pub fn with_backtrace(mut self) -> Self {
+        let bt = std::backtrace::Backtrace::force_capture();
+        self.backtrace = Some(bt.to_string());
+        self
+    }
+
+    /// Capture a backtrace (chain style)
+    ///
+    /// This requires the `nightly` feature; otherwise it does nothing.
+    #[cfg(not(feature = "nightly"))]
+    pub fn with_backtrace(self) -> Self {
+        self
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        if let Some(ref location) = self.location {
+            writeln!(f, "Location: {}", location)?;
+        }
+        if let Some(ref thread) = self.thread {
+            writeln!(f, "Thread: {}", thread)?;
+        }
+        if let Some(ref details) = self.details {
+            writeln!(f, "{}", details.trim_end())?;
+        }
+        match self.backtrace {
+            Some(ref bt) => write!(f, "Backtrace:\n{}", bt),
+            None => write!(f, "Backtrace unavailable"),
+        }
+    }
+}
+
+/// Install a panic hook reporting panics to `f`
+///
+/// The previous hook is called first (by default, this prints the message to
+/// `stderr`). The hook then constructs an [`ErrorReport`] (including a
+/// backtrace, where possible; see [`ErrorReport::with_backtrace`]) and passes
+/// it to `f`. Note that `f` is called on the panicking thread.
+pub fn set_panic_hook<F: Fn(ErrorReport) + Send + Sync + 'static>(f: F) {
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        prev(info);
+        let payload = info.payload();
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Box<Any>".to_string()
+        };
+        let mut report = ErrorReport::new(format!("Panic: {}", message)).with_backtrace();
+        report.location = info.location().map(|l| l.to_string());
+        f(report);
+    }));
+}
+
+#[derive(Clone, Debug, VoidMsg)]
+enum ErrorMsg {
+    Copy,
+    Close,
+}
+
+/// An error-reporting dialog
+///
+/// This displays the message of an [`ErrorReport`] followed by its details
+/// (including any backtrace) in a scrollable region, with buttons to copy the
+/// full report to the clipboard and to close the window.
+///
+/// Usually this is constructed via [`error`].
+#[layout(column)]
+#[widget(config=noauto)]
+#[derive(Debug, Widget)]
+pub struct ErrorDialog {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: CowString,
+    #[widget]
+    label: Label,
+    #[widget]
+    details: ScrollRegion<SelectableLabel>,
+    #[widget(handler = handle_button)]
+    buttons: Row<TextButton<ErrorMsg>>,
+    report: ErrorReport,
+}
+
+impl ErrorDialog {
+    /// Construct
+    pub fn new<T: Into<CowString>>(title: T, report: ErrorReport) -> Self {
+        let mut details = report.to_string();
+        // The first line is the message, displayed separately
+        let start = details.find('\n').map(|i| i + 1).unwrap_or(details.len());
+        details.replace_range(..start, "");
+
+        ErrorDialog {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: title.into(),
+            label: Label::new(escape(&report.message)),
+            details: ScrollRegion::new(SelectableLabel::new(escape(&details))),
+            buttons: Row::new(vec![
+                TextButton::new("C&opy", ErrorMsg::Copy),
+                TextButton::new("&Close", ErrorMsg::Close).with_keys(&[
+                    VirtualKeyCode::Escape,
+                    VirtualKeyCode::Return,
+                    VirtualKeyCode::NumpadEnter,
+                ]),
+            ]),
+            report,
+        }
+    }
+
+    /// Access the report
+    pub fn report(&self) -> &ErrorReport {
+        &self.report
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: ErrorMsg) -> Response<VoidMsg> {
+        match msg {
+            ErrorMsg::Copy => mgr.set_clipboard(self.report.to_string().into()),
+            ErrorMsg::Close => mgr.send_action(TkAction::CLOSE),
+        }
+        Response::None
+    }
+}
+
+impl WidgetConfig for ErrorDialog {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.enable_alt_bypass(true);
+    }
+}
+
+impl kas::Window for ErrorDialog {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn restrict_dimensions(&self) -> (bool, bool) {
+        (true, false)
+    }
+
+    // do not support overlays (yet?)
+    fn add_popup(&mut self, _: &mut Manager, _: WindowId, _: kas::Popup) {
+        panic!("ErrorDialog does not (currently) support pop-ups");
+    }
+
+    fn remove_popup(&mut self, _: &mut Manager, _: WindowId) {}
+    fn resize_popups(&mut self, _: &mut dyn SizeHandle) {}
+}
+
+/// Construct an error-reporting window
+///
+/// The dialog must be added to the toolkit (e.g. via
+/// [`Manager::add_window`]).
+pub fn error(report: ErrorReport) -> ErrorDialog {
+    ErrorDialog::new("Error", report)
+}
@@ -0,0 +1,298 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Text documents
+//!
+//! A [`TextDocument`] is a text model which may be shared between multiple
+//! views (e.g. [`kas::widget::TextEditor`]). Storage uses a rope, thus edits
+//! have `O(log n)` cost even on large documents.
+//!
+//! Views are notified of changes via the document's [`UpdateHandle`]. Each
+//! view has its own edit cursor; after an edit by another view, cursors are
+//! adjusted via [`TextDocument::map_pos`].
+
+use ropey::Rope;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Range;
+use std::rc::Rc;
+
+use kas::event::{Manager, UpdateHandle};
+
+/// Number of edits recorded for use by [`TextDocument::map_pos`]
+const MAX_EDIT_LOG: usize = 256;
+
+/// Characters considered line breaks (as by [`Rope::lines`])
+const LINE_BREAKS: &[char] = &[
+    '\n', '\r', '\u{000B}', '\u{000C}', '\u{0085}', '\u{2028}', '\u{2029}',
+];
+
+/// A single edit: replacement of a byte range
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edit {
+    /// Start of the edit (byte index)
+    pub pos: usize,
+    /// Length of removed text (bytes)
+    pub removed: usize,
+    /// Length of inserted text (bytes)
+    pub inserted: usize,
+}
+
+impl Edit {
+    /// Map a byte index from before the edit to after
+    ///
+    /// Positions before (or at) the edit are unaffected; positions after the
+    /// edit are moved; positions within removed text are moved to the end of
+    /// the inserted text.
+    pub fn map_pos(&self, pos: usize) -> usize {
+        if pos <= self.pos {
+            pos
+        } else if pos < self.pos + self.removed {
+            self.pos + self.inserted
+        } else {
+            pos - self.removed + self.inserted
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    rope: Rope,
+    revision: u64,
+    log: VecDeque<Edit>,
+}
+
+/// A shared, editable text document
+///
+/// This is a handle to shared state: clones refer to the same document.
+///
+/// All positions are byte indices and must lie on `char` boundaries.
+/// Line indices start from zero; a document always has at least one line.
+///
+/// Edit methods do not notify views; call [`TextDocument::notify`] after
+/// editing (views included in KAS do this automatically).
+#[derive(Clone)]
+pub struct TextDocument {
+    inner: Rc<RefCell<Inner>>,
+    handle: UpdateHandle,
+}
+
+impl fmt::Debug for TextDocument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = self.inner.borrow();
+        write!(
+            f,
+            "TextDocument {{ handle: {:?}, revision: {}, len: {}, lines: {}, ... }}",
+            self.handle,
+            inner.revision,
+            inner.rope.len_bytes(),
+            inner.rope.len_lines()
+        )
+    }
+}
+
+impl Default for TextDocument {
+    fn default() -> Self {
+        TextDocument::new("")
+    }
+}
+
+impl TextDocument {
+    /// Construct with the given text
+    pub fn new(text: &str) -> Self {
+        TextDocument {
+            inner: Rc::new(RefCell::new(Inner {
+                rope: Rope::from_str(text),
+                revision: 0,
+                log: VecDeque::new(),
+            })),
+            handle: UpdateHandle::new(),
+        }
+    }
+
+    /// Read from a file
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let rope = Rope::from_reader(std::io::BufReader::new(file))?;
+        let doc = TextDocument::new("");
+        doc.inner.borrow_mut().rope = rope;
+        Ok(doc)
+    }
+
+    /// Write to a file
+    pub fn write_file<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = std::io::BufWriter::new(file);
+        self.inner.borrow().rope.write_to(&mut writer)
+    }
+
+    /// Get the update handle
+    ///
+    /// Views should subscribe to this via [`Manager::update_on_handle`].
+    /// The update payload is the new [`TextDocument::revision`].
+    #[inline]
+    pub fn update_handle(&self) -> UpdateHandle {
+        self.handle
+    }
+
+    /// Notify views of changes
+    #[inline]
+    pub fn notify(&self, mgr: &mut Manager) {
+        mgr.trigger_update(self.handle, self.revision());
+    }
+
+    /// Get the revision number
+    ///
+    /// This is incremented by each edit.
+    #[inline]
+    pub fn revision(&self) -> u64 {
+        self.inner.borrow().revision
+    }
+
+    /// Length in bytes
+    pub fn len(&self) -> usize {
+        self.inner.borrow().rope.len_bytes()
+    }
+
+    /// True if the document is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of lines
+    ///
+    /// A trailing line break starts a new (empty) line.
+    pub fn len_lines(&self) -> usize {
+        self.inner.borrow().rope.len_lines()
+    }
+
+    /// Get the index of the line containing byte `pos`
+    pub fn line_of(&self, pos: usize) -> usize {
+        self.inner.borrow().rope.byte_to_line(pos)
+    }
+
+    /// Get the start of line `line` (byte index)
+    ///
+    /// Panics if `line > self.len_lines()`. (Line `len_lines()` starts at the
+    /// end of the document.)
+    pub fn line_start(&self, line: usize) -> usize {
+        self.inner.borrow().rope.line_to_byte(line)
+    }
+
+    /// Get the contents of line `line`, excluding the line break
+    pub fn line(&self, line: usize) -> String {
+        let inner = self.inner.borrow();
+        let mut text = inner.rope.line(line).to_string();
+        let len = text.trim_end_matches(LINE_BREAKS).len();
+        text.truncate(len);
+        text
+    }
+
+    /// Get a range of text as a `String`
+    pub fn slice(&self, range: Range<usize>) -> String {
+        let rope = &self.inner.borrow().rope;
+        let range = rope.byte_to_char(range.start)..rope.byte_to_char(range.end);
+        rope.slice(range).to_string()
+    }
+
+    /// Get the whole text as a `String`
+    ///
+    /// This has `O(n)` cost.
+    pub fn text(&self) -> String {
+        self.inner.borrow().rope.to_string()
+    }
+
+    /// Insert `text` at `pos`
+    pub fn insert(&self, pos: usize, text: &str) {
+        self.replace(pos..pos, text);
+    }
+
+    /// Remove `range`
+    pub fn remove(&self, range: Range<usize>) {
+        self.replace(range, "");
+    }
+
+    /// Replace the whole text
+    pub fn set_text(&self, text: &str) {
+        let len = self.len();
+        self.replace(0..len, text);
+    }
+
+    /// Replace `range` with `text`
+    pub fn replace(&self, range: Range<usize>, text: &str) {
+        let mut inner = self.inner.borrow_mut();
+        let rope = &mut inner.rope;
+        debug_assert!(is_char_boundary(rope, range.start));
+        debug_assert!(is_char_boundary(rope, range.end));
+        let start = rope.byte_to_char(range.start);
+        if range.end > range.start {
+            rope.remove(start..rope.byte_to_char(range.end));
+        }
+        rope.insert(start, text);
+
+        inner.revision += 1;
+        if inner.log.len() == MAX_EDIT_LOG {
+            inner.log.pop_front();
+        }
+        inner.log.push_back(Edit {
+            pos: range.start,
+            removed: range.end - range.start,
+            inserted: text.len(),
+        });
+    }
+
+    /// Get edits made since revision `since`
+    ///
+    /// Returns `None` when the edit history is unavailable (only the last 256
+    /// edits are recorded).
+    pub fn edits_since(&self, since: u64) -> Option<Vec<Edit>> {
+        let inner = self.inner.borrow();
+        let n = inner.revision.checked_sub(since)? as usize;
+        if n > inner.log.len() {
+            return None;
+        }
+        let skip = inner.log.len() - n;
+        Some(inner.log.iter().skip(skip).cloned().collect())
+    }
+
+    /// Map a byte index from revision `since` to the current revision
+    ///
+    /// Where the edit history is unavailable, `pos` is clamped to the
+    /// document length and a `char` boundary.
+    pub fn map_pos(&self, pos: usize, since: u64) -> usize {
+        match self.edits_since(since) {
+            Some(edits) => edits.iter().fold(pos, |pos, edit| edit.map_pos(pos)),
+            None => self.clamp_pos(pos),
+        }
+    }
+
+    /// Clamp a byte index to the document length and a `char` boundary
+    pub fn clamp_pos(&self, pos: usize) -> usize {
+        let rope = &self.inner.borrow().rope;
+        let pos = pos.min(rope.len_bytes());
+        rope.char_to_byte(rope.byte_to_char(pos))
+    }
+}
+
+fn is_char_boundary(rope: &Rope, pos: usize) -> bool {
+    pos <= rope.len_bytes() && rope.char_to_byte(rope.byte_to_char(pos)) == pos
+}
+
+#[test]
+fn map_pos() {
+    let doc = TextDocument::new("one\ntwo\n");
+    assert_eq!(doc.len_lines(), 3);
+    assert_eq!(doc.line(1), "two");
+
+    let rev = doc.revision();
+    doc.insert(0, "zero\n");
+    doc.replace(5..8, "ONE");
+    assert_eq!(doc.text(), "zero\nONE\ntwo\n");
+    assert_eq!(doc.map_pos(0, rev), 0);
+    assert_eq!(doc.map_pos(1, rev), 8);
+    assert_eq!(doc.map_pos(4, rev), 9);
+    assert_eq!(doc.edits_since(rev).map(|v| v.len()), Some(2));
+}
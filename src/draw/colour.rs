@@ -7,6 +7,7 @@
 
 /// Standard colour description
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Colour {
     pub r: f32,
     pub g: f32,
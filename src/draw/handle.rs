@@ -5,17 +5,19 @@
 
 //! "Handle" types used by themes
 
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 
-use kas::draw::{Draw, Pass};
-use kas::geom::{Coord, Rect, Size, Vec2};
+use kas::draw::{vertical_text, Colour, Draw, ImageId, Pass, TextLine, TextOrientation};
+use kas::geom::{Coord, Quad, Rect, Size, Vec2};
 use kas::layout::{AxisInfo, Margins, SizeRules};
 use kas::{Align, Direction};
 
 /// Classification of a clip region
 pub enum ClipRegion {
     Popup,
+    Overlay,
     Scroll,
+    Layer,
 }
 
 /// Input and highlighting state of a widget
@@ -82,6 +84,37 @@ impl Default for TextClass {
     }
 }
 
+/// Resolved metrics of the active theme
+///
+/// All sizes are in physical pixels, for the current scale factor. This is a
+/// snapshot, obtained via [`SizeHandle::metrics`] or
+/// [`kas::event::Manager::theme_metrics`]; values change when the theme or
+/// scale factor changes (at which time widgets are resized).
+///
+/// Applications may use these to compute custom layouts consistent with the
+/// theme.
+#[derive(Clone, Copy, Debug)]
+pub struct ThemeMetrics {
+    /// Scale (DPI) factor; see [`SizeHandle::scale_factor`]
+    pub scale_factor: f32,
+    /// Frame size on each side; see [`SizeHandle::frame`]
+    pub frame: Size,
+    /// Menu frame size on each side; see [`SizeHandle::menu_frame`]
+    pub menu_frame: Size,
+    /// Margin within widgets; see [`SizeHandle::inner_margin`]
+    pub inner_margin: Size,
+    /// Margins between widgets; see [`SizeHandle::outer_margins`]
+    pub outer_margins: Margins,
+    /// Height of a line of label text
+    pub line_height: u32,
+    /// Height of a line of edit-box text
+    pub edit_line_height: u32,
+    /// Width (thickness) of a scroll bar
+    pub scrollbar_width: u32,
+    /// Distance scrolled by one step; see [`SizeHandle::scroll_step`]
+    pub scroll_step: u32,
+}
+
 /// Handle passed to objects during draw and sizing operations
 ///
 /// This handle is provided by the toolkit (usually via a theme implementation)
@@ -149,6 +182,149 @@ pub trait SizeHandle {
         pos: Vec2,
     ) -> usize;
 
+    /// Get a text label size bound, with relative scale
+    ///
+    /// Sizing requirements of [`DrawHandle::text_scaled`]: as
+    /// [`SizeHandle::text_bound`], except that the font size (and line height)
+    /// is multiplied by `scale`. This allows widgets to zoom their text
+    /// independently of the theme's font size.
+    ///
+    /// The default implementation ignores `scale`.
+    fn text_bound_scaled(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        let _ = scale;
+        self.text_bound(text, class, axis)
+    }
+
+    /// Find the text index nearest to `pos`, with relative scale
+    ///
+    /// As [`SizeHandle::text_index_nearest`], for text drawn by
+    /// [`DrawHandle::text_scaled`].
+    ///
+    /// The default implementation ignores `scale`.
+    fn text_index_nearest_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        pos: Vec2,
+    ) -> usize {
+        let _ = scale;
+        self.text_index_nearest(rect, text, class, align, pos)
+    }
+
+    /// Find the lines of text, as laid out
+    ///
+    /// Text is assumed to be positioned as in [`DrawHandle::text`]; as with
+    /// [`SizeHandle::text_index_nearest`], `rect` is not adjusted by the
+    /// `clip_region`'s `offset`. See [`kas::draw::DrawText::text_lines`].
+    ///
+    /// The default implementation returns a single line at the top of `rect`.
+    fn text_lines(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        let _ = align;
+        let top = rect.pos.1 as f32;
+        let bottom = top + self.line_height(class) as f32;
+        vec![TextLine {
+            range: 0..text.len(),
+            top,
+            bottom,
+        }]
+    }
+
+    /// Find the lines of text, as laid out, with relative scale
+    ///
+    /// As [`SizeHandle::text_lines`], for text drawn by
+    /// [`DrawHandle::text_scaled`].
+    ///
+    /// The default implementation ignores `scale`.
+    fn text_lines_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        let _ = scale;
+        self.text_lines(rect, text, class, align)
+    }
+
+    /// Get the rect of the edit marker (caret)
+    ///
+    /// Returns the rect of the marker drawn by [`DrawHandle::edit_marker`].
+    /// As with [`SizeHandle::text_index_nearest`], `rect` is not adjusted by
+    /// the `clip_region`'s `offset`.
+    ///
+    /// The default implementation returns a marker at the start of `rect`.
+    fn edit_marker_rect(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        let _ = (text, align, byte);
+        Rect::new(rect.pos, Size(1, self.line_height(class)))
+    }
+
+    /// Get the rect of the edit marker (caret), with relative scale
+    ///
+    /// As [`SizeHandle::edit_marker_rect`], for text drawn by
+    /// [`DrawHandle::text_scaled`].
+    ///
+    /// The default implementation ignores `scale`.
+    fn edit_marker_rect_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        let _ = scale;
+        self.edit_marker_rect(rect, text, class, align, byte)
+    }
+
+    /// Get a text label size bound, with orientation
+    ///
+    /// Sizing requirements of [`DrawHandle::text_oriented`]: as
+    /// [`SizeHandle::text_bound`], except that text is laid out according to
+    /// `orientation`.
+    ///
+    /// The default implementation uses [`SizeHandle::text_bound`], with
+    /// transposed axes for rotated text.
+    fn text_bound_oriented(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        match orientation {
+            TextOrientation::Horizontal => self.text_bound(text, class, axis),
+            TextOrientation::Vertical => self.text_bound(&vertical_text(text), class, axis),
+            TextOrientation::RotatedCw | TextOrientation::RotatedCcw => {
+                let axis = AxisInfo::new(axis.is_horizontal(), axis.other());
+                self.text_bound(text, class, axis)
+            }
+        }
+    }
+
     /// Size of the sides of a button.
     ///
     /// Returns `(top_left, bottom_right)` dimensions as two `Size`s.
@@ -188,6 +364,27 @@ pub trait SizeHandle {
     ///
     /// Required bound: `min_len >= size.0`.
     fn slider(&self) -> (Size, u32);
+
+    /// Distance scrolled by one step
+    ///
+    /// This is the distance (in pixels) a scroll region scrolls per mouse
+    /// wheel "line" or per press of an arrow key.
+    fn scroll_step(&self) -> u32;
+
+    /// Get the resolved metrics of the theme
+    fn metrics(&self) -> ThemeMetrics {
+        ThemeMetrics {
+            scale_factor: self.scale_factor(),
+            frame: self.frame(),
+            menu_frame: self.menu_frame(),
+            inner_margin: self.inner_margin(),
+            outer_margins: self.outer_margins(),
+            line_height: self.line_height(TextClass::Label),
+            edit_line_height: self.line_height(TextClass::Edit),
+            scrollbar_width: (self.scrollbar().0).1,
+            scroll_step: self.scroll_step(),
+        }
+    }
 }
 
 /// Handle passed to objects during draw and sizing operations
@@ -286,12 +483,209 @@ pub trait DrawHandle {
         byte: usize,
     );
 
+    /// Draw some text with relative scale
+    ///
+    /// As [`DrawHandle::text`], except that the font size is multiplied by
+    /// `scale`. The dimensions required may be queried with
+    /// [`SizeHandle::text_bound_scaled`].
+    ///
+    /// The default implementation ignores `scale`.
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        let _ = scale;
+        self.text(rect, text, class, align)
+    }
+
+    /// Draw some text with the given orientation
+    ///
+    /// As [`DrawHandle::text`], except that text is laid out according to
+    /// `orientation`. For rotated text, `align` is relative to the text, i.e.
+    /// `align.0` is alignment along the direction of reading. The dimensions
+    /// required may be queried with [`SizeHandle::text_bound_oriented`].
+    ///
+    /// The default implementation ignores `orientation`.
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        let _ = orientation;
+        self.text(rect, text, class, align)
+    }
+
+    /// Draw an edit marker on text drawn with relative scale
+    ///
+    /// As [`DrawHandle::edit_marker`], for text drawn by
+    /// [`DrawHandle::text_scaled`].
+    ///
+    /// The default implementation ignores `scale`.
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        let _ = scale;
+        self.edit_marker(rect, text, class, align, byte)
+    }
+
+    /// Draw a highlight behind selected text
+    ///
+    /// The selection is given as a byte `range` of `text`; other parameters
+    /// are as for [`DrawHandle::text`]. This should be called before drawing
+    /// the text itself.
+    ///
+    /// The default implementation draws nothing.
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        let _ = (rect, text, class, align, range);
+    }
+
+    /// Draw a highlight behind selected text drawn with relative scale
+    ///
+    /// As [`DrawHandle::text_selection`], for text drawn by
+    /// [`DrawHandle::text_scaled`].
+    ///
+    /// The default implementation ignores `scale`.
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        let _ = scale;
+        self.text_selection(rect, text, class, align, range)
+    }
+
+    /// Draw an overscroll indicator
+    ///
+    /// This indicates that content has been scrolled past its end, e.g. by a
+    /// touch drag. It is drawn at the edge of `rect` in the given `direction`
+    /// (e.g. `Direction::Up` for the top edge), with `amount` in the range
+    /// `0.0..=1.0` giving the intensity.
+    ///
+    /// The default implementation draws nothing.
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        let _ = (rect, direction, amount);
+    }
+
+    /// Draw an image, scaled to fill `rect`
+    ///
+    /// The image must have been loaded via
+    /// [`DrawShared::load_image`](kas::draw::DrawShared::load_image).
+    ///
+    /// The default implementation draws via [`DrawHandle::draw_device`].
+    fn image(&mut self, rect: Rect, id: ImageId) {
+        let (pass, offset, draw) = self.draw_device();
+        draw.image(pass, (rect + offset).into(), id);
+    }
+
+    /// Draw a nine-patch image, stretched to fill `rect`
+    ///
+    /// The image, of pixel size `size`, is divided by `insets` (in pixels)
+    /// into nine regions: corners are drawn at their natural size, edges are
+    /// stretched along their length and the centre is stretched to fill the
+    /// remainder. Themes may use this for bitmap decorations such as button
+    /// backgrounds and window frames. Where `rect` is too small for the
+    /// insets, the borders are reduced proportionally.
+    ///
+    /// The default implementation draws via [`DrawHandle::draw_device`].
+    fn ninepatch(&mut self, rect: Rect, id: ImageId, size: Size, insets: Margins) {
+        let (pass, offset, draw) = self.draw_device();
+        let rect = Quad::from(rect + offset);
+        let (l, r) = (insets.horiz.0 as f32, insets.horiz.1 as f32);
+        let (t, b) = (insets.vert.0 as f32, insets.vert.1 as f32);
+        let extent = rect.size();
+        let sx = (extent.0 / (l + r)).min(1.0);
+        let sy = (extent.1 / (t + b)).min(1.0);
+
+        // Grid lines in destination and texture coordinates
+        let xs = [rect.a.0, rect.a.0 + l * sx, rect.b.0 - r * sx, rect.b.0];
+        let ys = [rect.a.1, rect.a.1 + t * sy, rect.b.1 - b * sy, rect.b.1];
+        let size = Vec2::from(size);
+        let us = [0.0, l / size.0, 1.0 - r / size.0, 1.0];
+        let vs = [0.0, t / size.1, 1.0 - b / size.1, 1.0];
+
+        for j in 0..3 {
+            for i in 0..3 {
+                let rect = Quad::with_coords(Vec2(xs[i], ys[j]), Vec2(xs[i + 1], ys[j + 1]));
+                let region = Quad::with_coords(Vec2(us[i], vs[j]), Vec2(us[i + 1], vs[j + 1]));
+                draw.image_region(pass, rect, id, region);
+            }
+        }
+    }
+
+    /// Dim content within `rect`, except for the `spotlight` area
+    ///
+    /// This is drawn over existing content (typically from an overlay; see
+    /// [`kas::Overlay`]) to draw attention to the `spotlight`, if any, or to
+    /// content drawn afterwards.
+    ///
+    /// The default implementation draws translucent black via
+    /// [`DrawHandle::draw_device`].
+    fn dim(&mut self, rect: Rect, spotlight: Option<Rect>) {
+        let (pass, offset, draw) = self.draw_device();
+        let col = Colour {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.5,
+        };
+        let s = match spotlight.and_then(|s| s.intersection(&rect)) {
+            Some(s) => s,
+            None => return draw.rect(pass, (rect + offset).into(), col),
+        };
+        let (a, b) = (rect.pos, rect.pos + rect.size);
+        let (c, d) = (s.pos, s.pos + s.size);
+        // Bands above, below, left of and right of the spotlight
+        let bands = [
+            Rect::new(a, Size(rect.size.0, (c.1 - a.1) as u32)),
+            Rect::new(Coord(a.0, d.1), Size(rect.size.0, (b.1 - d.1) as u32)),
+            Rect::new(Coord(a.0, c.1), Size((c.0 - a.0) as u32, s.size.1)),
+            Rect::new(Coord(d.0, c.1), Size((b.0 - d.0) as u32, s.size.1)),
+        ];
+        for band in &bands {
+            if band.size.0 > 0 && band.size.1 > 0 {
+                draw.rect(pass, (*band + offset).into(), col);
+            }
+        }
+    }
+
     /// Draw the background of a menu entry
     fn menu_entry(&mut self, rect: Rect, state: InputState);
 
     /// Draw button sides, background and margin-area highlight
     fn button(&mut self, rect: Rect, state: InputState);
 
+    /// Draw UI element: tab
+    ///
+    /// A tab of a tab bar (see [`crate::widget::TabbedStack`]), including
+    /// sides, background and margin-area highlight. The `active` tab is the
+    /// one whose page is shown; this is usually drawn with more emphasis.
+    /// The tab's label is not part of this element.
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState);
+
     /// Draw edit box sides, background and margin-area highlight
     fn edit_box(&mut self, rect: Rect, state: InputState);
 
@@ -322,6 +716,20 @@ pub trait DrawHandle {
     /// -   `dir`: direction of slider (currently only LTR or TTB)
     /// -   `state`: highlighting information
     fn slider(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState);
+
+    /// Draw UI element: progress bar
+    ///
+    /// -   `rect`: area of whole widget
+    /// -   `dir`: direction of progress (the bar fills from the start)
+    /// -   `fraction`: fraction complete, in the range `0.0 ..= 1.0`
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32);
+
+    /// Draw UI element: progress bar of indeterminate progress
+    ///
+    /// This is a variant of [`DrawHandle::progress_bar`] indicating activity
+    /// of unknown duration. The widget animates the element by varying
+    /// `phase` from `0.0` to `1.0` (repeatedly).
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32);
 }
 
 impl<S: SizeHandle> SizeHandle for Box<S> {
@@ -359,6 +767,80 @@ impl<S: SizeHandle> SizeHandle for Box<S> {
         self.deref_mut()
             .text_index_nearest(rect, text, class, align, pos)
     }
+    fn text_bound_scaled(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        self.deref_mut().text_bound_scaled(text, class, scale, axis)
+    }
+    fn text_index_nearest_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        pos: Vec2,
+    ) -> usize {
+        self.deref_mut()
+            .text_index_nearest_scaled(rect, text, class, scale, align, pos)
+    }
+    fn text_lines(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        self.deref_mut().text_lines(rect, text, class, align)
+    }
+    fn text_lines_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        self.deref_mut()
+            .text_lines_scaled(rect, text, class, scale, align)
+    }
+    fn edit_marker_rect(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        self.deref_mut()
+            .edit_marker_rect(rect, text, class, align, byte)
+    }
+    fn edit_marker_rect_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        self.deref_mut()
+            .edit_marker_rect_scaled(rect, text, class, scale, align, byte)
+    }
+    fn text_bound_oriented(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        self.deref_mut()
+            .text_bound_oriented(text, class, orientation, axis)
+    }
 
     fn button_surround(&self) -> (Size, Size) {
         self.deref().button_surround()
@@ -379,6 +861,9 @@ impl<S: SizeHandle> SizeHandle for Box<S> {
     fn slider(&self) -> (Size, u32) {
         self.deref().slider()
     }
+    fn scroll_step(&self) -> u32 {
+        self.deref().scroll_step()
+    }
 }
 
 #[cfg(feature = "stack_dst")]
@@ -420,6 +905,80 @@ where
         self.deref_mut()
             .text_index_nearest(rect, text, class, align, pos)
     }
+    fn text_bound_scaled(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        self.deref_mut().text_bound_scaled(text, class, scale, axis)
+    }
+    fn text_index_nearest_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        pos: Vec2,
+    ) -> usize {
+        self.deref_mut()
+            .text_index_nearest_scaled(rect, text, class, scale, align, pos)
+    }
+    fn text_lines(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        self.deref_mut().text_lines(rect, text, class, align)
+    }
+    fn text_lines_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) -> Vec<TextLine> {
+        self.deref_mut()
+            .text_lines_scaled(rect, text, class, scale, align)
+    }
+    fn edit_marker_rect(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        self.deref_mut()
+            .edit_marker_rect(rect, text, class, align, byte)
+    }
+    fn edit_marker_rect_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        self.deref_mut()
+            .edit_marker_rect_scaled(rect, text, class, scale, align, byte)
+    }
+    fn text_bound_oriented(
+        &mut self,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        self.deref_mut()
+            .text_bound_oriented(text, class, orientation, axis)
+    }
 
     fn button_surround(&self) -> (Size, Size) {
         self.deref().button_surround()
@@ -440,6 +999,9 @@ where
     fn slider(&self) -> (Size, u32) {
         self.deref().slider()
     }
+    fn scroll_step(&self) -> u32 {
+        self.deref().scroll_step()
+    }
 }
 
 impl<H: DrawHandle> DrawHandle for Box<H> {
@@ -483,12 +1045,227 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
     ) {
         self.deref_mut().edit_marker(rect, text, class, align, byte)
     }
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        self.deref_mut()
+            .text_scaled(rect, text, class, scale, align)
+    }
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        self.deref_mut()
+            .text_oriented(rect, text, class, orientation, align)
+    }
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        self.deref_mut()
+            .edit_marker_scaled(rect, text, class, scale, align, byte)
+    }
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.deref_mut()
+            .text_selection(rect, text, class, align, range)
+    }
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.deref_mut()
+            .text_selection_scaled(rect, text, class, scale, align, range)
+    }
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        self.deref_mut().overscroll(rect, direction, amount)
+    }
+    fn image(&mut self, rect: Rect, id: ImageId) {
+        self.deref_mut().image(rect, id)
+    }
+    fn ninepatch(&mut self, rect: Rect, id: ImageId, size: Size, insets: Margins) {
+        self.deref_mut().ninepatch(rect, id, size, insets)
+    }
+    fn dim(&mut self, rect: Rect, spotlight: Option<Rect>) {
+        self.deref_mut().dim(rect, spotlight)
+    }
+    fn menu_entry(&mut self, rect: Rect, state: InputState) {
+        self.deref_mut().menu_entry(rect, state)
+    }
+    fn button(&mut self, rect: Rect, state: InputState) {
+        self.deref_mut().button(rect, state)
+    }
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState) {
+        self.deref_mut().tab(rect, active, state)
+    }
+    fn edit_box(&mut self, rect: Rect, state: InputState) {
+        self.deref_mut().edit_box(rect, state)
+    }
+    fn checkbox(&mut self, rect: Rect, checked: bool, state: InputState) {
+        self.deref_mut().checkbox(rect, checked, state)
+    }
+    fn radiobox(&mut self, rect: Rect, checked: bool, state: InputState) {
+        self.deref_mut().radiobox(rect, checked, state)
+    }
+    fn scrollbar(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
+        self.deref_mut().scrollbar(rect, h_rect, dir, state)
+    }
+    fn slider(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
+        self.deref_mut().slider(rect, h_rect, dir, state)
+    }
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32) {
+        self.deref_mut().progress_bar(rect, dir, fraction)
+    }
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        self.deref_mut()
+            .progress_bar_indeterminate(rect, dir, phase)
+    }
+}
+
+impl<'a, H: DrawHandle + ?Sized> DrawHandle for &'a mut H {
+    fn size_handle_dyn(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        self.deref_mut().size_handle_dyn(f)
+    }
+    fn draw_device(&mut self) -> (Pass, Coord, &mut dyn Draw) {
+        self.deref_mut().draw_device()
+    }
+    fn clip_region(
+        &mut self,
+        rect: Rect,
+        offset: Coord,
+        class: ClipRegion,
+        f: &mut dyn FnMut(&mut dyn DrawHandle),
+    ) {
+        self.deref_mut().clip_region(rect, offset, class, f)
+    }
+    fn target_rect(&self) -> Rect {
+        self.deref().target_rect()
+    }
+    fn outer_frame(&mut self, rect: Rect) {
+        self.deref_mut().outer_frame(rect);
+    }
+    fn menu_frame(&mut self, rect: Rect) {
+        self.deref_mut().menu_frame(rect);
+    }
+    fn separator(&mut self, rect: Rect) {
+        self.deref_mut().separator(rect);
+    }
+    fn text(&mut self, rect: Rect, text: &str, class: TextClass, align: (Align, Align)) {
+        self.deref_mut().text(rect, text, class, align)
+    }
+    fn edit_marker(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        self.deref_mut().edit_marker(rect, text, class, align, byte)
+    }
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        self.deref_mut()
+            .text_scaled(rect, text, class, scale, align)
+    }
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        self.deref_mut()
+            .text_oriented(rect, text, class, orientation, align)
+    }
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        self.deref_mut()
+            .edit_marker_scaled(rect, text, class, scale, align, byte)
+    }
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.deref_mut()
+            .text_selection(rect, text, class, align, range)
+    }
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.deref_mut()
+            .text_selection_scaled(rect, text, class, scale, align, range)
+    }
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        self.deref_mut().overscroll(rect, direction, amount)
+    }
+    fn image(&mut self, rect: Rect, id: ImageId) {
+        self.deref_mut().image(rect, id)
+    }
+    fn ninepatch(&mut self, rect: Rect, id: ImageId, size: Size, insets: Margins) {
+        self.deref_mut().ninepatch(rect, id, size, insets)
+    }
+    fn dim(&mut self, rect: Rect, spotlight: Option<Rect>) {
+        self.deref_mut().dim(rect, spotlight)
+    }
     fn menu_entry(&mut self, rect: Rect, state: InputState) {
         self.deref_mut().menu_entry(rect, state)
     }
     fn button(&mut self, rect: Rect, state: InputState) {
         self.deref_mut().button(rect, state)
     }
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState) {
+        self.deref_mut().tab(rect, active, state)
+    }
     fn edit_box(&mut self, rect: Rect, state: InputState) {
         self.deref_mut().edit_box(rect, state)
     }
@@ -504,6 +1281,13 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
     fn slider(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
         self.deref_mut().slider(rect, h_rect, dir, state)
     }
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32) {
+        self.deref_mut().progress_bar(rect, dir, fraction)
+    }
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        self.deref_mut()
+            .progress_bar_indeterminate(rect, dir, phase)
+    }
 }
 
 #[cfg(feature = "stack_dst")]
@@ -551,12 +1335,84 @@ where
     ) {
         self.deref_mut().edit_marker(rect, text, class, align, byte)
     }
+    fn text_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+    ) {
+        self.deref_mut()
+            .text_scaled(rect, text, class, scale, align)
+    }
+    fn text_oriented(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        orientation: TextOrientation,
+        align: (Align, Align),
+    ) {
+        self.deref_mut()
+            .text_oriented(rect, text, class, orientation, align)
+    }
+    fn edit_marker_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        byte: usize,
+    ) {
+        self.deref_mut()
+            .edit_marker_scaled(rect, text, class, scale, align, byte)
+    }
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.deref_mut()
+            .text_selection(rect, text, class, align, range)
+    }
+    fn text_selection_scaled(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        class: TextClass,
+        scale: f32,
+        align: (Align, Align),
+        range: Range<usize>,
+    ) {
+        self.deref_mut()
+            .text_selection_scaled(rect, text, class, scale, align, range)
+    }
+    fn overscroll(&mut self, rect: Rect, direction: Direction, amount: f32) {
+        self.deref_mut().overscroll(rect, direction, amount)
+    }
+    fn image(&mut self, rect: Rect, id: ImageId) {
+        self.deref_mut().image(rect, id)
+    }
+    fn ninepatch(&mut self, rect: Rect, id: ImageId, size: Size, insets: Margins) {
+        self.deref_mut().ninepatch(rect, id, size, insets)
+    }
+    fn dim(&mut self, rect: Rect, spotlight: Option<Rect>) {
+        self.deref_mut().dim(rect, spotlight)
+    }
     fn menu_entry(&mut self, rect: Rect, state: InputState) {
         self.deref_mut().menu_entry(rect, state)
     }
     fn button(&mut self, rect: Rect, state: InputState) {
         self.deref_mut().button(rect, state)
     }
+    fn tab(&mut self, rect: Rect, active: bool, state: InputState) {
+        self.deref_mut().tab(rect, active, state)
+    }
     fn edit_box(&mut self, rect: Rect, state: InputState) {
         self.deref_mut().edit_box(rect, state)
     }
@@ -572,4 +1428,11 @@ where
     fn slider(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
         self.deref_mut().slider(rect, h_rect, dir, state)
     }
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, fraction: f32) {
+        self.deref_mut().progress_bar(rect, dir, fraction)
+    }
+    fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        self.deref_mut()
+            .progress_bar_indeterminate(rect, dir, phase)
+    }
 }
@@ -26,9 +26,12 @@
 //! and may provide their own extension traits. For this reason, themes are
 //! parameterised over an object `D: Draw + ...` (with specified trait bounds).
 //!
-//! The medium-level API will be extended in the future to support texturing
-//! (not yet supported) and potentially a more comprehensive path-based API
-//! (e.g. Lyon).
+//! Raster images are supported: an image is loaded via
+//! [`DrawShared::load_image`] (or [`crate::event::Manager::load_image`]),
+//! then drawn via [`Draw::image`] or [`DrawHandle::image`].
+//!
+//! The medium-level API may be extended in the future with a more
+//! comprehensive path-based API (e.g. Lyon).
 //!
 //! ### Low-level interface
 //!
@@ -39,15 +42,19 @@
 
 mod colour;
 mod handle;
+mod svg;
 mod text;
 
 use std::any::Any;
 
-use crate::geom::{Quad, Rect, Vec2};
+use crate::geom::{Quad, Rect, Size, Vec2};
 
 pub use colour::Colour;
-pub use handle::{ClipRegion, DrawHandle, InputState, SizeHandle, TextClass};
-pub use text::{DrawText, DrawTextShared, FontArc, FontId, TextProperties};
+pub use handle::{ClipRegion, DrawHandle, InputState, SizeHandle, TextClass, ThemeMetrics};
+pub use svg::SvgDraw;
+pub use text::{measure_text, vertical_text, TextMeasure};
+pub use text::{DrawText, DrawTextShared, FontArc, FontId, TextLine, TextOrientation};
+pub use text::{TextProperties, TextRendering};
 
 /// Pass identifier
 ///
@@ -80,9 +87,53 @@ impl Pass {
     }
 }
 
+/// Image identifier
+///
+/// Users should treat this type as an opaque handle.
+/// An instance may be obtained by [`DrawShared::load_image`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ImageId(pub u32);
+
 /// Bounds on type shared across [`Draw`] implementations
 pub trait DrawShared {
     type Draw: Draw;
+
+    /// Load an image
+    ///
+    /// The image is specified as 8-bit RGBA pixel data (sRGB colour space,
+    /// four bytes per pixel) in row-major order, top row first; `data.len()`
+    /// must equal `4 * size.0 * size.1`. Images are shared by all windows.
+    ///
+    /// Returns `None` if loading the image would exceed the memory limit (see
+    /// [`DrawShared::set_memory_limit`]). Implementations may panic on invalid
+    /// input.
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId>;
+
+    /// Free an image
+    ///
+    /// The `id` must not be used afterwards; it may be re-used by a new image.
+    fn remove_image(&mut self, id: ImageId);
+
+    /// Approximate memory used by shared resources, in bytes
+    ///
+    /// This counts resources which may be freed by the application (currently
+    /// images), but not fixed costs such as pipelines.
+    ///
+    /// The default implementation returns zero.
+    fn memory_usage(&self) -> u64 {
+        0
+    }
+
+    /// Limit the memory used by shared resources
+    ///
+    /// When set, [`DrawShared::load_image`] fails where the total
+    /// [`DrawShared::memory_usage`] would exceed `limit` bytes. Resources
+    /// already loaded are not affected.
+    ///
+    /// The default implementation does nothing.
+    fn set_memory_limit(&mut self, limit: Option<u64>) {
+        let _ = limit;
+    }
 }
 
 /// Base abstraction over drawing
@@ -117,12 +168,31 @@ pub trait Draw: Any {
     fn add_clip_region(&mut self, rect: Rect, depth: f32) -> Pass;
 
     /// Draw a rectangle of uniform colour
+    ///
+    /// The colour may be translucent (alpha less than 1), e.g. to dim content.
     fn rect(&mut self, pass: Pass, rect: Quad, col: Colour);
 
     /// Draw a frame of uniform colour
     ///
     /// The frame is defined by the area inside `outer` and not inside `inner`.
     fn frame(&mut self, pass: Pass, outer: Quad, inner: Quad, col: Colour);
+
+    /// Draw an image, scaled to fill `rect`
+    ///
+    /// Images may use transparency.
+    ///
+    /// The default implementation draws the whole image via
+    /// [`Draw::image_region`].
+    fn image(&mut self, pass: Pass, rect: Quad, id: ImageId) {
+        let whole = Quad::with_coords(Vec2::ZERO, Vec2::splat(1.0));
+        self.image_region(pass, rect, id, whole);
+    }
+
+    /// Draw a region of an image, scaled to fill `rect`
+    ///
+    /// The `region` is specified in texture coordinates: `(0, 0)` is the
+    /// top-left corner of the image and `(1, 1)` the bottom-right corner.
+    fn image_region(&mut self, pass: Pass, rect: Quad, id: ImageId, region: Quad);
 }
 
 /// Drawing commands for rounded shapes
@@ -0,0 +1,602 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! SVG drawing backend
+
+use std::any::Any;
+use std::fmt::{self, Write};
+use std::ops::Range;
+
+use ab_glyph::{Font, FontArc, GlyphId, OutlineCurve, Point, ScaleFont};
+
+use super::{measure_text, FontId, ImageId, Pass, TextLine, TextMeasure, TextProperties};
+use super::{Colour, Draw, DrawRounded, DrawShaded, DrawShared, DrawText, DrawTextShared};
+use crate::geom::{Coord, Quad, Rect, Size, Vec2};
+use crate::Align;
+
+/// A [`Draw`] implementation recording to SVG
+///
+/// This implements [`Draw`] and the [`DrawRounded`], [`DrawShaded`] and
+/// [`DrawText`] extensions, thus may be used with the themes of `kas-theme`,
+/// or directly (for example, to export a chart). The result is retrieved via
+/// [`SvgDraw::to_svg`]. Since output is deterministic, this may also be used
+/// to compare draw output against golden files in tests.
+///
+/// Since the same type is used for shared and per-window state, a theme is
+/// initialised directly over a `SvgDraw` (which loads fonts into it).
+///
+/// Limitations: shading is not supported (shaded primitives are drawn in a
+/// flat colour), text is drawn as glyph outlines (thus is not selectable
+/// in the output) with simple line-wrapping and no shaping, and images are
+/// drawn as grey placeholder rects.
+#[derive(Clone, Debug)]
+pub struct SvgDraw {
+    size: Size,
+    fonts: Vec<FontArc>,
+    next_image: u32,
+    passes: Vec<SvgPass>,
+}
+
+#[derive(Clone, Debug)]
+struct SvgPass {
+    rect: Rect,
+    depth: f32,
+    content: String,
+}
+
+#[derive(Clone, Debug)]
+struct Line {
+    range: Range<usize>,
+    width: f32,
+}
+
+// Paint attribute (e.g. "fill") and colour
+struct Paint(&'static str, Colour);
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Colours are linear; SVG uses sRGB
+        fn component(x: f32) -> u8 {
+            let x = x.max(0.0).min(1.0);
+            let s = if x <= 0.003_130_8 {
+                12.92 * x
+            } else {
+                1.055 * x.powf(1.0 / 2.4) - 0.055
+            };
+            (s * 255.0).round() as u8
+        }
+        let (attr, c) = (self.0, self.1);
+        let (r, g, b) = (component(c.r), component(c.g), component(c.b));
+        write!(f, "{}=\"#{:02x}{:02x}{:02x}\"", attr, r, g, b)?;
+        if c.a < 1.0 {
+            write!(f, " {}-opacity=\"{}\"", attr, c.a.max(0.0))?;
+        }
+        Ok(())
+    }
+}
+
+// Append a rectangle with corners centred on `quad` to a path
+//
+// The rect is expanded by `d0` before `quad.a` and by `d1` after `quad.b`.
+// Corners are oval with these radii.
+fn rounded_rect(d: &mut String, quad: Quad, d0: Vec2, d1: Vec2) {
+    let (a, b) = (quad.a, quad.b);
+    let _ = write!(d, "M{} {}", a.0 - d0.0, a.1);
+    let _ = write!(d, "A{} {} 0 0 1 {} {}", d0.0, d0.1, a.0, a.1 - d0.1);
+    let _ = write!(d, "L{} {}", b.0, a.1 - d0.1);
+    let _ = write!(d, "A{} {} 0 0 1 {} {}", d1.0, d0.1, b.0 + d1.0, a.1);
+    let _ = write!(d, "L{} {}", b.0 + d1.0, b.1);
+    let _ = write!(d, "A{} {} 0 0 1 {} {}", d1.0, d1.1, b.0, b.1 + d1.1);
+    let _ = write!(d, "L{} {}", a.0, b.1 + d1.1);
+    let _ = write!(d, "A{} {} 0 0 1 {} {}", d0.0, d1.1, a.0 - d0.0, b.1);
+    d.push('Z');
+}
+
+// Append an axis-aligned oval within `quad` to a path
+fn oval(d: &mut String, quad: Quad) {
+    let r = quad.size() * 0.5;
+    let y = quad.a.1 + r.1;
+    let _ = write!(d, "M{} {}", quad.a.0, y);
+    let _ = write!(d, "A{} {} 0 1 0 {} {}", r.0, r.1, quad.b.0, y);
+    let _ = write!(d, "A{} {} 0 1 0 {} {}Z", r.0, r.1, quad.a.0, y);
+}
+
+impl SvgDraw {
+    /// Construct, with the given canvas size
+    pub fn new(size: Size) -> Self {
+        let rect = Rect::new(Coord::ZERO, size);
+        SvgDraw {
+            size,
+            fonts: vec![],
+            next_image: 0,
+            passes: vec![SvgPass {
+                rect,
+                depth: 0.0,
+                content: String::new(),
+            }],
+        }
+    }
+
+    /// Get the canvas size
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Remove all draw commands and set the canvas size
+    ///
+    /// Loaded fonts are kept.
+    pub fn clear(&mut self, size: Size) {
+        self.size = size;
+        self.passes.truncate(1);
+        self.passes[0].rect.size = size;
+        self.passes[0].content.clear();
+    }
+
+    /// Get the SVG document
+    ///
+    /// Clip regions are drawn in order of depth.
+    pub fn to_svg(&self) -> String {
+        let Size(w, h) = self.size;
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+            w, h, w, h
+        );
+
+        let mut order: Vec<usize> = (0..self.passes.len()).collect();
+        order.sort_by(|a, b| {
+            let (a, b) = (self.passes[*a].depth, self.passes[*b].depth);
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for n in order {
+            let pass = &self.passes[n];
+            if pass.content.is_empty() {
+                continue;
+            }
+            if n == 0 {
+                svg.push_str(&pass.content);
+                continue;
+            }
+            let Rect { pos, size } = pass.rect;
+            let _ = writeln!(
+                svg,
+                "<clipPath id=\"clip{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>",
+                n, pos.0, pos.1, size.0, size.1
+            );
+            let _ = writeln!(svg, "<g clip-path=\"url(#clip{})\">", n);
+            svg.push_str(&pass.content);
+            svg.push_str("</g>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn push(&mut self, pass: Pass, element: fmt::Arguments) {
+        let content = &mut self.passes[pass.pass()].content;
+        let _ = content.write_fmt(element);
+        content.push('\n');
+    }
+
+    fn path(&mut self, pass: Pass, d: &str, even_odd: bool, col: Colour) {
+        let rule = if even_odd {
+            " fill-rule=\"evenodd\""
+        } else {
+            ""
+        };
+        self.push(
+            pass,
+            format_args!("<path d=\"{}\"{} {}/>", d, rule, Paint("fill", col)),
+        );
+    }
+
+    fn font(&self, id: FontId) -> &FontArc {
+        self.fonts
+            .get(id.0)
+            .expect("SvgDraw: font not loaded (see DrawTextShared::load_font)")
+    }
+
+    // Width of a single line of text
+    fn advance(&self, text: &str, font_id: FontId, scale: f32) -> f32 {
+        let font = self.font(font_id).as_scaled(scale);
+        let mut prev: Option<GlyphId> = None;
+        let mut x = 0.0;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(prev) = prev {
+                x += font.kern(prev, id);
+            }
+            x += font.h_advance(id);
+            prev = Some(id);
+        }
+        x
+    }
+
+    // Split text into lines, wrapping on white-space if `wrap`
+    fn lines(&self, text: &str, font_id: FontId, scale: f32, width: f32, wrap: bool) -> Vec<Line> {
+        let wrap_width = if wrap { Some(width) } else { None };
+        let measure = measure_text(self.font(font_id), scale, text, wrap_width);
+        (measure.lines.into_iter())
+            .zip(measure.widths)
+            .map(|(range, width)| Line { range, width })
+            .collect()
+    }
+
+    // Lay out text: returns lines, the text-space top-left of each, and the
+    // distance between lines
+    fn layout(
+        &self,
+        rect: Rect,
+        text: &str,
+        props: &TextProperties,
+    ) -> (Vec<Line>, Vec<Vec2>, f32) {
+        let rect = props.orientation.to_text_space(rect);
+        let scale = props.scale.y;
+        let font = self.font(props.font).as_scaled(scale);
+        let line_gap = font.line_gap();
+        let height = font.height();
+
+        let width = rect.size.0 as f32;
+        let lines = self.lines(text, props.font, scale, width, props.line_wrap);
+        let n = lines.len() as f32;
+        let text_height = n * height + (n - 1.0) * line_gap;
+
+        let pos = Vec2::from(rect.pos);
+        let extra = rect.size.1 as f32 - text_height;
+        let mut y = pos.1 + props.baseline;
+        y += match props.align.1 {
+            Align::Begin | Align::Stretch => 0.0,
+            Align::Centre => 0.5 * extra,
+            Align::End => extra,
+        };
+
+        let mut origins = Vec::with_capacity(lines.len());
+        for line in &lines {
+            let extra = width - line.width;
+            let mut x = pos.0;
+            x += match props.align.0 {
+                Align::Begin | Align::Stretch => 0.0,
+                Align::Centre => 0.5 * extra,
+                Align::End => extra,
+            };
+            if props.rendering.hinting {
+                x = x.round();
+            }
+            origins.push(Vec2(x, y));
+            y += height + line_gap;
+        }
+        (lines, origins, height + line_gap)
+    }
+}
+
+impl DrawShared for SvgDraw {
+    type Draw = SvgDraw;
+
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        assert_eq!(data.len(), 4 * size.0 as usize * size.1 as usize);
+        let id = ImageId(self.next_image);
+        self.next_image += 1;
+        Some(id)
+    }
+
+    fn remove_image(&mut self, _: ImageId) {}
+}
+
+impl Draw for SvgDraw {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn add_clip_region(&mut self, rect: Rect, depth: f32) -> Pass {
+        let pass = self.passes.len();
+        self.passes.push(SvgPass {
+            rect,
+            depth,
+            content: String::new(),
+        });
+        Pass::new_pass_with_depth(pass as u32, depth)
+    }
+
+    fn rect(&mut self, pass: Pass, rect: Quad, col: Colour) {
+        let size = rect.size();
+        self.push(
+            pass,
+            format_args!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+                rect.a.0,
+                rect.a.1,
+                size.0,
+                size.1,
+                Paint("fill", col)
+            ),
+        );
+    }
+
+    fn image_region(&mut self, pass: Pass, rect: Quad, _: ImageId, _: Quad) {
+        self.rect(pass, rect, Colour::grey(0.5));
+    }
+
+    fn frame(&mut self, pass: Pass, outer: Quad, inner: Quad, col: Colour) {
+        let mut d = String::new();
+        rounded_rect(&mut d, outer, Vec2::ZERO, Vec2::ZERO);
+        rounded_rect(&mut d, inner, Vec2::ZERO, Vec2::ZERO);
+        self.path(pass, &d, true, col);
+    }
+}
+
+impl DrawRounded for SvgDraw {
+    fn rounded_line(&mut self, pass: Pass, p1: Vec2, p2: Vec2, radius: f32, col: Colour) {
+        self.push(
+            pass,
+            format_args!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\" {}/>",
+                p1.0,
+                p1.1,
+                p2.0,
+                p2.1,
+                2.0 * radius,
+                Paint("stroke", col)
+            ),
+        );
+    }
+
+    fn circle(&mut self, pass: Pass, rect: Quad, inner_radius: f32, col: Colour) {
+        let mut d = String::new();
+        oval(&mut d, rect);
+        if inner_radius > 0.0 {
+            let margin = rect.size() * (0.5 * (1.0 - inner_radius));
+            oval(&mut d, Quad::with_coords(rect.a + margin, rect.b - margin));
+        }
+        self.path(pass, &d, true, col);
+    }
+
+    fn rounded_frame(
+        &mut self,
+        pass: Pass,
+        outer: Quad,
+        inner: Quad,
+        inner_radius: f32,
+        col: Colour,
+    ) {
+        let d0 = inner.a - outer.a;
+        let d1 = outer.b - inner.b;
+        let mut d = String::new();
+        rounded_rect(&mut d, inner, d0, d1);
+        rounded_rect(&mut d, inner, d0 * inner_radius, d1 * inner_radius);
+        self.path(pass, &d, true, col);
+    }
+}
+
+impl DrawShaded for SvgDraw {
+    fn shaded_square(&mut self, pass: Pass, rect: Quad, _: (f32, f32), col: Colour) {
+        self.rect(pass, rect, col);
+    }
+
+    fn shaded_circle(&mut self, pass: Pass, rect: Quad, _: (f32, f32), col: Colour) {
+        self.circle(pass, rect, 0.0, col);
+    }
+
+    fn shaded_square_frame(
+        &mut self,
+        pass: Pass,
+        outer: Quad,
+        inner: Quad,
+        _: (f32, f32),
+        col: Colour,
+    ) {
+        self.frame(pass, outer, inner, col);
+    }
+
+    fn shaded_round_frame(
+        &mut self,
+        pass: Pass,
+        outer: Quad,
+        inner: Quad,
+        _: (f32, f32),
+        col: Colour,
+    ) {
+        self.rounded_frame(pass, outer, inner, 0.0, col);
+    }
+}
+
+impl DrawTextShared for SvgDraw {
+    fn load_font(&mut self, font: FontArc) -> FontId {
+        let id = FontId(self.fonts.len());
+        self.fonts.push(font);
+        id
+    }
+
+    fn measure_text(
+        &self,
+        text: &str,
+        font: FontId,
+        scale: f32,
+        wrap_width: Option<f32>,
+    ) -> TextMeasure {
+        measure_text(self.font(font), scale, text, wrap_width)
+    }
+}
+
+impl DrawText for SvgDraw {
+    fn text(&mut self, pass: Pass, rect: Rect, text: &str, props: TextProperties) {
+        let (lines, origins, _) = self.layout(rect, text, &props);
+        let font = self.font(props.font).as_scaled(props.scale.y);
+        let (sx, sy) = (font.h_scale_factor(), font.v_scale_factor());
+        let ascent = font.ascent();
+
+        let mut d = String::new();
+        for (line, origin) in lines.iter().zip(origins) {
+            let mut x = origin.0;
+            let baseline = origin.1 + ascent;
+            let mut prev: Option<GlyphId> = None;
+            for c in text[line.range.clone()].chars() {
+                let id = font.glyph_id(c);
+                if let Some(prev) = prev {
+                    x += font.kern(prev, id);
+                }
+                let map = |p: Point| (x + p.x * sx, baseline - p.y * sy);
+                if let Some(outline) = font.font.outline(id) {
+                    let mut last = None;
+                    for curve in outline.curves {
+                        let (p0, rest) = match curve {
+                            OutlineCurve::Line(p0, p1) => (p0, vec![p1]),
+                            OutlineCurve::Quad(p0, p1, p2) => (p0, vec![p1, p2]),
+                            OutlineCurve::Cubic(p0, p1, p2, p3) => (p0, vec![p1, p2, p3]),
+                        };
+                        if last != Some(p0) {
+                            if last.is_some() {
+                                d.push('Z');
+                            }
+                            let (x, y) = map(p0);
+                            let _ = write!(d, "M{} {}", x, y);
+                        }
+                        d.push(match rest.len() {
+                            1 => 'L',
+                            2 => 'Q',
+                            _ => 'C',
+                        });
+                        for (i, p) in rest.iter().enumerate() {
+                            let (x, y) = map(*p);
+                            let sep = if i == 0 { "" } else { " " };
+                            let _ = write!(d, "{}{} {}", sep, x, y);
+                        }
+                        last = rest.last().cloned();
+                    }
+                    if last.is_some() {
+                        d.push('Z');
+                    }
+                }
+                x += font.h_advance(id);
+                prev = Some(id);
+            }
+        }
+
+        if d.is_empty() {
+            return;
+        }
+        let col = Paint("fill", props.col);
+        match props.orientation.is_rotated() {
+            false => self.push(pass, format_args!("<path d=\"{}\" {}/>", d, col)),
+            true => {
+                let angle = match props.orientation {
+                    super::TextOrientation::RotatedCw => 90,
+                    _ => -90,
+                };
+                self.push(
+                    pass,
+                    format_args!(
+                        "<path transform=\"rotate({})\" d=\"{}\" {}/>",
+                        angle, d, col
+                    ),
+                )
+            }
+        }
+    }
+
+    fn text_bound(
+        &mut self,
+        text: &str,
+        font_id: FontId,
+        font_scale: f32,
+        bounds: (f32, f32),
+        line_wrap: bool,
+    ) -> (f32, f32) {
+        let font = self.font(font_id).as_scaled(font_scale);
+        let (height, line_gap) = (font.height(), font.line_gap());
+        let lines = self.lines(text, font_id, font_scale, bounds.0, line_wrap);
+        let width = lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        let n = lines.len() as f32;
+        (width, n * height + (n - 1.0) * line_gap)
+    }
+
+    fn text_glyph_pos(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        props: TextProperties,
+        byte: usize,
+    ) -> Vec2 {
+        let (lines, origins, _) = self.layout(rect, text, &props);
+        let index = lines
+            .iter()
+            .position(|line| byte <= line.range.end)
+            .unwrap_or(lines.len() - 1);
+        let line = &lines[index];
+        let start = line.range.start.min(byte);
+        let x = self.advance(&text[start..byte], props.font, props.scale.y);
+        let pos = origins[index] + Vec2(x, 0.0);
+        props.orientation.vec_from_text_space(pos)
+    }
+
+    fn text_index_nearest(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        props: TextProperties,
+        pos: Vec2,
+    ) -> usize {
+        let (lines, origins, line_height) = self.layout(rect, text, &props);
+        let pos = props.orientation.vec_to_text_space(pos);
+        let index = ((pos.1 - origins[0].1) / line_height).max(0.0) as usize;
+        let index = index.min(lines.len() - 1);
+        let line = &lines[index];
+
+        let font = self.font(props.font).as_scaled(props.scale.y);
+        let mut x = origins[index].0;
+        let mut prev: Option<GlyphId> = None;
+        for (i, c) in text[line.range.clone()].char_indices() {
+            let id = font.glyph_id(c);
+            if let Some(prev) = prev {
+                x += font.kern(prev, id);
+            }
+            let advance = font.h_advance(id);
+            if pos.0 < x + 0.5 * advance {
+                return line.range.start + i;
+            }
+            x += advance;
+            prev = Some(id);
+        }
+        line.range.end
+    }
+
+    fn text_lines(&mut self, rect: Rect, text: &str, props: TextProperties) -> Vec<TextLine> {
+        let (lines, origins, _) = self.layout(rect, text, &props);
+        let height = self.font(props.font).as_scaled(props.scale.y).height();
+        lines
+            .into_iter()
+            .zip(origins)
+            .map(|(line, origin)| TextLine {
+                range: line.range,
+                top: origin.1,
+                bottom: origin.1 + height,
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn svg_clip_regions() {
+    let mut draw = SvgDraw::new(Size(40, 30));
+    let pass = Pass::new_pass_with_depth(0, 0.0);
+    let clip = draw.add_clip_region(Rect::new(Coord(5, 5), Size(10, 10)), 1.0);
+    draw.rect(
+        clip,
+        Quad::from(Rect::new(Coord(0, 0), Size(20, 20))),
+        Colour::grey(1.0),
+    );
+    draw.rect(
+        pass,
+        Quad::from(Rect::new(Coord(0, 0), Size(40, 30))),
+        Colour::grey(0.0),
+    );
+
+    let svg = draw.to_svg();
+    let background = svg.find("fill=\"#000000\"").unwrap();
+    let clipped = svg.find("clip-path=\"url(#clip1)\"").unwrap();
+    assert!(background < clipped);
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"20\" height=\"20\" fill=\"#ffffff\"/>"));
+}
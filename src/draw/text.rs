@@ -7,8 +7,12 @@
 
 pub use ab_glyph::{FontArc, PxScale};
 
+use ab_glyph::{Font, GlyphId, ScaleFont};
+
+use std::ops::Range;
+
 use super::{Colour, Draw, DrawShared, Pass};
-use crate::geom::{Rect, Vec2};
+use crate::geom::{Coord, Rect, Size, Vec2};
 use crate::Align;
 
 /// Font identifier
@@ -21,6 +25,122 @@ use crate::Align;
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct FontId(pub usize);
 
+/// Orientation of text
+///
+/// Text is always laid out as horizontal lines within a *text space*; the
+/// orientation describes how this maps to the screen (see
+/// [`TextOrientation::to_text_space`]).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TextOrientation {
+    /// Horizontal lines of text (the default)
+    Horizontal,
+    /// Upright glyphs stacked top-to-bottom
+    ///
+    /// This is the usual orientation of vertical CJK text. Glyphs are centred
+    /// horizontally within the column; line-wrapping is not supported.
+    Vertical,
+    /// Horizontal text rotated 90° clockwise (reading top-to-bottom)
+    RotatedCw,
+    /// Horizontal text rotated 90° anti-clockwise (reading bottom-to-top)
+    ///
+    /// This is the usual orientation of labels on a vertical chart axis.
+    RotatedCcw,
+}
+
+impl Default for TextOrientation {
+    fn default() -> Self {
+        TextOrientation::Horizontal
+    }
+}
+
+impl TextOrientation {
+    /// True for the rotated orientations
+    #[inline]
+    pub fn is_rotated(self) -> bool {
+        match self {
+            TextOrientation::RotatedCw | TextOrientation::RotatedCcw => true,
+            _ => false,
+        }
+    }
+
+    /// Map a screen-space `rect` to text space
+    ///
+    /// For rotated orientations, the result has transposed size and is
+    /// positioned such that rotating it back yields `rect`. Other
+    /// orientations use the identity mapping.
+    pub fn to_text_space(self, rect: Rect) -> Rect {
+        let Rect { pos, size } = rect;
+        let transposed = Size(size.1, size.0);
+        match self {
+            TextOrientation::RotatedCw => {
+                Rect::new(Coord(pos.1, -(pos.0 + size.0 as i32)), transposed)
+            }
+            TextOrientation::RotatedCcw => {
+                Rect::new(Coord(-(pos.1 + size.1 as i32), pos.0), transposed)
+            }
+            _ => rect,
+        }
+    }
+
+    /// Map a screen-space position to text space
+    pub fn vec_to_text_space(self, v: Vec2) -> Vec2 {
+        match self {
+            TextOrientation::RotatedCw => Vec2(v.1, -v.0),
+            TextOrientation::RotatedCcw => Vec2(-v.1, v.0),
+            _ => v,
+        }
+    }
+
+    /// Map a text-space position to screen space
+    ///
+    /// This is the inverse of [`TextOrientation::vec_to_text_space`].
+    pub fn vec_from_text_space(self, v: Vec2) -> Vec2 {
+        match self {
+            TextOrientation::RotatedCw => Vec2(-v.1, v.0),
+            TextOrientation::RotatedCcw => Vec2(v.1, -v.0),
+            _ => v,
+        }
+    }
+}
+
+/// Prepare `text` for layout with [`TextOrientation::Vertical`]
+///
+/// This inserts a line break after each `char` except the last, thus each
+/// glyph is laid out on its own line.
+pub fn vertical_text(text: &str) -> String {
+    let mut result = String::with_capacity(2 * text.len());
+    for (i, c) in text.chars().enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Text rendering options
+///
+/// Default values follow platform conventions: hinting is enabled except on
+/// macOS.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextRendering {
+    /// Snap glyphs to the pixel grid
+    ///
+    /// When enabled, the origin of each glyph is rounded to a whole pixel.
+    /// This makes small text sharper, especially on low-DPI screens, at the
+    /// cost of less accurate glyph spacing.
+    pub hinting: bool,
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        TextRendering {
+            hinting: !cfg!(target_os = "macos"),
+        }
+    }
+}
+
 /// Text properties for use by [`DrawText::text`]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct TextProperties {
@@ -38,6 +158,19 @@ pub struct TextProperties {
     pub align: (Align, Align),
     /// True if text should automatically be line-wrapped
     pub line_wrap: bool,
+    /// Text orientation
+    ///
+    /// For rotated orientations, `align` is relative to the text, i.e.
+    /// `align.0` is alignment along the direction of reading.
+    pub orientation: TextOrientation,
+    /// Baseline offset
+    ///
+    /// Text is shifted by this many pixels (in text space) perpendicular to
+    /// the baseline; positive values shift horizontal text down (e.g. for
+    /// subscripts) and negative values shift it up.
+    pub baseline: f32,
+    /// Rendering options
+    pub rendering: TextRendering,
 }
 
 impl Default for TextProperties {
@@ -48,14 +181,149 @@ impl Default for TextProperties {
             col: Default::default(),
             align: Default::default(),
             line_wrap: Default::default(),
+            orientation: Default::default(),
+            baseline: 0.0,
+            rendering: Default::default(),
         }
     }
 }
 
+/// A line of text, as laid out
+///
+/// Returned by [`DrawText::text_lines`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLine {
+    /// Byte range of the line's text
+    ///
+    /// This excludes any line break, as well as white-space at which the line
+    /// was wrapped. Empty lines have an empty range.
+    pub range: Range<usize>,
+    /// Vertical position of the top of the line (in text space)
+    pub top: f32,
+    /// Vertical position of the bottom of the line (in text space)
+    pub bottom: f32,
+}
+
+/// Measurements of a text
+///
+/// Returned by [`measure_text`] and [`DrawTextShared::measure_text`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextMeasure {
+    /// Byte range of each line
+    ///
+    /// This excludes any line break, as well as white-space at which the line
+    /// was wrapped.
+    pub lines: Vec<Range<usize>>,
+    /// Width of each line
+    pub widths: Vec<f32>,
+    /// Size of the whole text
+    pub size: Vec2,
+}
+
+impl TextMeasure {
+    /// Number of lines (at least 1)
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+/// Measure text without drawing it
+///
+/// Text is split into lines at each `'\n'` and, if `wrap_width` is given,
+/// wrapped at white-space to fit this width (words wider than `wrap_width`
+/// are not broken). Kerning is applied, but there is no shaping; measurements
+/// may thus differ slightly from text as drawn by a toolkit.
+///
+/// The `scale` is as in [`TextProperties::scale`]; measurements are in the
+/// same units (usually physical pixels).
+pub fn measure_text(
+    font: &FontArc,
+    scale: f32,
+    text: &str,
+    wrap_width: Option<f32>,
+) -> TextMeasure {
+    let font = font.as_scaled(scale);
+    let width = wrap_width.unwrap_or(f32::INFINITY);
+    let advance = |text: &str| {
+        let mut prev: Option<GlyphId> = None;
+        let mut x = 0.0;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(prev) = prev {
+                x += font.kern(prev, id);
+            }
+            x += font.h_advance(id);
+            prev = Some(id);
+        }
+        x
+    };
+
+    let mut lines = vec![];
+    let mut widths = vec![];
+    let mut start = 0;
+    let mut x = 0.0;
+    let mut prev: Option<GlyphId> = None;
+    let mut brk: Option<(usize, f32)> = None;
+    for (i, c) in text.char_indices() {
+        if c == '\n' {
+            lines.push(start..i);
+            widths.push(x);
+            start = i + 1;
+            x = 0.0;
+            prev = None;
+            brk = None;
+            continue;
+        }
+
+        let id = font.glyph_id(c);
+        if let Some(prev) = prev {
+            x += font.kern(prev, id);
+        }
+        if c.is_whitespace() {
+            brk = Some((i, x));
+        }
+        x += font.h_advance(id);
+        prev = Some(id);
+
+        if x > width && !c.is_whitespace() {
+            if let Some((b, w)) = brk.take() {
+                lines.push(start..b);
+                widths.push(w);
+                start = b + text[b..].chars().next().unwrap().len_utf8();
+                x = advance(&text[start..i + c.len_utf8()]);
+            }
+        }
+    }
+    lines.push(start..text.len());
+    widths.push(x);
+
+    let n = lines.len() as f32;
+    let height = n * font.height() + (n - 1.0) * font.line_gap();
+    let width = widths.iter().cloned().fold(0.0, f32::max);
+    TextMeasure {
+        lines,
+        widths,
+        size: Vec2(width, height),
+    }
+}
+
 /// Abstraction over type shared by [`DrawText`] implementations
 pub trait DrawTextShared: DrawShared {
     /// Load a font
     fn load_font(&mut self, font: FontArc) -> FontId;
+
+    /// Measure text without drawing it
+    ///
+    /// This allows text sizes to be calculated without a window or widget,
+    /// e.g. to choose the size of a window. See [`measure_text`].
+    fn measure_text(
+        &self,
+        text: &str,
+        font: FontId,
+        scale: f32,
+        wrap_width: Option<f32>,
+    ) -> TextMeasure;
 }
 
 /// Abstraction over text rendering
@@ -78,6 +346,10 @@ pub trait DrawText: Draw {
     /// This may be used with [`DrawText::text`] to calculate size requirements
     /// within [`kas::Layout::size_rules`].
     ///
+    /// The bound is calculated for horizontal text. For other orientations,
+    /// transpose the bounds of rotated text and use [`vertical_text`] to
+    /// prepare vertical text.
+    ///
     /// Bounds of `(f32::INFINITY, f32::INFINITY)` may be used if there are no
     /// constraints. This parameter allows forcing line-wrapping behaviour
     /// within the given bounds.
@@ -112,4 +384,14 @@ pub trait DrawText: Draw {
         props: TextProperties,
         pos: Vec2,
     ) -> usize;
+
+    /// Find the lines of text, as laid out
+    ///
+    /// Text is laid out as in [`DrawText::text`]. Lines are returned in order,
+    /// top to bottom; the result has at least one line (which may be empty).
+    ///
+    /// Vertical positions are in text space (see
+    /// [`TextOrientation::to_text_space`]), thus are only useful for
+    /// horizontal text.
+    fn text_lines(&mut self, rect: Rect, text: &str, props: TextProperties) -> Vec<TextLine>;
 }
@@ -0,0 +1,45 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event handling configuration
+
+/// Event handling configuration
+///
+/// Toolkits pass this to each window's [`ManagerState`](super::ManagerState);
+/// widgets may read it via [`ManagerState::config`](super::ManagerState::config).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    /// Support the primary selection
+    ///
+    /// When enabled, selecting text copies it to the primary selection and a
+    /// middle-click pastes from it. This is a convention of X11 and Wayland
+    /// desktops, hence by default it is enabled only on platforms using these.
+    pub primary_selection: bool,
+    /// Scroll horizontally with Shift+wheel
+    ///
+    /// When enabled, scroll regions interpret scrolling with Shift held as
+    /// horizontal scrolling (see [`Manager::scroll_delta`]). Since macOS
+    /// applies this convention itself, by default this is disabled there.
+    ///
+    /// [`Manager::scroll_delta`]: super::Manager::scroll_delta
+    pub shift_scroll_horizontal: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            primary_selection: cfg!(all(
+                unix,
+                not(any(
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android"
+                ))
+            )),
+            shift_scroll_horizontal: !cfg!(target_os = "macos"),
+        }
+    }
+}
@@ -6,11 +6,14 @@
 //! Event handling: events
 
 #[allow(unused)]
-use super::{GrabMode, Manager, Response}; // for doc-links
-use super::{MouseButton, UpdateHandle, VirtualKeyCode};
+use super::{GrabMode, Help, Manager, Response}; // for doc-links
+use super::{ModifiersState, MouseButton, UpdateData, UpdateHandle, VirtualKeyCode};
 
-use crate::geom::{Coord, DVec2};
-use crate::{WidgetId, WindowId};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::geom::{Coord, DVec2, Vec2};
+use crate::{Overlay, WidgetId, WindowId};
 
 /// Events addressed to a widget
 #[non_exhaustive]
@@ -25,6 +28,8 @@ pub enum Event {
     ///
     /// This event is triggered by keyboard navigation and accelerator key
     /// bindings, and may be used by a parent widget to activate a child.
+    /// The triggering input is available via
+    /// [`ManagerState::activate_source`](super::ManagerState::activate_source).
     Activate,
     /// Control / Navigation key input
     ///
@@ -101,10 +106,14 @@ pub enum Event {
         delta: DVec2,
     },
     /// A mouse button was pressed or touch event started
+    ///
+    /// Press events report the position both as a `coord` (whole pixels) and
+    /// as `pos`, with sub-pixel precision where supported by the platform.
     PressStart {
         source: PressSource,
         start_id: WidgetId,
         coord: Coord,
+        pos: Vec2,
     },
     /// Movement of mouse or a touch press
     ///
@@ -113,6 +122,7 @@ pub enum Event {
         source: PressSource,
         cur_id: Option<WidgetId>,
         coord: Coord,
+        pos: Vec2,
         delta: Coord,
     },
     /// End of a click/touch press
@@ -125,20 +135,66 @@ pub enum Event {
         source: PressSource,
         end_id: Option<WidgetId>,
         coord: Coord,
+        pos: Vec2,
     },
+    /// Raw relative motion of the mouse
+    ///
+    /// Received only while the pointer is locked
+    /// ([`Manager::lock_pointer`]), in addition to [`Event::PressMove`].
+    /// The `delta` is reported by the device and is not subject to pointer
+    /// acceleration or confinement to the window, thus is appropriate for
+    /// interactions such as rotating a 3D view. Units are device-specific.
+    PointerMotion { delta: Vec2 },
+    /// Files dragged from another application are hovering over the window
+    ///
+    /// This is sent to the widget under the mouse cursor, or to the window
+    /// where the cursor position is unknown (platforms may not report motion
+    /// during a drag). A widget accepting dropped files may highlight itself
+    /// until it receives [`Event::FilesHoverCancelled`] or
+    /// [`Event::FilesDrop`]; otherwise it should reply with
+    /// [`Response::Unhandled`], allowing a parent to accept the files instead.
+    FilesHover(Vec<PathBuf>),
+    /// The files previously hovering were dragged away
+    ///
+    /// This is sent to the same widget as [`Event::FilesHover`].
+    FilesHoverCancelled,
+    /// Files were dropped on the window from another application
+    ///
+    /// This is sent to the same widget as the preceding [`Event::FilesHover`],
+    /// if any, otherwise to the widget under the mouse cursor.
+    FilesDrop(Vec<PathBuf>),
+    /// Gamepad (game controller) input
+    ///
+    /// This is sent to the widget with nav focus (or otherwise to the owner
+    /// of the top-most pop-up, or to the nav fallback). `gamepad` identifies
+    /// the device. When not handled, button presses are mapped to keyboard
+    /// navigation (see [`Manager::handle_gamepad`]).
+    Gamepad { gamepad: u32, input: GamepadInput },
     /// Update from a timer
     ///
     /// This event is received after requesting timed wake-up(s)
-    /// (see [`Manager::update_on_timer`]).
+    /// (see [`Manager::update_after`] and [`Manager::update_every`]).
     TimerUpdate,
+    /// Start of a new frame
+    ///
+    /// This event is received once, immediately before drawing the next frame,
+    /// after requesting it via [`Manager::request_frame`]. Animations should
+    /// use the presentation time given here instead of `Instant::now()`.
+    Frame(FrameInfo),
     /// Update triggerred via an [`UpdateHandle`]
     ///
     /// This event may be received after registering an [`UpdateHandle`] via
     /// [`Manager::update_on_handle`].
     ///
     /// A user-defined payload is passed. Interpretation of this payload is
-    /// user-defined and unfortunately not type safe.
-    HandleUpdate { handle: UpdateHandle, payload: u64 },
+    /// user-defined and unfortunately not type safe. Typed `data` may also
+    /// be passed (see [`Manager::trigger_update_data`]); recipients may use
+    /// [`UpdateData::downcast_ref`] to access it.
+    HandleUpdate {
+        handle: UpdateHandle,
+        payload: u64,
+        data: Option<UpdateData>,
+    },
     /// Notification that a new popup has been created
     ///
     /// This is sent to the parent of each open popup when a new popup is
@@ -155,6 +211,87 @@ pub enum Event {
     ///
     /// The widget should reply with [`Response::Focus`].
     NavFocus,
+    /// Request to select the widget
+    ///
+    /// This may be sent by a parent to select an item such as a list row,
+    /// tab or menu entry. Widgets supporting selection should update their
+    /// state and reply with [`Response::Select`]; other widgets should reply
+    /// with [`Response::Unhandled`].
+    Select,
+    /// Request to show help in a pop-up
+    ///
+    /// This is sent to the window when a widget is clicked in *What's this?*
+    /// mode (see [`Help`]). The `WidgetId` is that of the clicked widget or
+    /// its nearest ancestor with help registered.
+    ShowHelp(WidgetId),
+    /// Request to open help
+    ///
+    /// This is sent to the window when F1 is pressed (see [`Help`]). The
+    /// `WidgetId` is that of the widget with keyboard focus or its nearest
+    /// ancestor with help registered.
+    HelpRequested(WidgetId),
+    /// Request to show a tooltip
+    ///
+    /// This is sent to the window when the mouse has hovered over a widget
+    /// with a tooltip (see [`Manager::register_tooltip`]) for a short time. The
+    /// `WidgetId` is that of the hovered widget or its nearest ancestor with a
+    /// tooltip.
+    ///
+    /// [`Manager::register_tooltip`]: super::Manager::register_tooltip
+    ShowTooltip(WidgetId),
+    /// Request to hide the tooltip
+    ///
+    /// This is sent to the window when the mouse leaves the widget whose
+    /// tooltip was shown.
+    HideTooltip,
+    /// Request to show an overlay
+    ///
+    /// This is sent to the window by [`Manager::add_overlay`]. If an overlay
+    /// with the same widget identifier is already shown, it is replaced.
+    ShowOverlay(Overlay),
+    /// Request to hide an overlay
+    ///
+    /// This is sent to the window by [`Manager::remove_overlay`]. The
+    /// `WidgetId` is that of the overlay widget.
+    HideOverlay(WidgetId),
+}
+
+impl Event {
+    /// Get the name of this event's variant (e.g. `"PressStart"`)
+    ///
+    /// This is used for filtering by [`EventTrace`](super::EventTrace).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::None => "None",
+            Event::Activate => "Activate",
+            Event::Control(_) => "Control",
+            Event::LostCharFocus => "LostCharFocus",
+            Event::ReceivedCharacter(_) => "ReceivedCharacter",
+            Event::Scroll(_) => "Scroll",
+            Event::Pan { .. } => "Pan",
+            Event::PressStart { .. } => "PressStart",
+            Event::PressMove { .. } => "PressMove",
+            Event::PressEnd { .. } => "PressEnd",
+            Event::PointerMotion { .. } => "PointerMotion",
+            Event::FilesHover(_) => "FilesHover",
+            Event::FilesHoverCancelled => "FilesHoverCancelled",
+            Event::FilesDrop(_) => "FilesDrop",
+            Event::Gamepad { .. } => "Gamepad",
+            Event::TimerUpdate => "TimerUpdate",
+            Event::Frame(_) => "Frame",
+            Event::HandleUpdate { .. } => "HandleUpdate",
+            Event::NewPopup(_) => "NewPopup",
+            Event::PopupRemoved(_) => "PopupRemoved",
+            Event::NavFocus => "NavFocus",
+            Event::Select => "Select",
+            Event::ShowHelp(_) => "ShowHelp",
+            Event::HelpRequested(_) => "HelpRequested",
+            Event::ShowTooltip(_) => "ShowTooltip",
+            Event::HideTooltip => "HideTooltip",
+            Event::ShowOverlay(_) => "ShowOverlay",
+            Event::HideOverlay(_) => "HideOverlay",
+        }
+    }
 }
 
 /// Control / Navigation key ([`Event::Control`])
@@ -265,6 +402,80 @@ impl ControlKey {
             _ => return None,
         })
     }
+
+    /// Try constructing from a [`VirtualKeyCode`] and modifiers
+    ///
+    /// In addition to [`ControlKey::new`], this maps standard shortcuts for
+    /// clipboard and undo actions. Where "Cmd" is Command on macOS and Ctrl
+    /// elsewhere, these are:
+    ///
+    /// -   Cmd+X and Shift+Delete: [`ControlKey::Cut`]
+    /// -   Cmd+C and Ctrl+Insert: [`ControlKey::Copy`]
+    /// -   Cmd+V and Shift+Insert: [`ControlKey::Paste`]
+    /// -   Cmd+Z: [`ControlKey::Undo`]
+    /// -   Cmd+Shift+Z and Cmd+Y: [`ControlKey::Redo`]
+    pub fn new_with_modifiers(vkey: VirtualKeyCode, modifiers: ModifiersState) -> Option<Self> {
+        use ControlKey as CK;
+        use VirtualKeyCode::*;
+        let shift = modifiers.shift();
+        if is_command(modifiers) {
+            match vkey {
+                X => return Some(CK::Cut),
+                C => return Some(CK::Copy),
+                V => return Some(CK::Paste),
+                Z if shift => return Some(CK::Redo),
+                Z => return Some(CK::Undo),
+                Y => return Some(CK::Redo),
+                _ => (),
+            }
+        }
+        match vkey {
+            Delete if shift => Some(CK::Cut),
+            Insert if modifiers.ctrl() => Some(CK::Copy),
+            Insert if shift => Some(CK::Paste),
+            vkey => ControlKey::new(vkey),
+        }
+    }
+}
+
+/// True if `modifiers` include the platform's command modifier
+///
+/// This is Command (the logo key) on macOS and Ctrl elsewhere, excepting
+/// Ctrl+Alt (used as AltGr on some platforms).
+pub(crate) fn is_command(modifiers: ModifiersState) -> bool {
+    if cfg!(target_os = "macos") {
+        modifiers.logo()
+    } else {
+        modifiers.ctrl() && !modifiers.alt()
+    }
+}
+
+/// An action which may be performed on a widget
+///
+/// Widgets report the actions they currently support via
+/// [`WidgetConfig::actions`], each paired with the [`Event`] which performs it
+/// when sent to the widget. This allows widgets to be inspected and driven
+/// uniformly, e.g. by accessibility tools, test scripts (see
+/// [`Script::perform`]) and automation.
+///
+/// [`WidgetConfig::actions`]: crate::WidgetConfig::actions
+/// [`Script::perform`]: crate::test::Script::perform
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WidgetAction {
+    /// Activate the widget (e.g. press a button)
+    Activate,
+    /// Toggle a boolean state (e.g. a check box)
+    Toggle,
+    /// Select the widget from a group (e.g. a radio box or tab)
+    Select,
+    /// Open a pop-up (e.g. a menu or combo box)
+    OpenPopup,
+    /// Increase the value (e.g. a slider)
+    Increment,
+    /// Decrease the value
+    Decrement,
+    /// Start editing text (i.e. request character focus)
+    Edit,
 }
 
 /// Source of `EventChild::Press`
@@ -287,7 +498,159 @@ impl PressSource {
     }
 }
 
+/// Input which triggered an [`Event::Activate`]
+///
+/// See [`ManagerState::activate_source`].
+///
+/// [`ManagerState::activate_source`]: super::ManagerState::activate_source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivateSource {
+    /// Not triggered directly by input
+    ///
+    /// For example, the event was sent by a parent widget or a test script.
+    Other,
+    /// A key: Space or Return (with navigation focus), or an accelerator key
+    /// or shortcut
+    Keyboard,
+    /// A gamepad button (with navigation focus)
+    Gamepad,
+    /// A click or touch, released at the given coordinate
+    Press(PressSource, Coord),
+}
+
+impl Default for ActivateSource {
+    fn default() -> Self {
+        ActivateSource::Other
+    }
+}
+
+impl ActivateSource {
+    /// True if triggered by a mouse click or touch
+    #[inline]
+    pub fn is_press(self) -> bool {
+        match self {
+            ActivateSource::Press(..) => true,
+            _ => false,
+        }
+    }
+
+    /// True if triggered by a keyboard or gamepad
+    ///
+    /// Widgets may use this, for example, to show a focus indicator only on
+    /// keyboard-driven activation.
+    #[inline]
+    pub fn is_key(self) -> bool {
+        match self {
+            ActivateSource::Keyboard | ActivateSource::Gamepad => true,
+            _ => false,
+        }
+    }
+
+    /// The coordinate of a click or touch, if applicable
+    #[inline]
+    pub fn coord(self) -> Option<Coord> {
+        match self {
+            ActivateSource::Press(_, coord) => Some(coord),
+            _ => None,
+        }
+    }
+}
+
+/// Auto-repeat timing for press-and-hold
+///
+/// See [`Handler::press_repeat`](super::Handler::press_repeat).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PressRepeat {
+    /// Delay from the start of the press until the first repeat
+    pub delay: Duration,
+    /// Interval between subsequent repeats
+    pub interval: Duration,
+}
+
+impl Default for PressRepeat {
+    fn default() -> Self {
+        PressRepeat {
+            delay: Duration::from_millis(400),
+            interval: Duration::from_millis(60),
+        }
+    }
+}
+
+/// A gamepad button
+///
+/// Face buttons are named by position since labels differ between devices:
+/// [`GamepadButton::South`] is "A" on Xbox controllers and "✕" on PlayStation
+/// controllers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// Bottom face button
+    South,
+    /// Right face button
+    East,
+    /// Top face button
+    North,
+    /// Left face button
+    West,
+    /// Left shoulder button or trigger
+    LeftTrigger,
+    /// Right shoulder button or trigger
+    RightTrigger,
+    /// Select / back button
+    Select,
+    /// Start button
+    Start,
+    /// Mode / home button
+    Mode,
+    /// Left stick press
+    LeftThumb,
+    /// Right stick press
+    RightThumb,
+    /// D-pad up
+    DPadUp,
+    /// D-pad down
+    DPadDown,
+    /// D-pad left
+    DPadLeft,
+    /// D-pad right
+    DPadRight,
+}
+
+/// A gamepad axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    /// Left stick, horizontal (positive is right)
+    LeftStickX,
+    /// Left stick, vertical (positive is up)
+    LeftStickY,
+    /// Right stick, horizontal (positive is right)
+    RightStickX,
+    /// Right stick, vertical (positive is up)
+    RightStickY,
+    /// Left analogue trigger
+    LeftZ,
+    /// Right analogue trigger
+    RightZ,
+}
+
+/// Type used by [`Event::Gamepad`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GamepadInput {
+    /// A button was pressed
+    ButtonPressed(GamepadButton),
+    /// A button was released
+    ButtonReleased(GamepadButton),
+    /// An axis changed; values are in the range `[-1, 1]`
+    AxisChanged(GamepadAxis, f32),
+}
+
 /// Type used by [`Event::Scroll`]
+///
+/// Both horizontal and vertical components are reported, thus horizontal
+/// wheels and touchpad gestures are supported. Positive line deltas scroll
+/// right and up (i.e. reveal content to the right and above); pixel deltas
+/// are the amount by which content should be moved.
+///
+/// See also [`Manager::scroll_delta`] regarding the Shift+wheel convention.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ScrollDelta {
     /// Scroll a given number of lines
@@ -295,3 +658,32 @@ pub enum ScrollDelta {
     /// Scroll a given number of pixels
     PixelDelta(Coord),
 }
+
+impl ScrollDelta {
+    /// Swap horizontal and vertical components
+    ///
+    /// Scrolling up becomes scrolling left and scrolling down becomes
+    /// scrolling right (and vice-versa).
+    pub fn swap_axes(self) -> Self {
+        match self {
+            ScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(-y, -x),
+            ScrollDelta::PixelDelta(d) => ScrollDelta::PixelDelta(Coord(d.1, d.0)),
+        }
+    }
+}
+
+/// Timing information for a frame
+///
+/// See [`Event::Frame`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameInfo {
+    /// Predicted time at which the frame will be presented
+    ///
+    /// This is an estimate: toolkits typically predict the next display
+    /// refresh following the previous presentation.
+    pub presentation: Instant,
+    /// Display refresh interval
+    ///
+    /// Toolkits unable to determine this use a nominal 60Hz.
+    pub interval: Duration,
+}
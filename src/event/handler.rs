@@ -6,6 +6,7 @@
 //! Event handling - handler
 
 use super::*;
+use crate::geom::Coord;
 #[allow(unused)]
 use crate::Widget; // for doc-links
 use crate::{WidgetConfig, WidgetId};
@@ -47,6 +48,22 @@ pub trait Handler: WidgetConfig {
         false
     }
 
+    /// Generic handler: auto-repeat activation while a press is held
+    ///
+    /// This is configuration for [`Manager::handle_generic`] and only
+    /// applies when [`Handler::activation_via_press`] returns true. If this
+    /// returns `Some`, [`Event::Activate`] is sent immediately on the start
+    /// of a press, then repeatedly (see [`PressRepeat`]) while the press is
+    /// held and the widget is depressed, and not on the end of the press.
+    /// This is useful for "hold to increment" buttons, e.g. spin-box arrows.
+    ///
+    /// [`Event::TimerUpdate`] is used to schedule repeats, thus widgets using
+    /// this should not rely on that event for other purposes.
+    #[inline]
+    fn press_repeat(&self) -> Option<PressRepeat> {
+        None
+    }
+
     /// Handle an event and return a user-defined message
     ///
     /// Widgets should handle any events applicable to themselves here, and
@@ -119,26 +136,62 @@ impl<'a> Manager<'a> {
     pub fn handle_generic<W>(
         widget: &mut W,
         mgr: &mut Manager,
-        mut event: Event,
+        event: Event,
     ) -> Response<<W as Handler>::Msg>
     where
         W: Handler + ?Sized,
     {
         if widget.activation_via_press() {
             // Translate press events
+            let id = widget.id();
             match event {
                 Event::PressStart { source, coord, .. } if source.is_primary() => {
-                    mgr.request_grab(widget.id(), source, coord, GrabMode::Grab, None);
+                    if mgr.request_grab(id, source, coord, GrabMode::Grab, None) {
+                        if let Some(repeat) = widget.press_repeat() {
+                            mgr.set_press_repeat(id, Some((source, coord)));
+                            mgr.update_after(repeat.delay, id);
+                            return activate_by_press(widget, mgr, source, coord);
+                        }
+                    }
                     return Response::None;
                 }
-                Event::PressMove { source, cur_id, .. } => {
-                    let cond = cur_id == Some(widget.id());
+                Event::PressMove {
+                    source,
+                    cur_id,
+                    coord,
+                    ..
+                } => {
+                    let cond = cur_id == Some(id);
                     let target = if cond { cur_id } else { None };
                     mgr.set_grab_depress(source, target);
+                    if mgr.press_repeat(id).map(|press| press.0) == Some(source) {
+                        mgr.set_press_repeat(id, Some((source, coord)));
+                    }
                     return Response::None;
                 }
-                Event::PressEnd { end_id, .. } if end_id == Some(widget.id()) => {
-                    event = Event::Activate;
+                Event::PressEnd {
+                    source,
+                    end_id,
+                    coord,
+                    ..
+                } => {
+                    if mgr.press_repeat(id).map(|press| press.0) == Some(source) {
+                        mgr.set_press_repeat(id, None);
+                        return Response::None;
+                    } else if end_id == Some(id) {
+                        return activate_by_press(widget, mgr, source, coord);
+                    }
+                }
+                Event::TimerUpdate => {
+                    if let Some((source, coord)) = mgr.press_repeat(id) {
+                        if let Some(repeat) = widget.press_repeat() {
+                            mgr.update_after(repeat.interval, id);
+                            if mgr.is_depressed(id) {
+                                return activate_by_press(widget, mgr, source, coord);
+                            }
+                            return Response::None;
+                        }
+                    }
                 }
                 _ => (),
             };
@@ -147,6 +200,22 @@ impl<'a> Manager<'a> {
             Event::NavFocus => return Response::Focus(widget.rect()),
             _ => (),
         }
-        widget.handle(mgr, event)
+        widget.handle(mgr, event).apply_action(mgr)
     }
 }
+
+// Send Event::Activate to widget, reporting the press as its source
+fn activate_by_press<W>(
+    widget: &mut W,
+    mgr: &mut Manager,
+    source: PressSource,
+    coord: Coord,
+) -> Response<<W as Handler>::Msg>
+where
+    W: Handler + ?Sized,
+{
+    let source = ActivateSource::Press(source, coord);
+    mgr.with_activate_source(source, |mgr| {
+        widget.handle(mgr, Event::Activate).apply_action(mgr)
+    })
+}
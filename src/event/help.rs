@@ -0,0 +1,54 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Context help
+
+use kas::string::CowString;
+
+/// Context help for a widget
+///
+/// Widgets may register [`Help`] via [`Manager::register_help`] (usually from
+/// [`WidgetConfig::configure`]). Help applies to the widget and all its
+/// descendants, except where a descendant registers its own help.
+///
+/// Two means of accessing help are supported:
+///
+/// -   *What's this?* mode, entered via Shift+F1 or [`Manager::enter_help_mode`],
+///     changes the cursor to [`CursorIcon::Help`]; the next click shows the
+///     help of the clicked widget in a pop-up (see [`Event::ShowHelp`]).
+///     Escape leaves this mode without clicking.
+/// -   F1 requests help for the widget with keyboard focus (or, if none, the
+///     window) via [`Event::HelpRequested`]; [`kas::widget::Window`] passes
+///     this to an application callback.
+///
+/// [`Manager::register_help`]: super::Manager::register_help
+/// [`Manager::enter_help_mode`]: super::Manager::enter_help_mode
+/// [`WidgetConfig::configure`]: crate::WidgetConfig::configure
+/// [`CursorIcon::Help`]: super::CursorIcon::Help
+/// [`Event::ShowHelp`]: super::Event::ShowHelp
+/// [`Event::HelpRequested`]: super::Event::HelpRequested
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Help {
+    /// A short description, shown in *What's this?* mode
+    pub text: CowString,
+    /// An optional link to further documentation
+    pub url: Option<CowString>,
+}
+
+impl Help {
+    /// Construct with the given description
+    pub fn new<T: Into<CowString>>(text: T) -> Self {
+        Help {
+            text: text.into(),
+            url: None,
+        }
+    }
+
+    /// Set a link to further documentation
+    pub fn with_url<U: Into<CowString>>(mut self, url: U) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
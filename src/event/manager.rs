@@ -8,14 +8,16 @@
 // Without winit, several things go unused
 #![cfg_attr(not(feature = "winit"), allow(unused))]
 
-use log::trace;
+use log::{debug, trace};
 use smallvec::SmallVec;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::u16;
 
 use super::*;
-use crate::geom::Coord;
+use crate::geom::{Coord, Rect, Vec2};
+use crate::string::CowString;
 #[allow(unused)]
 use crate::WidgetConfig; // for doc-links
 use crate::{TkAction, TkWindow, Widget, WidgetId, WindowId};
@@ -23,6 +25,9 @@ use crate::{TkAction, TkWindow, Widget, WidgetId, WindowId};
 mod mgr_pub;
 mod mgr_tk;
 
+/// Delay between the mouse entering a widget and its tooltip showing
+const TOOLTIP_DELAY: Duration = Duration::from_millis(700);
+
 /// Controls the types of events delivered by [`Manager::request_grab`]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GrabMode {
@@ -38,6 +43,146 @@ pub enum GrabMode {
     PanOnly,
 }
 
+/// Focus tracking information
+///
+/// This describes the position of the focused widget and (for text widgets)
+/// the edit marker (caret), for use by assistive tools such as screen
+/// magnifiers. See [`ManagerState::focus_rect`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusRect {
+    /// The widget with character focus, or otherwise navigation focus
+    pub id: WidgetId,
+    /// The widget's rect, in window coordinates
+    pub rect: Rect,
+    /// The rect of the edit marker, in window coordinates, if reported
+    ///
+    /// See [`Manager::set_caret_rect`].
+    pub caret: Option<Rect>,
+}
+
+/// Filter controlling event tracing
+///
+/// Event tracing is an opt-in debugging aid: when enabled (see
+/// [`Manager::set_event_trace`]), each matching event is logged at `debug`
+/// level together with its routing path (the widgets visited, from the window
+/// down to the target) and the [`Response`] returned.
+///
+/// Tracing may also be enabled via the `KAS_EVENT_TRACE` environment variable,
+/// which is read when the [`ManagerState`] is constructed. Supported values
+/// are `all` or a comma-separated list of event names, e.g.
+/// `KAS_EVENT_TRACE=PressStart,PressEnd`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventTrace {
+    /// If set, only trace events whose routing path includes this widget
+    pub widget: Option<WidgetId>,
+    /// If non-empty, only trace events with a listed [`Event::name`]
+    pub events: Vec<String>,
+}
+
+impl EventTrace {
+    /// Construct a filter matching all events
+    pub fn all() -> Self {
+        Default::default()
+    }
+
+    /// Only trace events routed through `id`
+    pub fn with_widget(mut self, id: WidgetId) -> Self {
+        self.widget = Some(id);
+        self
+    }
+
+    /// Only trace events with the given name (may be called multiple times)
+    pub fn with_event<S: ToString>(mut self, name: S) -> Self {
+        self.events.push(name.to_string());
+        self
+    }
+
+    fn from_env() -> Option<Self> {
+        std::env::var("KAS_EVENT_TRACE")
+            .ok()
+            .map(|v| Self::parse(&v))
+    }
+
+    // Parse a value of `KAS_EVENT_TRACE`
+    fn parse(v: &str) -> Self {
+        let mut trace = EventTrace::all();
+        if !v.eq_ignore_ascii_case("all") {
+            trace.events = v
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect();
+        }
+        trace
+    }
+
+    /// Returns the routing path to `id`, if `event` matches the filter
+    fn path(&self, widget: &dyn WidgetConfig, id: WidgetId, event: &Event) -> Option<String> {
+        if !self.events.is_empty() && !self.events.iter().any(|name| name == event.name()) {
+            return None;
+        }
+
+        let mut matched = self.widget.is_none();
+        let mut path = String::new();
+        let mut w = Some(widget);
+        while let Some(widget) = w {
+            if !path.is_empty() {
+                path.push_str(" > ");
+            }
+            path.push_str(&widget.identify());
+            matched = matched || self.widget == Some(widget.id());
+            if widget.id() == id {
+                break;
+            }
+            // Children have lower identifiers than their parent; the first
+            // child with an identifier no less than `id` is on the path.
+            w = (0..widget.len())
+                .filter_map(|i| widget.get(i))
+                .find(|child| id <= child.id());
+        }
+
+        if matched {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+/// Find `id` or its nearest ancestor which is a key of `map`
+fn find_registered<V>(
+    map: &HashMap<WidgetId, V>,
+    widget: &dyn WidgetConfig,
+    id: WidgetId,
+) -> Option<WidgetId> {
+    let mut found = None;
+    let mut w = Some(widget);
+    while let Some(widget) = w {
+        if map.contains_key(&widget.id()) {
+            found = Some(widget.id());
+        }
+        if widget.id() == id {
+            break;
+        }
+        w = (0..widget.len())
+            .filter_map(|i| widget.get(i))
+            .find(|child| id <= child.id());
+    }
+    found
+}
+
+fn response_name<M>(r: &Response<M>) -> &'static str {
+    match r {
+        Response::None => "None",
+        Response::Unhandled(_) => "Unhandled",
+        Response::Focus(_) => "Focus",
+        Response::Select => "Select",
+        Response::Msg(_) => "Msg",
+        Response::Action(..) => "Action",
+    }
+}
+
 #[derive(Clone, Debug)]
 struct MouseGrab {
     button: MouseButton,
@@ -45,6 +190,7 @@ struct MouseGrab {
     depress: Option<WidgetId>,
     mode: GrabMode,
     pan_grab: (u16, u16),
+    pointer_lock: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -90,6 +236,7 @@ enum Pending {
 // `SmallVec` is used to keep contents in local memory.
 #[derive(Debug)]
 pub struct ManagerState {
+    config: Config,
     end_id: WidgetId,
     dpi_factor: f64,
     modifiers: ModifiersState,
@@ -101,26 +248,116 @@ pub struct ManagerState {
     hover_icon: CursorIcon,
     key_depress: SmallVec<[(u32, WidgetId); 10]>,
     last_mouse_coord: Coord,
+    last_mouse_pos: Vec2,
+    // True when the cursor left the window during a mouse grab
+    cursor_left: bool,
     mouse_grab: Option<MouseGrab>,
     touch_grab: SmallVec<[TouchGrab; 10]>,
     pan_grab: SmallVec<[PanGrab; 4]>,
     accel_stack: Vec<(bool, HashMap<VirtualKeyCode, WidgetId>)>,
     accel_layers: HashMap<WidgetId, (bool, HashMap<VirtualKeyCode, WidgetId>)>,
+    shortcuts: HashMap<Shortcut, WidgetId>,
     popups: SmallVec<[(WindowId, kas::Popup); 16]>,
     new_popups: SmallVec<[WidgetId; 16]>,
     popup_removed: SmallVec<[(WidgetId, WindowId); 16]>,
+    // Events to send to the window: ShowOverlay and HideOverlay
+    overlay_events: SmallVec<[Event; 4]>,
+    // File hover, cancel and drop events since the last update, in order of
+    // arrival; the platform reports one event per file, which we merge
+    file_events: Vec<Event>,
+    // Recipient of the last Event::FilesHover
+    files_target: Option<WidgetId>,
+    help: HashMap<WidgetId, Help>,
+    help_mode: bool,
+    tooltips: HashMap<WidgetId, CowString>,
+    // Widget whose tooltip applies to the hovered widget, if any
+    tooltip: Option<WidgetId>,
+    // Time at which to show the tooltip; None when shown or not applicable
+    tooltip_time: Option<Instant>,
+    tooltip_shown: bool,
+    // Direction of the left stick, if past the threshold (x, y)
+    gamepad_stick: (i8, i8),
+    // If set, input is restricted to this subtree (and pop-ups)
+    input_lock: Option<WidgetId>,
+    // Input triggering the Activate event being handled, if any
+    activate_source: ActivateSource,
+    // Held presses which auto-repeat activation (see Handler::press_repeat)
+    press_repeats: SmallVec<[(WidgetId, PressSource, Coord); 2]>,
+    // Edit marker position reported by a text widget
+    caret: Option<(WidgetId, Rect)>,
+    window_data: HashMap<TypeId, Box<dyn Any>>,
 
     time_start: Instant,
+    // If set, the current time is this rather than the system clock
+    virtual_time: Option<Instant>,
     time_updates: Vec<(Instant, WidgetId)>,
+    // Widgets with repeating timer updates, and their interval
+    time_repeats: Vec<(WidgetId, Duration)>,
+    frame_updates: SmallVec<[WidgetId; 8]>,
     // TODO(opt): consider other containers, e.g. C++ multimap
     // or sorted Vec with binary search yielding a range
     handle_updates: HashMap<UpdateHandle, Vec<WidgetId>>,
     pending: SmallVec<[Pending; 8]>,
     action: TkAction,
+    event_trace: Option<EventTrace>,
 }
 
 /// internals
 impl ManagerState {
+    /// True if input to `id` is blocked by [`Manager::set_input_lock`]
+    fn is_locked_out(&self, widget: &dyn WidgetConfig, id: WidgetId) -> bool {
+        let lock = match self.input_lock {
+            Some(lock) => lock,
+            None => return false,
+        };
+        let within = |root| {
+            widget
+                .find(root)
+                .map(|w| w.is_ancestor_of(id))
+                .unwrap_or(false)
+        };
+        !within(lock) && !self.popups.iter().any(|(_, popup)| within(popup.id))
+    }
+
+    // The current time, as measured by the virtual clock if enabled
+    fn now(&self) -> Instant {
+        self.virtual_time.unwrap_or_else(Instant::now)
+    }
+
+    // Schedule Event::TimerUpdate for w_id at time, unless already sooner
+    fn schedule_update(&mut self, time: Instant, w_id: WidgetId) {
+        'outer: loop {
+            for row in &mut self.time_updates {
+                if row.1 == w_id {
+                    if row.0 <= time {
+                        return;
+                    } else {
+                        row.0 = time;
+                        break 'outer;
+                    }
+                }
+            }
+
+            self.time_updates.push((time, w_id));
+            break;
+        }
+
+        self.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
+    }
+
+    // Find the recipient of file hover and drop events
+    fn files_target<W: Widget + ?Sized>(&self, widget: &W) -> WidgetId {
+        self.find_input_target(widget, self.last_mouse_coord)
+            .unwrap_or_else(|| widget.id())
+    }
+
+    /// Find the widget under `coord`, excepting any blocked by the input lock
+    fn find_input_target<W: Widget + ?Sized>(&self, widget: &W, coord: Coord) -> Option<WidgetId> {
+        widget
+            .find_id(coord)
+            .filter(|id| !self.is_locked_out(widget.as_widget(), *id))
+    }
+
     fn set_pan_on(
         &mut self,
         id: WidgetId,
@@ -162,20 +399,33 @@ impl ManagerState {
     fn remove_pan(&mut self, index: usize) {
         trace!("Manager: end pan grab {}", index);
         self.pan_grab.remove(index);
-        if let Some(grab) = &mut self.mouse_grab {
-            let p0 = grab.pan_grab.0;
-            if p0 >= index as u16 && p0 != u16::MAX {
-                grab.pan_grab.0 = p0 - 1;
+        // Grabs on the removed pan grab are detached; later indices shift
+        fn update(pan_grab: &mut (u16, u16), index: u16) {
+            let p0 = pan_grab.0;
+            if p0 == index {
+                *pan_grab = (u16::MAX, 0);
+            } else if p0 > index && p0 != u16::MAX {
+                pan_grab.0 = p0 - 1;
             }
         }
+        if let Some(grab) = &mut self.mouse_grab {
+            update(&mut grab.pan_grab, index as u16);
+        }
         for grab in &mut self.touch_grab {
-            let p0 = grab.pan_grab.0;
-            if p0 >= index as u16 && p0 != u16::MAX {
-                grab.pan_grab.0 = p0 - 1;
-            }
+            update(&mut grab.pan_grab, index as u16);
         }
     }
 
+    /// Find `id` or its nearest ancestor with registered help
+    fn find_help(&self, widget: &dyn WidgetConfig, id: WidgetId) -> Option<WidgetId> {
+        find_registered(&self.help, widget, id)
+    }
+
+    /// Find `id` or its nearest ancestor with a registered tooltip
+    fn find_tooltip(&self, widget: &dyn WidgetConfig, id: WidgetId) -> Option<WidgetId> {
+        find_registered(&self.tooltips, widget, id)
+    }
+
     fn remove_pan_grab(&mut self, g: (u16, u16)) {
         if let Some(grab) = self.pan_grab.get_mut(g.0 as usize) {
             grab.n -= 1;
@@ -183,7 +433,7 @@ impl ManagerState {
                 return self.remove_pan(g.0 as usize);
             }
             assert!(grab.source_is_touch);
-            for i in (g.1 as usize)..(grab.n as usize - 1) {
+            for i in (g.1 as usize)..(grab.n as usize).min(MAX_PAN_GRABS - 1) {
                 grab.coords[i] = grab.coords[i + 1];
             }
         } else {
@@ -221,11 +471,52 @@ pub struct Manager<'a> {
 
 /// Internal methods
 impl<'a> Manager<'a> {
+    // Call f with activate_source set, restoring the previous value after
+    pub(super) fn with_activate_source<F, R>(&mut self, source: ActivateSource, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let prev = std::mem::replace(&mut self.mgr.activate_source, source);
+        let r = f(self);
+        self.mgr.activate_source = prev;
+        r
+    }
+
+    /// Get the auto-repeating press on widget `id`, if any
+    pub(super) fn press_repeat(&self, id: WidgetId) -> Option<(PressSource, Coord)> {
+        self.mgr
+            .press_repeats
+            .iter()
+            .find(|press| press.0 == id)
+            .map(|press| (press.1, press.2))
+    }
+
+    /// Check whether widget `id` is visually depressed
+    pub(super) fn is_depressed(&self, id: WidgetId) -> bool {
+        self.mgr.is_depressed(id)
+    }
+
+    /// Set or clear the auto-repeating press on widget `id`
+    pub(super) fn set_press_repeat(&mut self, id: WidgetId, press: Option<(PressSource, Coord)>) {
+        let repeats = &mut self.mgr.press_repeats;
+        repeats.retain(|press| press.0 != id);
+        if let Some((source, coord)) = press {
+            repeats.push((id, source, coord));
+        }
+    }
+
+    fn send_activate<W>(&mut self, widget: &mut W, id: WidgetId, source: ActivateSource)
+    where
+        W: Widget + ?Sized,
+    {
+        self.with_activate_source(source, |mgr| mgr.send_event(widget, id, Event::Activate));
+    }
+
     fn set_hover<W: Widget + ?Sized>(&mut self, widget: &mut W, w_id: Option<WidgetId>) {
         if self.mgr.hover != w_id {
             trace!("Manager: hover = {:?}", w_id);
             self.mgr.hover = w_id;
-            self.send_action(TkAction::Redraw);
+            self.send_action(TkAction::REDRAW);
 
             if let Some(id) = w_id {
                 let icon = widget
@@ -234,11 +525,21 @@ impl<'a> Manager<'a> {
                     .unwrap_or(CursorIcon::Default);
                 if icon != self.mgr.hover_icon {
                     self.mgr.hover_icon = icon;
-                    if self.mgr.mouse_grab.is_none() {
+                    if self.mgr.mouse_grab.is_none() && !self.mgr.help_mode {
                         self.tkw.set_cursor_icon(icon);
                     }
                 }
             }
+
+            let tooltip = w_id.and_then(|id| self.mgr.find_tooltip(widget.as_widget(), id));
+            if tooltip != self.mgr.tooltip {
+                if self.mgr.tooltip_shown {
+                    self.mgr.tooltip_shown = false;
+                    self.send_event(widget, widget.id(), Event::HideTooltip);
+                }
+                self.mgr.tooltip = tooltip;
+                self.mgr.tooltip_time = tooltip.map(|_| self.mgr.now() + TOOLTIP_DELAY);
+            }
         }
     }
 
@@ -247,10 +548,41 @@ impl<'a> Manager<'a> {
         W: Widget<Msg = VoidMsg> + ?Sized,
     {
         use VirtualKeyCode as VK;
+        if vkey == VK::F1 {
+            if self.mgr.modifiers.shift() {
+                self.enter_help_mode();
+            } else {
+                let focus = (self.mgr.char_focus.or(self.mgr.nav_focus)).unwrap_or(widget.id());
+                if let Some(id) = self.mgr.find_help(widget.as_widget(), focus) {
+                    self.send_event(widget, widget.id(), Event::HelpRequested(id));
+                }
+            }
+            return;
+        } else if vkey == VK::Escape && self.mgr.help_mode {
+            self.end_help_mode();
+            return;
+        }
+
+        // Escape closes pop-ups and clears focus first; only otherwise may it
+        // be used as a shortcut (e.g. to go "back")
+        let escape_used = vkey == VK::Escape
+            && (self.mgr.char_focus.is_some()
+                || self.mgr.nav_focus.is_some()
+                || !self.mgr.popups.is_empty());
+        let shortcut = Shortcut::new(self.mgr.modifiers, vkey);
+        if let Some(id) = self.mgr.shortcuts.get(&shortcut).cloned() {
+            if !escape_used {
+                if !self.mgr.is_locked_out(widget.as_widget(), id) {
+                    self.send_activate(widget, id, ActivateSource::Keyboard);
+                }
+                return;
+            }
+        }
+
         if let Some(id) = self.mgr.char_focus {
             if vkey == VK::Escape {
                 self.set_char_focus(None);
-            } else if let Some(key) = ControlKey::new(vkey) {
+            } else if let Some(key) = ControlKey::new_with_modifiers(vkey, self.mgr.modifiers) {
                 self.send_event(widget, id, Event::Control(key));
             }
             return;
@@ -278,7 +610,9 @@ impl<'a> Manager<'a> {
                 if let Some(nav_id) = self.mgr.nav_focus {
                     if vkey == VK::Space || vkey == VK::Return || vkey == VK::NumpadEnter {
                         id_action = Some((nav_id, Event::Activate));
-                    } else if let Some(nav_key) = ControlKey::new(vkey) {
+                    } else if let Some(nav_key) =
+                        ControlKey::new_with_modifiers(vkey, self.mgr.modifiers)
+                    {
                         id_action = Some((nav_id, Event::Control(nav_key)));
                     }
                 }
@@ -327,9 +661,15 @@ impl<'a> Manager<'a> {
                 }
             }
 
+            let id_action =
+                id_action.filter(|(id, _)| !self.mgr.is_locked_out(widget.as_widget(), *id));
             if let Some((id, event)) = id_action {
                 let is_activate = event == Event::Activate;
-                self.send_event(widget, id, event);
+                if is_activate {
+                    self.send_activate(widget, id, ActivateSource::Keyboard);
+                } else {
+                    self.send_event(widget, id, event);
+                }
 
                 // Event::Activate causes buttons to be visually depressed
                 if is_activate {
@@ -367,6 +707,61 @@ impl<'a> Manager<'a> {
         }
     }
 
+    fn end_help_mode(&mut self) {
+        trace!("Manager: end help mode");
+        self.mgr.help_mode = false;
+        self.tkw.set_cursor_icon(self.mgr.hover_icon);
+    }
+
+    /// Handle a press in *What's this?* mode
+    fn press_help<W: Widget + ?Sized>(&mut self, widget: &mut W, start_id: Option<WidgetId>) {
+        self.end_help_mode();
+        let id = start_id.and_then(|id| self.mgr.find_help(widget.as_widget(), id));
+        if let Some(id) = id {
+            self.send_event(widget, widget.id(), Event::ShowHelp(id));
+        }
+    }
+
+    /// Handle motion of the mouse cursor, possibly outside the window
+    fn cursor_moved<W: Widget + ?Sized>(&mut self, widget: &mut W, coord: Coord, pos: Vec2) {
+        // Update hovered widget
+        let cur_id = self.mgr.find_input_target(widget, coord);
+        let delta = coord - self.mgr.last_mouse_coord;
+        self.set_hover(widget, cur_id);
+
+        if let Some(grab) = self.mouse_grab() {
+            if grab.mode == GrabMode::Grab {
+                let source = PressSource::Mouse(grab.button);
+                let event = Event::PressMove {
+                    source,
+                    cur_id,
+                    coord,
+                    pos,
+                    delta,
+                };
+                self.send_event(widget, grab.start_id, event);
+            } else if let Some(pan) = self.mgr.pan_grab.get_mut(grab.pan_grab.0 as usize) {
+                pan.coords[grab.pan_grab.1 as usize].1 = coord;
+            }
+        } else if let Some(id) = self.mgr.popups.last().map(|(_, p)| p.parent) {
+            // Use a fake button!
+            let source = PressSource::Mouse(MouseButton::Other(0));
+            let event = Event::PressMove {
+                source,
+                cur_id,
+                coord,
+                pos,
+                delta,
+            };
+            self.send_event(widget, id, event);
+        } else {
+            // We don't forward move events without a grab
+        }
+
+        self.mgr.last_mouse_coord = coord;
+        self.mgr.last_mouse_pos = pos;
+    }
+
     fn mouse_grab(&self) -> Option<MouseGrab> {
         self.mgr.mouse_grab.clone()
     }
@@ -384,6 +779,9 @@ impl<'a> Manager<'a> {
         if let Some(grab) = self.mgr.mouse_grab.take() {
             trace!("Manager: end mouse grab by {}", grab.start_id);
             self.tkw.set_cursor_icon(self.mgr.hover_icon);
+            if grab.pointer_lock {
+                self.tkw.set_pointer_lock(false);
+            }
             self.redraw(grab.start_id);
             self.mgr.remove_pan_grab(grab.pan_grab);
         }
@@ -432,13 +830,29 @@ impl<'a> Manager<'a> {
 
     fn send_event<W: Widget + ?Sized>(&mut self, widget: &mut W, id: WidgetId, event: Event) {
         trace!("Send to {}: {:?}", id, event);
-        let _ = widget.send(self, id, event);
+        let _ = self.send_traced(widget, id, event);
+    }
+
+    fn send_traced<W: Widget + ?Sized>(
+        &mut self,
+        widget: &mut W,
+        id: WidgetId,
+        event: Event,
+    ) -> Response<W::Msg> {
+        let msg = (self.mgr.event_trace.as_ref())
+            .and_then(|trace| trace.path(widget.as_widget(), id, &event))
+            .map(|path| format!("Event trace: {:?} via {}", event, path));
+        let r = widget.send(self, id, event).apply_action(self);
+        if let Some(msg) = msg {
+            debug!("{} -> {}", msg, response_name(&r));
+        }
+        r
     }
 
     fn send_popup_first<W: Widget + ?Sized>(&mut self, widget: &mut W, id: WidgetId, event: Event) {
         while let Some((wid, parent)) = self.mgr.popups.last().map(|(wid, p)| (*wid, p.parent)) {
             trace!("Send to popup parent: {}: {:?}", parent, event);
-            match widget.send(self, parent, event.clone()) {
+            match self.send_traced(widget, parent, event.clone()) {
                 Response::Unhandled(_) => (),
                 _ => return,
             }
@@ -483,3 +897,52 @@ impl<'a: 'b, 'b> ConfigureManager<'a, 'b> {
         self.mgr
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{Script, Selector};
+    use crate::widget::{Label, Row, Window};
+
+    #[test]
+    fn trace_from_env() {
+        assert_eq!(EventTrace::parse("all"), EventTrace::all());
+        assert_eq!(EventTrace::parse("ALL"), EventTrace::all());
+        assert_eq!(
+            EventTrace::parse(" PressStart, ,PressEnd,"),
+            EventTrace::all()
+                .with_event("PressStart")
+                .with_event("PressEnd")
+        );
+
+        std::env::set_var("KAS_EVENT_TRACE", "Activate");
+        let trace = EventTrace::from_env();
+        std::env::remove_var("KAS_EVENT_TRACE");
+        assert_eq!(trace, Some(EventTrace::all().with_event("Activate")));
+        assert_eq!(EventTrace::from_env(), None);
+    }
+
+    #[test]
+    fn trace_filters() {
+        let row = Row::new(vec![Label::new("a"), Label::new("b")]);
+        let script = Script::new(Window::new("Trace", row));
+        let window = script.widget();
+        let a = Selector::nth("Label", 0).find(window).unwrap();
+        let b = Selector::nth("Label", 1).find(window).unwrap();
+
+        let path = EventTrace::all().path(window, a, &Event::Activate);
+        assert!(path.unwrap().ends_with(&format!("Label{}", a)));
+
+        let trace = EventTrace::all().with_event("PressStart");
+        assert_eq!(trace.path(window, a, &Event::Activate), None);
+        let trace = trace.with_event("Activate");
+        assert!(trace.path(window, a, &Event::Activate).is_some());
+
+        let trace = EventTrace::all().with_widget(a);
+        assert!(trace.path(window, a, &Event::Activate).is_some());
+        assert_eq!(trace.path(window, b, &Event::Activate), None);
+        // The filter matches events routed through the widget's ancestors
+        let trace = EventTrace::all().with_widget(window.id());
+        assert!(trace.path(window, b, &Event::Activate).is_some());
+    }
+}
@@ -6,17 +6,20 @@
 //! Event manager — public API
 
 use log::{debug, trace, warn};
+#[cfg(feature = "raw-window-handle")]
+use raw_window_handle::RawWindowHandle;
 use std::time::{Duration, Instant};
 use std::u16;
 
 use super::*;
-use crate::draw::SizeHandle;
-use crate::geom::Coord;
+use crate::draw::{ImageId, SizeHandle, ThemeMetrics};
+use crate::geom::{Coord, Rect, Size};
 use crate::string::{CowString, CowStringL};
 #[allow(unused)]
-use crate::WidgetConfig; // for doc-links
+use crate::{Layout, WidgetChildren, WidgetConfig}; // for doc-links
 use crate::{ThemeAction, ThemeApi, TkAction, WidgetId, WindowId};
 
+/// Add an action (see [`Manager::send_action`])
 impl<'a> std::ops::AddAssign<TkAction> for Manager<'a> {
     #[inline]
     fn add_assign(&mut self, action: TkAction) {
@@ -26,6 +29,12 @@ impl<'a> std::ops::AddAssign<TkAction> for Manager<'a> {
 
 /// Public API (around event manager state)
 impl ManagerState {
+    /// Get the event handling configuration
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     /// True when accelerator key labels should be shown
     ///
     /// (True when Alt is held.)
@@ -36,6 +45,23 @@ impl ManagerState {
         self.modifiers.alt()
     }
 
+    /// Get the state of keyboard modifiers (e.g. Ctrl)
+    #[inline]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Get the input which triggered the [`Event::Activate`] being handled
+    ///
+    /// This allows a widget to distinguish, for example, a click from a key
+    /// press (e.g. to decide whether to show a focus indicator, or where to
+    /// place a pop-up). While not handling an `Activate` event triggered by
+    /// input, this is [`ActivateSource::Other`].
+    #[inline]
+    pub fn activate_source(&self) -> ActivateSource {
+        self.activate_source
+    }
+
     /// Get whether this widget has a grab on character input
     #[inline]
     pub fn char_focus(&self, w_id: WidgetId) -> bool {
@@ -48,6 +74,28 @@ impl ManagerState {
         self.nav_focus == Some(w_id)
     }
 
+    /// Get the position of the focused widget, for focus tracking
+    ///
+    /// This finds the widget with character focus (or otherwise navigation
+    /// focus) under `widget` (usually the window) and returns its rect,
+    /// translated to window coordinates, together with the edit marker rect
+    /// if reported via [`Manager::set_caret_rect`].
+    ///
+    /// Toolkits may call this after each update and notify assistive tools
+    /// (e.g. screen magnifiers) of changes.
+    pub fn focus_rect(&self, widget: &dyn WidgetConfig) -> Option<FocusRect> {
+        let id = self.char_focus.or(self.nav_focus)?;
+        let rect = crate::widget::find_rect(widget, id)?;
+        let caret = self.caret.and_then(|(caret_id, caret)| {
+            if caret_id != id {
+                return None;
+            }
+            let offset = rect.pos - widget.find(id)?.rect().pos;
+            Some(caret + offset)
+        });
+        Some(FocusRect { id, rect, caret })
+    }
+
     /// Get whether the widget is under the mouse cursor
     #[inline]
     pub fn is_hovered(&self, w_id: WidgetId) -> bool {
@@ -57,29 +105,84 @@ impl ManagerState {
     /// Check whether the given widget is visually depressed
     #[inline]
     pub fn is_depressed(&self, w_id: WidgetId) -> bool {
-        for (_, id) in &self.key_depress {
-            if *id == w_id {
-                return true;
-            }
-        }
-        if self.mouse_grab.as_ref().and_then(|grab| grab.depress) == Some(w_id) {
-            return true;
-        }
-        for touch in &self.touch_grab {
-            if touch.depress == Some(w_id) {
-                return true;
-            }
-        }
-        false
+        self.depressed().any(|id| id == w_id)
+    }
+
+    /// Get whether `widget` or any descendant has character focus
+    ///
+    /// This and the following `*_within` methods allow composite widgets
+    /// (e.g. a list row) to style themselves according to the state of their
+    /// descendants. See also [`WidgetChildren::input_state_within`].
+    pub fn char_focus_within(&self, widget: &dyn WidgetConfig) -> bool {
+        (self.char_focus)
+            .map(|id| widget.is_ancestor_of(id))
+            .unwrap_or(false)
+    }
+
+    /// Get whether `widget` or any descendant has keyboard focus
+    pub fn nav_focus_within(&self, widget: &dyn WidgetConfig) -> bool {
+        (self.nav_focus)
+            .map(|id| widget.is_ancestor_of(id))
+            .unwrap_or(false)
+    }
+
+    /// Get whether `widget` or any descendant is under the mouse cursor
+    pub fn is_hovered_within(&self, widget: &dyn WidgetConfig) -> bool {
+        self.mouse_grab.is_none()
+            && (self.hover)
+                .map(|id| widget.is_ancestor_of(id))
+                .unwrap_or(false)
+    }
+
+    /// Check whether `widget` or any descendant is visually depressed
+    pub fn is_depressed_within(&self, widget: &dyn WidgetConfig) -> bool {
+        self.depressed().any(|id| widget.is_ancestor_of(id))
+    }
+
+    // Iterate over all depressed widgets
+    fn depressed<'b>(&'b self) -> impl Iterator<Item = WidgetId> + 'b {
+        let keys = self.key_depress.iter().map(|(_, id)| *id);
+        let mouse = self.mouse_grab.as_ref().and_then(|grab| grab.depress);
+        let touches = self.touch_grab.iter().filter_map(|touch| touch.depress);
+        keys.chain(mouse).chain(touches)
+    }
+
+    /// Get data of type `T` attached to this window, if any
+    ///
+    /// See [`Manager::window_data`].
+    pub fn window_data<T: 'static>(&self) -> Option<&T> {
+        let data = self.window_data.get(&TypeId::of::<T>())?;
+        data.downcast_ref()
     }
 }
 
 /// Public API (around toolkit functionality)
 impl<'a> Manager<'a> {
-    /// Schedule an update
+    /// Get the state of keyboard modifiers (e.g. Ctrl)
+    #[inline]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.mgr.modifiers
+    }
+
+    /// Adjust a scroll delta for the Shift+wheel convention
+    ///
+    /// If [`Config::shift_scroll_horizontal`] is enabled and Shift is held,
+    /// this returns [`ScrollDelta::swap_axes`], otherwise `delta`. Scrollable
+    /// widgets should call this on receiving [`Event::Scroll`].
+    pub fn scroll_delta(&self, delta: ScrollDelta) -> ScrollDelta {
+        if self.mgr.config.shift_scroll_horizontal && self.mgr.modifiers.shift() {
+            delta.swap_axes()
+        } else {
+            delta
+        }
+    }
+
+    /// Schedule a one-shot update
     ///
     /// Widgets requiring animation should schedule an update; as a result,
     /// [`Event::TimerUpdate`] will be sent, roughly at time `now + duration`.
+    /// If an update is already scheduled for this widget, the earlier of the
+    /// two times is used.
     ///
     /// Timings may be a few ms out, but should be sufficient for e.g. updating
     /// a clock each second. Very short positive durations (e.g. 1ns) may be
@@ -89,25 +192,67 @@ impl<'a> Manager<'a> {
     /// This may be called from [`WidgetConfig::configure`] or from an event
     /// handler. Note that previously-scheduled updates are cleared when
     /// widgets are reconfigured.
+    pub fn update_after(&mut self, duration: Duration, w_id: WidgetId) {
+        self.mgr.schedule_update(self.mgr.now() + duration, w_id);
+    }
+
+    /// Schedule a one-shot update
+    ///
+    /// This is the former name of [`Manager::update_after`].
+    #[deprecated(note = "use update_after")]
+    #[inline]
     pub fn update_on_timer(&mut self, duration: Duration, w_id: WidgetId) {
-        let time = Instant::now() + duration;
-        'outer: loop {
-            for row in &mut self.mgr.time_updates {
-                if row.1 == w_id {
-                    if row.0 <= time {
-                        return;
-                    } else {
-                        row.0 = time;
-                        break 'outer;
-                    }
-                }
-            }
+        self.update_after(duration, w_id);
+    }
 
-            self.mgr.time_updates.push((time, w_id));
-            break;
+    /// Schedule repeating updates
+    ///
+    /// [`Event::TimerUpdate`] will be sent roughly every `interval`, starting
+    /// at `now + interval`, until cancelled via [`Manager::cancel_updates`].
+    /// Updates are scheduled relative to the previous due time, thus do not
+    /// drift; updates missed entirely (e.g. while the application is busy)
+    /// are skipped, not queued. Calling this again replaces the interval.
+    ///
+    /// A one-shot update (see [`Manager::update_after`]) for the same widget
+    /// is merged with the next repeat: only one event is sent, at the earlier
+    /// time. As with one-shot updates, repeats are cleared when widgets are
+    /// reconfigured.
+    ///
+    /// Panics if `interval` is zero.
+    pub fn update_every(&mut self, interval: Duration, w_id: WidgetId) {
+        assert!(
+            interval > Duration::from_secs(0),
+            "update_every: zero interval"
+        );
+        let repeats = &mut self.mgr.time_repeats;
+        match repeats.iter_mut().find(|row| row.0 == w_id) {
+            Some(row) => row.1 = interval,
+            None => repeats.push((w_id, interval)),
         }
+        self.mgr.schedule_update(self.mgr.now() + interval, w_id);
+    }
 
-        self.mgr.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
+    /// Cancel timer updates
+    ///
+    /// This cancels both one-shot and repeating updates scheduled for the
+    /// widget (see [`Manager::update_after`], [`Manager::update_every`]).
+    pub fn cancel_updates(&mut self, w_id: WidgetId) {
+        self.mgr.time_updates.retain(|(_, id)| *id != w_id);
+        self.mgr.time_repeats.retain(|(id, _)| *id != w_id);
+    }
+
+    /// Request [`Event::Frame`] before the next frame is drawn
+    ///
+    /// This schedules a redraw. The widget receives the event once per
+    /// request; animations should request again from the event handler.
+    ///
+    /// Unlike [`Manager::update_after`], this synchronises updates with
+    /// display refresh.
+    pub fn request_frame(&mut self, w_id: WidgetId) {
+        if !self.mgr.frame_updates.contains(&w_id) {
+            self.mgr.frame_updates.push(w_id);
+        }
+        self.send_action(TkAction::REDRAW);
     }
 
     /// Subscribe to an update handle
@@ -133,29 +278,56 @@ impl<'a> Manager<'a> {
     pub fn redraw(&mut self, _id: WidgetId) {
         // Theoretically, notifying by WidgetId allows selective redrawing
         // (damage events). This is not yet implemented.
-        self.send_action(TkAction::Redraw);
+        self.send_action(TkAction::REDRAW);
     }
 
     /// Notify that a [`TkAction`] action should happen
     ///
-    /// This causes the given action to happen after event handling.
+    /// This causes the given action to happen after event handling. Multiple
+    /// actions are combined (see [`TkAction`]), thus e.g. a `REDRAW` and a
+    /// `RECONFIGURE` sent while handling the same event result in a single
+    /// reconfigure. This is equivalent to `*mgr += action`.
     ///
     /// Whenever a widget is added, removed or replaced, a reconfigure action is
     /// required. Should a widget's size requirements change, these will only
     /// affect the UI after a reconfigure action.
     #[inline]
     pub fn send_action(&mut self, action: TkAction) {
-        self.action = self.action.max(action);
+        self.action |= action;
+    }
+
+    /// Notify that a [`TkAction`] should happen in another window
+    ///
+    /// This is like [`Manager::send_action`], except that the action applies
+    /// to the window `id` (which may be the current window). If `id` refers to
+    /// a pop-up, the action applies to the pop-up's parent window; to close a
+    /// pop-up use [`Manager::close_window`] instead.
+    ///
+    /// The action is delivered after handling of the current event completes.
+    /// Unknown window identifiers are ignored.
+    #[inline]
+    pub fn send_action_to(&mut self, id: WindowId, action: TkAction) {
+        self.tkw.send_action_to(Some(id), action);
+    }
+
+    /// Notify that a [`TkAction`] should happen in all windows
+    ///
+    /// This may be used e.g. by a preferences dialog to reconfigure all
+    /// windows. Like [`Manager::send_action_to`], the action is delivered
+    /// after handling of the current event completes.
+    #[inline]
+    pub fn send_action_all(&mut self, action: TkAction) {
+        self.tkw.send_action_to(None, action);
     }
 
-    /// Get the current [`TkAction`], replacing with `None`
+    /// Get the current [`TkAction`], replacing with the empty set
     ///
     /// The caller is responsible for ensuring the action is handled correctly;
     /// generally this means matching only actions which can be handled locally
     /// and downgrading the action, adding the result back to the [`Manager`].
     pub fn pop_action(&mut self) -> TkAction {
         let action = self.action;
-        self.action = TkAction::None;
+        self.action = TkAction::empty();
         action
     }
 
@@ -180,6 +352,36 @@ impl<'a> Manager<'a> {
         id
     }
 
+    /// Add an overlay
+    ///
+    /// An overlay is a widget drawn over window content, anchored to another
+    /// widget; see [`kas::Overlay`]. Unlike a pop-up it does not take focus and
+    /// is only removed via [`Manager::remove_overlay`]. Adding an overlay whose
+    /// widget is already shown as an overlay updates its anchor and placement.
+    ///
+    /// The request is handled by the window after the current event.
+    #[inline]
+    pub fn add_overlay(&mut self, overlay: kas::Overlay) {
+        self.mgr.overlay_events.push(Event::ShowOverlay(overlay));
+    }
+
+    /// Remove the overlay showing widget `id`, if any
+    #[inline]
+    pub fn remove_overlay(&mut self, id: WidgetId) {
+        self.mgr.overlay_events.push(Event::HideOverlay(id));
+    }
+
+    /// Get the raw handle of the native window, if any
+    ///
+    /// This allows external renderers (e.g. video players or OpenGL contexts)
+    /// to draw into the window; see [`kas::widget::ExternalSurface`].
+    /// Toolkits not drawing to a native window return `None`.
+    #[cfg(feature = "raw-window-handle")]
+    #[inline]
+    pub fn raw_window_handle(&self) -> Option<RawWindowHandle> {
+        self.tkw.raw_window_handle()
+    }
+
     /// Add a window
     ///
     /// Typically an application adds at least one window before the event-loop
@@ -217,7 +419,7 @@ impl<'a> Manager<'a> {
             }
         }
 
-        self.mgr.send_action(TkAction::RegionMoved);
+        self.mgr.send_action(TkAction::REGION_MOVED);
         self.tkw.close_window(id);
     }
 
@@ -227,7 +429,21 @@ impl<'a> Manager<'a> {
     /// windows, will receive an update.
     #[inline]
     pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
-        self.tkw.trigger_update(handle, payload);
+        self.tkw.trigger_update(handle, payload, None);
+    }
+
+    /// Update all subscribed widgets, passing typed data
+    ///
+    /// This is like [`Manager::trigger_update`], but additionally passes
+    /// `data` via [`Event::HandleUpdate`].
+    pub fn trigger_update_data<T: Any + Send + Sync>(
+        &mut self,
+        handle: UpdateHandle,
+        payload: u64,
+        data: T,
+    ) {
+        let data = Some(UpdateData::new(data));
+        self.tkw.trigger_update(handle, payload, data);
     }
 
     /// Attempt to get clipboard contents
@@ -245,12 +461,66 @@ impl<'a> Manager<'a> {
         self.tkw.set_clipboard(content)
     }
 
+    /// Attempt to get the primary selection contents
+    ///
+    /// Widgets supporting paste should paste from the primary selection on
+    /// middle-click. This returns `None` unless enabled by
+    /// [`Config::primary_selection`].
+    #[inline]
+    pub fn get_primary(&mut self) -> Option<CowString> {
+        if self.mgr.config.primary_selection {
+            self.tkw.get_primary()
+        } else {
+            None
+        }
+    }
+
+    /// Attempt to set the primary selection contents
+    ///
+    /// Widgets supporting text selection should call this whenever the user
+    /// selects text. This does nothing unless enabled by
+    /// [`Config::primary_selection`].
+    #[inline]
+    pub fn set_primary<'c>(&mut self, content: CowStringL<'c>) {
+        if self.mgr.config.primary_selection {
+            self.tkw.set_primary(content)
+        }
+    }
+
     /// Adjust the theme
     #[inline]
     pub fn adjust_theme<F: FnMut(&mut dyn ThemeApi) -> ThemeAction>(&mut self, mut f: F) {
         self.tkw.adjust_theme(&mut f);
     }
 
+    /// Load an image
+    ///
+    /// The image is specified as 8-bit sRGBA pixel data (four bytes per pixel)
+    /// in row-major order; see [`crate::draw::DrawShared::load_image`].
+    /// Returns `None` if the toolkit does not support images or if the image
+    /// would exceed the toolkit's memory limit.
+    ///
+    /// Images are shared by all windows and should be freed via
+    /// [`Manager::remove_image`] when no longer needed.
+    #[inline]
+    pub fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        self.tkw.load_image(size, data)
+    }
+
+    /// Free an image
+    #[inline]
+    pub fn remove_image(&mut self, id: ImageId) {
+        self.tkw.remove_image(id);
+    }
+
+    /// Approximate memory used by resources shared between windows, in bytes
+    ///
+    /// This includes images loaded via [`Manager::load_image`].
+    #[inline]
+    pub fn memory_usage(&self) -> u64 {
+        self.tkw.memory_usage()
+    }
+
     /// Access a [`SizeHandle`]
     pub fn size_handle<F: FnMut(&mut dyn SizeHandle) -> T, T>(&mut self, mut f: F) -> T {
         let mut result = None;
@@ -259,10 +529,139 @@ impl<'a> Manager<'a> {
         });
         result.expect("TkWindow::size_handle_dyn impl failed to call function argument")
     }
+
+    /// Get the resolved metrics of the active theme
+    ///
+    /// This is a convenience wrapper around [`SizeHandle::metrics`].
+    pub fn theme_metrics(&mut self) -> ThemeMetrics {
+        self.size_handle(|h| h.metrics())
+    }
 }
 
 /// Public API (around event manager state)
 impl<'a> Manager<'a> {
+    /// Access data of type `T` attached to this window
+    ///
+    /// Each window may have at most one value of each type attached. If no
+    /// value of type `T` is attached, `T::default()` is attached first.
+    ///
+    /// This allows widgets of a window to share state without passing it
+    /// through every widget constructor. The data persists for the lifetime
+    /// of the window; it is not shared with other windows (including pop-ups,
+    /// which belong to their parent window).
+    pub fn window_data<T: Default + 'static>(&mut self) -> &mut T {
+        self.mgr
+            .window_data
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()))
+            .downcast_mut()
+            .unwrap()
+    }
+
+    /// Attach data of type `T` to this window
+    ///
+    /// Returns the previous value of type `T`, if any.
+    /// See [`Manager::window_data`].
+    pub fn set_window_data<T: 'static>(&mut self, data: T) -> Option<T> {
+        let prev = self
+            .mgr
+            .window_data
+            .insert(TypeId::of::<T>(), Box::new(data));
+        prev.and_then(|prev| prev.downcast().ok()).map(|prev| *prev)
+    }
+
+    /// Remove data of type `T` from this window
+    ///
+    /// See [`Manager::window_data`].
+    pub fn take_window_data<T: 'static>(&mut self) -> Option<T> {
+        let data = self.mgr.window_data.remove(&TypeId::of::<T>())?;
+        data.downcast().ok().map(|data| *data)
+    }
+
+    /// Notify `widget` and its descendants of being shown or hidden
+    ///
+    /// This calls [`WidgetConfig::shown`] (if `visible`) or
+    /// [`WidgetConfig::hidden`] on each widget, children before parents.
+    pub fn notify_visibility<W>(&mut self, widget: &mut W, visible: bool)
+    where
+        W: WidgetConfig + ?Sized,
+    {
+        widget.walk_mut_dyn(&mut |w| match visible {
+            true => w.shown(self),
+            false => w.hidden(self),
+        });
+    }
+
+    /// Destroy `widget` and its descendants
+    ///
+    /// This calls [`WidgetConfig::destroy`] on each widget, children before
+    /// parents, then cancels timer, frame and handle updates registered by
+    /// these widgets and releases any focus or grab held by them.
+    pub fn destroy<W: WidgetConfig + ?Sized>(&mut self, widget: &mut W) {
+        let mut ids = vec![];
+        widget.walk_mut_dyn(&mut |w| {
+            w.destroy(self);
+            ids.push(w.id());
+        });
+        trace!("Manager: destroyed {} widgets", ids.len());
+
+        let mgr = &mut *self.mgr;
+        mgr.time_updates.retain(|(_, id)| !ids.contains(id));
+        mgr.time_repeats.retain(|(id, _)| !ids.contains(id));
+        mgr.frame_updates.retain(|id| !ids.contains(id));
+        for list in mgr.handle_updates.values_mut() {
+            list.retain(|id| !ids.contains(id));
+        }
+        mgr.pending
+            .retain(|Pending::LostCharFocus(id)| !ids.contains(id));
+        if mgr.char_focus.map(|id| ids.contains(&id)).unwrap_or(false) {
+            mgr.char_focus = None;
+        }
+        if mgr.nav_focus.map(|id| ids.contains(&id)).unwrap_or(false) {
+            mgr.nav_focus = None;
+        }
+        if mgr
+            .nav_fallback
+            .map(|id| ids.contains(&id))
+            .unwrap_or(false)
+        {
+            mgr.nav_fallback = None;
+        }
+        mgr.key_depress.retain(|(_, id)| !ids.contains(id));
+        mgr.press_repeats.retain(|(id, _, _)| !ids.contains(id));
+
+        if let Some(button) =
+            mgr.mouse_grab
+                .as_ref()
+                .and_then(|grab| match ids.contains(&grab.start_id) {
+                    true => Some(grab.button),
+                    false => None,
+                })
+        {
+            self.end_mouse_grab(button);
+        }
+        let touches: SmallVec<[u64; 10]> = self
+            .mgr
+            .touch_grab
+            .iter()
+            .filter(|grab| ids.contains(&grab.start_id))
+            .map(|grab| grab.touch_id)
+            .collect();
+        for touch_id in touches {
+            if let Some(grab) = self.remove_touch(touch_id) {
+                self.mgr.remove_pan_grab(grab.pan_grab);
+            }
+        }
+        let mut i = 0;
+        while i < self.mgr.pan_grab.len() {
+            if ids.contains(&self.mgr.pan_grab[i].id) {
+                self.mgr.remove_pan(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
     /// Attempts to set a fallback to receive [`Event::Control`]
     ///
     /// In case a navigation key is pressed (see [`ControlKey`]) but no widget has
@@ -353,6 +752,74 @@ impl<'a> Manager<'a> {
         }
     }
 
+    /// Adds a keyboard shortcut for a widget
+    ///
+    /// When the [`Shortcut`] is pressed (with exactly the given modifiers),
+    /// the widget with this `id` receives [`Event::Activate`]. Shortcuts take
+    /// priority over all other key handling except for help (F1).
+    ///
+    /// Each shortcut may be bound to only one widget; the first registration
+    /// wins.
+    ///
+    /// This should only be called from [`WidgetConfig::configure`].
+    pub fn add_shortcut(&mut self, id: WidgetId, shortcut: Shortcut) {
+        if !self.read_only {
+            self.mgr.shortcuts.entry(shortcut).or_insert(id);
+        }
+    }
+
+    /// Register context help for a widget
+    ///
+    /// Help applies to widget `id` and its descendants, except those with
+    /// their own help; see [`Help`].
+    ///
+    /// This should only be called from [`WidgetConfig::configure`].
+    pub fn register_help(&mut self, id: WidgetId, help: Help) {
+        if !self.read_only {
+            self.mgr.help.insert(id, help);
+        }
+    }
+
+    /// Get the help registered for widget `id`, if any
+    ///
+    /// Note that this does not search ancestors of `id`.
+    pub fn help(&self, id: WidgetId) -> Option<&Help> {
+        self.mgr.help.get(&id)
+    }
+
+    /// Register a tooltip for a widget
+    ///
+    /// The tooltip is shown (via [`Event::ShowTooltip`]) when the mouse hovers
+    /// over widget `id` or its descendants (except those with their own
+    /// tooltip) for a short time.
+    ///
+    /// This should only be called from [`WidgetConfig::configure`].
+    pub fn register_tooltip(&mut self, id: WidgetId, text: CowString) {
+        if !self.read_only {
+            self.mgr.tooltips.insert(id, text);
+        }
+    }
+
+    /// Get the tooltip registered for widget `id`, if any
+    ///
+    /// Note that this does not search ancestors of `id`.
+    pub fn tooltip(&self, id: WidgetId) -> Option<&CowString> {
+        self.mgr.tooltips.get(&id)
+    }
+
+    /// Enter *What's this?* mode
+    ///
+    /// In this mode, the cursor is [`CursorIcon::Help`] and the next click
+    /// requests help for the clicked widget via [`Event::ShowHelp`] instead of
+    /// being delivered normally. This mode is also entered via Shift+F1.
+    pub fn enter_help_mode(&mut self) {
+        if !self.read_only {
+            trace!("Manager: enter help mode");
+            self.mgr.help_mode = true;
+            self.tkw.set_cursor_icon(CursorIcon::Help);
+        }
+    }
+
     /// Request character-input focus
     ///
     /// If successful, [`Event::ReceivedCharacter`] events are sent to this
@@ -427,6 +894,7 @@ impl<'a> Manager<'a> {
                     button,
                     mode,
                     pan_grab,
+                    pointer_lock: false,
                 });
                 if let Some(icon) = cursor {
                     self.tkw.set_cursor_icon(icon);
@@ -484,7 +952,87 @@ impl<'a> Manager<'a> {
                 }
             }
         }
-        self.mgr.send_action(TkAction::Redraw);
+        self.mgr.send_action(TkAction::REDRAW);
+    }
+
+    /// Lock the mouse pointer for the duration of a mouse grab
+    ///
+    /// This may be called by the widget `id` owning a mouse grab
+    /// ([`Manager::request_grab`]). While locked, the cursor is hidden and
+    /// confined to the window, and the widget additionally receives
+    /// [`Event::PointerMotion`] events reporting raw relative motion. The lock
+    /// is released automatically when the grab ends.
+    ///
+    /// Returns false if `id` does not own a mouse grab or if the toolkit does
+    /// not support pointer locking.
+    pub fn lock_pointer(&mut self, id: WidgetId) -> bool {
+        match self.mgr.mouse_grab.as_ref() {
+            Some(grab) if grab.start_id == id && !self.read_only => (),
+            _ => return false,
+        }
+        if !self.tkw.set_pointer_lock(true) {
+            return false;
+        }
+        trace!("Manager: lock pointer for {}", id);
+        if let Some(grab) = self.mgr.mouse_grab.as_mut() {
+            grab.pointer_lock = true;
+        }
+        true
+    }
+
+    /// Enable or disable event tracing
+    ///
+    /// When enabled, events matching the filter are logged (at `debug` level)
+    /// with their routing path and the resulting [`Response`]. This is intended
+    /// for debugging, e.g. to find out why some widget doesn't receive a click.
+    /// See [`EventTrace`].
+    ///
+    /// Note: the filter applies to the current window only. A filter on a
+    /// widget is updated when widgets are reconfigured; if the widget no
+    /// longer exists, tracing is disabled.
+    pub fn set_event_trace(&mut self, trace: Option<EventTrace>) {
+        debug!("Manager: event_trace = {:?}", trace);
+        self.mgr.event_trace = trace;
+    }
+
+    /// Restrict input to a subtree of widgets
+    ///
+    /// While set, pointer, touch, keyboard and gamepad input is only delivered
+    /// to `id` and its descendants (and to pop-ups); other widgets are not
+    /// hovered and may not be activated via shortcuts or accelerator keys.
+    /// Keyboard navigation is likewise restricted to this subtree. This is
+    /// intended for kiosk-style applications and for modal interaction.
+    ///
+    /// Setting or clearing the lock clears keyboard navigation and character
+    /// focus. Use `None` to remove the lock.
+    ///
+    /// Note: the lock applies to the current window only.
+    pub fn set_input_lock(&mut self, id: Option<WidgetId>) {
+        if id == self.mgr.input_lock {
+            return;
+        }
+        debug!("Manager: input_lock = {:?}", id);
+        self.mgr.input_lock = id;
+        self.set_char_focus(None);
+        self.clear_nav_focus();
+        // Update hovered widget
+        self.send_action(TkAction::REGION_MOVED);
+    }
+
+    /// Report the position of the edit marker (caret)
+    ///
+    /// Text widgets should call this when the edit marker moves, with `rect`
+    /// in the widget's coordinate space (as used by [`Layout::set_rect`]).
+    /// This is used for focus tracking (see [`ManagerState::focus_rect`]).
+    pub fn set_caret_rect(&mut self, id: WidgetId, rect: Rect) {
+        self.mgr.caret = Some((id, rect));
+    }
+
+    /// Get the widget to which input is restricted, if any
+    ///
+    /// See [`Manager::set_input_lock`].
+    pub fn input_lock(&self) -> Option<WidgetId> {
+        self.mgr.input_lock
     }
 
     /// Get the current keyboard navigation focus, if any
@@ -527,7 +1075,8 @@ impl<'a> Manager<'a> {
         type WidgetStack<'b> = SmallVec<[&'b dyn WidgetConfig; 16]>;
         let mut widget_stack = WidgetStack::new();
 
-        if let Some(id) = self.mgr.popups.last().map(|(_, p)| p.id) {
+        let popup = self.mgr.popups.last().map(|(_, p)| p.id);
+        if let Some(id) = popup.or(self.mgr.input_lock) {
             if let Some(w) = widget.find(id) {
                 widget = w;
             } else {
@@ -659,7 +1208,7 @@ impl<'a> Manager<'a> {
 
         // We redraw in all cases. Since this is not part of widget event
         // processing, we can push directly to self.mgr.action.
-        self.mgr.send_action(TkAction::Redraw);
+        self.mgr.send_action(TkAction::REDRAW);
         let nav_stack = &mut self.mgr.nav_stack;
 
         if !reverse {
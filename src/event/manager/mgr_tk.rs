@@ -8,10 +8,12 @@
 use log::*;
 use smallvec::SmallVec;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use super::*;
-use crate::geom::{Coord, DVec2};
+use crate::event::events::is_command;
+use crate::geom::{Coord, DVec2, Vec2};
 #[allow(unused)]
 use crate::WidgetConfig; // for doc-links
 use crate::{TkAction, TkWindow, Widget, WidgetId};
@@ -25,6 +27,7 @@ impl ManagerState {
     #[inline]
     pub fn new(dpi_factor: f64) -> Self {
         ManagerState {
+            config: Default::default(),
             end_id: Default::default(),
             dpi_factor,
             modifiers: ModifiersState::empty(),
@@ -36,23 +39,51 @@ impl ManagerState {
             hover_icon: CursorIcon::Default,
             key_depress: Default::default(),
             last_mouse_coord: Coord::ZERO,
+            last_mouse_pos: Vec2::ZERO,
+            cursor_left: false,
             mouse_grab: None,
             touch_grab: Default::default(),
             pan_grab: SmallVec::new(),
             accel_stack: vec![],
             accel_layers: HashMap::new(),
+            shortcuts: HashMap::new(),
             popups: Default::default(),
             new_popups: Default::default(),
             popup_removed: Default::default(),
+            overlay_events: Default::default(),
+            file_events: vec![],
+            files_target: None,
+            help: HashMap::new(),
+            help_mode: false,
+            tooltips: HashMap::new(),
+            tooltip: None,
+            tooltip_time: None,
+            tooltip_shown: false,
+            gamepad_stick: (0, 0),
+            input_lock: None,
+            activate_source: ActivateSource::Other,
+            press_repeats: SmallVec::new(),
+            caret: None,
+            window_data: HashMap::new(),
 
             time_start: Instant::now(),
+            virtual_time: None,
             time_updates: vec![],
+            time_repeats: vec![],
+            frame_updates: SmallVec::new(),
             handle_updates: HashMap::new(),
             pending: SmallVec::new(),
-            action: TkAction::None,
+            action: TkAction::empty(),
+            event_trace: EventTrace::from_env(),
         }
     }
 
+    /// Set the event handling configuration
+    #[inline]
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
     /// Configure event manager for a widget tree.
     ///
     /// This should be called by the toolkit on the widget tree when the window
@@ -62,7 +93,7 @@ impl ManagerState {
         W: Widget<Msg = VoidMsg> + ?Sized,
     {
         trace!("Manager::configure");
-        self.action = TkAction::None;
+        self.action = TkAction::empty();
 
         // Re-assigning WidgetIds might invalidate state; to avoid this we map
         // existing ids to new ids
@@ -72,7 +103,14 @@ impl ManagerState {
         // We re-set these instead of remapping:
         self.accel_stack.clear();
         self.accel_layers.clear();
+        self.shortcuts.clear();
+        self.help.clear();
+        self.tooltips.clear();
+        self.tooltip = None;
+        self.tooltip_time = None;
         self.time_updates.clear();
+        self.time_repeats.clear();
+        self.frame_updates.clear();
         self.handle_updates.clear();
         self.pending.clear();
         self.nav_fallback = None;
@@ -89,11 +127,11 @@ impl ManagerState {
             mgr.pop_accel_layer(widget.id());
             debug_assert!(mgr.mgr.accel_stack.is_empty());
 
-            let hover = widget.find_id(coord);
+            let hover = mgr.mgr.find_input_target(widget, coord);
             mgr.set_hover(widget, hover);
         });
-        if self.action == TkAction::Reconfigure {
-            warn!("Detected TkAction::Reconfigure during configure. This may cause a reconfigure-loop.");
+        if self.action.contains(TkAction::RECONFIGURE) {
+            warn!("Detected TkAction::RECONFIGURE during configure. This may cause a reconfigure-loop.");
             if id == self.end_id {
                 panic!("Reconfigure occurred with the same number of widgets — we are probably stuck in a reconfigure-loop.");
             }
@@ -104,6 +142,18 @@ impl ManagerState {
 
         self.char_focus = self.char_focus.and_then(|id| map.get(&id).cloned());
         self.nav_focus = self.nav_focus.and_then(|id| map.get(&id).cloned());
+        self.input_lock = self.input_lock.and_then(|id| map.get(&id).cloned());
+        self.caret = (self.caret).and_then(|(id, rect)| map.get(&id).map(|id| (*id, rect)));
+        if let Some(old_id) = self.event_trace.as_ref().and_then(|trace| trace.widget) {
+            // Never widen a filtered trace: disable it if the widget is gone
+            match map.get(&old_id) {
+                Some(id) => self.event_trace.as_mut().unwrap().widget = Some(*id),
+                None => {
+                    warn!("Event trace: widget {} removed; disabling trace", old_id);
+                    self.event_trace = None;
+                }
+            }
+        }
         self.mouse_grab = self.mouse_grab.as_ref().and_then(|grab| {
             map.get(&grab.start_id).map(|id| MouseGrab {
                 start_id: *id,
@@ -111,6 +161,7 @@ impl ManagerState {
                 button: grab.button,
                 mode: grab.mode,
                 pan_grab: grab.pan_grab,
+                pointer_lock: grab.pointer_lock,
             })
         });
 
@@ -156,6 +207,12 @@ impl ManagerState {
         do_map!(self.key_depress, |elt: (u32, WidgetId)| map
             .get(&elt.1)
             .map(|id| (elt.0, *id)));
+
+        // Timer updates were cleared above, thus these presses no longer
+        // repeat, but we remember them to avoid activation on release.
+        do_map!(self.press_repeats, |elt: (WidgetId, PressSource, Coord)| {
+            map.get(&elt.0).map(|id| (*id, elt.1, elt.2))
+        });
     }
 
     /// Update the widgets under the cursor and touch events
@@ -171,11 +228,12 @@ impl ManagerState {
             .and_then(|id| widget.find(id).map(|w| w.id()));
 
         // Update hovered widget
-        let hover = widget.find_id(self.last_mouse_coord);
+        let hover = self.find_input_target(widget, self.last_mouse_coord);
         self.with(tkw, |mgr| mgr.set_hover(widget, hover));
 
-        for touch in &mut self.touch_grab {
-            touch.cur_id = widget.find_id(touch.coord);
+        for i in 0..self.touch_grab.len() {
+            let coord = self.touch_grab[i].coord;
+            self.touch_grab[i].cur_id = self.find_input_target(widget, coord);
         }
     }
 
@@ -186,9 +244,40 @@ impl ManagerState {
         self.dpi_factor = dpi_factor;
     }
 
+    /// Use a virtual clock in place of the system clock
+    ///
+    /// Timer updates (see [`Manager::update_after`]) and tooltip delays are
+    /// then measured against a clock which starts at the current time and
+    /// advances only via [`ManagerState::advance_clock`]. This is intended for
+    /// deterministic tests (see [`crate::test::Script::wait`]).
+    pub fn use_virtual_clock(&mut self) {
+        self.virtual_time = Some(Instant::now());
+    }
+
+    /// Advance the virtual clock by `duration`
+    ///
+    /// This does not deliver updates now due; the toolkit should call
+    /// [`Manager::update_timer`] after. Panics unless
+    /// [`ManagerState::use_virtual_clock`] was called.
+    pub fn advance_clock(&mut self, duration: Duration) {
+        let time = self.virtual_time.as_mut();
+        *time.expect("advance_clock: virtual clock not enabled") += duration;
+    }
+
     /// Get the next resume time
     pub fn next_resume(&self) -> Option<Instant> {
-        self.time_updates.last().map(|time| time.0)
+        let next = self.time_updates.last().map(|time| time.0);
+        match (next, self.tooltip_time) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// True if any widget requested [`Event::Frame`]
+    ///
+    /// If so, the toolkit should call [`Manager::update_frame`] before drawing.
+    pub fn frame_requested(&self) -> bool {
+        !self.frame_updates.is_empty()
     }
 
     /// Set an action
@@ -197,7 +286,7 @@ impl ManagerState {
     /// available to do this job: `*mgr += action;`.
     #[inline]
     pub fn send_action(&mut self, action: TkAction) {
-        self.action = self.action.max(action);
+        self.action |= action;
     }
 
     /// Construct a [`Manager`] referring to this state
@@ -212,7 +301,7 @@ impl ManagerState {
             read_only: false,
             mgr: self,
             tkw,
-            action: TkAction::None,
+            action: TkAction::empty(),
         };
         f(&mut mgr);
         let action = mgr.action;
@@ -229,7 +318,7 @@ impl ManagerState {
             read_only: false,
             mgr: self,
             tkw,
-            action: TkAction::None,
+            action: TkAction::empty(),
         };
 
         while let Some((parent, wid)) = mgr.mgr.popup_removed.pop() {
@@ -246,6 +335,29 @@ impl ManagerState {
                 mgr.send_event(widget, parent, Event::NewPopup(id));
             }
         }
+        for event in std::mem::take(&mut mgr.mgr.overlay_events) {
+            mgr.send_event(widget, widget.id(), event);
+        }
+
+        for event in std::mem::take(&mut mgr.mgr.file_events) {
+            let id = match event {
+                Event::FilesHover(_) => {
+                    let id = mgr.mgr.files_target(widget);
+                    mgr.mgr.files_target = Some(id);
+                    id
+                }
+                Event::FilesHoverCancelled => match mgr.mgr.files_target.take() {
+                    Some(id) => id,
+                    None => continue,
+                },
+                _ => {
+                    // Drop on the widget which saw the hover, if any
+                    let target = mgr.mgr.files_target.take();
+                    target.unwrap_or_else(|| mgr.mgr.files_target(widget))
+                }
+            };
+            mgr.send_event(widget, id, event);
+        }
 
         for gi in 0..mgr.mgr.pan_grab.len() {
             let grab = &mut mgr.mgr.pan_grab[gi];
@@ -306,7 +418,7 @@ impl ManagerState {
 
         let mut action = mgr.action;
         action += self.action;
-        self.action = TkAction::None;
+        self.action = TkAction::empty();
         action
     }
 }
@@ -316,19 +428,54 @@ impl ManagerState {
 impl<'a> Manager<'a> {
     /// Update widgets due to timer
     pub fn update_timer<W: Widget + ?Sized>(&mut self, widget: &mut W) {
-        let now = Instant::now();
+        let now = self.mgr.now();
 
         // assumption: time_updates are sorted in reverse order
+        let mut fired = vec![];
         while !self.mgr.time_updates.is_empty() {
             if self.mgr.time_updates.last().unwrap().0 > now {
                 break;
             }
 
             let update = self.mgr.time_updates.pop().unwrap();
+            fired.push(update);
             self.send_event(widget, update.1, Event::TimerUpdate);
         }
 
+        // Reschedule repeating updates (unless cancelled by the handler).
+        // The next update is relative to the due time, thus does not drift,
+        // but updates missed entirely are skipped.
+        for (time, id) in fired {
+            let repeat = self.mgr.time_repeats.iter().find(|row| row.0 == id);
+            if let Some(interval) = repeat.map(|row| row.1) {
+                let mut next = time + interval;
+                if next <= now {
+                    next = now + interval;
+                }
+                self.mgr.schedule_update(next, id);
+            }
+        }
+
         self.mgr.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
+
+        let show_tooltip = self.mgr.tooltip_time.map(|time| time <= now);
+        if show_tooltip.unwrap_or(false) {
+            self.mgr.tooltip_time = None;
+            if let Some(id) = self.mgr.tooltip {
+                self.mgr.tooltip_shown = true;
+                self.send_event(widget, widget.id(), Event::ShowTooltip(id));
+            }
+        }
+    }
+
+    /// Send [`Event::Frame`] to widgets which requested it
+    ///
+    /// This should be called immediately before drawing.
+    pub fn update_frame<W: Widget + ?Sized>(&mut self, widget: &mut W, info: FrameInfo) {
+        let ids = std::mem::take(&mut self.mgr.frame_updates);
+        for w_id in ids {
+            self.send_event(widget, w_id, Event::Frame(info));
+        }
     }
 
     /// Update widgets due to handle
@@ -337,16 +484,193 @@ impl<'a> Manager<'a> {
         widget: &mut W,
         handle: UpdateHandle,
         payload: u64,
+        data: Option<UpdateData>,
     ) {
         // NOTE: to avoid borrow conflict, we must clone values!
         if let Some(mut values) = self.mgr.handle_updates.get(&handle).cloned() {
             for w_id in values.drain(..) {
-                let event = Event::HandleUpdate { handle, payload };
+                let data = data.clone();
+                let event = Event::HandleUpdate {
+                    handle,
+                    payload,
+                    data,
+                };
                 self.send_event(widget, w_id, event);
             }
         }
     }
 
+    /// Handle raw relative mouse motion
+    ///
+    /// The toolkit should call this for device mouse-motion events (not
+    /// subject to pointer acceleration). These are forwarded as
+    /// [`Event::PointerMotion`] to the widget holding a pointer lock, if any.
+    ///
+    /// Additionally, if the cursor left the window during a mouse grab and the
+    /// platform does not report further cursor motion (i.e. does not capture
+    /// the pointer), [`Event::PressMove`] events are synthesised from this
+    /// motion. Coordinates are approximate in this case.
+    pub fn handle_pointer_motion<W>(&mut self, widget: &mut W, delta: Vec2)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        if let Some(grab) = self.mouse_grab() {
+            if grab.pointer_lock {
+                let event = Event::PointerMotion { delta };
+                self.send_event(widget, grab.start_id, event);
+            }
+            if self.mgr.cursor_left {
+                let pos = self.mgr.last_mouse_pos + delta;
+                self.cursor_moved(widget, pos.into(), pos);
+            }
+        }
+    }
+
+    /// Handle the start of a touch
+    ///
+    /// Touch input is usually handled via `handle_winit`; these `handle_touch_*`
+    /// methods are available to other toolkits (and for testing).
+    ///
+    /// Each touch is identified by a `touch_id`, unique among active touches.
+    /// Touches are independent: each may be grabbed by a different widget.
+    pub fn handle_touch_start<W>(&mut self, widget: &mut W, touch_id: u64, coord: Coord, pos: Vec2)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        if self.mgr.help_mode {
+            let start_id = self.mgr.find_input_target(widget, coord);
+            self.press_help(widget, start_id);
+        } else if let Some(start_id) = self.mgr.find_input_target(widget, coord) {
+            let source = PressSource::Touch(touch_id);
+            let event = Event::PressStart {
+                source,
+                start_id,
+                coord,
+                pos,
+            };
+            self.send_popup_first(widget, start_id, event);
+        }
+    }
+
+    /// Handle motion of a touch
+    pub fn handle_touch_move<W>(&mut self, widget: &mut W, touch_id: u64, coord: Coord, pos: Vec2)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        let cur_id = self.mgr.find_input_target(widget, coord);
+
+        let mut r = None;
+        let mut pan_grab = None;
+        if let Some(grab) = self.get_touch(touch_id) {
+            if grab.mode == GrabMode::Grab {
+                let id = grab.start_id;
+                let event = Event::PressMove {
+                    source: PressSource::Touch(touch_id),
+                    cur_id,
+                    coord,
+                    pos,
+                    delta: coord - grab.coord,
+                };
+                // Only when 'depressed' status changes:
+                let redraw = grab.cur_id != cur_id
+                    && (grab.cur_id == Some(grab.start_id) || cur_id == Some(grab.start_id));
+
+                grab.cur_id = cur_id;
+                grab.coord = coord;
+
+                r = Some((id, event, redraw));
+            } else {
+                pan_grab = Some(grab.pan_grab);
+            }
+        }
+
+        if let Some((id, event, redraw)) = r {
+            if redraw {
+                self.send_action(TkAction::REDRAW);
+            }
+            self.send_event(widget, id, event);
+        } else if let Some(pan_grab) = pan_grab {
+            if (pan_grab.1 as usize) < MAX_PAN_GRABS {
+                if let Some(pan) = self.mgr.pan_grab.get_mut(pan_grab.0 as usize) {
+                    pan.coords[pan_grab.1 as usize].1 = coord;
+                }
+            }
+        }
+    }
+
+    /// Handle the end of a touch
+    ///
+    /// If `cancelled`, the widget owning the grab (if any) receives
+    /// [`Event::PressEnd`] with `end_id: None`.
+    pub fn handle_touch_end<W>(
+        &mut self,
+        widget: &mut W,
+        touch_id: u64,
+        coord: Coord,
+        pos: Vec2,
+        cancelled: bool,
+    ) where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        if let Some(grab) = self.remove_touch(touch_id) {
+            if grab.mode == GrabMode::Grab {
+                let event = Event::PressEnd {
+                    source: PressSource::Touch(touch_id),
+                    end_id: if cancelled { None } else { grab.cur_id },
+                    coord,
+                    pos,
+                };
+                if let Some(cur_id) = grab.cur_id {
+                    self.redraw(cur_id);
+                }
+                self.redraw(grab.start_id);
+                self.send_event(widget, grab.start_id, event);
+            } else {
+                self.mgr.remove_pan_grab(grab.pan_grab);
+            }
+        }
+    }
+
+    /// Handle motion of the mouse cursor
+    ///
+    /// Like the `handle_touch_*` methods, this is usually handled via
+    /// `handle_winit` but is available to other toolkits (and for testing).
+    pub fn handle_cursor_moved<W>(&mut self, widget: &mut W, coord: Coord, pos: Vec2)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        // The platform reports motion, thus synthesis is not needed
+        self.mgr.cursor_left = false;
+        self.cursor_moved(widget, coord, pos);
+    }
+
+    /// Handle a file hovering over the window
+    ///
+    /// File drag-and-drop is usually handled via `handle_winit`; these
+    /// methods are available to other toolkits (and for testing). Events are
+    /// delivered in order of arrival on the next update, with consecutive
+    /// files hovered or dropped merged into one [`Event::FilesHover`] or
+    /// [`Event::FilesDrop`].
+    pub fn handle_hovered_file(&mut self, path: PathBuf) {
+        match self.mgr.file_events.last_mut() {
+            Some(Event::FilesHover(files)) => files.push(path),
+            _ => self.mgr.file_events.push(Event::FilesHover(vec![path])),
+        }
+    }
+
+    /// Handle cancellation of hovering files
+    pub fn handle_hovered_file_cancelled(&mut self) {
+        self.mgr.file_events.push(Event::FilesHoverCancelled);
+    }
+
+    /// Handle a file dropped on the window
+    pub fn handle_dropped_file(&mut self, path: PathBuf) {
+        match self.mgr.file_events.last_mut() {
+            Some(Event::FilesDrop(files)) => files.push(path),
+            _ => self.mgr.file_events.push(Event::FilesDrop(vec![path])),
+        }
+    }
+
     /// Handle a winit `WindowEvent`.
     ///
     /// Note that some event types are not *does not* handled, since for these
@@ -364,35 +688,29 @@ impl<'a> Manager<'a> {
         // Unhandled events here, so we can freely ignore all responses.
 
         match event {
-            CloseRequested => self.send_action(TkAction::Close),
-            /* Not yet supported: see #98
-            DroppedFile(path) => ,
-            HoveredFile(path) => ,
-            HoveredFileCancelled => ,
-            */
+            CloseRequested => self.send_action(TkAction::CLOSE),
+            DroppedFile(path) => self.handle_dropped_file(path),
+            HoveredFile(path) => self.handle_hovered_file(path),
+            HoveredFileCancelled => self.handle_hovered_file_cancelled(),
             ReceivedCharacter(c) => {
-                if let Some(id) = self.mgr.char_focus {
-                    // Filter out control codes (Unicode 5.11) which
-                    // should be sent via Event::Control
-                    if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') {
-                        // We ignore these when winit also sends a corresponding
-                        // VirtualKeyCode but match those codes it doesn't.
-                        // Possibly we should instead directly match Control+Letter
-                        // combos and handle localisation ourselves, but we
-                        // don't yet have localisation or customisation support.
-                        let key = match c {
-                            '\u{03}' => ControlKey::Copy,
-                            '\u{16}' => ControlKey::Paste,
-                            '\u{18}' => ControlKey::Cut,
-                            '\u{1A}' => ControlKey::Undo, // also redo; we can't differentiate
-                            _ => return,
-                        };
-                        let event = Event::Control(key);
-                        self.send_event(widget, id, event);
-                    } else {
+                let char_focus = (self.mgr.char_focus)
+                    .filter(|id| !self.mgr.is_locked_out(widget.as_widget(), *id));
+                if let Some(id) = char_focus {
+                    // Filter out control codes (Unicode 5.11) and characters
+                    // typed with the command modifier: the corresponding key
+                    // events are sent via Event::Control (see
+                    // ControlKey::new_with_modifiers).
+                    let control = c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}');
+                    if !control && !is_command(self.mgr.modifiers) {
                         let event = Event::ReceivedCharacter(c);
                         self.send_event(widget, id, event);
                     }
+                } else if let Some(id) = self.mgr.popups.last().map(|(_, p)| p.parent) {
+                    // Without char focus, characters go to the owner of the
+                    // top-most pop-up (e.g. for menu type-ahead selection)
+                    if !c.is_control() {
+                        self.send_event(widget, id, Event::ReceivedCharacter(c));
+                    }
                 }
             }
             KeyboardInput {
@@ -411,53 +729,24 @@ impl<'a> Manager<'a> {
             ModifiersChanged(state) => {
                 if state.alt() != self.mgr.modifiers.alt() {
                     // This controls drawing of accelerator key indicators
-                    self.mgr.send_action(TkAction::Redraw);
+                    self.mgr.send_action(TkAction::REDRAW);
                 }
                 self.mgr.modifiers = state;
             }
             CursorMoved { position, .. } => {
-                let coord = position.into();
-
-                // Update hovered widget
-                let cur_id = widget.find_id(coord);
-                let delta = coord - self.mgr.last_mouse_coord;
-                self.set_hover(widget, cur_id);
-
-                if let Some(grab) = self.mouse_grab() {
-                    if grab.mode == GrabMode::Grab {
-                        let source = PressSource::Mouse(grab.button);
-                        let event = Event::PressMove {
-                            source,
-                            cur_id,
-                            coord,
-                            delta,
-                        };
-                        self.send_event(widget, grab.start_id, event);
-                    } else if let Some(pan) = self.mgr.pan_grab.get_mut(grab.pan_grab.0 as usize) {
-                        pan.coords[grab.pan_grab.1 as usize].1 = coord;
-                    }
-                } else if let Some(id) = self.mgr.popups.last().map(|(_, p)| p.parent) {
-                    // Use a fake button!
-                    let source = PressSource::Mouse(MouseButton::Other(0));
-                    let event = Event::PressMove {
-                        source,
-                        cur_id,
-                        coord,
-                        delta,
-                    };
-                    self.send_event(widget, id, event);
-                } else {
-                    // We don't forward move events without a grab
-                }
-
-                self.mgr.last_mouse_coord = coord;
+                self.handle_cursor_moved(widget, position.into(), position.into());
             }
             // CursorEntered { .. },
             CursorLeft { .. } => {
-                if self.mouse_grab().is_none() {
-                    // If there's a mouse grab, we will continue to receive
-                    // coordinates; if not, set a fake coordinate off the window
+                if self.mouse_grab().is_some() {
+                    // Most platforms capture the pointer during a press, in
+                    // which case we will continue to receive coordinates.
+                    // Otherwise, these are synthesised from raw motion.
+                    self.mgr.cursor_left = true;
+                } else {
+                    // Set a fake coordinate off the window
                     self.mgr.last_mouse_coord = Coord(-1, -1);
+                    self.mgr.last_mouse_pos = Vec2(-1.0, -1.0);
                     self.set_hover(widget, None);
                 }
             }
@@ -474,9 +763,17 @@ impl<'a> Manager<'a> {
             }
             MouseInput { state, button, .. } => {
                 let coord = self.mgr.last_mouse_coord;
+                let pos = self.mgr.last_mouse_pos;
                 let source = PressSource::Mouse(button);
+                // Pressing cancels any pending tooltip
+                self.mgr.tooltip_time = None;
 
-                if let Some(grab) = self.mouse_grab() {
+                if self.mgr.help_mode && self.mouse_grab().is_none() {
+                    if state == ElementState::Pressed {
+                        let start_id = self.mgr.hover;
+                        self.press_help(widget, start_id);
+                    }
+                } else if let Some(grab) = self.mouse_grab() {
                     match grab.mode {
                         GrabMode::Grab => {
                             // Mouse grab active: send events there
@@ -485,6 +782,7 @@ impl<'a> Manager<'a> {
                                 source,
                                 end_id: self.mgr.hover,
                                 coord,
+                                pos,
                             };
                             self.send_event(widget, grab.start_id, event);
                         }
@@ -494,6 +792,13 @@ impl<'a> Manager<'a> {
 
                     if state == ElementState::Released {
                         self.end_mouse_grab(button);
+                        if self.mgr.cursor_left {
+                            // The press ended outside the window
+                            self.mgr.cursor_left = false;
+                            self.mgr.last_mouse_coord = Coord(-1, -1);
+                            self.mgr.last_mouse_pos = Vec2(-1.0, -1.0);
+                            self.set_hover(widget, None);
+                        }
                     }
                 } else if let Some(start_id) = self.mgr.hover {
                     // No mouse grab but have a hover target
@@ -502,6 +807,7 @@ impl<'a> Manager<'a> {
                             source,
                             start_id,
                             coord,
+                            pos,
                         };
                         self.send_popup_first(widget, start_id, event);
                     }
@@ -510,93 +816,124 @@ impl<'a> Manager<'a> {
             // TouchpadPressure { pressure: f32, stage: i64, },
             // AxisMotion { axis: AxisId, value: f64, },
             Touch(touch) => {
-                let source = PressSource::Touch(touch.id);
                 let coord = touch.location.into();
+                let pos = touch.location.into();
                 match touch.phase {
-                    TouchPhase::Started => {
-                        if let Some(start_id) = widget.find_id(coord) {
-                            let event = Event::PressStart {
-                                source,
-                                start_id,
-                                coord,
-                            };
-                            self.send_popup_first(widget, start_id, event);
-                        }
+                    TouchPhase::Started => self.handle_touch_start(widget, touch.id, coord, pos),
+                    TouchPhase::Moved => self.handle_touch_move(widget, touch.id, coord, pos),
+                    TouchPhase::Ended => self.handle_touch_end(widget, touch.id, coord, pos, false),
+                    TouchPhase::Cancelled => {
+                        self.handle_touch_end(widget, touch.id, coord, pos, true)
                     }
-                    TouchPhase::Moved => {
-                        let cur_id = widget.find_id(coord);
-
-                        let mut r = None;
-                        let mut pan_grab = None;
-                        if let Some(grab) = self.get_touch(touch.id) {
-                            if grab.mode == GrabMode::Grab {
-                                let id = grab.start_id;
-                                let event = Event::PressMove {
-                                    source,
-                                    cur_id,
-                                    coord,
-                                    delta: coord - grab.coord,
-                                };
-                                // Only when 'depressed' status changes:
-                                let redraw = grab.cur_id != cur_id
-                                    && (grab.cur_id == Some(grab.start_id)
-                                        || cur_id == Some(grab.start_id));
-
-                                grab.cur_id = cur_id;
-                                grab.coord = coord;
-
-                                r = Some((id, event, redraw));
-                            } else {
-                                pan_grab = Some(grab.pan_grab);
-                            }
-                        }
+                }
+            }
+            _ => (),
+        }
+    }
 
-                        if let Some((id, event, redraw)) = r {
-                            if redraw {
-                                self.send_action(TkAction::Redraw);
-                            }
-                            self.send_event(widget, id, event);
-                        } else if let Some(pan_grab) = pan_grab {
-                            if (pan_grab.1 as usize) < MAX_PAN_GRABS {
-                                if let Some(pan) = self.mgr.pan_grab.get_mut(pan_grab.0 as usize) {
-                                    pan.coords[pan_grab.1 as usize].1 = coord;
-                                }
-                            }
-                        }
+    /// Handle gamepad input
+    ///
+    /// The input is first sent as [`Event::Gamepad`] to the widget with nav
+    /// focus (or otherwise to the owner of the top-most pop-up, or the nav
+    /// fallback). If not handled, button presses are mapped to keyboard
+    /// navigation:
+    ///
+    /// -   the D-pad (and the left stick) send arrow keys as
+    ///     [`Event::Control`] to the widget with nav focus, or move nav focus
+    ///     if not handled
+    /// -   [`GamepadButton::South`] activates the widget with nav focus
+    /// -   [`GamepadButton::East`] closes the top-most pop-up or cancels nav
+    ///     focus, like the Escape key
+    pub fn handle_gamepad<W>(&mut self, widget: &mut W, gamepad: u32, input: GamepadInput)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        use GamepadButton as GB;
+
+        let popup_parent = self.mgr.popups.last().map(|(_, p)| p.parent);
+        let target = (self.mgr.nav_focus)
+            .or(popup_parent)
+            .or(self.mgr.nav_fallback)
+            .filter(|id| !self.mgr.is_locked_out(widget.as_widget(), *id));
+        if let Some(id) = target {
+            let event = Event::Gamepad { gamepad, input };
+            trace!("Send to {}: {:?}", id, event);
+            match self.send_traced(widget, id, event) {
+                Response::Unhandled(_) => (),
+                _ => return,
+            }
+        }
+
+        let button = match input {
+            GamepadInput::ButtonPressed(button) => button,
+            GamepadInput::ButtonReleased(_) => return,
+            GamepadInput::AxisChanged(axis, value) => {
+                // Treat the left stick as a D-pad, with hysteresis
+                let (old, button) = match axis {
+                    GamepadAxis::LeftStickX => {
+                        (&mut self.mgr.gamepad_stick.0, (GB::DPadLeft, GB::DPadRight))
                     }
-                    TouchPhase::Ended => {
-                        if let Some(grab) = self.remove_touch(touch.id) {
-                            if grab.mode == GrabMode::Grab {
-                                let event = Event::PressEnd {
-                                    source,
-                                    end_id: grab.cur_id,
-                                    coord,
-                                };
-                                if let Some(cur_id) = grab.cur_id {
-                                    self.redraw(cur_id);
-                                }
-                                self.send_event(widget, grab.start_id, event);
-                            } else {
-                                self.mgr.remove_pan_grab(grab.pan_grab);
-                            }
-                        }
-                    }
-                    TouchPhase::Cancelled => {
-                        if let Some(grab) = self.remove_touch(touch.id) {
-                            let event = Event::PressEnd {
-                                source,
-                                end_id: None,
-                                coord,
-                            };
-                            if let Some(cur_id) = grab.cur_id {
-                                self.redraw(cur_id);
-                            }
-                            self.send_event(widget, grab.start_id, event);
-                        }
+                    GamepadAxis::LeftStickY => {
+                        (&mut self.mgr.gamepad_stick.1, (GB::DPadDown, GB::DPadUp))
                     }
+                    _ => return,
+                };
+                let dir = match *old {
+                    0 if value <= -0.6 => -1,
+                    0 if value >= 0.6 => 1,
+                    -1 if value > -0.4 => 0,
+                    1 if value < 0.4 => 0,
+                    dir => dir,
+                };
+                if dir == *old {
+                    return;
+                }
+                *old = dir;
+                match dir {
+                    -1 => button.0,
+                    1 => button.1,
+                    _ => return,
                 }
             }
-            _ => (),
+        };
+
+        let key = match button {
+            GB::DPadUp => ControlKey::Up,
+            GB::DPadDown => ControlKey::Down,
+            GB::DPadLeft => ControlKey::Left,
+            GB::DPadRight => ControlKey::Right,
+            GB::South => {
+                if let Some(id) = self.mgr.nav_focus {
+                    self.send_activate(widget, id, ActivateSource::Gamepad);
+                }
+                return;
+            }
+            GB::East => {
+                if self.mgr.help_mode {
+                    self.end_help_mode();
+                } else if let Some(id) = self.mgr.popups.last().map(|(id, _)| *id) {
+                    self.close_window(id);
+                } else {
+                    self.clear_nav_focus();
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if let Some(id) = self.mgr.nav_focus.or(popup_parent) {
+            match self.send_traced(widget, id, Event::Control(key)) {
+                Response::Unhandled(_) => (),
+                _ => return,
+            }
+        }
+
+        let reverse = key == ControlKey::Up || key == ControlKey::Left;
+        if !self.next_nav_focus(widget.as_widget(), reverse) {
+            self.clear_nav_focus();
+        }
+        if let Some(id) = self.mgr.nav_focus {
+            self.send_event(widget, id, Event::NavFocus);
         }
     }
 }
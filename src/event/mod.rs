@@ -29,7 +29,13 @@
 //! The [`Response`] enum has a few variants; most important is `Msg(msg)`
 //! which passes a user-defined payload up to a parent widget. The
 //! `Unhandled(event)` and `Focus(rect)` variants may be trapped by any parent
-//! for secondary purposes, e.g. to adjust a `ScrollRegion`.
+//! for secondary purposes, e.g. to adjust a `ScrollRegion`. Similarly, the
+//! `Select` variant is the standard notification that an item (e.g. list
+//! row, tab or menu entry) was selected, allowing a parent to track the
+//! current item without a custom message type. The
+//! `Action(action, msg)` variant allows a handler to return a
+//! [`TkAction`](crate::TkAction) together with (or instead of) a message; the
+//! action is applied to the [`Manager`] before the parent receives the message.
 //!
 //! ## Mouse and touch events
 //!
@@ -68,10 +74,12 @@
 //! [`WidgetId`]: crate::WidgetId
 
 mod callback;
+mod config;
 #[cfg(not(feature = "winit"))]
 mod enums;
 mod events;
 mod handler;
+mod help;
 mod manager;
 mod response;
 mod update;
@@ -89,13 +97,15 @@ pub use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
 pub use winit::window::CursorIcon;
 
 pub use callback::Callback;
+pub use config::Config;
 #[cfg(not(feature = "winit"))]
 pub use enums::{CursorIcon, ModifiersState, MouseButton, VirtualKeyCode};
 pub use events::*;
 pub use handler::{Handler, SendEvent};
-pub use manager::{ConfigureManager, GrabMode, Manager, ManagerState};
+pub use help::Help;
+pub use manager::{ConfigureManager, EventTrace, FocusRect, GrabMode, Manager, ManagerState};
 pub use response::Response;
-pub use update::UpdateHandle;
+pub use update::{UpdateData, UpdateHandle};
 
 /// A type supporting a small number of key bindings
 ///
@@ -105,12 +115,92 @@ pub use update::UpdateHandle;
 /// but cause allocation.)
 pub type VirtualKeyCodes = SmallVec<[VirtualKeyCode; 5]>;
 
+/// A keyboard shortcut: a key combined with modifiers
+///
+/// Unlike accelerator keys (see [`Manager::add_accel_keys`]), shortcuts
+/// require an exact match of modifiers and are active regardless of the
+/// state of Alt or of keyboard focus. Register via [`Manager::add_shortcut`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Required modifiers
+    pub modifiers: ModifiersState,
+    /// Key
+    pub key: VirtualKeyCode,
+}
+
+impl Shortcut {
+    /// Construct
+    pub fn new(modifiers: ModifiersState, key: VirtualKeyCode) -> Self {
+        Shortcut { modifiers, key }
+    }
+
+    /// Construct a shortcut using the Ctrl modifier
+    pub fn ctrl(key: VirtualKeyCode) -> Self {
+        Shortcut::new(ModifiersState::CTRL, key)
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.modifiers.ctrl() {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.alt() {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.shift() {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.logo() {
+            write!(f, "Logo+")?;
+        }
+        // Debug representation of the key is mostly usable, excepting digits
+        let key = format!("{:?}", self.key);
+        match key.strip_prefix("Key") {
+            Some(digit) if digit.len() == 1 => write!(f, "{}", digit),
+            _ => write!(f, "{}", key),
+        }
+    }
+}
+
 #[test]
 fn size_of_virtual_key_codes() {
     // Currently sized to maximise use of available space on 64-bit platforms
     assert!(std::mem::size_of::<VirtualKeyCodes>() <= 32);
 }
 
+#[test]
+fn shortcut_display() {
+    let shortcut = Shortcut::ctrl(VirtualKeyCode::S);
+    assert_eq!(shortcut.to_string(), "Ctrl+S");
+    let modifiers = ModifiersState::CTRL | ModifiersState::SHIFT;
+    let shortcut = Shortcut::new(modifiers, VirtualKeyCode::Key1);
+    assert_eq!(shortcut.to_string(), "Ctrl+Shift+1");
+}
+
+#[test]
+fn control_key_clipboard_shortcuts() {
+    let cmd = match cfg!(target_os = "macos") {
+        true => ModifiersState::LOGO,
+        false => ModifiersState::CTRL,
+    };
+    let key = |m, vkey| ControlKey::new_with_modifiers(vkey, m);
+    assert_eq!(key(cmd, VirtualKeyCode::X), Some(ControlKey::Cut));
+    assert_eq!(key(cmd, VirtualKeyCode::C), Some(ControlKey::Copy));
+    assert_eq!(key(cmd, VirtualKeyCode::V), Some(ControlKey::Paste));
+    assert_eq!(
+        key(cmd | ModifiersState::SHIFT, VirtualKeyCode::Z),
+        Some(ControlKey::Redo)
+    );
+    let shift = ModifiersState::SHIFT;
+    assert_eq!(key(shift, VirtualKeyCode::Insert), Some(ControlKey::Paste));
+    assert_eq!(key(shift, VirtualKeyCode::C), None);
+    assert_eq!(
+        key(ModifiersState::empty(), VirtualKeyCode::Insert),
+        Some(ControlKey::Insert)
+    );
+}
+
 /// A void message
 ///
 /// This type is not constructible, therefore `Response<VoidMsg>` is known at
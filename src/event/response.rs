@@ -5,14 +5,20 @@
 
 //! Event handling: Response type
 
-use super::{Event, VoidResponse};
+use super::{Event, Manager, VoidResponse};
 use kas::geom::Rect;
+use kas::TkAction;
 
 /// Response type from [`Handler::handle`].
 ///
 /// This type wraps [`Handler::Msg`] allowing both custom messages and toolkit
 /// messages.
 ///
+/// A handler may combine a message with a [`TkAction`] via
+/// [`Response::msg_action`] (or return only an action via
+/// [`Response::action`]) instead of applying the action via
+/// `*mgr += action`.
+///
 /// [`Handler::handle`]: super::Handler::handle
 /// [`Handler::Msg`]: super::Handler::Msg
 #[derive(Clone, Debug)]
@@ -24,8 +30,26 @@ pub enum Response<M> {
     Unhandled(Event),
     /// (Keyboard) focus has changed. This region should be made visible.
     Focus(Rect),
+    /// The widget has been selected
+    ///
+    /// This is the standard notification from selectable items such as list
+    /// rows, tabs and menu entries, whether selected by the user or in
+    /// response to [`Event::Select`]. A parent may trap this (e.g. to update
+    /// the current item of a view) without requiring a custom message type;
+    /// otherwise it should be passed on to its own parent.
+    Select,
     /// Custom message type
     Msg(M),
+    /// An action for the toolkit, optionally with a message
+    ///
+    /// The action is applied to the [`Manager`] by [`Response::apply_action`],
+    /// which replaces this variant with `Msg` or `None`. This happens on
+    /// return from [`Manager::handle_generic`] and from
+    /// [`SendEvent::send`] as derived by `derive(Widget)`, thus parents
+    /// normally receive only the message.
+    ///
+    /// [`SendEvent::send`]: super::SendEvent::send
+    Action(TkAction, Option<M>),
 }
 
 // Unfortunately we cannot write generic `From` / `TryFrom` impls
@@ -49,6 +73,15 @@ impl<M> Response<M> {
         }
     }
 
+    /// True if variant is `Select`
+    #[inline]
+    pub fn is_select(&self) -> bool {
+        match self {
+            &Response::Select => true,
+            _ => false,
+        }
+    }
+
     /// True if variant is `Msg`
     #[inline]
     pub fn is_msg(&self) -> bool {
@@ -58,6 +91,45 @@ impl<M> Response<M> {
         }
     }
 
+    /// True if variant is `Action`
+    #[inline]
+    pub fn is_action(&self) -> bool {
+        match self {
+            &Response::Action(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Construct with an action and no message
+    #[inline]
+    pub fn action(action: TkAction) -> Self {
+        Response::Action(action, None)
+    }
+
+    /// Construct with a message and an action
+    ///
+    /// Example: a widget whose value changed may return
+    /// `Response::msg_action(value, TkAction::REDRAW)`.
+    #[inline]
+    pub fn msg_action(msg: M, action: TkAction) -> Self {
+        Response::Action(action, Some(msg))
+    }
+
+    /// Apply any action to the [`Manager`]
+    ///
+    /// An `Action` variant is replaced by `Msg` or `None`; other variants are
+    /// returned unchanged.
+    #[inline]
+    pub fn apply_action(self, mgr: &mut Manager) -> Self {
+        match self {
+            Response::Action(action, msg) => {
+                *mgr += action;
+                msg.into()
+            }
+            r => r,
+        }
+    }
+
     /// Map from one `Response` type to another
     ///
     /// Once Rust supports specialisation, this will likely be replaced with a
@@ -67,8 +139,15 @@ impl<M> Response<M> {
     where
         M: From<N>,
     {
-        r.try_into()
-            .unwrap_or_else(|msg| Response::Msg(M::from(msg)))
+        use Response::*;
+        match r {
+            None => None,
+            Unhandled(e) => Unhandled(e),
+            Focus(rect) => Focus(rect),
+            Select => Select,
+            Msg(m) => Msg(M::from(m)),
+            Action(action, m) => Action(action, m.map(M::from)),
+        }
     }
 
     /// Map one `Response` type into another
@@ -84,21 +163,31 @@ impl<M> Response<M> {
 
     /// Try mapping from one `Response` type to another, failing on `Msg`
     /// variant and returning the payload.
+    ///
+    /// An `Action` variant with a message also fails, returning the message
+    /// together with the action; for the `Msg` variant the returned action is
+    /// empty. The caller is responsible for applying this action, e.g. via
+    /// `*mgr += action`.
     #[inline]
-    pub fn try_from<N>(r: Response<N>) -> Result<Self, N> {
+    pub fn try_from<N>(r: Response<N>) -> Result<Self, (N, TkAction)> {
         use Response::*;
         match r {
             None => Ok(None),
             Unhandled(e) => Ok(Unhandled(e)),
             Focus(rect) => Ok(Focus(rect)),
-            Msg(m) => Err(m),
+            Select => Ok(Select),
+            Msg(m) => Err((m, TkAction::empty())),
+            Action(action, Option::None) => Ok(Action(action, Option::None)),
+            Action(action, Some(m)) => Err((m, action)),
         }
     }
 
     /// Try mapping one `Response` type into another, failing on `Msg`
     /// variant and returning the payload.
+    ///
+    /// See [`Response::try_from`].
     #[inline]
-    pub fn try_into<N>(self) -> Result<Response<N>, M> {
+    pub fn try_into<N>(self) -> Result<Response<N>, (M, TkAction)> {
         Response::try_from(self)
     }
 }
@@ -106,7 +195,7 @@ impl<M> Response<M> {
 impl VoidResponse {
     /// Convert a `Response<VoidMsg>` to another `Response`
     pub fn void_into<M>(self) -> Response<M> {
-        self.try_into().unwrap_or(Response::None)
+        self.try_into().unwrap_or_else(|(msg, _)| match msg {})
     }
 }
 
@@ -5,8 +5,14 @@
 
 //! Event handling: updates
 
+use std::any::Any;
+use std::fmt;
 use std::num::NonZeroU32;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+#[allow(unused)]
+use super::{Event, Manager}; // for doc-links
 
 /// An update handle
 ///
@@ -35,3 +41,46 @@ impl UpdateHandle {
         }
     }
 }
+
+/// Typed data passed with an update
+///
+/// This may be passed via [`Manager::trigger_update_data`] and is received
+/// by subscribed widgets via [`Event::HandleUpdate`]. Data is shared (not
+/// copied) between recipients; comparison tests identity.
+#[derive(Clone)]
+pub struct UpdateData(Arc<dyn Any + Send + Sync>);
+
+impl UpdateData {
+    /// Construct from a value
+    pub fn new<T: Any + Send + Sync>(data: T) -> Self {
+        UpdateData(Arc::new(data))
+    }
+
+    /// True if the data is of type `T`
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+
+    /// Get a reference to the data, if of type `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl From<Arc<dyn Any + Send + Sync>> for UpdateData {
+    fn from(data: Arc<dyn Any + Send + Sync>) -> Self {
+        UpdateData(data)
+    }
+}
+
+impl PartialEq for UpdateData {
+    fn eq(&self, rhs: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &rhs.0)
+    }
+}
+
+impl fmt::Debug for UpdateData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UpdateData {{ .. }}")
+    }
+}
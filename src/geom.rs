@@ -110,6 +110,15 @@ impl<X: Pixel> From<PhysicalPosition<X>> for Coord {
     }
 }
 
+#[cfg(feature = "winit")]
+impl<X: Pixel> From<PhysicalPosition<X>> for Vec2 {
+    #[inline]
+    fn from(pos: PhysicalPosition<X>) -> Vec2 {
+        let pos: (f32, f32) = pos.cast::<f32>().into();
+        Vec2(pos.0, pos.1)
+    }
+}
+
 #[cfg(feature = "winit")]
 impl<X: Pixel> From<Coord> for PhysicalPosition<X> {
     #[inline]
@@ -268,7 +277,7 @@ impl std::ops::SubAssign for Size {
 }
 
 /// A rectangular region.
-#[derive(Clone, Copy, Default, Debug)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 pub struct Rect {
     pub pos: Coord,
     pub size: Size,
@@ -305,6 +314,19 @@ impl Rect {
         let size = Size(w, h);
         Rect { pos, size }
     }
+
+    /// Get the intersection of two rects, if not empty
+    #[inline]
+    pub fn intersection(&self, rhs: &Rect) -> Option<Rect> {
+        let pos = self.pos.max(rhs.pos);
+        let end = self.pos_end().min(rhs.pos_end());
+        if pos.0 < end.0 && pos.1 < end.1 {
+            let size = Size((end.0 - pos.0) as u32, (end.1 - pos.1) as u32);
+            Some(Rect { pos, size })
+        } else {
+            None
+        }
+    }
 }
 
 impl std::ops::Add<Coord> for Rect {
@@ -97,6 +97,7 @@ impl From<Rect> for Quad {
 /// vectors (consider for `lhs = (0, 1), rhs = (1, 0)`).
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2(pub f32, pub f32);
 
 /// 2D vector (double precision)
@@ -136,6 +137,12 @@ macro_rules! impl_vec2 {
                 $T(self.0.abs(), self.1.abs())
             }
 
+            /// Round each component to the nearest integer
+            #[inline]
+            pub fn round(self) -> Self {
+                $T(self.0.round(), self.1.round())
+            }
+
             /// For each component, return `±1` with the same sign as `self`.
             #[inline]
             pub fn sign(self) -> Self {
@@ -12,6 +12,7 @@ use crate::geom::{Coord, Rect, Size};
 use kas::{Align, AlignHints};
 
 /// Per-child information
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct GridChildInfo {
     /// Column index (first column when in a span)
     pub col: u32,
@@ -43,10 +44,24 @@ impl<CSR: Default, RSR: Default, S: GridStorage> GridSolver<CSR, RSR, S> {
     /// - `axis`: `AxisInfo` instance passed into `size_rules`
     /// - `(cols, rows)`: number of columns and rows
     /// - `storage`: reference to persistent storage
-    pub fn new(axis: AxisInfo, (cols, rows): (usize, usize), storage: &mut S) -> Self {
-        let col_spans = CSR::default();
-        let row_spans = RSR::default();
+    pub fn new(axis: AxisInfo, dim: (usize, usize), storage: &mut S) -> Self {
+        Self::new_with_spans(axis, dim, (CSR::default(), RSR::default()), storage)
+    }
+}
 
+impl<CSR, RSR, S: GridStorage> GridSolver<CSR, RSR, S> {
+    /// Construct, with explicit span storage
+    ///
+    /// This is like [`GridSolver::new`], except that span storage is passed:
+    /// `(col_spans, row_spans)` must have length equal to the number of
+    /// children spanning multiple columns and rows respectively. This allows
+    /// use of `Vec` for span storage.
+    pub fn new_with_spans(
+        axis: AxisInfo,
+        (cols, rows): (usize, usize),
+        (col_spans, row_spans): (CSR, RSR),
+        storage: &mut S,
+    ) -> Self {
         storage.set_dims(cols, rows);
 
         let mut solver = GridSolver {
@@ -246,10 +261,8 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> GridSetter<RT, CT, S> {
             SizeRules::solve_seq_total(widths, rules, rect.size.0);
             for i in 1..w_offsets.as_mut().len() {
                 let i1 = i - 1;
-                let m1 = storage.width_rules()[i1].margins().1;
-                let m0 = storage.width_rules()[i].margins().0;
-                w_offsets.as_mut()[i] =
-                    w_offsets.as_mut()[i1] + storage.widths()[i1] + m1.max(m0) as u32;
+                let m = storage.width_rules()[i1].margin_between(storage.width_rules()[i]);
+                w_offsets.as_mut()[i] = w_offsets.as_mut()[i1] + storage.widths()[i1] + m;
             }
         }
 
@@ -271,10 +284,8 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> GridSetter<RT, CT, S> {
             SizeRules::solve_seq_total(heights, rules, rect.size.1);
             for i in 1..h_offsets.as_mut().len() {
                 let i1 = i - 1;
-                let m1 = storage.height_rules()[i1].margins().1;
-                let m0 = storage.height_rules()[i].margins().0;
-                h_offsets.as_mut()[i] =
-                    h_offsets.as_mut()[i1] + storage.heights()[i1] + m1.max(m0) as u32;
+                let m = storage.height_rules()[i1].margin_between(storage.height_rules()[i]);
+                h_offsets.as_mut()[i] = h_offsets.as_mut()[i1] + storage.heights()[i1] + m;
             }
         }
 
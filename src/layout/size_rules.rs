@@ -100,6 +100,8 @@ impl Default for StretchPolicy {
 /// - the minimum size required for correct operation
 /// - the preferred / ideal size
 /// - a [`StretchPolicy`]
+/// - whether the content can *trade* size on this axis against size on the
+///   other axis (see [`SizeRules::with_trade_off`])
 ///
 /// Available space is distributed between widgets depending on whether the
 /// space is below the minimum, between the minimum and preferred, or above
@@ -116,9 +118,13 @@ impl Default for StretchPolicy {
 /// "pre" (left/top) and "post" (right/bottom). These are stored as `u16` values
 /// on the assumption that no margin need exceed 65536.
 ///
-/// When widgets are placed next to each other, their margins may be combined;
-/// e.g. if a widget with margin of 6px is followed by another with margin 2px,
-/// the required margin between the two is the maximum, 6px.
+/// When widgets are placed next to each other, their margins collapse (like
+/// CSS margin collapsing); e.g. if a widget with margin of 6px is followed by
+/// another with margin 2px, the required margin between the two is the
+/// maximum, 6px. This applies consistently to rows, columns and grids, thus a
+/// theme's outer margins may be used as the spacing between widgets. Rules may
+/// opt out of collapsing via [`SizeRules::with_margin_collapse`], in which
+/// case adjacent margins are added; see [`SizeRules::margin_between`].
 ///
 /// Only the layout engine and parent widgets need consider margins (beyond
 /// their specification). For these cases, one needs to be aware that due to
@@ -151,14 +157,24 @@ pub struct SizeRules {
     // (pre, post) margins
     m: (u16, u16),
     stretch: StretchPolicy,
+    // size on the other axis depends on size on this axis
+    trade_off: bool,
+    // adjacent margins are added instead of collapsed
+    add_margins: bool,
 }
 
 impl fmt::Debug for SizeRules {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "SizeRules {{ a: {}, b: {}, m: ({}, {}), stretch: {:?} }}",
-            self.a, self.b, self.m.0, self.m.1, self.stretch
+            "SizeRules {{ a: {}, b: {}, m: ({}, {}), stretch: {:?}, trade_off: {}, add_margins: {} }}",
+            self.a,
+            self.b,
+            self.m.0,
+            self.m.1,
+            self.stretch,
+            self.trade_off,
+            self.add_margins
         )
     }
 }
@@ -182,6 +198,8 @@ impl SizeRules {
             b: 0,
             m: (0, 0),
             stretch,
+            trade_off: false,
+            add_margins: false,
         }
     }
 
@@ -193,6 +211,8 @@ impl SizeRules {
             b: size,
             m: margins,
             stretch: StretchPolicy::Fixed,
+            trade_off: false,
+            add_margins: false,
         }
     }
 
@@ -205,6 +225,8 @@ impl SizeRules {
                 b: size.0,
                 m: margin.horiz,
                 stretch: StretchPolicy::Fixed,
+                trade_off: false,
+                add_margins: false,
             }
         } else {
             SizeRules {
@@ -212,6 +234,8 @@ impl SizeRules {
                 b: size.1,
                 m: margin.vert,
                 stretch: StretchPolicy::Fixed,
+                trade_off: false,
+                add_margins: false,
             }
         }
     }
@@ -229,9 +253,42 @@ impl SizeRules {
             b: ideal.max(min),
             m: margins,
             stretch,
+            trade_off: false,
+            add_margins: false,
         }
     }
 
+    /// Mark content as trading size on this axis for size on the other axis
+    /// (chain style)
+    ///
+    /// Typical example: wrapped text, which becomes taller as it is made
+    /// narrower. The width of such content should be described by its
+    /// `min`-imum (where it becomes unusable) and its `ideal` (where it needs
+    /// no more space on the other axis) separately.
+    ///
+    /// When a sequence cannot be given its ideal size, the solver negotiates in
+    /// two passes: first, space is given to content with a trade-off up to its
+    /// ideal size, then the remainder is shared between other content. This
+    /// avoids degenerate layouts such as tall, narrow columns of text next to
+    /// widgets which would happily accept less than their ideal size.
+    ///
+    /// Trade-offs propagate: rules combined from multiple rules (e.g. via
+    /// [`SizeRules::append`] or [`SizeRules::max`]) have a trade-off if any
+    /// input does.
+    #[inline]
+    pub fn with_trade_off(mut self, trade_off: bool) -> Self {
+        self.trade_off = trade_off;
+        self
+    }
+
+    /// Whether the content trades size on this axis for size on the other
+    ///
+    /// See [`SizeRules::with_trade_off`].
+    #[inline]
+    pub fn has_trade_off(self) -> bool {
+        self.trade_off
+    }
+
     /// Get the minimum size
     #[inline]
     pub fn min_size(self) -> u32 {
@@ -250,6 +307,46 @@ impl SizeRules {
         self.m
     }
 
+    /// Set the stretch policy
+    #[inline]
+    pub fn set_stretch(&mut self, stretch: StretchPolicy) {
+        self.stretch = stretch;
+    }
+
+    /// Set whether margins collapse with adjacent margins (chain style)
+    ///
+    /// By default, the margin between two adjacent widgets is the maximum of
+    /// the two margins. If `collapse` is false, margins adjacent to these
+    /// rules are instead added. Rules combined from multiple rules (e.g. via
+    /// [`SizeRules::append`]) opt out if any input does.
+    #[inline]
+    pub fn with_margin_collapse(mut self, collapse: bool) -> Self {
+        self.add_margins = !collapse;
+        self
+    }
+
+    /// Whether margins collapse with adjacent margins
+    ///
+    /// See [`SizeRules::with_margin_collapse`].
+    #[inline]
+    pub fn margin_collapse(self) -> bool {
+        !self.add_margins
+    }
+
+    /// Size of the margin between `self` and `next`, placed after `self`
+    ///
+    /// This is the maximum of `self`'s post-margin and `next`'s pre-margin,
+    /// or the sum where either opts out of collapsing.
+    #[inline]
+    pub fn margin_between(self, next: Self) -> u32 {
+        let (m1, m0) = (self.m.1 as u32, next.m.0 as u32);
+        if self.add_margins || next.add_margins {
+            m1 + m0
+        } else {
+            m1.max(m0)
+        }
+    }
+
     /// Set margins to max of own margins and given margins
     pub fn include_margins(&mut self, margins: (u16, u16)) {
         self.m.0 = self.m.0.max(margins.0);
@@ -264,6 +361,8 @@ impl SizeRules {
             b: self.b.max(rhs.b),
             m: (self.m.0.max(rhs.m.0), self.m.1.max(rhs.m.1)),
             stretch: self.stretch.max(rhs.stretch),
+            trade_off: self.trade_off || rhs.trade_off,
+            add_margins: self.add_margins || rhs.add_margins,
         }
     }
 
@@ -281,11 +380,13 @@ impl SizeRules {
     /// Note also that appending [`SizeRules::EMPTY`] does include interior
     /// margins (those between `EMPTY` and the other rules) within the result.
     pub fn append(&mut self, rhs: SizeRules) {
-        let c = self.m.1.max(rhs.m.0) as u32;
+        let c = self.margin_between(rhs);
         self.a += rhs.a + c;
         self.b += rhs.b + c;
         self.m.1 = rhs.m.1;
         self.stretch = self.stretch.max(rhs.stretch);
+        self.trade_off |= rhs.trade_off;
+        self.add_margins |= rhs.add_margins;
     }
 
     /// Return the rules for self appended by `rhs`
@@ -298,12 +399,14 @@ impl SizeRules {
     /// margins (those between `EMPTY` and the other rules) within the result.
     #[inline]
     pub fn appended(self, rhs: SizeRules) -> Self {
-        let c = self.m.1.max(rhs.m.0) as u32;
+        let c = self.margin_between(rhs);
         SizeRules {
             a: self.a + rhs.a + c,
             b: self.b + rhs.b + c,
             m: (self.m.0, rhs.m.1),
             stretch: self.stretch.max(rhs.stretch),
+            trade_off: self.trade_off || rhs.trade_off,
+            add_margins: self.add_margins || rhs.add_margins,
         }
     }
 
@@ -323,6 +426,8 @@ impl SizeRules {
             b: self.b + frame.b + c,
             m,
             stretch: self.stretch.max(frame.stretch),
+            trade_off: self.trade_off || frame.trade_off,
+            add_margins: self.add_margins || frame.add_margins,
         }
     }
 
@@ -341,10 +446,11 @@ impl SizeRules {
 
         let mut rules = range[0];
         for r in &range[1..] {
-            rules.a += rules.m.1.max(r.m.0) as u32 + r.a;
+            rules.a += rules.margin_between(*r) + r.a;
+            rules.m.1 = r.m.1;
+            rules.add_margins |= r.add_margins;
         }
         rules.b = rules.a;
-        rules.m.1 = range[range.len() - 1].m.1;
         rules
     }
 
@@ -354,6 +460,8 @@ impl SizeRules {
         self.b = self.b + y.b - x.b;
         self.m.1 = y.m.1;
         self.stretch = self.stretch.max(y.stretch);
+        self.trade_off |= y.trade_off;
+        self.add_margins |= y.add_margins;
     }
 
     /// Reduce the minimum size
@@ -393,6 +501,8 @@ impl SizeRules {
     ///     met without decreasing any widths
     /// -   Excess space is divided evenly among members with the highest
     ///     stretch policy
+    /// -   When ideal sizes cannot all be met, members with a trade-off (see
+    ///     [`SizeRules::with_trade_off`]) are preferred up to their ideal size
     ///
     /// Input requirements: `rules.len() == out.len()`.
     ///
@@ -429,7 +539,7 @@ impl SizeRules {
             let mut dist_over_b = out[0].saturating_sub(rules[0].b);
             for i in 1..N {
                 out[i] = out[i].max(rules[i].a);
-                margin_sum += (rules[i - 1].m.1).max(rules[i].m.0) as u32;
+                margin_sum += rules[i - 1].margin_between(rules[i]);
                 sum += out[i];
                 dist_under_b += rules[i].b.saturating_sub(out[i]);
                 dist_over_b += out[i].saturating_sub(rules[i].b);
@@ -506,18 +616,39 @@ impl SizeRules {
                     debug_assert_eq!(target, (0..N).fold(0, |x, i| x + out[i]));
                 } else {
                     // We cannot increase sizes as far as their ideal: instead
-                    // increase over minimum size and under ideal
+                    // increase over minimum size and under ideal. Members
+                    // with a trade-off are increased first (up to ideal).
                     let mut targets = Targets::new();
+                    let mut trade_targets = Targets::new();
                     let mut over = 0;
+                    let mut trade_dist = 0;
                     for i in 0..N {
                         if out[i] < rules[i].b {
                             over += out[i] - rules[i].a;
-                            targets.push(i as u32);
+                            if rules[i].trade_off {
+                                trade_dist += rules[i].b - rules[i].a;
+                                trade_targets.push(i as u32);
+                            } else {
+                                targets.push(i as u32);
+                            }
                         }
                     }
 
                     let avail = target - sum + over;
-                    increase_targets(out, &mut targets, |i| rules[i].a, avail);
+                    if trade_targets.is_empty() {
+                        increase_targets(out, &mut targets, |i| rules[i].a, avail);
+                    } else if avail >= trade_dist {
+                        for i in trade_targets.iter().map(|i| *i as usize) {
+                            out[i] = rules[i].b;
+                        }
+                        let avail = avail - trade_dist;
+                        increase_targets(out, &mut targets, |i| rules[i].a, avail);
+                    } else {
+                        for i in targets.iter().map(|i| *i as usize) {
+                            out[i] = rules[i].a;
+                        }
+                        increase_targets(out, &mut trade_targets, |i| rules[i].a, avail);
+                    }
                     debug_assert_eq!(target, (0..N).fold(0, |x, i| x + out[i]));
                 }
             } else {
@@ -602,17 +733,33 @@ impl SizeRules {
                     // No size can exceed the ideal
                     // First, ensure nothing exceeds the ideal:
                     let mut targets = Targets::new();
+                    let mut trade_targets = Targets::new();
+                    let mut trade_over = 0;
                     sum = 0;
                     for i in 0..N {
                         out[i] = out[i].min(rules[i].b);
                         sum += out[i];
                         if out[i] > rules[i].a {
-                            targets.push(i as u32);
+                            if rules[i].trade_off {
+                                trade_over += out[i] - rules[i].a;
+                                trade_targets.push(i as u32);
+                            } else {
+                                targets.push(i as u32);
+                            }
                         }
                     }
                     if sum > target {
+                        // Members with a trade-off are reduced last
                         let avail = target + margin_sum - total.a;
-                        reduce_targets(out, &mut targets, |i| rules[i].a, avail);
+                        if avail >= trade_over {
+                            let avail = avail - trade_over;
+                            reduce_targets(out, &mut targets, |i| rules[i].a, avail);
+                        } else {
+                            for i in targets.iter().map(|i| *i as usize) {
+                                out[i] = rules[i].a;
+                            }
+                            reduce_targets(out, &mut trade_targets, |i| rules[i].a, avail);
+                        }
                     }
                     debug_assert_eq!(target, (0..N).fold(0, |x, i| x + out[i]));
                 }
@@ -746,3 +893,43 @@ impl<'a> Sum<&'a Self> for SizeRules {
         }
     }
 }
+
+#[test]
+fn solve_seq_trade_off() {
+    let text = SizeRules::new(60, 150, (0, 0), StretchPolicy::LowUtility);
+    let button = SizeRules::new(20, 80, (0, 0), StretchPolicy::Fixed);
+
+    let mut out = [0; 2];
+    SizeRules::solve_seq(&mut out, &[text, button], 140);
+    assert_eq!(out, [90, 50]);
+
+    let text = text.with_trade_off(true);
+    let mut out = [0; 2];
+    SizeRules::solve_seq(&mut out, &[text, button], 140);
+    assert_eq!(out, [120, 20]);
+    SizeRules::solve_seq(&mut out, &[text, button], 200);
+    assert_eq!(out, [150, 50]);
+    SizeRules::solve_seq(&mut out, &[text, button], 160);
+    assert_eq!(out, [140, 20]);
+}
+
+#[test]
+fn margin_collapse() {
+    let a = SizeRules::fixed(10, (2, 6));
+    let b = SizeRules::fixed(20, (4, 3));
+    assert_eq!(a.margin_between(b), 6);
+    assert_eq!(a.appended(b).min_size(), 36);
+    assert_eq!(
+        SizeRules::min_sum(&[a, b, a]).min_size(),
+        10 + 6 + 20 + 3 + 10
+    );
+
+    let b = b.with_margin_collapse(false);
+    assert_eq!(a.margin_between(b), 10);
+    assert_eq!(a.appended(b).min_size(), 40);
+    assert!(!a.appended(b).margin_collapse());
+
+    let mut out = [0; 2];
+    SizeRules::solve_seq(&mut out, &[a, b], 50);
+    assert_eq!(out, [15, 25]);
+}
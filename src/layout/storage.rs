@@ -210,6 +210,8 @@ impl GridStorage for DynGridStorage {
     fn set_dims(&mut self, cols: usize, rows: usize) {
         self.width_rules.resize(cols + 1, SizeRules::EMPTY);
         self.height_rules.resize(rows + 1, SizeRules::EMPTY);
+        self.widths.resize(cols, 0);
+        self.heights.resize(rows, 0);
     }
 
     fn rules_and_widths(&mut self) -> (&mut [SizeRules], &mut [u32]) {
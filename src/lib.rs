@@ -20,9 +20,8 @@
 //! This document is a reference manual. There is currently no tutorial, but the
 //! [examples](https://github.com/kas-gui/kas/tree/master/kas-wgpu/examples)
 //! provide a starting point.
-#![cfg_attr(feature = "nightly", feature(new_uninit))]
+#![cfg_attr(feature = "nightly", feature(new_uninit, backtrace))]
 
-#[cfg(not(feature = "winit"))]
 #[macro_use]
 extern crate bitflags;
 
@@ -35,13 +34,24 @@ mod toolkit;
 mod traits;
 
 // public implementations:
+pub mod action;
 pub mod class;
+pub mod debug;
+#[cfg(feature = "declarative")]
+pub mod declarative;
+pub mod dialog;
+#[cfg(feature = "editor")]
+pub mod document;
 pub mod draw;
 pub mod event;
 pub mod geom;
 pub mod layout;
 pub mod prelude;
+pub mod session;
+#[cfg(feature = "settings")]
+pub mod settings;
 pub mod string;
+pub mod test;
 pub mod widget;
 
 // macro re-exports
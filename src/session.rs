@@ -0,0 +1,101 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Window sessions
+//!
+//! A [`Session`] records the set of open windows, their geometry and the
+//! widget with keyboard navigation focus. Toolkits may produce a session on
+//! exit and restore a session on start-up (for `kas_wgpu`, see
+//! `Toolkit::save_session_on_exit` and `Toolkit::restore_session`).
+//!
+//! Windows are identified by [`kas::Window::session_key`]; on restore, the
+//! application provides a *factory* constructing a window from its key.
+//! Focus and the offsets of scrollable widgets (see
+//! [`kas::WidgetConfig::scroll_offset`]) are recorded by paths of child
+//! indices (see [`kas::WidgetChildren::path_of`]), thus are restored
+//! correctly only where the widget tree is unchanged.
+//!
+//! With feature `settings`, sessions may be saved to a RON file or to a
+//! [`Store`](kas::settings::Store).
+
+#[cfg(feature = "settings")]
+use kas::settings::{Store, StoreError, Value};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "settings")]
+use std::path::Path;
+
+/// Saved state of a window
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowState {
+    /// Key identifying the window (see [`kas::Window::session_key`])
+    pub key: String,
+    /// Outer position of the window, in physical pixels, if known
+    pub position: Option<(i32, i32)>,
+    /// Inner size of the window, in physical pixels
+    pub size: (u32, u32),
+    /// Path to the widget with navigation focus, if any
+    pub focus: Option<Vec<usize>>,
+    /// Paths to scrolled widgets with their offsets
+    ///
+    /// Only non-zero offsets are recorded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll: Vec<(Vec<usize>, (i32, i32))>,
+}
+
+/// Saved state of an application's windows
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Session {
+    /// Windows, in order of creation
+    pub windows: Vec<WindowState>,
+}
+
+impl Session {
+    /// Construct an empty session
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    /// True if the session contains no windows
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+}
+
+#[cfg(feature = "settings")]
+impl Session {
+    /// Load from a RON file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let text = std::fs::read_to_string(path).map_err(StoreError::Io)?;
+        ron::de::from_str(&text).map_err(StoreError::Ron)
+    }
+
+    /// Save to a RON file
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> Result<(), StoreError> {
+        let pretty = ron::ser::PrettyConfig::default();
+        let text = ron::ser::to_string_pretty(self, pretty).map_err(StoreError::Ron)?;
+        std::fs::write(path, text).map_err(StoreError::Io)
+    }
+
+    /// Load from `key` of a [`Store`]
+    ///
+    /// Returns `Ok(None)` if the key is not present.
+    pub fn from_store(store: &Store, key: &str) -> Result<Option<Self>, StoreError> {
+        match store.get_text(key) {
+            Some(text) => ron::de::from_str(text).map(Some).map_err(StoreError::Ron),
+            None => Ok(None),
+        }
+    }
+
+    /// Save to `key` of a [`Store`]
+    ///
+    /// Returns true if the stored value changed.
+    pub fn write_store(&self, store: &mut Store, key: &str) -> Result<bool, StoreError> {
+        let text = ron::ser::to_string(self).map_err(StoreError::Ron)?;
+        Ok(store.set(key, Value::Text(text)))
+    }
+}
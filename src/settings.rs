@@ -0,0 +1,481 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Preferences pages
+//!
+//! A [`Page`] describes a list of settings, each with a key, a label, a kind
+//! and a default value. [`Page::build`] turns this description into a
+//! [`SettingsPage`] widget bound to a [`Store`] of values:
+//!
+//! -   booleans are edited via a [`CheckBox`]
+//! -   choices via a [`ComboBox`]
+//! -   numbers and strings via an [`EditBox`]; input is validated on each edit
+//!     and committed on activation (the Return key) or loss of focus
+//!
+//! A [`Store`] may be loaded from and saved to a
+//! [RON](https://github.com/ron-rs/ron) file.
+//!
+//! Example:
+//! ```notest
+//! let page = Page::new()
+//!     .bool("autosave", "&Save automatically", true)
+//!     .choice("theme", "Theme", &["Flat", "Shaded"], 0)
+//!     .int("history", "History length", 0..=1000, 100)
+//!     .text_with("name", "User name", "", |s| !s.trim().is_empty());
+//! let store = Store::from_file("settings.ron").unwrap_or_default();
+//! let page = page.build(store).on_change(|page, _| {
+//!     let _ = page.store().write_file("settings.ron");
+//! });
+//! ```
+//!
+//! **Feature gated**: this is only available with feature `settings`.
+//!
+//! [`CheckBox`]: crate::widget::CheckBox
+//! [`ComboBox`]: crate::widget::ComboBox
+//! [`EditBox`]: crate::widget::EditBox
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::rc::Rc;
+
+use kas::class::HasText;
+use kas::prelude::*;
+use kas::widget::{BoxColumn, CheckBox, ComboBox, EditBox, EditGuard, Label};
+
+/// A setting value
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+/// A store of setting values, by key
+///
+/// Choices are stored as the text of the chosen item.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Store {
+    values: BTreeMap<String, Value>,
+}
+
+/// Error loading or saving a [`Store`]
+#[derive(Debug)]
+pub enum StoreError {
+    /// File IO error
+    Io(std::io::Error),
+    /// RON parse or serialisation error
+    Ron(ron::Error),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "settings: IO error: {}", e),
+            StoreError::Ron(e) => write!(f, "settings: RON error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl Store {
+    /// Construct an empty store
+    pub fn new() -> Self {
+        Store::default()
+    }
+
+    /// Load from a RON file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let text = std::fs::read_to_string(path).map_err(StoreError::Io)?;
+        ron::de::from_str(&text).map_err(StoreError::Ron)
+    }
+
+    /// Save to a RON file
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> Result<(), StoreError> {
+        let pretty = ron::ser::PrettyConfig::default();
+        let text = ron::ser::to_string_pretty(self, pretty).map_err(StoreError::Ron)?;
+        std::fs::write(path, text).map_err(StoreError::Io)
+    }
+
+    /// Get a value
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    /// Get a boolean value
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.values.get(key) {
+            Some(Value::Bool(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Get an integer value
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        match self.values.get(key) {
+            Some(Value::Int(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Get a floating-point value
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        match self.values.get(key) {
+            Some(Value::Float(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Get a text value (including choices)
+    pub fn get_text(&self, key: &str) -> Option<&str> {
+        match self.values.get(key) {
+            Some(Value::Text(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Set a value, returning true if changed
+    pub fn set<K: Into<String>>(&mut self, key: K, value: Value) -> bool {
+        let key = key.into();
+        if self.values.get(&key) == Some(&value) {
+            return false;
+        }
+        self.values.insert(key, value);
+        true
+    }
+}
+
+/// A change message: key and new value
+#[derive(Clone, Debug, VoidMsg)]
+struct Change {
+    key: String,
+    value: Value,
+}
+
+/// The kind of a setting
+#[derive(Clone)]
+enum Kind {
+    Bool,
+    Choice(Vec<String>),
+    Int(RangeInclusive<i64>),
+    Float(RangeInclusive<f64>),
+    Text(Option<Rc<dyn Fn(&str) -> bool>>),
+}
+
+impl Kind {
+    /// Check the type (and for choices, the value) of a stored value
+    fn accepts(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Kind::Bool, Value::Bool(_)) => true,
+            (Kind::Choice(choices), Value::Text(v)) => choices.contains(v),
+            (Kind::Int(range), Value::Int(v)) => range.contains(v),
+            (Kind::Float(range), Value::Float(v)) => range.contains(v),
+            (Kind::Text(_), Value::Text(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Parse and validate edit-box input
+    fn parse(&self, text: &str) -> Option<Value> {
+        match self {
+            Kind::Int(range) => text
+                .trim()
+                .parse()
+                .ok()
+                .filter(|v| range.contains(v))
+                .map(Value::Int),
+            Kind::Float(range) => text
+                .trim()
+                .parse()
+                .ok()
+                .filter(|v| range.contains(v))
+                .map(Value::Float),
+            Kind::Text(None) => Some(Value::Text(text.to_string())),
+            Kind::Text(Some(f)) if f(text) => Some(Value::Text(text.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// A single setting
+#[derive(Clone)]
+struct Setting {
+    key: String,
+    label: String,
+    kind: Kind,
+    default: Value,
+}
+
+/// Description of a preferences page
+///
+/// Settings are displayed in the order added.
+#[derive(Clone, Default)]
+pub struct Page {
+    settings: Vec<Setting>,
+}
+
+impl Debug for Page {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keys: Vec<_> = self.settings.iter().map(|s| &s.key).collect();
+        write!(f, "Page {{ keys: {:?} }}", keys)
+    }
+}
+
+impl Page {
+    /// Construct an empty page
+    pub fn new() -> Self {
+        Page::default()
+    }
+
+    fn push(mut self, key: &str, label: &str, kind: Kind, default: Value) -> Self {
+        self.settings.push(Setting {
+            key: key.to_string(),
+            label: label.to_string(),
+            kind,
+            default,
+        });
+        self
+    }
+
+    /// Add a boolean setting
+    ///
+    /// The label may include an accelerator key (e.g. `"&Enable"`).
+    pub fn bool(self, key: &str, label: &str, default: bool) -> Self {
+        self.push(key, label, Kind::Bool, Value::Bool(default))
+    }
+
+    /// Add a choice among `choices`, with default `choices[default]`
+    ///
+    /// Panics if `choices` is empty or `default` is out of range.
+    pub fn choice(self, key: &str, label: &str, choices: &[&str], default: usize) -> Self {
+        let choices: Vec<String> = choices.iter().map(|s| s.to_string()).collect();
+        let value = Value::Text(choices[default].clone());
+        self.push(key, label, Kind::Choice(choices), value)
+    }
+
+    /// Add an integer setting restricted to `range`
+    pub fn int(self, key: &str, label: &str, range: RangeInclusive<i64>, default: i64) -> Self {
+        self.push(key, label, Kind::Int(range), Value::Int(default))
+    }
+
+    /// Add a floating-point setting restricted to `range`
+    pub fn float(self, key: &str, label: &str, range: RangeInclusive<f64>, default: f64) -> Self {
+        self.push(key, label, Kind::Float(range), Value::Float(default))
+    }
+
+    /// Add a text setting
+    pub fn text(self, key: &str, label: &str, default: &str) -> Self {
+        self.push(key, label, Kind::Text(None), Value::Text(default.into()))
+    }
+
+    /// Add a text setting with a validator
+    ///
+    /// Input for which `validator` returns false is highlighted as an error
+    /// and not stored.
+    pub fn text_with<F: Fn(&str) -> bool + 'static>(
+        self,
+        key: &str,
+        label: &str,
+        default: &str,
+        validator: F,
+    ) -> Self {
+        let kind = Kind::Text(Some(Rc::new(validator)));
+        self.push(key, label, kind, Value::Text(default.into()))
+    }
+
+    /// Build a widget bound to `store`
+    ///
+    /// Values missing from `store` (or of the wrong type) are replaced by the
+    /// setting's default.
+    pub fn build(self, mut store: Store) -> SettingsPage {
+        let mut widgets: Vec<Box<dyn Widget<Msg = Change>>> = vec![];
+        for setting in self.settings {
+            let value = match store.get(&setting.key) {
+                Some(value) if setting.kind.accepts(value) => value.clone(),
+                _ => {
+                    store.set(setting.key.clone(), setting.default.clone());
+                    setting.default.clone()
+                }
+            };
+            widgets.push(setting.widget(value));
+        }
+
+        SettingsPage {
+            core: Default::default(),
+            list: BoxColumn::new(widgets),
+            store,
+            on_change: None,
+        }
+    }
+}
+
+impl Setting {
+    fn widget(self, value: Value) -> Box<dyn Widget<Msg = Change>> {
+        let key = self.key;
+        match (self.kind, value) {
+            (Kind::Bool, Value::Bool(state)) => {
+                let check = CheckBox::new(self.label).state(state);
+                Box::new(check.on_toggle(move |state| Change {
+                    key: key.clone(),
+                    value: Value::Bool(state),
+                }))
+            }
+            (Kind::Choice(choices), Value::Text(text)) => {
+                let active = choices.iter().position(|c| *c == text).unwrap_or(0);
+                let iter = choices.iter().map(|c| {
+                    let msg = Change {
+                        key: key.clone(),
+                        value: Value::Text(c.clone()),
+                    };
+                    (c.clone(), msg)
+                });
+                let mut combo = ComboBox::new(iter);
+                combo.set_active(active);
+                Box::new(Row::new(self.label, combo))
+            }
+            (kind, value) => {
+                let text = match value {
+                    Value::Bool(v) => v.to_string(),
+                    Value::Int(v) => v.to_string(),
+                    Value::Float(v) => v.to_string(),
+                    Value::Text(v) => v,
+                };
+                let guard = ValueGuard { key, kind };
+                Box::new(Row::new(self.label, EditBox::new(text).with_guard(guard)))
+            }
+        }
+    }
+}
+
+/// Validates edit-box input, emitting a [`Change`] on commit
+struct ValueGuard {
+    key: String,
+    kind: Kind,
+}
+
+impl Debug for ValueGuard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ValueGuard {{ key: {:?}, ... }}", self.key)
+    }
+}
+
+impl ValueGuard {
+    fn commit(edit: &mut EditBox<Self>) -> Option<Change> {
+        let value = edit.guard.kind.parse(edit.get_text());
+        edit.set_error_state(value.is_none());
+        value.map(|value| Change {
+            key: edit.guard.key.clone(),
+            value,
+        })
+    }
+}
+
+impl EditGuard for ValueGuard {
+    type Msg = Change;
+
+    fn activate(edit: &mut EditBox<Self>) -> Option<Change> {
+        Self::commit(edit)
+    }
+
+    fn focus_lost(edit: &mut EditBox<Self>) -> Option<Change> {
+        Self::commit(edit)
+    }
+
+    fn edit(edit: &mut EditBox<Self>) -> Option<Change> {
+        let valid = edit.guard.kind.parse(edit.get_text()).is_some();
+        edit.set_error_state(!valid);
+        None
+    }
+}
+
+/// A labelled control
+#[layout(row)]
+#[handler(msg = Change)]
+#[derive(Debug, Widget)]
+struct Row<W: Widget<Msg = Change>> {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget]
+    label: Label,
+    #[widget]
+    control: W,
+}
+
+impl<W: Widget<Msg = Change>> Row<W> {
+    fn new(label: String, control: W) -> Self {
+        Row {
+            core: Default::default(),
+            layout_data: Default::default(),
+            label: Label::new(label),
+            control,
+        }
+    }
+}
+
+/// A preferences page
+///
+/// Constructed via [`Page::build`]. The page owns its [`Store`], updating it
+/// as settings are changed; its event handler then emits the key of the
+/// changed setting.
+#[layout(single)]
+#[handler(msg = String)]
+#[derive(Widget)]
+pub struct SettingsPage {
+    #[widget_core]
+    core: CoreData,
+    #[widget(handler = handle_change)]
+    list: BoxColumn<Change>,
+    store: Store,
+    on_change: Option<Box<dyn Fn(&SettingsPage, &str)>>,
+}
+
+impl Debug for SettingsPage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SettingsPage {{ core: {:?}, list: {:?}, store: {:?}, ... }}",
+            self.core, self.list, self.store,
+        )
+    }
+}
+
+impl SettingsPage {
+    /// Set a handler called after each change
+    ///
+    /// The handler is passed the page and the key of the changed setting;
+    /// it may for example save the store to a file.
+    pub fn on_change<F: Fn(&SettingsPage, &str) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Access the store
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Take the store, consuming the page
+    pub fn into_store(self) -> Store {
+        self.store
+    }
+
+    fn handle_change(&mut self, _: &mut Manager, change: Change) -> Response<String> {
+        let Change { key, value } = change;
+        if !self.store.set(key.clone(), value) {
+            return Response::None;
+        }
+        if let Some(f) = self.on_change.as_ref() {
+            f(self, &key);
+        }
+        key.into()
+    }
+}
@@ -0,0 +1,992 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Scripted UI testing
+//!
+//! This module provides a deterministic, headless driver for widget trees,
+//! intended for integration tests. A [`Script`] owns a widget (usually a
+//! [`Window`](crate::widget::Window)) and applies a sequence of high-level
+//! actions to it, each resolved against the widget tree via a [`Selector`].
+//!
+//! No toolkit, theme or layout is involved: events are delivered directly to
+//! the target [`WidgetId`]. As a consequence, actions depending on widget
+//! positions (e.g. mouse clicks at a coordinate) and pop-ups are unsupported.
+//! Widgets querying text metrics while handling events (e.g. an `EditBox`)
+//! are given a fixed-metric [`SizeHandle`], measuring text as if set in a
+//! monospace font. Timers use a virtual clock (see [`Script::wait`]).
+//!
+//! Example:
+//! ```
+//! use kas::class::HasBool;
+//! use kas::event::VoidMsg;
+//! use kas::test::{Script, Selector};
+//! use kas::widget::{CheckBoxBare, Window};
+//!
+//! let window = Window::new("Test", CheckBoxBare::new());
+//! let check = Selector::nth("CheckBoxBare", 0);
+//! Script::new(window)
+//!     .expect(&check, |w: &CheckBoxBare<VoidMsg>| !w.get_bool())
+//!     .activate(&check)
+//!     .expect(&check, |w: &CheckBoxBare<VoidMsg>| w.get_bool());
+//! ```
+
+use log::{debug, trace};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::draw::{ImageId, SizeHandle, TextClass, TextLine};
+use crate::event::{ControlKey, Event, Manager, ManagerState, PressSource};
+use crate::event::{UpdateData, UpdateHandle, VoidMsg, WidgetAction};
+use crate::geom::{Coord, Rect, Size, Vec2};
+use crate::layout::{AxisInfo, Margins, SizeRules};
+use crate::string::{CowString, CowStringL};
+use crate::{Align, Popup, ThemeAction, ThemeApi, TkAction, TkWindow};
+use crate::{Widget, WidgetConfig, WidgetId, WindowId};
+
+/// Identifies a widget within a [`Script`]
+///
+/// Selectors are resolved against the widget tree immediately before each
+/// action, thus remain valid across reconfigures.
+pub enum Selector {
+    /// Select a widget by identifier
+    Id(WidgetId),
+    /// Select the `n`-th widget (from zero) with the given
+    /// [`crate::WidgetCore::widget_name`], in depth-first order
+    Nth(&'static str, usize),
+    /// Select a widget by a path of names
+    ///
+    /// See [`crate::WidgetChildren::find_name_path`].
+    Named(String),
+    /// Select the first widget satisfying a predicate, in depth-first order
+    Test(Box<dyn Fn(&dyn WidgetConfig) -> bool>),
+}
+
+impl Selector {
+    /// Construct from a predicate
+    ///
+    /// The predicate is usually implemented via downcasting, for example:
+    /// `|w| w.as_any().downcast_ref::<Label>().map(|l| l.get_text() == "3").unwrap_or(false)`.
+    pub fn new<F: Fn(&dyn WidgetConfig) -> bool + 'static>(f: F) -> Self {
+        Selector::Test(Box::new(f))
+    }
+
+    /// Construct from a widget name and index; see [`Selector::Nth`]
+    pub fn nth(name: &'static str, n: usize) -> Self {
+        Selector::Nth(name, n)
+    }
+
+    /// Construct from a path of names; see [`Selector::Named`]
+    pub fn named<S: ToString>(path: S) -> Self {
+        Selector::Named(path.to_string())
+    }
+
+    /// Find the identifier of the selected widget, if any
+    pub fn find(&self, widget: &dyn WidgetConfig) -> Option<WidgetId> {
+        if let Selector::Named(path) = self {
+            return widget.find_name_path(path).map(|w| w.id());
+        }
+
+        let mut result = None;
+        let mut count = 0;
+        widget.walk_dyn(&mut |w| {
+            if result.is_some() {
+                return;
+            }
+            let found = match self {
+                Selector::Id(id) => w.id() == *id,
+                Selector::Nth(name, n) => {
+                    if w.widget_name() == *name {
+                        count += 1;
+                        count == n + 1
+                    } else {
+                        false
+                    }
+                }
+                Selector::Named(_) => unreachable!(),
+                Selector::Test(f) => f(w),
+            };
+            if found {
+                result = Some(w.id());
+            }
+        });
+        result
+    }
+}
+
+impl From<WidgetId> for Selector {
+    fn from(id: WidgetId) -> Self {
+        Selector::Id(id)
+    }
+}
+
+impl std::fmt::Debug for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Selector::Id(id) => write!(f, "Selector::Id({})", id),
+            Selector::Nth(name, n) => write!(f, "Selector::Nth({:?}, {})", name, n),
+            Selector::Named(path) => write!(f, "Selector::Named({:?})", path),
+            Selector::Test(_) => write!(f, "Selector::Test(..)"),
+        }
+    }
+}
+
+/// Headless [`TkWindow`] used by [`Script`]
+#[derive(Debug, Default)]
+struct ScriptWindow {
+    next_window_id: u32,
+    updates: Vec<(UpdateHandle, u64, Option<UpdateData>)>,
+    action: TkAction,
+    clipboard: Option<String>,
+    /// Size in bytes of each loaded image
+    images: HashMap<ImageId, u64>,
+    next_image: u32,
+}
+
+impl ScriptWindow {
+    fn next_window_id(&mut self) -> WindowId {
+        self.next_window_id += 1;
+        WindowId::new(NonZeroU32::new(self.next_window_id).unwrap())
+    }
+}
+
+impl TkWindow for ScriptWindow {
+    fn add_popup(&mut self, _: Popup) -> WindowId {
+        debug!("Script: pop-ups are not supported");
+        self.next_window_id()
+    }
+
+    fn add_window(&mut self, _: Box<dyn crate::Window>) -> WindowId {
+        debug!("Script: ignoring new window");
+        self.next_window_id()
+    }
+
+    fn close_window(&mut self, _: WindowId) {}
+
+    fn send_action_to(&mut self, _: Option<WindowId>, action: TkAction) {
+        // There is only one window; we assume that is the target
+        self.action += action;
+    }
+
+    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64, data: Option<UpdateData>) {
+        self.updates.push((handle, payload, data));
+    }
+
+    fn get_clipboard(&mut self) -> Option<CowString> {
+        self.clipboard.clone().map(|s| s.into())
+    }
+
+    fn set_clipboard<'c>(&mut self, content: CowStringL<'c>) {
+        self.clipboard = Some(content.to_string());
+    }
+
+    fn adjust_theme(&mut self, _: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {}
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        f(&mut ScriptSizeHandle);
+    }
+
+    fn set_cursor_icon(&mut self, _: crate::event::CursorIcon) {}
+
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        assert_eq!(data.len(), 4 * size.0 as usize * size.1 as usize);
+        let id = ImageId(self.next_image);
+        self.next_image += 1;
+        self.images.insert(id, data.len() as u64);
+        Some(id)
+    }
+
+    fn remove_image(&mut self, id: ImageId) {
+        self.images.remove(&id);
+    }
+
+    fn memory_usage(&self) -> u64 {
+        self.images.values().sum()
+    }
+}
+
+const CHAR_WIDTH: u32 = 8;
+const LINE_HEIGHT: u32 = 16;
+
+/// Headless [`SizeHandle`] used by [`Script`]
+///
+/// Text is measured as if set in a monospace font with each `char`
+/// `CHAR_WIDTH` wide and each line `LINE_HEIGHT` high. Lines are broken only
+/// at `'\n'`; alignment and scale are ignored.
+struct ScriptSizeHandle;
+
+impl ScriptSizeHandle {
+    // Byte ranges of each line, excluding line breaks
+    fn lines(text: &str) -> Vec<Range<usize>> {
+        let mut start = 0;
+        let mut lines = vec![];
+        for (i, _) in text.match_indices('\n') {
+            lines.push(start..i);
+            start = i + 1;
+        }
+        lines.push(start..text.len());
+        lines
+    }
+}
+
+impl SizeHandle for ScriptSizeHandle {
+    fn scale_factor(&self) -> f32 {
+        1.0
+    }
+
+    fn frame(&self) -> Size {
+        Size::uniform(1)
+    }
+
+    fn menu_frame(&self) -> Size {
+        Size::uniform(1)
+    }
+
+    fn inner_margin(&self) -> Size {
+        Size::ZERO
+    }
+
+    fn outer_margins(&self) -> Margins {
+        Margins::ZERO
+    }
+
+    fn line_height(&self, _: TextClass) -> u32 {
+        LINE_HEIGHT
+    }
+
+    fn text_bound(&mut self, text: &str, _: TextClass, axis: AxisInfo) -> SizeRules {
+        let lines = Self::lines(text);
+        let size = match axis.is_horizontal() {
+            true => {
+                let chars = lines.into_iter().map(|r| text[r].chars().count());
+                chars.max().unwrap_or(0) as u32 * CHAR_WIDTH
+            }
+            false => lines.len() as u32 * LINE_HEIGHT,
+        };
+        SizeRules::fixed(size, (0, 0))
+    }
+
+    fn text_index_nearest(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        _: TextClass,
+        _: (Align, Align),
+        pos: Vec2,
+    ) -> usize {
+        let lines = Self::lines(text);
+        let y = (pos.1 - rect.pos.1 as f32) / LINE_HEIGHT as f32;
+        let line = lines[(y.max(0.0) as usize).min(lines.len() - 1)].clone();
+        let x = (pos.0 - rect.pos.0 as f32) / CHAR_WIDTH as f32;
+        let s = &text[line.clone()];
+        let index = s.char_indices().nth(x.round().max(0.0) as usize);
+        line.start + index.map(|(i, _)| i).unwrap_or(s.len())
+    }
+
+    fn text_lines(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        _: TextClass,
+        _: (Align, Align),
+    ) -> Vec<TextLine> {
+        let lines = Self::lines(text).into_iter().enumerate();
+        lines
+            .map(|(i, range)| {
+                let top = rect.pos.1 as f32 + (i as u32 * LINE_HEIGHT) as f32;
+                let bottom = top + LINE_HEIGHT as f32;
+                TextLine { range, top, bottom }
+            })
+            .collect()
+    }
+
+    fn edit_marker_rect(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        _: TextClass,
+        _: (Align, Align),
+        byte: usize,
+    ) -> Rect {
+        let lines = Self::lines(text);
+        let index = lines.iter().rposition(|line| line.start <= byte).unwrap();
+        let col = text[lines[index].start..byte].chars().count() as u32;
+        let offset = Coord(
+            (col * CHAR_WIDTH) as i32,
+            (index as u32 * LINE_HEIGHT) as i32,
+        );
+        Rect::new(rect.pos + offset, Size(1, LINE_HEIGHT))
+    }
+
+    fn button_surround(&self) -> (Size, Size) {
+        (Size::uniform(1), Size::uniform(1))
+    }
+
+    fn edit_surround(&self) -> (Size, Size) {
+        (Size::uniform(1), Size::uniform(1))
+    }
+
+    fn checkbox(&self) -> Size {
+        Size::uniform(LINE_HEIGHT)
+    }
+
+    fn radiobox(&self) -> Size {
+        Size::uniform(LINE_HEIGHT)
+    }
+
+    fn scrollbar(&self) -> (Size, u32) {
+        (Size::uniform(CHAR_WIDTH), 2 * CHAR_WIDTH)
+    }
+
+    fn slider(&self) -> (Size, u32) {
+        (Size::uniform(CHAR_WIDTH), 2 * CHAR_WIDTH)
+    }
+
+    fn scroll_step(&self) -> u32 {
+        LINE_HEIGHT
+    }
+}
+
+/// A deterministic driver for scripted UI tests
+///
+/// Each action method resolves its [`Selector`] and delivers the appropriate
+/// events, then processes any resulting updates. Methods panic with a
+/// descriptive message when a selector cannot be resolved or an expectation
+/// fails, hence are suitable for direct use in `#[test]` functions.
+pub struct Script<W: Widget<Msg = VoidMsg>> {
+    widget: W,
+    mgr: ManagerState,
+    tkw: ScriptWindow,
+    closed: bool,
+}
+
+impl<W: Widget<Msg = VoidMsg>> Script<W> {
+    /// Construct, configuring `widget`
+    ///
+    /// The event manager uses a virtual clock, advanced only by
+    /// [`Script::wait`].
+    pub fn new(mut widget: W) -> Self {
+        let mut mgr = ManagerState::new(1.0);
+        mgr.use_virtual_clock();
+        let mut tkw = ScriptWindow::default();
+        mgr.configure(&mut tkw, &mut widget);
+        Script {
+            widget,
+            mgr,
+            tkw,
+            closed: false,
+        }
+    }
+
+    /// Access the widget
+    pub fn widget(&self) -> &W {
+        &self.widget
+    }
+
+    /// Resolve a selector, panicking on failure
+    pub fn find(&self, selector: &Selector) -> WidgetId {
+        selector
+            .find(self.widget.as_widget())
+            .unwrap_or_else(|| panic!("Script: no widget matches {:?}", selector))
+    }
+
+    /// Send an event to the selected widget
+    pub fn send(&mut self, selector: &Selector, event: Event) -> &mut Self {
+        let id = self.find(selector);
+        if let Some(w) = self.widget.find(id) {
+            trace!("Script: send to {}: {:?}", w.identify(), event);
+        }
+        let widget = &mut self.widget;
+        self.mgr.with(&mut self.tkw, |mgr| {
+            let _ = widget.send(mgr, id, event).apply_action(mgr);
+        });
+        self.update()
+    }
+
+    /// Activate the selected widget (e.g. click a button)
+    pub fn activate(&mut self, selector: &Selector) -> &mut Self {
+        self.send(selector, Event::Activate)
+    }
+
+    /// Get the actions supported by the selected widget
+    ///
+    /// See [`WidgetConfig::actions`]. Disabled widgets support no actions.
+    pub fn actions(&self, selector: &Selector) -> Vec<WidgetAction> {
+        let id = self.find(selector);
+        let w = self.widget.find(id).unwrap();
+        if w.is_disabled() {
+            return vec![];
+        }
+        w.actions().into_iter().map(|(action, _)| action).collect()
+    }
+
+    /// Perform an action on the selected widget
+    ///
+    /// Panics if the widget does not currently support `action` (see
+    /// [`Script::actions`]).
+    pub fn perform(&mut self, selector: &Selector, action: WidgetAction) -> &mut Self {
+        let id = self.find(selector);
+        let w = self.widget.find(id).unwrap();
+        let event = (w.actions().into_iter())
+            .find(|(a, _)| *a == action)
+            .filter(|_| !w.is_disabled())
+            .map(|(_, event)| event)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Script: widget {} ({}) matched by {:?} does not support {:?}",
+                    id,
+                    w.widget_name(),
+                    selector,
+                    action
+                )
+            });
+        self.send(&Selector::Id(id), event)
+    }
+
+    /// Send a control key to the selected widget
+    pub fn control(&mut self, selector: &Selector, key: ControlKey) -> &mut Self {
+        self.send(selector, Event::Control(key))
+    }
+
+    /// Type text into the selected widget
+    ///
+    /// The widget is first activated (which, for an `EditBox`, requests
+    /// character focus), then sent each `char` of `text` in turn.
+    pub fn type_text(&mut self, selector: &Selector, text: &str) -> &mut Self {
+        self.activate(selector);
+        for c in text.chars() {
+            self.send(selector, Event::ReceivedCharacter(c));
+        }
+        self
+    }
+
+    /// Start a touch on the selected widget
+    ///
+    /// The widget receives [`Event::PressStart`] directly (with a zero
+    /// coordinate). Subsequent [`Script::touch_move`] and [`Script::touch_end`]
+    /// actions on the same `touch_id` are routed via the event manager to the
+    /// widget grabbing the touch, if any.
+    pub fn touch_start(&mut self, selector: &Selector, touch_id: u64) -> &mut Self {
+        let event = Event::PressStart {
+            source: PressSource::Touch(touch_id),
+            start_id: self.find(selector),
+            coord: Coord::ZERO,
+            pos: Vec2::ZERO,
+        };
+        self.send(selector, event)
+    }
+
+    /// Move a touch
+    pub fn touch_move(&mut self, touch_id: u64, coord: Coord) -> &mut Self {
+        let widget = &mut self.widget;
+        self.mgr.with(&mut self.tkw, |mgr| {
+            mgr.handle_touch_move(widget, touch_id, coord, coord.into());
+        });
+        self.update()
+    }
+
+    /// End a touch
+    pub fn touch_end(&mut self, touch_id: u64) -> &mut Self {
+        let widget = &mut self.widget;
+        self.mgr.with(&mut self.tkw, |mgr| {
+            mgr.handle_touch_end(widget, touch_id, Coord::ZERO, Vec2::ZERO, false);
+        });
+        self.update()
+    }
+
+    /// Move the mouse cursor
+    pub fn move_cursor(&mut self, coord: Coord) -> &mut Self {
+        let widget = &mut self.widget;
+        self.mgr.with(&mut self.tkw, |mgr| {
+            mgr.handle_cursor_moved(widget, coord, coord.into());
+        });
+        self.update()
+    }
+
+    /// Report file drag-and-drop events
+    ///
+    /// `f` may report events via [`Manager::handle_hovered_file`] and similar
+    /// methods; these are delivered in order on the following update.
+    pub fn drag_files<F: FnOnce(&mut Manager)>(&mut self, f: F) -> &mut Self {
+        self.with_manager(f)
+    }
+
+    /// Call `f` with the event manager, then process any resulting updates
+    ///
+    /// This may be used e.g. to schedule timer updates.
+    pub fn with_manager<F: FnOnce(&mut Manager)>(&mut self, f: F) -> &mut Self {
+        self.mgr.with(&mut self.tkw, f);
+        self.update()
+    }
+
+    /// Advance the clock by `duration`, then deliver any timer updates now due
+    ///
+    /// This does not sleep: the event manager's virtual clock is advanced.
+    /// See [`Manager::update_after`] and [`Manager::update_every`].
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        self.mgr.advance_clock(duration);
+        let widget = &mut self.widget;
+        self.mgr.with(&mut self.tkw, |mgr| mgr.update_timer(widget));
+        self.update()
+    }
+
+    /// Trigger an update handle, as [`crate::event::Manager::trigger_update`]
+    pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) -> &mut Self {
+        self.tkw.updates.push((handle, payload, None));
+        self.update()
+    }
+
+    /// Check a property of the selected widget
+    ///
+    /// Panics if the widget is not of type `T` or `f` returns false.
+    pub fn expect<T, F>(&mut self, selector: &Selector, f: F) -> &mut Self
+    where
+        T: 'static,
+        F: FnOnce(&T) -> bool,
+    {
+        let id = self.find(selector);
+        let w = self.widget.find(id).unwrap();
+        let w = w.as_any().downcast_ref::<T>().unwrap_or_else(|| {
+            panic!(
+                "Script: widget {} ({}) matched by {:?} is not of the expected type",
+                id,
+                w.widget_name(),
+                selector
+            )
+        });
+        if !f(w) {
+            panic!("Script: expectation failed for {:?}", selector);
+        }
+        self
+    }
+
+    /// Destroy the widget tree, as a toolkit does when closing a window
+    ///
+    /// This calls [`WidgetConfig::destroy`] on each widget; see
+    /// [`crate::event::Manager::destroy`]. No further actions should be
+    /// applied afterwards.
+    pub fn destroy(&mut self) -> &mut Self {
+        let widget = &mut self.widget;
+        self.mgr.with(&mut self.tkw, |mgr| mgr.destroy(widget));
+        self.closed = true;
+        self
+    }
+
+    /// Memory used by images loaded via [`crate::event::Manager::load_image`]
+    ///
+    /// Images are simulated; the size of each is that of its pixel data.
+    pub fn memory_usage(&self) -> u64 {
+        self.tkw.memory_usage()
+    }
+
+    /// Access the event manager state
+    ///
+    /// This may be used to check input state, e.g.
+    /// [`ManagerState::is_depressed`].
+    pub fn manager(&self) -> &ManagerState {
+        &self.mgr
+    }
+
+    /// True if the window has requested to close
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn update(&mut self) -> &mut Self {
+        loop {
+            // Deliver in the order triggered; updates triggered meanwhile are
+            // delivered on the next iteration
+            let updates = std::mem::take(&mut self.tkw.updates);
+            for (handle, payload, data) in updates {
+                let widget = &mut self.widget;
+                self.mgr.with(&mut self.tkw, |mgr| {
+                    mgr.update_handle(widget, handle, payload, data);
+                });
+            }
+
+            let action = std::mem::take(&mut self.tkw.action);
+            self.mgr.send_action(action);
+            let action = self.mgr.update(&mut self.tkw, &mut self.widget);
+            if action.intersects(TkAction::CLOSE | TkAction::CLOSE_ALL) {
+                self.closed = true;
+                break;
+            }
+            let reconfigure = action.contains(TkAction::RECONFIGURE);
+            if reconfigure {
+                self.mgr.configure(&mut self.tkw, &mut self.widget);
+            }
+            if self.tkw.updates.is_empty() && !reconfigure {
+                break;
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod recorder {
+    use kas::event::UpdateHandle;
+    use kas::prelude::*;
+
+    /// Test widget recording the events it receives
+    ///
+    /// On [`Event::Activate`], triggers `handle` with each of `payloads` in turn.
+    #[handler(handle=noauto)]
+    #[widget(config=noauto)]
+    #[derive(Clone, Debug, Widget)]
+    pub struct Recorder {
+        #[widget_core]
+        core: CoreData,
+        handle: UpdateHandle,
+        payloads: Vec<u64>,
+        pub events: Vec<Event>,
+    }
+
+    impl Recorder {
+        pub fn new(handle: UpdateHandle, payloads: Vec<u64>) -> Self {
+            Recorder {
+                core: Default::default(),
+                handle,
+                payloads,
+                events: vec![],
+            }
+        }
+
+        /// Payloads received via [`Event::HandleUpdate`]
+        pub fn updates(&self) -> Vec<u64> {
+            (self.events.iter())
+                .filter_map(|event| match event {
+                    Event::HandleUpdate { payload, .. } => Some(*payload),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    impl WidgetConfig for Recorder {
+        fn configure(&mut self, mgr: &mut Manager) {
+            mgr.update_on_handle(self.handle, self.id());
+        }
+    }
+
+    impl Layout for Recorder {
+        fn size_rules(&mut self, _: &mut dyn SizeHandle, _: AxisInfo) -> SizeRules {
+            SizeRules::EMPTY
+        }
+
+        fn draw(&self, _: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {}
+    }
+
+    impl event::Handler for Recorder {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<VoidMsg> {
+            if event == Event::Activate {
+                for payload in &self.payloads {
+                    mgr.trigger_update(self.handle, *payload);
+                }
+            }
+            self.events.push(event);
+            Response::None
+        }
+    }
+}
+
+#[test]
+fn updates_in_order() {
+    use crate::widget::Window;
+    use recorder::Recorder;
+
+    let handle = UpdateHandle::new();
+    let recorder = Recorder::new(handle, vec![1, 2, 3]);
+    let mut script = Script::new(Window::new("Updates", recorder));
+    let recorder = Selector::nth("Recorder", 0);
+    script
+        .activate(&recorder)
+        .expect(&recorder, |w: &Recorder| w.updates() == [1, 2, 3]);
+}
+
+#[test]
+fn select_via_parent() {
+    use crate::widget::{ComboBox, Row, TabbedStack, WidgetExt, Window};
+    use crate::Boxed;
+    use recorder::Recorder;
+
+    let handle = UpdateHandle::new();
+    let tabs = TabbedStack::new()
+        .with_page("One", Recorder::new(handle, vec![]))
+        .with_page("Two", Recorder::new(handle, vec![]));
+    let combo = ComboBox::new(&[("one", 1), ("two", 2)]).discard_msg();
+    let window = Window::new("Select", Row::new(vec![tabs.boxed(), combo.boxed()]));
+    let mut script = Script::new(window);
+
+    let tabs = Selector::nth("TabbedStack", 0);
+    script
+        .send(&Selector::nth("Tab", 1), Event::Select)
+        .expect(&tabs, |w: &TabbedStack<Recorder>| w.active_index() == 1)
+        .activate(&Selector::nth("Tab", 0))
+        .expect(&tabs, |w: &TabbedStack<Recorder>| w.active_index() == 0);
+
+    let combo = Selector::nth("ComboBox", 0);
+    script
+        .send(&Selector::nth("MenuEntry", 1), Event::Select)
+        .expect(&combo, |w: &ComboBox<i32>| w.active() == 1);
+}
+
+#[test]
+fn files_drag_and_drop() {
+    use crate::geom::Rect;
+    use crate::widget::Row;
+    use crate::WidgetCore;
+    use recorder::Recorder;
+    use std::path::PathBuf;
+
+    let handle = UpdateHandle::new();
+    let mut row = Row::new(vec![Recorder::new(handle, vec![]); 2]);
+    row.core_data_mut().rect = Rect::new(Coord::ZERO, Size(20, 10));
+    row[0].core_data_mut().rect = Rect::new(Coord::ZERO, Size(10, 10));
+    row[1].core_data_mut().rect = Rect::new(Coord(10, 0), Size(10, 10));
+    let mut script = Script::new(row);
+    let row = Selector::nth("List", 0);
+    let files = |w: &Row<Recorder>, n: usize| -> Vec<Event> {
+        let events = w[n].events.iter();
+        events
+            .filter(|ev| ev.name().starts_with("Files"))
+            .cloned()
+            .collect()
+    };
+    let path = |s: &str| PathBuf::from(s);
+
+    // Files are dropped on the widget which saw the hover, even if the cursor
+    // has since moved
+    script
+        .drag_files(|mgr| mgr.handle_hovered_file(path("a")))
+        .move_cursor(Coord(15, 5))
+        .drag_files(|mgr| mgr.handle_dropped_file(path("a")));
+    let expected = vec![
+        Event::FilesHover(vec![path("a")]),
+        Event::FilesDrop(vec![path("a")]),
+    ];
+    script.expect(&row, |w: &Row<Recorder>| files(w, 0) == expected);
+
+    // Events are delivered in order of arrival
+    script.drag_files(|mgr| {
+        mgr.handle_hovered_file(path("b"));
+        mgr.handle_hovered_file_cancelled();
+        mgr.handle_hovered_file(path("c"));
+        mgr.handle_hovered_file(path("d"));
+        mgr.handle_dropped_file(path("c"));
+    });
+    let expected = vec![
+        Event::FilesHover(vec![path("b")]),
+        Event::FilesHoverCancelled,
+        Event::FilesHover(vec![path("c"), path("d")]),
+        Event::FilesDrop(vec![path("c")]),
+    ];
+    script.expect(&row, |w: &Row<Recorder>| files(w, 1) == expected);
+}
+
+#[test]
+fn timer_updates() {
+    use crate::widget::Window;
+    use recorder::Recorder;
+
+    let recorder = Recorder::new(UpdateHandle::new(), vec![]);
+    let mut script = Script::new(Window::new("Timers", recorder));
+    let recorder = Selector::nth("Recorder", 0);
+    let id = script.find(&recorder);
+    let ms = Duration::from_millis;
+    let timers = |n: usize| {
+        move |w: &Recorder| {
+            w.events
+                .iter()
+                .filter(|ev| **ev == Event::TimerUpdate)
+                .count()
+                == n
+        }
+    };
+
+    // One-shot updates are delivered once, including via the deprecated name
+    script
+        .with_manager(|mgr| mgr.update_after(ms(1), id))
+        .wait(ms(10))
+        .expect(&recorder, timers(1))
+        .wait(ms(10))
+        .expect(&recorder, timers(1));
+    #[allow(deprecated)]
+    script
+        .with_manager(|mgr| mgr.update_on_timer(ms(1), id))
+        .wait(ms(10))
+        .expect(&recorder, timers(2));
+
+    // The clock is virtual: only time waited counts
+    script
+        .with_manager(|mgr| mgr.update_after(Duration::from_secs(60), id))
+        .wait(Duration::from_secs(59))
+        .expect(&recorder, timers(2))
+        .wait(Duration::from_secs(1))
+        .expect(&recorder, timers(3));
+
+    // Repeating updates continue until cancelled
+    script
+        .with_manager(|mgr| mgr.update_every(ms(5), id))
+        .wait(ms(20))
+        .expect(&recorder, timers(4))
+        .wait(ms(20))
+        .expect(&recorder, timers(5))
+        .with_manager(|mgr| mgr.cancel_updates(id))
+        .wait(ms(20))
+        .expect(&recorder, timers(5));
+}
+
+#[test]
+fn type_text() {
+    use crate::class::HasText;
+    use crate::widget::{EditBox, EditBoxVoid, Window};
+
+    let mut script = Script::new(Window::new("Edit", EditBox::new("")));
+    let edit = Selector::nth("EditBox", 0);
+    let text = |s: &'static str| move |w: &EditBoxVoid| w.get_text() == s;
+    script
+        .type_text(&edit, "Hello")
+        .expect(&edit, text("Hello"))
+        .type_text(&edit, ", world!")
+        .expect(&edit, text("Hello, world!"))
+        .control(&edit, ControlKey::Home)
+        .type_text(&edit, "> ")
+        .expect(&edit, text("> Hello, world!"));
+}
+
+#[test]
+fn multi_touch_grabs() {
+    use crate::widget::{Row, Slider, WidgetExt, Window};
+    use crate::Right;
+
+    let sliders = (0..2).map(|_| Slider::<i32, Right>::new(0, 10, 1).discard_msg());
+    let window = Window::new("Mixer", Row::new(sliders.collect()));
+    let mut script = Script::new(window);
+    let handle = |n| Selector::nth("DragHandle", n);
+    let depressed = |script: &Script<_>, n| {
+        let id = script.find(&handle(n));
+        script.manager().is_depressed(id)
+    };
+
+    // Two touches grab distinct widgets simultaneously
+    script.touch_start(&handle(0), 1).touch_start(&handle(1), 2);
+    assert!(depressed(&script, 0) && depressed(&script, 1));
+
+    // Motion and release are routed per touch
+    script.touch_move(2, Coord(5, 0)).touch_end(1);
+    assert!(!depressed(&script, 0) && depressed(&script, 1));
+    script.touch_end(2);
+    assert!(!depressed(&script, 0) && !depressed(&script, 1));
+}
+
+#[test]
+fn images_freed_on_close() {
+    use crate::widget::{Image, Row, Window};
+
+    // Repeatedly open and close a window holding images
+    for _ in 0..3 {
+        let images = (0..2).map(|_| Image::new(Size(2, 3), vec![0; 24]));
+        let mut script = Script::new(Window::new("Images", Row::new(images.collect())));
+        assert_eq!(script.memory_usage(), 48);
+        script.destroy();
+        assert_eq!(script.memory_usage(), 0);
+    }
+}
+
+#[test]
+fn animated_image_frames() {
+    use crate::widget::{Image, Window};
+
+    // Three frames of 2×3 pixels, shown for 10, 20 and 30 ms
+    let ms = Duration::from_millis;
+    let frames = (1..4).map(|i| (vec![i; 24], ms(10 * i as u64)));
+    let image = Image::new_animated(Size(2, 3), frames.collect());
+    let mut script = Script::new(Window::new("Animation", image));
+    let image = Selector::nth("Image", 0);
+    let frame = |n| move |w: &Image| w.current_frame() == n && w.image_id().is_some();
+
+    // Only the current frame is loaded
+    script.expect(&image, frame(0));
+    assert_eq!(script.memory_usage(), 24);
+    script
+        .wait(ms(10))
+        .expect(&image, frame(1))
+        .wait(ms(10))
+        .expect(&image, frame(1))
+        .wait(ms(10))
+        .expect(&image, frame(2))
+        .wait(ms(30))
+        .expect(&image, frame(0));
+    assert_eq!(script.memory_usage(), 24);
+    script.destroy();
+    assert_eq!(script.memory_usage(), 0);
+}
+
+#[test]
+fn widget_actions() {
+    use crate::class::HasBool;
+    use crate::widget::{CheckBoxBare, Column, Slider, WidgetExt, Window};
+    use crate::{Boxed, Right};
+
+    let slider = Slider::<i32, Right>::new(0, 2, 1).discard_msg();
+    let check = CheckBoxBare::new().discard_msg();
+    let window = Window::new("Actions", Column::new(vec![slider.boxed(), check.boxed()]));
+    let mut script = Script::new(window);
+    let slider = Selector::nth("Slider", 0);
+    let check = Selector::nth("CheckBoxBare", 0);
+
+    assert_eq!(script.actions(&slider), vec![WidgetAction::Increment]);
+    script
+        .perform(&slider, WidgetAction::Increment)
+        .perform(&slider, WidgetAction::Increment)
+        .expect(&slider, |w: &Slider<i32, Right>| w.value() == 2);
+    assert_eq!(script.actions(&slider), vec![WidgetAction::Decrement]);
+
+    assert_eq!(script.actions(&check), vec![WidgetAction::Toggle]);
+    script
+        .perform(&check, WidgetAction::Toggle)
+        .expect(&check, |w: &CheckBoxBare<VoidMsg>| w.get_bool());
+}
+
+#[test]
+fn slider_page_step() {
+    use crate::widget::{Slider, WidgetExt, Window};
+    use crate::Right;
+
+    // A page is eight steps, limited by the range without overflowing
+    let slider = Slider::<u8, Right>::new(0, 250, 100).discard_msg();
+    let mut script = Script::new(Window::new("Page", slider));
+    let slider = Selector::nth("Slider", 0);
+    script
+        .control(&slider, ControlKey::PageDown)
+        .expect(&slider, |w: &Slider<u8, Right>| w.value() == 250)
+        .control(&slider, ControlKey::PageUp)
+        .expect(&slider, |w: &Slider<u8, Right>| w.value() == 0);
+}
+
+#[test]
+fn edit_notify() {
+    use crate::class::HasText;
+    use crate::widget::{Edit, EditBox, EditGuard, EditNotifier, EditNotify};
+
+    // Programmatic edits are reported on focus loss but not emitted directly
+    let mut edit = EditBox::new("").with_notify(EditNotify::FocusOut);
+    assert_eq!(EditNotifier::focus_lost(&mut edit), None);
+    let _ = edit.set_text("ab");
+    assert_eq!(EditNotifier::edit(&mut edit), None);
+    let changed = Some(Edit::Changed("ab".into()));
+    assert_eq!(EditNotifier::focus_lost(&mut edit), changed);
+    assert_eq!(EditNotifier::focus_lost(&mut edit), None);
+    let submitted = Some(Edit::Submitted("ab".into()));
+    assert_eq!(EditNotifier::activate(&mut edit), submitted);
+
+    edit.guard.set_notify(EditNotify::Keystroke);
+    assert_eq!(EditNotifier::edit(&mut edit), changed);
+    assert_eq!(EditNotifier::focus_lost(&mut edit), None);
+}
@@ -14,9 +14,12 @@
 //!
 //! [winit]: https://github.com/rust-windowing/winit
 
+#[cfg(feature = "raw-window-handle")]
+use raw_window_handle::RawWindowHandle;
 use std::num::NonZeroU32;
 
-use crate::draw::SizeHandle;
+use crate::draw::{ImageId, SizeHandle};
+use crate::geom::Size;
 use crate::string::{CowString, CowStringL};
 use crate::{event, ThemeAction, ThemeApi};
 
@@ -36,70 +39,126 @@ impl WindowId {
     }
 }
 
-/// Action required after processing
-///
-/// This type is returned by many widgets on modification to self and is tracked
-/// internally by [`event::Manager`] to determine which updates are needed to
-/// the UI.
-///
-/// All variants are *progressive*: e.g. `Reconfigure` implies all actions
-/// needed to handle `Popup`, `RegionMoved` and `Redraw`.
-///
-/// Two `TkAction` values may be combined by taking their maximum. Since this
-/// is a common operation, the `+` operator is defined to do this job, together
-/// with `+=` on `TkAction` and [`event::Manager`].
-///
-/// Users receiving a value of this type from a widget update method should
-/// generally call `*mgr += action;` during event handling. Prior to
-/// starting the event loop (`toolkit.run()`), these values can be ignored.
-#[must_use]
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
-pub enum TkAction {
-    /// No action needed
-    None,
-    /// Whole window requires redrawing
-    ///
-    /// Note that [`Manager::redraw`] can instead be used for more selective
-    /// redrawing, if supported by the toolkit.
-    ///
-    /// [`Manager::redraw`]: crate::event::Manager::redraw
-    Redraw,
-    /// Some widgets within a region moved
-    ///
-    /// This action should be emitted when e.g. a scroll-region is moved or
-    /// widget layout is adjusted to allow for the fact that coordinates
-    /// (e.g. mouse position) have changed relative to widgets.
-    // NOTE: one could specify a Rect here, but there's not much advantage
-    RegionMoved,
-    /// A pop-up opened/closed/needs resizing
-    Popup,
-    /// Whole window requires reconfiguring
-    ///
-    /// *Configuring* widgets assigns [`WidgetId`] identifiers and calls
-    /// [`kas::WidgetConfig::configure`].
-    ///
-    /// [`WidgetId`]: crate::WidgetId
-    /// [`event::Manager`]: crate::event::Manager
-    Reconfigure,
-    /// The window or pop-up should be closed
-    Close,
-    /// All windows should close (toolkit exit)
-    CloseAll,
+bitflags! {
+    /// Action required after processing
+    ///
+    /// This type is returned by many widgets on modification to self and is
+    /// tracked internally by [`event::Manager`] to determine which updates are
+    /// needed to the UI.
+    ///
+    /// This is a set of flags: actions are combined by union (via `|`, `+`, or
+    /// `+=` on `TkAction` and [`event::Manager`]; an iterator of actions may be
+    /// combined with `sum()`). Combining is commutative and associative, thus
+    /// the order in which actions are added does not matter, and independent
+    /// actions (e.g. `REDRAW` and `CLOSE`) are never lost. The empty set
+    /// ([`TkAction::empty`]) means no action is required.
+    ///
+    /// Some flags imply others (see [`TkAction::implies`]): handling
+    /// `RECONFIGURE` also handles `POPUP`, which handles `REGION_MOVED`, which
+    /// handles `REDRAW`; `CLOSE_ALL` handles `CLOSE`. Toolkits should thus
+    /// handle only the most comprehensive of these flags.
+    ///
+    /// Users receiving a value of this type from a widget update method should
+    /// generally call `*mgr += action;` during event handling. Prior to
+    /// starting the event loop (`toolkit.run()`), these values can be ignored.
+    ///
+    /// Actions added to a [`event::Manager`] accumulate until handling of the
+    /// current event (including resulting update-handle and pop-up
+    /// notifications) is complete; the combined action is then applied to the
+    /// window once. A parent may intercept actions of its children via
+    /// [`event::Manager::pop_action`] (e.g. to close a pop-up on `CLOSE`).
+    /// Actions apply to the window handling the event, unless sent via
+    /// [`event::Manager::send_action_to`] or
+    /// [`event::Manager::send_action_all`]; thus e.g. `RECONFIGURE` in one
+    /// window and `REDRAW` in another may be requested while handling one
+    /// event. Each window combines received actions independently.
+    #[must_use]
+    #[derive(Default)]
+    pub struct TkAction: u32 {
+        /// Whole window requires redrawing
+        ///
+        /// Note that [`Manager::redraw`] can instead be used for more selective
+        /// redrawing, if supported by the toolkit.
+        ///
+        /// [`Manager::redraw`]: crate::event::Manager::redraw
+        const REDRAW = 1 << 0;
+        /// Some widgets within a region moved
+        ///
+        /// This action should be emitted when e.g. a scroll-region is moved or
+        /// widget layout is adjusted to allow for the fact that coordinates
+        /// (e.g. mouse position) have changed relative to widgets.
+        // NOTE: one could specify a Rect here, but there's not much advantage
+        const REGION_MOVED = 1 << 1;
+        /// A pop-up opened/closed/needs resizing
+        const POPUP = 1 << 2;
+        /// Whole window requires reconfiguring
+        ///
+        /// *Configuring* widgets assigns [`WidgetId`] identifiers and calls
+        /// [`kas::WidgetConfig::configure`].
+        ///
+        /// [`WidgetId`]: crate::WidgetId
+        const RECONFIGURE = 1 << 3;
+        /// The window or pop-up should be closed
+        const CLOSE = 1 << 4;
+        /// All windows should close (toolkit exit)
+        const CLOSE_ALL = 1 << 5;
+    }
+}
+
+impl TkAction {
+    /// Add the flags implied by those set
+    ///
+    /// See [`TkAction`] for the implications between flags.
+    pub fn with_implied(self) -> Self {
+        let mut action = self;
+        if action.contains(TkAction::RECONFIGURE) {
+            action |= TkAction::POPUP;
+        }
+        if action.contains(TkAction::POPUP) {
+            action |= TkAction::REGION_MOVED;
+        }
+        if action.contains(TkAction::REGION_MOVED) {
+            action |= TkAction::REDRAW;
+        }
+        if action.contains(TkAction::CLOSE_ALL) {
+            action |= TkAction::CLOSE;
+        }
+        action
+    }
+
+    /// True if handling `self` also handles `other`
+    ///
+    /// This is true when every flag of `other` is set in `self` or is implied
+    /// by a flag set in `self` (see [`TkAction::with_implied`]).
+    #[inline]
+    pub fn implies(self, other: TkAction) -> bool {
+        self.with_implied().contains(other)
+    }
 }
 
+impl std::iter::Sum for TkAction {
+    fn sum<I: Iterator<Item = TkAction>>(iter: I) -> Self {
+        iter.fold(TkAction::empty(), |a, b| a | b)
+    }
+}
+
+// Addition is kept from when actions were ordered by precedence: combining two
+// action sets is their union.
 impl std::ops::Add for TkAction {
     type Output = Self;
 
     #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn add(self, rhs: TkAction) -> Self {
-        self.max(rhs)
+        self | rhs
     }
 }
 
 impl std::ops::AddAssign for TkAction {
     #[inline]
+    #[allow(clippy::suspicious_op_assign_impl)]
     fn add_assign(&mut self, rhs: TkAction) {
-        *self = (*self).max(rhs);
+        *self |= rhs;
     }
 }
 
@@ -129,11 +188,22 @@ pub trait TkWindow {
     /// Close a window
     fn close_window(&mut self, id: WindowId);
 
+    /// Send an action to another window
+    ///
+    /// The action should be applied to window `id` or, if `None`, to all
+    /// windows, after handling of the current event completes.
+    fn send_action_to(&mut self, id: Option<WindowId>, action: TkAction);
+
     /// Updates all subscribed widgets
     ///
     /// All widgets subscribed to the given [`event::UpdateHandle`], across all
     /// windows, will receive an update.
-    fn trigger_update(&mut self, handle: event::UpdateHandle, payload: u64);
+    fn trigger_update(
+        &mut self,
+        handle: event::UpdateHandle,
+        payload: u64,
+        data: Option<event::UpdateData>,
+    );
 
     /// Attempt to get clipboard contents
     ///
@@ -144,6 +214,19 @@ pub trait TkWindow {
     /// Attempt to set clipboard contents
     fn set_clipboard<'c>(&mut self, content: CowStringL<'c>);
 
+    /// Attempt to get the primary selection contents
+    ///
+    /// The primary selection is a convention of X11 and Wayland desktops.
+    /// The default implementation returns `None`.
+    fn get_primary(&mut self) -> Option<CowString> {
+        None
+    }
+
+    /// Attempt to set the primary selection contents
+    ///
+    /// The default implementation does nothing.
+    fn set_primary<'c>(&mut self, _content: CowStringL<'c>) {}
+
     /// Adjust the theme
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction);
 
@@ -156,6 +239,47 @@ pub trait TkWindow {
 
     /// Set the mouse cursor
     fn set_cursor_icon(&mut self, icon: event::CursorIcon);
+
+    /// Load an image
+    ///
+    /// See [`crate::draw::DrawShared::load_image`].
+    /// The default implementation returns `None` (images are not supported).
+    fn load_image(&mut self, size: Size, data: &[u8]) -> Option<ImageId> {
+        let _ = (size, data);
+        None
+    }
+
+    /// Free an image
+    ///
+    /// The default implementation does nothing.
+    fn remove_image(&mut self, id: ImageId) {
+        let _ = id;
+    }
+
+    /// Approximate memory used by resources shared between windows, in bytes
+    ///
+    /// See [`crate::draw::DrawShared::memory_usage`].
+    /// The default implementation returns zero.
+    fn memory_usage(&self) -> u64 {
+        0
+    }
+
+    /// Lock or release the mouse pointer
+    ///
+    /// When locked, the cursor should be hidden and confined to the window.
+    /// Returns true on success. The default implementation returns false.
+    fn set_pointer_lock(&mut self, lock: bool) -> bool {
+        let _ = lock;
+        false
+    }
+
+    /// Get the raw handle of the native window, if any
+    ///
+    /// The default implementation returns `None`.
+    #[cfg(feature = "raw-window-handle")]
+    fn raw_window_handle(&self) -> Option<RawWindowHandle> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -163,10 +287,15 @@ mod test {
     use super::*;
 
     #[test]
-    fn action_precedence() {
-        assert!(TkAction::None < TkAction::Redraw);
-        assert!(TkAction::Redraw < TkAction::Reconfigure);
-        assert!(TkAction::Reconfigure < TkAction::Close);
-        assert!(TkAction::Close < TkAction::CloseAll);
+    fn action_combination() {
+        let action = TkAction::REDRAW + TkAction::CLOSE;
+        assert!(action.contains(TkAction::REDRAW) && action.contains(TkAction::CLOSE));
+        assert_eq!(TkAction::empty() + TkAction::REDRAW, TkAction::REDRAW);
+
+        assert!(TkAction::RECONFIGURE.implies(TkAction::REDRAW | TkAction::POPUP));
+        assert!(!TkAction::REDRAW.implies(TkAction::REGION_MOVED));
+        assert!(!TkAction::CLOSE.implies(TkAction::REDRAW));
+        assert!(TkAction::CLOSE_ALL.implies(TkAction::CLOSE));
+        assert!(TkAction::REDRAW.implies(TkAction::empty()));
     }
 }
@@ -7,11 +7,12 @@
 
 use std::any::Any;
 use std::fmt;
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
 
 use crate::draw::SizeHandle;
 use crate::event::{self, Manager};
-use crate::{layout, Direction, WidgetId, WindowId};
+use crate::{layout, Align, Direction, WidgetId, WindowId};
 
 mod impls;
 mod utils;
@@ -73,11 +74,57 @@ pub struct Popup {
     pub direction: Direction,
 }
 
+/// A widget drawn over window content, anchored to another widget
+///
+/// An overlay is like a [`Popup`] drawn as a layer in the current window, but
+/// is not modal: it does not take focus, is not closed by input elsewhere and
+/// does not affect event handling, except that it hides content beneath it.
+/// Uses include badges, validation messages and hints.
+///
+/// The overlay widget `id` is positioned relative to the `anchor` widget
+/// according to its [`Placement`], and moves with the anchor whenever the
+/// window is resized.
+///
+/// As with a pop-up, the overlay widget should be a permanent child of some
+/// widget, but is not visible until [`Manager::add_overlay`] is called, and
+/// its parent must not call any [`Layout`] methods on it.
+///
+/// Overlays are managed by the window (see [`event::Event::ShowOverlay`]);
+/// windows not supporting them (e.g. simple dialogs) ignore requests.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overlay {
+    pub id: WidgetId,
+    pub anchor: WidgetId,
+    pub placement: Placement,
+}
+
+/// Placement of an [`Overlay`] relative to its anchor
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// Over the anchor, aligned within its rect (horizontal, vertical)
+    ///
+    /// The overlay is sized to its ideal size, except that [`Align::Stretch`]
+    /// matches the anchor's size. It may extend beyond the anchor's rect (but
+    /// not beyond the window).
+    Over(Align, Align),
+    /// Next to the anchor, in the given direction (if possible)
+    ///
+    /// This is the same placement as used for a [`Popup`].
+    Beside(Direction),
+}
+
 /// Functionality required by a window
 pub trait Window: Widget<Msg = event::VoidMsg> {
     /// Get the window title
     fn title(&self) -> &str;
 
+    /// Get the key identifying this window in a saved session
+    ///
+    /// By default this is the window title. See [`kas::session`].
+    fn session_key(&self) -> &str {
+        self.title()
+    }
+
     /// Whether to limit the maximum size of a window
     ///
     /// All widgets' size rules allow calculation of two sizes: the minimum
@@ -98,7 +145,7 @@ pub trait Window: Widget<Msg = event::VoidMsg> {
     /// Resize popups
     ///
     /// This is called immediately after [`Layout::set_rect`] to resize
-    /// existing pop-ups.
+    /// existing pop-ups (and any [`Overlay`]s).
     fn resize_popups(&mut self, size_handle: &mut dyn SizeHandle);
 
     /// Trigger closure of a pop-up
@@ -146,6 +193,17 @@ pub trait ThemeApi {
     // TODO: revise scheme identification and error handling?
     fn set_colours(&mut self, _scheme: &str) -> ThemeAction;
 
+    /// Load a colour scheme from a file and apply it
+    ///
+    /// Supported file formats depend on the theme (for themes provided by
+    /// `kas_theme`, see `kas_theme::ThemeColours::from_file`). If loading
+    /// fails, a warning is logged and the scheme is unchanged.
+    ///
+    /// The default implementation does nothing.
+    fn load_colour_scheme(&mut self, _path: &Path) -> ThemeAction {
+        ThemeAction::None
+    }
+
     /// Switch the theme
     ///
     /// Most themes do not react to this method; `kas_theme::MultiTheme` uses
@@ -153,6 +211,22 @@ pub trait ThemeApi {
     fn set_theme(&mut self, _theme: &str) -> ThemeAction {
         ThemeAction::None
     }
+
+    /// List the names of themes available via [`ThemeApi::set_theme`]
+    ///
+    /// The default implementation returns an empty list.
+    fn list_themes(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Access the theme as [`Any`], if supported
+    ///
+    /// This allows adjustment of theme-specific properties at run-time by
+    /// downcasting to the concrete theme type. Wrapping themes (e.g.
+    /// `kas_theme::MultiTheme`) forward to the active theme.
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
 }
 
 impl<T: ThemeApi> ThemeApi for Box<T> {
@@ -162,7 +236,16 @@ impl<T: ThemeApi> ThemeApi for Box<T> {
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
         self.deref_mut().set_colours(scheme)
     }
+    fn load_colour_scheme(&mut self, path: &Path) -> ThemeAction {
+        self.deref_mut().load_colour_scheme(path)
+    }
     fn set_theme(&mut self, theme: &str) -> ThemeAction {
         self.deref_mut().set_theme(theme)
     }
+    fn list_themes(&self) -> Vec<String> {
+        self.deref().list_themes()
+    }
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        self.deref_mut().as_any_mut()
+    }
 }
@@ -10,7 +10,7 @@ use crate::draw::{DrawHandle, SizeHandle};
 use crate::event::{self, Event, Manager, Response};
 use crate::geom::{Coord, Rect};
 use crate::layout::{AxisInfo, SizeRules};
-use crate::{AlignHints, CoreData, WidgetId};
+use crate::{AlignHints, CoreData, TkAction, WidgetId};
 
 impl<M: 'static> WidgetCore for Box<dyn Widget<Msg = M>> {
     fn as_any(&self) -> &dyn Any {
@@ -76,6 +76,16 @@ impl<M: 'static> WidgetConfig for Box<dyn Widget<Msg = M>> {
     fn cursor_icon(&self) -> event::CursorIcon {
         self.as_ref().cursor_icon()
     }
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        self.as_ref().actions()
+    }
+
+    fn scroll_offset(&self) -> Option<Coord> {
+        self.as_ref().scroll_offset()
+    }
+    fn set_scroll_offset(&mut self, offset: Coord) -> TkAction {
+        self.as_mut().set_scroll_offset(offset)
+    }
 }
 
 impl<M: 'static> Layout for Box<dyn Widget<Msg = M>> {
@@ -102,6 +112,9 @@ impl<M: 'static> event::Handler for Box<dyn Widget<Msg = M>> {
     fn activation_via_press(&self) -> bool {
         self.as_ref().activation_via_press()
     }
+    fn press_repeat(&self) -> Option<event::PressRepeat> {
+        self.as_ref().press_repeat()
+    }
 
     fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
         self.as_mut().handle(mgr, event)
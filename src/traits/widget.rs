@@ -13,6 +13,7 @@ use crate::draw::{DrawHandle, InputState, SizeHandle};
 use crate::event::{self, ConfigureManager, Manager, ManagerState};
 use crate::geom::{Coord, Rect};
 use crate::layout::{AxisInfo, SizeRules};
+use crate::string::CowString;
 use crate::{AlignHints, CoreData, TkAction, WidgetId};
 
 impl dyn WidgetCore {
@@ -82,7 +83,7 @@ pub trait WidgetCore: Any + fmt::Debug {
     #[inline]
     fn set_disabled(&mut self, disabled: bool) -> TkAction {
         self.core_data_mut().disabled = disabled;
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 
     /// Set disabled state (chaining)
@@ -102,6 +103,50 @@ pub trait WidgetCore: Any + fmt::Debug {
         self
     }
 
+    /// Get the widget's name, if any
+    ///
+    /// Names are optional, user-assigned strings allowing a widget to be
+    /// found (see [`WidgetChildren::find_named`]) and identified in debug
+    /// logs. Names need not be unique. (Compare [`WidgetCore::widget_name`],
+    /// the name of the widget's type.)
+    #[inline]
+    fn name(&self) -> Option<&str> {
+        self.core_data().name.as_deref()
+    }
+
+    /// Set the widget's name
+    #[inline]
+    fn set_name(&mut self, name: CowString) {
+        self.core_data_mut().name = Some(name);
+    }
+
+    /// Set the widget's name (chaining)
+    ///
+    /// Example:
+    /// ```
+    /// use kas::{WidgetCore, widget::TextButton};
+    /// let button = TextButton::new("&Save", ()).with_name("save");
+    /// ```
+    #[inline]
+    fn with_name<S: Into<CowString>>(mut self, name: S) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_name(name.into());
+        self
+    }
+
+    /// Identify the widget, for use in debug logs
+    ///
+    /// This is the type name followed by the widget's name (if set) or
+    /// otherwise its identifier, e.g. `TextButton"save"` or `TextButton#12`.
+    fn identify(&self) -> String {
+        match self.name() {
+            Some(name) => format!("{}{:?}", self.widget_name(), name),
+            None => format!("{}{}", self.widget_name(), self.id()),
+        }
+    }
+
     /// Get the widget's region, relative to its parent.
     #[inline]
     fn rect(&self) -> Rect {
@@ -145,7 +190,7 @@ pub trait WidgetCore: Any + fmt::Debug {
 /// cannot currently handle fields like `Vec<SomeWidget>`.
 ///
 /// Whenever the number of child widgets changes or child widgets are replaced,
-/// one must send [`TkAction::Reconfigure`].
+/// one must send [`TkAction::RECONFIGURE`].
 /// (TODO: this is slow. Find an option for partial reconfigures. This requires
 /// better widget identifiers; see #91.)
 ///
@@ -170,6 +215,24 @@ pub trait WidgetChildren: WidgetCore {
     /// This method may be removed in the future.
     fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig>;
 
+    /// Construct [`InputState`], including the state of descendants
+    ///
+    /// As [`WidgetCore::input_state`], except that the `hover`, `depress`,
+    /// `nav_focus` and `char_focus` states are true when applicable to this
+    /// widget or any descendant. For example, a composite widget may use this
+    /// to highlight its background while any child is hovered.
+    fn input_state_within(&self, mgr: &ManagerState, disabled: bool) -> InputState {
+        let widget = self.as_widget();
+        InputState {
+            disabled: self.core_data().disabled || disabled,
+            error: false,
+            hover: mgr.is_hovered_within(widget),
+            depress: mgr.is_depressed_within(widget),
+            nav_focus: mgr.nav_focus_within(widget),
+            char_focus: mgr.char_focus_within(widget),
+        }
+    }
+
     /// Check whether `id` is a descendant
     ///
     /// This function assumes that `id` is a valid widget.
@@ -226,6 +289,87 @@ pub trait WidgetChildren: WidgetCore {
         None
     }
 
+    /// Find the path of child indices from self to `id`
+    ///
+    /// The path is empty if `id` is self. Unlike a [`WidgetId`], a path is
+    /// stable across runs of an application (assuming an unchanged widget
+    /// tree), thus is suitable for persistence.
+    ///
+    /// This requires that the widget tree has already been configured.
+    fn path_of(&self, id: WidgetId) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        let mut widget = self.as_widget();
+        loop {
+            if id == widget.id() {
+                return Some(path);
+            } else if id > widget.id() {
+                return None;
+            }
+
+            let index = (0..widget.len()).find(|i| {
+                let child = widget.get(*i);
+                child.map(|w| id <= w.id()).unwrap_or(false)
+            })?;
+            path.push(index);
+            widget = widget.get(index).unwrap();
+        }
+    }
+
+    /// Find a descendant by a path of child indices
+    ///
+    /// This is the inverse of [`WidgetChildren::path_of`].
+    fn find_path(&self, path: &[usize]) -> Option<&dyn WidgetConfig> {
+        let mut widget = self.as_widget();
+        for index in path {
+            widget = widget.get(*index)?;
+        }
+        Some(widget)
+    }
+
+    /// Find self or a descendant by name
+    ///
+    /// Widgets are searched depth-first, parents before children; the first
+    /// widget with the given [`WidgetCore::name`] is returned.
+    fn find_named(&self, name: &str) -> Option<&dyn WidgetConfig> {
+        if self.name() == Some(name) {
+            return Some(self.as_widget());
+        }
+        (0..self.len())
+            .filter_map(|i| self.get(i))
+            .find_map(|w| w.find_named(name))
+    }
+
+    /// Find self or a descendant by name
+    ///
+    /// This is the mutable version of [`WidgetChildren::find_named`].
+    fn find_named_mut(&mut self, name: &str) -> Option<&mut dyn WidgetConfig> {
+        if self.name() == Some(name) {
+            return Some(self.as_widget_mut());
+        }
+        let index = (0..self.len()).find(|i| {
+            let child = self.get(*i);
+            child.and_then(|w| w.find_named(name)).is_some()
+        })?;
+        self.get_mut(index)?.find_named_mut(name)
+    }
+
+    /// Find a widget by a path of names
+    ///
+    /// The path is a list of names separated by `/`. Each component is found
+    /// (see [`WidgetChildren::find_named`]) amongst the descendants of the
+    /// previous component; intermediate unnamed widgets need not be listed.
+    /// For example, `"toolbar/save"` finds a widget named `save` within a
+    /// widget named `toolbar`.
+    fn find_name_path(&self, path: &str) -> Option<&dyn WidgetConfig> {
+        let mut widget = self.as_widget();
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            widget = (0..widget.len())
+                .filter_map(|i| widget.get(i))
+                .find_map(|w| w.find_named(name))?;
+        }
+        Some(widget)
+    }
+
     /// Walk through all widgets, calling `f` once on each.
     ///
     /// This walk is iterative (nonconcurrent), depth-first, and always calls
@@ -288,7 +432,7 @@ pub trait WidgetConfig: Layout {
     /// Configure widget
     ///
     /// Widgets are *configured* on window creation and when
-    /// [`TkAction::Reconfigure`] is sent.
+    /// [`TkAction::RECONFIGURE`] is sent.
     ///
     /// Configure is called before resizing (but after calculation of the
     /// initial window size). This method is called after
@@ -303,6 +447,37 @@ pub trait WidgetConfig: Layout {
     /// The default implementation of this method does nothing.
     fn configure(&mut self, _: &mut Manager) {}
 
+    /// Notification that the widget has been shown
+    ///
+    /// This is called via [`Manager::notify_visibility`] when a hidden widget
+    /// becomes visible, e.g. when a pop-up is opened or a page of a
+    /// [`kas::widget::Stack`] is activated. It is not called for the initial
+    /// state. As with `configure`, children are notified before their parent.
+    ///
+    /// The default implementation of this method does nothing.
+    fn shown(&mut self, _: &mut Manager) {}
+
+    /// Notification that the widget has been hidden
+    ///
+    /// This is the counterpart to [`WidgetConfig::shown`].
+    ///
+    /// The default implementation of this method does nothing.
+    fn hidden(&mut self, _: &mut Manager) {}
+
+    /// Notification that the widget is about to be destroyed
+    ///
+    /// This is called via [`Manager::destroy`] when the window is closed. The
+    /// owner of a widget removed from a container (e.g. via
+    /// [`kas::widget::List::remove`]) may call [`Manager::destroy`] itself.
+    /// As with `configure`, children are notified before their parent.
+    ///
+    /// The manager cancels timers and update-handle subscriptions registered
+    /// by destroyed widgets and releases their focus and grabs afterwards;
+    /// this method need only release resources held elsewhere.
+    ///
+    /// The default implementation of this method does nothing.
+    fn destroy(&mut self, _: &mut Manager) {}
+
     /// Configure self and children
     ///
     /// In most cases one should not override the default implementation of this
@@ -331,6 +506,38 @@ pub trait WidgetConfig: Layout {
     fn cursor_icon(&self) -> event::CursorIcon {
         event::CursorIcon::Default
     }
+
+    /// Get the actions currently supported by this widget
+    ///
+    /// Each [`event::WidgetAction`] is paired with the event which performs it
+    /// when sent to this widget. Actions should not be performed on disabled
+    /// widgets (see [`WidgetCore::is_disabled`]).
+    ///
+    /// Defaults to no actions.
+    fn actions(&self) -> Vec<(event::WidgetAction, event::Event)> {
+        vec![]
+    }
+
+    /// Get the scroll offset, if this widget scrolls its content
+    ///
+    /// Scrollable widgets (e.g. [`kas::widget::ScrollRegion`]) should
+    /// implement this and [`WidgetConfig::set_scroll_offset`]; this allows
+    /// scroll positions to be saved in a [`kas::session::Session`].
+    ///
+    /// Defaults to `None`.
+    fn scroll_offset(&self) -> Option<Coord> {
+        None
+    }
+
+    /// Set the scroll offset
+    ///
+    /// The offset is clamped to the valid range. Toolkits call this when
+    /// restoring a session, after the window has been sized.
+    ///
+    /// The default implementation does nothing.
+    fn set_scroll_offset(&mut self, _: Coord) -> TkAction {
+        TkAction::empty()
+    }
 }
 
 /// Positioning and drawing routines for widgets
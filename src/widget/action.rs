@@ -0,0 +1,266 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widgets bound to an [`Action`]
+
+use std::fmt::Debug;
+
+use super::Menu;
+use kas::action::Action;
+use kas::draw::TextClass;
+use kas::prelude::*;
+
+/// A push-button bound to an [`Action`]
+///
+/// This is similar to [`TextButton`](super::TextButton), but takes its label,
+/// shortcut, enabled state and message from the action. This widget is
+/// suitable for toolbars. Checkable actions are drawn depressed when checked.
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+#[derive(Clone, Debug, Widget)]
+pub struct ActionButton<M: Clone + Debug + 'static> {
+    #[widget_core]
+    core: kas::CoreData,
+    label: AccelString,
+    action: Action<M>,
+}
+
+impl<M: Clone + Debug + 'static> WidgetConfig for ActionButton<M> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        configure(self.id(), &self.label, &self.action, mgr);
+    }
+
+    fn key_nav(&self) -> bool {
+        self.action.is_enabled()
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        actions(&self.action)
+    }
+}
+
+impl<M: Clone + Debug + 'static> Layout for ActionButton<M> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let sides = size_handle.button_surround();
+        let margins = size_handle.outer_margins();
+        let frame_rules = SizeRules::extract_fixed(axis.is_vertical(), sides.0 + sides.1, margins);
+
+        let content_rules = size_handle.text_bound(self.label.get(false), TextClass::Button, axis);
+        content_rules.surrounded_by(frame_rules, true)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || !self.action.is_enabled();
+        let mut state = self.input_state(mgr, disabled);
+        state.depress |= self.action.is_checked();
+        draw_handle.button(self.core.rect, state);
+        let text = self.label.get(mgr.show_accel_labels());
+        let align = (Align::Centre, Align::Centre);
+        draw_handle.text(self.core.rect, text, TextClass::Button, align);
+    }
+}
+
+impl<M: Clone + Debug + 'static> ActionButton<M> {
+    /// Construct, bound to the given `action`
+    pub fn new(action: Action<M>) -> Self {
+        ActionButton {
+            core: Default::default(),
+            label: action.label(),
+            action,
+        }
+    }
+
+    /// Get the bound action
+    pub fn action(&self) -> &Action<M> {
+        &self.action
+    }
+}
+
+impl<M: Clone + Debug + 'static> event::Handler for ActionButton<M> {
+    type Msg = M;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<M> {
+        handle(self.id(), &mut self.label, &self.action, mgr, event)
+    }
+}
+
+/// A menu entry bound to an [`Action`]
+///
+/// This is similar to [`MenuEntry`](super::MenuEntry), but takes its label,
+/// shortcut, enabled state and message from the action. The shortcut, if any,
+/// is displayed. Checkable actions display a check box (or a radio box for
+/// actions in an [`ActionGroup`](kas::action::ActionGroup)).
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+#[derive(Clone, Debug, Widget)]
+pub struct MenuAction<M: Clone + Debug + 'static> {
+    #[widget_core]
+    core: kas::CoreData,
+    label: AccelString,
+    label_off: Coord,
+    check: (Size, u32),
+    action: Action<M>,
+}
+
+impl<M: Clone + Debug + 'static> WidgetConfig for MenuAction<M> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        configure(self.id(), &self.label, &self.action, mgr);
+    }
+
+    fn key_nav(&self) -> bool {
+        self.action.is_enabled()
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        actions(&self.action)
+    }
+}
+
+impl<M: Clone + Debug + 'static> Layout for MenuAction<M> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let size = size_handle.menu_frame();
+        self.label_off = size.into();
+        let frame_rules = SizeRules::extract_fixed(axis.is_vertical(), size + size, Margins::ZERO);
+        let mut text_rules = size_handle.text_bound(self.label.get(false), TextClass::Label, axis);
+        if self.action.is_checkable() {
+            let check = match self.action.is_grouped() {
+                false => size_handle.checkbox(),
+                true => size_handle.radiobox(),
+            };
+            let skip = check.0 + size_handle.inner_margin().0;
+            self.check = (check, skip);
+            if axis.is_horizontal() {
+                text_rules = SizeRules::fixed(skip, (0, 0)).appended(text_rules);
+            } else {
+                text_rules.max_with(SizeRules::fixed(check.1, (0, 0)));
+            }
+        }
+        if let Some(shortcut) = self.action.shortcut() {
+            let text = shortcut.to_string();
+            let rules = size_handle.text_bound(&text, TextClass::Label, axis);
+            if axis.is_horizontal() {
+                let gap = size_handle.inner_margin().0 * 4;
+                text_rules.append(SizeRules::fixed(gap, (0, 0)));
+                text_rules.append(rules);
+            } else {
+                text_rules.max_with(rules);
+            }
+        }
+        text_rules.surrounded_by(frame_rules, true)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || !self.action.is_enabled();
+        let state = self.input_state(mgr, disabled);
+        draw_handle.menu_entry(self.core.rect, state);
+        let mut rect = Rect {
+            pos: self.core.rect.pos + self.label_off,
+            size: self.core.rect.size - (self.label_off + self.label_off).into(),
+        };
+        if self.action.is_checkable() {
+            let (size, skip) = self.check;
+            let y = (rect.size.1 as i32 - size.1 as i32) / 2;
+            let check_rect = Rect::new(rect.pos + Coord(0, y), size);
+            let checked = self.action.is_checked();
+            match self.action.is_grouped() {
+                false => draw_handle.checkbox(check_rect, checked, state),
+                true => draw_handle.radiobox(check_rect, checked, state),
+            }
+            rect.pos.0 += skip as i32;
+            rect.size.0 = rect.size.0.saturating_sub(skip);
+        }
+        let text = self.label.get(mgr.show_accel_labels());
+        let align = (Align::Begin, Align::Centre);
+        draw_handle.text(rect, text, TextClass::Label, align);
+        if let Some(shortcut) = self.action.shortcut() {
+            let align = (Align::End, Align::Centre);
+            draw_handle.text(rect, &shortcut.to_string(), TextClass::Label, align);
+        }
+    }
+}
+
+impl<M: Clone + Debug + 'static> MenuAction<M> {
+    /// Construct, bound to the given `action`
+    pub fn new(action: Action<M>) -> Self {
+        MenuAction {
+            core: Default::default(),
+            label: action.label(),
+            label_off: Coord::ZERO,
+            check: (Size::ZERO, 0),
+            action,
+        }
+    }
+
+    /// Get the bound action
+    pub fn action(&self) -> &Action<M> {
+        &self.action
+    }
+}
+
+impl<M: Clone + Debug + 'static> event::Handler for MenuAction<M> {
+    type Msg = M;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<M> {
+        handle(self.id(), &mut self.label, &self.action, mgr, event)
+    }
+}
+
+impl<M: Clone + Debug + 'static> Menu for MenuAction<M> {
+    fn menu_label(&self) -> Option<&str> {
+        Some(self.label.get(false))
+    }
+}
+
+fn configure<M: Clone>(id: WidgetId, label: &AccelString, action: &Action<M>, mgr: &mut Manager) {
+    mgr.add_accel_keys(id, label.keys());
+    if let Some(shortcut) = action.shortcut() {
+        mgr.add_shortcut(id, shortcut);
+    }
+    mgr.update_on_handle(action.update_handle(), id);
+}
+
+fn actions<M: Clone>(action: &Action<M>) -> Vec<(event::WidgetAction, Event)> {
+    if !action.is_enabled() {
+        return vec![];
+    }
+    let kind = if action.is_grouped() {
+        event::WidgetAction::Select
+    } else if action.is_checkable() {
+        event::WidgetAction::Toggle
+    } else {
+        event::WidgetAction::Activate
+    };
+    vec![(kind, Event::Activate)]
+}
+
+fn handle<M: Clone>(
+    id: WidgetId,
+    label: &mut AccelString,
+    action: &Action<M>,
+    mgr: &mut Manager,
+    event: Event,
+) -> Response<M> {
+    match event {
+        Event::Activate => match action.activate(mgr) {
+            Some(msg) => msg.into(),
+            None => Response::None,
+        },
+        Event::HandleUpdate { handle, .. } if handle == action.update_handle() => {
+            let new_label = action.label();
+            if *label != new_label {
+                *label = new_label;
+                return Response::action(TkAction::RECONFIGURE);
+            }
+            mgr.redraw(id);
+            Response::None
+        }
+        event => Response::Unhandled(event),
+    }
+}
@@ -0,0 +1,326 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widget adapters
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::{Frame, ScrollRegion};
+use kas::prelude::*;
+
+/// Provides some convenience methods on widgets
+///
+/// This trait is implemented for all [`Widget`] types. Its methods wrap the
+/// widget, allowing widget trees to be built without [`make_widget!`]:
+/// ```ignore
+/// let w = Label::new("Hello").framed().with_tooltip("A greeting");
+/// ```
+///
+/// Boxing (as `Box<dyn Widget<Msg = M>>`) is available via the
+/// [`Boxed`](kas::Boxed) trait, also implemented for all widgets.
+///
+/// [`make_widget!`]: ../macros/index.html#the-make_widget-macro
+pub trait WidgetExt: Widget + Sized {
+    /// Wrap in a [`Frame`]
+    fn framed(self) -> Frame<Self> {
+        Frame::new(self)
+    }
+
+    /// Wrap in a [`ScrollRegion`]
+    fn scrollable(self) -> ScrollRegion<Self> {
+        ScrollRegion::new(self)
+    }
+
+    /// Wrap with the given alignment
+    ///
+    /// See [`Aligned`].
+    fn aligned(self, horiz: Align, vert: Align) -> Aligned<Self> {
+        Aligned::new(self, horiz, vert)
+    }
+
+    /// Wrap with the given stretch policy
+    ///
+    /// See [`Stretch`].
+    fn with_stretch(self, policy: StretchPolicy) -> Stretch<Self> {
+        Stretch::new(self, policy)
+    }
+
+    /// Wrap with a tooltip
+    ///
+    /// See [`WithTooltip`].
+    fn with_tooltip<T: Into<CowString>>(self, text: T) -> WithTooltip<Self> {
+        WithTooltip::new(self, text)
+    }
+
+    /// Construct a wrapper widget which maps messages via `f`
+    ///
+    /// Example: `TextButton::new("Go", ()).map_msg(|()| Msg::Go)`.
+    fn map_msg<F, M>(self, f: F) -> MapResponse<Self, M>
+    where
+        F: Fn(Self::Msg) -> M + 'static,
+    {
+        MapResponse::new(self, move |msg| Response::Msg(f(msg)))
+    }
+
+    /// Construct a wrapper widget which discards messages
+    ///
+    /// The wrapper may be given any message type; for example, the result
+    /// may be used where a widget with `Msg = VoidMsg` is required.
+    fn discard_msg<M>(self) -> MapResponse<Self, M> {
+        MapResponse::new(self, |_| Response::None)
+    }
+
+    /// Construct a wrapper widget which converts messages via [`From`]
+    fn map_msg_into<M: From<Self::Msg>>(self) -> MapResponse<Self, M> {
+        MapResponse::new(self, |msg| Response::Msg(M::from(msg)))
+    }
+}
+
+impl<W: Widget> WidgetExt for W {}
+
+/// Wrapper to map messages from the inner widget
+///
+/// Usually constructed via [`WidgetExt`] methods.
+#[layout(single)]
+#[handler(msg = M)]
+#[derive(Clone, Widget)]
+pub struct MapResponse<W: Widget, M: 'static> {
+    #[widget_core]
+    core: CoreData,
+    #[widget(handler = map_response)]
+    inner: W,
+    map: Rc<dyn Fn(W::Msg) -> Response<M>>,
+}
+
+impl<W: Widget, M> Debug for MapResponse<W, M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MapResponse {{ core: {:?}, inner: {:?}, ... }}",
+            self.core, self.inner
+        )
+    }
+}
+
+impl<W: Widget, M> MapResponse<W, M> {
+    /// Construct
+    ///
+    /// Messages from `inner` are passed to `map`; the result is returned from
+    /// the event handler.
+    pub fn new<F: Fn(W::Msg) -> Response<M> + 'static>(inner: W, map: F) -> Self {
+        MapResponse {
+            core: Default::default(),
+            inner,
+            map: Rc::new(map),
+        }
+    }
+
+    /// Access the inner widget
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+
+    /// Access the inner widget mutably
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    fn map_response(&mut self, _: &mut Manager, msg: W::Msg) -> Response<M> {
+        (self.map)(msg)
+    }
+}
+
+/// Wrapper to align the inner widget
+///
+/// The inner widget is sized to its ideal size (except on axes with
+/// [`Align::Stretch`]) and positioned within the available space. Alignment
+/// is also passed to the inner widget as [`AlignHints`].
+///
+/// Usually constructed via [`WidgetExt::aligned`].
+#[handler(msg = <W as Handler>::Msg)]
+#[derive(Clone, Debug, Default, Widget)]
+pub struct Aligned<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: W,
+    align: (Align, Align),
+    ideal: Size,
+}
+
+impl<W: Widget> Aligned<W> {
+    /// Construct
+    pub fn new(inner: W, horiz: Align, vert: Align) -> Self {
+        Aligned {
+            core: Default::default(),
+            inner,
+            align: (horiz, vert),
+            ideal: Size::ZERO,
+        }
+    }
+
+    /// Access the inner widget
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+
+    /// Access the inner widget mutably
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: Widget> Layout for Aligned<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let rules = self.inner.size_rules(size_handle, axis);
+        if axis.is_horizontal() {
+            self.ideal.0 = rules.ideal_size();
+        } else {
+            self.ideal.1 = rules.ideal_size();
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let hints = AlignHints::new(Some(self.align.0), Some(self.align.1));
+        let inner_rect = hints
+            .complete(Align::Stretch, Align::Stretch, self.ideal)
+            .apply(rect);
+        self.inner.set_rect(inner_rect, hints);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.inner.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.inner.draw(draw_handle, mgr, disabled);
+    }
+}
+
+/// Wrapper overriding the stretch policy of the inner widget
+///
+/// Within a row, column or grid, extra space is allocated to children
+/// according to their [`StretchPolicy`]; this allows e.g. one child of a row
+/// to take all extra space via [`StretchPolicy::Maximise`].
+///
+/// Usually constructed via [`WidgetExt::with_stretch`].
+#[handler(msg = <W as Handler>::Msg)]
+#[derive(Clone, Debug, Default, Widget)]
+pub struct Stretch<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: W,
+    policy: StretchPolicy,
+}
+
+impl<W: Widget> Stretch<W> {
+    /// Construct
+    pub fn new(inner: W, policy: StretchPolicy) -> Self {
+        Stretch {
+            core: Default::default(),
+            inner,
+            policy,
+        }
+    }
+
+    /// Access the inner widget
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+
+    /// Access the inner widget mutably
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: Widget> Layout for Stretch<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let mut rules = self.inner.size_rules(size_handle, axis);
+        rules.set_stretch(self.policy);
+        rules
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.inner.set_rect(rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.inner.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.inner.draw(draw_handle, mgr, disabled);
+    }
+}
+
+/// Wrapper adding a tooltip to the inner widget
+///
+/// The tooltip is shown when the mouse hovers over the widget for a short
+/// time; see [`Manager::register_tooltip`].
+///
+/// Usually constructed via [`WidgetExt::with_tooltip`].
+#[layout(single)]
+#[widget(config=noauto)]
+#[handler(msg = <W as Handler>::Msg)]
+#[derive(Clone, Debug, Default, Widget)]
+pub struct WithTooltip<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: W,
+    tooltip: CowString,
+}
+
+impl<W: Widget> WidgetConfig for WithTooltip<W> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.register_tooltip(self.id(), self.tooltip.clone());
+    }
+}
+
+impl<W: Widget> WithTooltip<W> {
+    /// Construct
+    pub fn new<T: Into<CowString>>(inner: W, tooltip: T) -> Self {
+        WithTooltip {
+            core: Default::default(),
+            inner,
+            tooltip: tooltip.into(),
+        }
+    }
+
+    /// Access the inner widget
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+
+    /// Access the inner widget mutably
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Set the tooltip text
+    ///
+    /// Tooltips are registered during configuration, thus this requires
+    /// [`TkAction::RECONFIGURE`].
+    pub fn set_tooltip<T: Into<CowString>>(&mut self, text: T) -> TkAction {
+        self.tooltip = text.into();
+        TkAction::RECONFIGURE
+    }
+}
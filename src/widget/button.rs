@@ -10,7 +10,7 @@ use std::fmt::Debug;
 
 use kas::class::HasText;
 use kas::draw::TextClass;
-use kas::event::{VirtualKeyCode, VirtualKeyCodes};
+use kas::event::{PressRepeat, VirtualKeyCode, VirtualKeyCodes};
 use kas::prelude::*;
 
 /// A push-button with a text label
@@ -24,6 +24,7 @@ pub struct TextButton<M: Clone + Debug + 'static> {
     // text_rect: Rect,
     label: AccelString,
     msg: M,
+    repeat: Option<PressRepeat>,
 }
 
 impl<M: Clone + Debug + 'static> WidgetConfig for TextButton<M> {
@@ -36,6 +37,10 @@ impl<M: Clone + Debug + 'static> WidgetConfig for TextButton<M> {
     fn key_nav(&self) -> bool {
         true
     }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        vec![(event::WidgetAction::Activate, Event::Activate)]
+    }
 }
 
 impl<M: Clone + Debug + 'static> Layout for TextButton<M> {
@@ -79,6 +84,7 @@ impl<M: Clone + Debug + 'static> TextButton<M> {
             // text_rect: Default::default(),
             label: label.into(),
             msg,
+            repeat: None,
         }
     }
 
@@ -88,6 +94,16 @@ impl<M: Clone + Debug + 'static> TextButton<M> {
         self
     }
 
+    /// Repeat activation while held (chain style)
+    ///
+    /// When set, the button's message is returned on the start of a press and
+    /// then repeatedly while the press is held, according to `repeat`, and is
+    /// not returned on release. See [`event::Handler::press_repeat`].
+    pub fn with_press_repeat(mut self, repeat: PressRepeat) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+
     /// Replace the message value
     pub fn set_msg(&mut self, msg: M) {
         self.msg = msg;
@@ -106,7 +122,7 @@ impl<M: Clone + Debug + 'static> HasText for TextButton<M> {
 
     fn set_cow_string(&mut self, text: CowString) -> TkAction {
         self.label = text.into();
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
 
@@ -118,6 +134,11 @@ impl<M: Clone + Debug + 'static> event::Handler for TextButton<M> {
         true
     }
 
+    #[inline]
+    fn press_repeat(&self) -> Option<PressRepeat> {
+        self.repeat
+    }
+
     fn handle(&mut self, _: &mut Manager, event: Event) -> Response<M> {
         match event {
             Event::Activate => self.msg.clone().into(),
@@ -13,7 +13,7 @@ use kas::class::HasBool;
 use kas::prelude::*;
 
 /// A bare checkbox (no label)
-#[widget(config(key_nav = true))]
+#[widget(config=noauto)]
 #[handler(handle=noauto)]
 #[derive(Clone, Default, Widget)]
 pub struct CheckBoxBare<M: 'static> {
@@ -33,6 +33,16 @@ impl<M: 'static> Debug for CheckBoxBare<M> {
     }
 }
 
+impl<M: 'static> WidgetConfig for CheckBoxBare<M> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        vec![(event::WidgetAction::Toggle, Event::Activate)]
+    }
+}
+
 impl<M: 'static> Layout for CheckBoxBare<M> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let size = size_handle.checkbox();
@@ -114,7 +124,7 @@ impl<M: 'static> HasBool for CheckBoxBare<M> {
 
     fn set_bool(&mut self, state: bool) -> TkAction {
         self.state = state;
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
 
@@ -8,7 +8,8 @@
 use std::fmt::Debug;
 use std::iter::FromIterator;
 
-use super::{Column, MenuEntry, MenuFrame};
+use super::menu::TypeAhead;
+use super::{Column, Menu, MenuEntry, MenuFrame};
 use kas::class::HasText;
 use kas::draw::TextClass;
 use kas::event::{ControlKey, GrabMode};
@@ -16,7 +17,10 @@ use kas::prelude::*;
 use kas::WindowId;
 
 /// A pop-up multiple choice menu
-#[widget(config(key_nav = true))]
+///
+/// Sending [`Event::Select`] to a choice's entry makes that choice active; the
+/// combobox then reports [`Response::Select`] (not a message).
+#[widget(config=noauto)]
 #[handler(noauto)]
 #[derive(Clone, Debug, Widget)]
 pub struct ComboBox<M: Clone + Debug + 'static> {
@@ -29,6 +33,20 @@ pub struct ComboBox<M: Clone + Debug + 'static> {
     active: usize,
     opening: bool,
     popup_id: Option<WindowId>,
+    type_ahead: TypeAhead,
+}
+
+impl<M: Clone + Debug + 'static> WidgetConfig for ComboBox<M> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        match self.popup_id {
+            None => vec![(event::WidgetAction::OpenPopup, Event::Activate)],
+            Some(_) => vec![],
+        }
+    }
 }
 
 impl<M: Clone + Debug + 'static> kas::Layout for ComboBox<M> {
@@ -103,6 +121,7 @@ impl<M: Clone + Debug + 'static> ComboBox<M> {
             active: 0,
             opening: false,
             popup_id: None,
+            type_ahead: Default::default(),
         }
     }
 
@@ -217,6 +236,7 @@ impl<M: Clone + Debug + 'static> ComboBox<M> {
                 ev => Response::Unhandled(ev),
             },
             Response::Focus(x) => Response::Focus(x),
+            Response::Select => Response::Select,
             Response::Msg(msg) => {
                 let index = msg as usize;
                 assert!(index < self.messages.len());
@@ -227,9 +247,13 @@ impl<M: Clone + Debug + 'static> ComboBox<M> {
                 mgr.redraw(self.id());
                 Response::Msg(self.messages[index].clone())
             }
+            Response::Action(action, msg) => {
+                *mgr += action;
+                self.map_response(mgr, msg.into())
+            }
         }
         // NOTE: as part of the Popup API we are expected to trap
-        // TkAction::Close here, but we know our widget doesn't generate
+        // TkAction::CLOSE here, but we know our widget doesn't generate
         // this action.
     }
 }
@@ -289,6 +313,7 @@ impl<M: Clone + Debug + 'static> event::Handler for ComboBox<M> {
                 source,
                 start_id,
                 coord,
+                ..
             } => {
                 if self.is_ancestor_of(start_id) {
                     if source.is_primary() {
@@ -349,6 +374,14 @@ impl<M: Clone + Debug + 'static> event::Handler for ComboBox<M> {
                 debug_assert_eq!(Some(id), self.popup_id);
                 self.popup_id = None;
             }
+            Event::ReceivedCharacter(c) if self.popup_id.is_some() => {
+                let prefix = self.type_ahead.push(c);
+                let column = &self.popup.inner.inner;
+                let labels = (0..column.len()).map(|i| column[i].menu_label());
+                if let Some(id) = TypeAhead::find(prefix, labels).map(|i| column[i].id()) {
+                    mgr.set_nav_focus(id);
+                }
+            }
             event => return Response::Unhandled(event),
         }
         Response::None
@@ -363,6 +396,14 @@ impl<M: Clone + Debug + 'static> event::SendEvent for ComboBox<M> {
 
         if id <= self.popup.id() {
             let r = self.popup.send(mgr, id, event);
+            if r.is_select() {
+                // A choice was selected via Event::Select: make it active
+                let column = &self.popup.inner.inner;
+                if let Some(index) = (0..column.len()).find(|i| id <= column[*i].id()) {
+                    self.active = index;
+                    mgr.redraw(self.id());
+                }
+            }
             self.map_response(mgr, r)
         } else {
             Manager::handle_generic(self, mgr, event)
@@ -0,0 +1,397 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Auto-completion for edit boxes
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::{EditBox, EditGuard, MenuEntry, MenuFrame};
+use kas::class::HasText;
+use kas::event::{ControlKey, GrabMode};
+use kas::layout::{RulesSetter, RulesSolver};
+use kas::prelude::*;
+use kas::WindowId;
+
+/// A source of suggestions for [`AutoComplete`]
+pub trait Completer {
+    /// Get suggestions for the given (non-empty) `text`
+    ///
+    /// Suggestions are displayed in the order given, up to the limit set by
+    /// [`AutoComplete::with_max_suggestions`].
+    fn suggestions(&self, text: &str) -> Vec<String>;
+}
+
+impl<F: Fn(&str) -> Vec<String>> Completer for F {
+    fn suggestions(&self, text: &str) -> Vec<String> {
+        self(text)
+    }
+}
+
+/// An [`EditBox`] with a pop-up list of suggestions
+///
+/// Whenever the text is edited, suggestions are requested from the
+/// [`Completer`] and displayed in a pop-up below the edit box. While the pop-up
+/// is open, the Up and Down keys select a suggestion, while Return commits the
+/// selected suggestion and Tab commits the selected (or first) suggestion.
+/// Suggestions may also be committed by clicking.
+///
+/// Committing a suggestion replaces the text of the edit box; this is
+/// treated as a user edit (calling [`EditGuard::edit`]).
+///
+/// Construct via [`EditBox::with_completer`].
+#[handler(noauto)]
+#[derive(Clone, Widget)]
+pub struct AutoComplete<G: EditGuard + 'static> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    edit: EditBox<G>,
+    #[widget]
+    popup: MenuFrame<Suggestions>,
+    completer: Rc<dyn Completer>,
+    selected: Option<usize>,
+    popup_id: Option<WindowId>,
+}
+
+impl<G: EditGuard> Debug for AutoComplete<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AutoComplete {{ core: {:?}, edit: {:?}, popup: {:?}, selected: {:?}, ... }}",
+            self.core, self.edit, self.popup, self.selected
+        )
+    }
+}
+
+impl<G: EditGuard + 'static> Layout for AutoComplete<G> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.edit.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.edit.set_rect(rect, align);
+    }
+
+    fn spatial_range(&self) -> (usize, usize) {
+        // Exclude the pop-up from navigation
+        (0, 0)
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.edit.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.edit.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl<G: EditGuard + 'static> AutoComplete<G> {
+    /// Construct from an [`EditBox`] and a [`Completer`]
+    pub fn new<C: Completer + 'static>(edit: EditBox<G>, completer: C) -> Self {
+        AutoComplete {
+            core: Default::default(),
+            edit,
+            popup: MenuFrame::new(Suggestions::new(8)),
+            completer: Rc::new(completer),
+            selected: None,
+            popup_id: None,
+        }
+    }
+
+    /// Set the maximum number of suggestions displayed (default: 8)
+    pub fn with_max_suggestions(mut self, max: usize) -> Self {
+        self.popup.inner = Suggestions::new(max);
+        self
+    }
+
+    /// Access the edit box
+    pub fn edit(&self) -> &EditBox<G> {
+        &self.edit
+    }
+
+    /// Access the edit box, mutably
+    pub fn edit_mut(&mut self) -> &mut EditBox<G> {
+        &mut self.edit
+    }
+
+    fn update_suggestions(&mut self, mgr: &mut Manager) {
+        let text = self.edit.get_text();
+        let suggestions = match text.is_empty() {
+            true => vec![],
+            false => self.completer.suggestions(text),
+        };
+
+        let list = &mut self.popup.inner;
+        list.len = suggestions.len().min(list.entries.len());
+        for (entry, text) in list.entries.iter_mut().zip(suggestions) {
+            *mgr += entry.set_text(text);
+        }
+        self.selected = None;
+
+        if list.len == 0 {
+            self.close_popup(mgr);
+        } else if self.popup_id.is_none() {
+            let id = mgr.add_popup(kas::Popup {
+                id: self.popup.id(),
+                parent: self.id(),
+                direction: Direction::Down,
+            });
+            self.popup_id = Some(id);
+        } else {
+            *mgr += TkAction::POPUP;
+        }
+    }
+
+    fn close_popup(&mut self, mgr: &mut Manager) {
+        if let Some(id) = self.popup_id {
+            mgr.close_window(id);
+        }
+    }
+
+    fn select(&mut self, mgr: &mut Manager, rev: bool) {
+        let len = self.popup.inner.len;
+        self.selected = match (self.selected, rev) {
+            (None, false) => Some(0),
+            (None, true) => Some(len - 1),
+            (Some(0), true) => None,
+            (Some(i), true) => Some(i - 1),
+            (Some(i), false) if i + 1 < len => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        match self.selected {
+            Some(i) => mgr.set_nav_focus(self.popup.inner.entries[i].id()),
+            None => mgr.clear_nav_focus(),
+        }
+    }
+
+    fn commit(&mut self, mgr: &mut Manager, index: usize) -> Response<G::Msg> {
+        let text = self.popup.inner.entries[index].get_text().to_string();
+        self.close_popup(mgr);
+        mgr.redraw(self.edit.id());
+        self.edit.replace_text(text).into()
+    }
+
+    fn send_edit(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<G::Msg> {
+        if self.popup_id.is_some() {
+            match event {
+                Event::Control(ControlKey::Up) => {
+                    self.select(mgr, true);
+                    return Response::None;
+                }
+                Event::Control(ControlKey::Down) => {
+                    self.select(mgr, false);
+                    return Response::None;
+                }
+                Event::Control(ControlKey::Tab) => {
+                    let index = self.selected.unwrap_or(0);
+                    return self.commit(mgr, index);
+                }
+                Event::Control(ControlKey::Return) if self.selected.is_some() => {
+                    let index = self.selected.unwrap();
+                    return self.commit(mgr, index);
+                }
+                Event::LostCharFocus => self.close_popup(mgr),
+                _ => (),
+            }
+        }
+
+        let old_text = self.edit.get_text().to_string();
+        let r = self.edit.send(mgr, id, event);
+        if self.edit.get_text() != old_text {
+            self.update_suggestions(mgr);
+        }
+        r
+    }
+}
+
+impl<G: EditGuard + 'static> event::Handler for AutoComplete<G> {
+    type Msg = G::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::PressStart {
+                source,
+                start_id,
+                coord,
+                ..
+            } if self.popup.is_ancestor_of(start_id) => {
+                if source.is_primary() {
+                    mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
+                    mgr.set_grab_depress(source, Some(start_id));
+                }
+            }
+            Event::PressMove { source, cur_id, .. } => {
+                let target = cur_id.filter(|id| self.popup.is_ancestor_of(*id));
+                mgr.set_grab_depress(source, target);
+                if let Some(id) = target {
+                    mgr.set_nav_focus(id);
+                }
+            }
+            Event::PressEnd { end_id, .. } => {
+                if let Some(id) = end_id {
+                    if self.popup_id.is_some() && self.popup.is_ancestor_of(id) {
+                        if let Response::Msg(index) = self.popup.send(mgr, id, Event::Activate) {
+                            return self.commit(mgr, index as usize);
+                        }
+                    }
+                }
+            }
+            Event::NewPopup(id) => {
+                if id != self.popup.id() {
+                    self.close_popup(mgr);
+                }
+            }
+            Event::PopupRemoved(id) => {
+                debug_assert_eq!(Some(id), self.popup_id);
+                self.popup_id = None;
+                self.selected = None;
+            }
+            event => return Response::Unhandled(event),
+        }
+        Response::None
+    }
+}
+
+impl<G: EditGuard + 'static> event::SendEvent for AutoComplete<G> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        if id <= self.edit.id() {
+            self.send_edit(mgr, id, event)
+        } else if id <= self.popup.id() {
+            match self.popup.send(mgr, id, event) {
+                Response::None => Response::None,
+                Response::Unhandled(event) => Response::Unhandled(event),
+                Response::Focus(rect) => Response::Focus(rect),
+                Response::Select => Response::Select,
+                Response::Msg(index) => self.commit(mgr, index as usize),
+                Response::Action(action, msg) => {
+                    *mgr += action;
+                    match msg {
+                        Some(index) => self.commit(mgr, index as usize),
+                        None => Response::None,
+                    }
+                }
+            }
+        } else {
+            Manager::handle_generic(self, mgr, event)
+        }
+    }
+}
+
+/// The list of suggestions
+///
+/// This has a fixed number of entries (allowing the pop-up to be resized
+/// without reconfiguring), of which only the first `len` are shown.
+#[handler(send=noauto, msg = u64)]
+#[widget(children=noauto)]
+#[derive(Clone, Debug, Widget)]
+struct Suggestions {
+    #[widget_core]
+    core: CoreData,
+    entries: Vec<MenuEntry<u64>>,
+    len: usize,
+    data: layout::DynRowStorage,
+}
+
+impl Suggestions {
+    fn new(max: usize) -> Self {
+        Suggestions {
+            core: Default::default(),
+            entries: (0..max).map(|i| MenuEntry::new("", i as u64)).collect(),
+            len: 0,
+            data: Default::default(),
+        }
+    }
+}
+
+impl WidgetChildren for Suggestions {
+    #[inline]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.entries.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.entries.get_mut(index).map(|w| w.as_widget_mut())
+    }
+}
+
+impl Layout for Suggestions {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let dim = (kas::Down, self.len);
+        let mut solver = layout::RowSolver::new(axis, dim, &mut self.data);
+        for (n, child) in self.entries[..self.len].iter_mut().enumerate() {
+            solver.for_child(&mut self.data, n, |axis| {
+                child.size_rules(size_handle, axis)
+            });
+        }
+        solver.finish(&mut self.data)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let dim = (kas::Down, self.len);
+        let mut setter =
+            layout::RowSetter::<kas::Down, Vec<u32>, _>::new(rect, dim, align, &mut self.data);
+
+        for (n, child) in self.entries.iter_mut().enumerate() {
+            let rect = match n < self.len {
+                true => setter.child_rect(&mut self.data, n),
+                false => Rect::default(),
+            };
+            child.set_rect(rect, AlignHints::default());
+        }
+    }
+
+    fn spatial_range(&self) -> (usize, usize) {
+        (0, self.len.wrapping_sub(1))
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        for child in &self.entries[..self.len] {
+            if let Some(id) = child.find_id(coord) {
+                return Some(id);
+            }
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        for child in &self.entries[..self.len] {
+            child.draw(draw_handle, mgr, disabled);
+        }
+    }
+}
+
+impl event::SendEvent for Suggestions {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            for child in &mut self.entries[..self.len] {
+                if id <= child.id() {
+                    return child.send(mgr, id, event);
+                }
+            }
+        }
+
+        Response::Unhandled(event)
+    }
+}
@@ -10,7 +10,7 @@
 
 use kas::event::VirtualKeyCode;
 use kas::prelude::*;
-use kas::widget::{Label, TextButton};
+use kas::widget::{SelectableLabel, TextButton};
 use kas::WindowId;
 
 #[derive(Clone, Debug, VoidMsg)]
@@ -29,7 +29,7 @@ pub struct MessageBox {
     layout_data: <Self as kas::LayoutData>::Data,
     title: CowString,
     #[widget]
-    label: Label,
+    label: SelectableLabel,
     #[widget(handler = handle_button)]
     button: TextButton<DialogButton>,
 }
@@ -40,7 +40,7 @@ impl MessageBox {
             core: Default::default(),
             layout_data: Default::default(),
             title: title.into(),
-            label: Label::new(message),
+            label: SelectableLabel::new(message),
             button: TextButton::new("Ok", DialogButton::Close).with_keys(&[
                 VirtualKeyCode::Return,
                 VirtualKeyCode::Space,
@@ -51,7 +51,7 @@ impl MessageBox {
 
     fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
         match msg {
-            DialogButton::Close => mgr.send_action(TkAction::Close),
+            DialogButton::Close => mgr.send_action(TkAction::CLOSE),
         };
         Response::None
     }
@@ -6,13 +6,23 @@
 //! Text widgets
 
 use std::fmt::{self, Debug};
+use std::ops::Range;
 use unicode_segmentation::GraphemeCursor;
 
+use super::{AutoComplete, Completer, SelectionHelper};
 use kas::class::{Editable, HasText};
-use kas::draw::TextClass;
-use kas::event::{ControlKey, GrabMode};
+use kas::draw::{ClipRegion, TextClass};
+use kas::event::{ControlKey, GrabMode, MouseButton, PressSource, ScrollDelta};
+use kas::geom::Vec2;
 use kas::prelude::*;
 
+/// Scale factor applied per scroll step when zooming
+const ZOOM_STEP: f32 = 1.1;
+/// Scroll distance (in pixels) equivalent to one line
+const ZOOM_PIXELS_PER_STEP: f32 = 20.0;
+const ZOOM_MIN: f32 = 0.25;
+const ZOOM_MAX: f32 = 4.0;
+
 #[derive(Clone, Debug, PartialEq)]
 enum LastEdit {
     None,
@@ -122,35 +132,147 @@ impl<F: Fn(&str) -> Option<M>, M> EditGuard for EditEdit<F, M> {
     }
 }
 
-/// An editable, single-line text box.
+/// Message emitted by an [`EditBox`] with an [`EditNotifier`] guard
+#[derive(Clone, Debug, PartialEq, Eq, VoidMsg)]
+pub enum Edit {
+    /// The text was changed; see [`EditNotify`] for when this is emitted
+    Changed(String),
+    /// The text was submitted via the Enter/Return key
+    Submitted(String),
+}
+
+/// When an [`EditNotifier`] reports changes to its [`EditBox`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditNotify {
+    /// Emit [`Edit::Changed`] on every user edit (e.g. each keystroke)
+    Keystroke,
+    /// Emit [`Edit::Changed`] when keyboard focus is lost, if the text has
+    /// changed since last reported
+    FocusOut,
+    /// Do not emit [`Edit::Changed`]; only [`Edit::Submitted`] is emitted
+    Submit,
+}
+
+/// An [`EditGuard`] impl emitting [`Edit`] messages
+///
+/// Regardless of the [`EditNotify`] granularity, [`Edit::Submitted`] is
+/// emitted when the Enter/Return key is pressed (single-line mode only, since
+/// in multi-line mode this key inserts a line break). Changes are considered
+/// relative to the text last reported (or the initial text); note that this
+/// includes programmatic edits.
+#[derive(Clone, Debug)]
+pub struct EditNotifier {
+    notify: EditNotify,
+    reported: String,
+}
+
+impl EditNotifier {
+    /// Get the notification granularity
+    pub fn notify(&self) -> EditNotify {
+        self.notify
+    }
+
+    /// Set the notification granularity
+    pub fn set_notify(&mut self, notify: EditNotify) {
+        self.notify = notify;
+    }
+}
+
+impl EditGuard for EditNotifier {
+    type Msg = Edit;
+
+    fn activate(edit: &mut EditBox<Self>) -> Option<Self::Msg> {
+        edit.guard.reported = edit.text.clone();
+        Some(Edit::Submitted(edit.text.clone()))
+    }
+
+    fn focus_lost(edit: &mut EditBox<Self>) -> Option<Self::Msg> {
+        if edit.guard.notify == EditNotify::FocusOut && edit.text != edit.guard.reported {
+            edit.guard.reported = edit.text.clone();
+            return Some(Edit::Changed(edit.text.clone()));
+        }
+        None
+    }
+
+    fn edit(edit: &mut EditBox<Self>) -> Option<Self::Msg> {
+        if edit.guard.notify == EditNotify::Keystroke {
+            edit.guard.reported = edit.text.clone();
+            return Some(Edit::Changed(edit.text.clone()));
+        }
+        None
+    }
+}
+
+/// An editable text box.
 ///
 /// This widget is intended for use with short input strings. Internally it
-/// uses a [`String`], for which edits have `O(n)` cost.
+/// uses a [`String`], for which edits have `O(n)` cost; this is required since
+/// [`HasText`] exposes the text as a `&str`.
+///
+/// In multi-line mode (see [`EditBox::multi_line`]), text is wrapped to the
+/// width of the box and the Return key inserts a line break. Up/Down move the
+/// edit position by visual line (keeping its horizontal position), Home/End
+/// move to the start/end of the line (with Ctrl, of the text) and Page Up/Down
+/// move by the height of the box. Content taller than the box is scrolled to
+/// keep the edit position visible, and may be scrolled via the mouse wheel.
+/// For larger texts, use [`TextEditor`](super::TextEditor), which uses rope
+/// storage and lays out only visible lines.
 ///
-/// Currently, this widget has a [`Widget::multi_line`] mode, with some
-/// limitations (incorrect positioning of the edit cursor at line end,
-/// non-functional up/down keys, lack of scrolling). Later this will be replaced
-/// by a dedicated multi-line widget, probably using the `ropey` crate.
-#[widget(config(key_nav = true, cursor_icon = event::CursorIcon::Text))]
+/// The parent is notified of changes via an [`EditGuard`]. For forms, the
+/// simplest option is [`EditBox::with_notify`], which emits [`Edit`] messages
+/// on each keystroke, on loss of focus or only on submission.
+#[widget(config=noauto)]
 #[handler(handle=noauto, generics = <> where G: EditGuard)]
-#[derive(Clone, Default, Widget)]
+#[derive(Clone, Widget)]
 pub struct EditBox<G: 'static> {
     #[widget_core]
     core: CoreData,
     frame_offset: Coord,
     frame_size: Size,
     text_rect: Rect,
+    // Scroll offset of text within text_rect (multi-line only)
+    view_offset: Coord,
+    // Height of text as laid out (multi-line only)
+    text_height: u32,
+    // Horizontal position to keep when moving between lines
+    edit_x: Option<f32>,
     editable: bool,
     multi_line: bool,
     text: String,
-    edit_pos: usize,
+    selection: SelectionHelper,
     old_state: Option<(String, usize)>,
     last_edit: LastEdit,
     error_state: bool,
+    text_scale: f32,
+    zoom: bool,
     /// The associated [`EditGuard`] implementation
     pub guard: G,
 }
 
+impl<G: Default> Default for EditBox<G> {
+    fn default() -> Self {
+        EditBox {
+            core: Default::default(),
+            frame_offset: Default::default(),
+            frame_size: Default::default(),
+            text_rect: Default::default(),
+            view_offset: Coord::ZERO,
+            text_height: 0,
+            edit_x: None,
+            editable: false,
+            multi_line: false,
+            text: Default::default(),
+            selection: Default::default(),
+            old_state: None,
+            last_edit: LastEdit::None,
+            error_state: false,
+            text_scale: 1.0,
+            zoom: false,
+            guard: Default::default(),
+        }
+    }
+}
+
 impl<G> Debug for EditBox<G> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -161,6 +283,23 @@ impl<G> Debug for EditBox<G> {
     }
 }
 
+impl<G: 'static> WidgetConfig for EditBox<G> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> event::CursorIcon {
+        event::CursorIcon::Text
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        match self.editable {
+            true => vec![(event::WidgetAction::Edit, Event::Activate)],
+            false => vec![],
+        }
+    }
+}
+
 impl<G: 'static> Layout for EditBox<G> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let frame_sides = size_handle.edit_surround();
@@ -171,13 +310,12 @@ impl<G: 'static> Layout for EditBox<G> {
         let margins = size_handle.outer_margins();
         let frame_rules = SizeRules::extract_fixed(axis.is_vertical(), frame_size, margins);
 
-        let class = if self.multi_line {
-            TextClass::EditMulti
-        } else {
-            TextClass::Edit
-        };
-        let content_rules = size_handle.text_bound(&self.text, class, axis);
+        let class = self.text_class();
+        let content_rules = size_handle.text_bound_scaled(&self.text, class, self.text_scale, axis);
         let m = content_rules.margins();
+        if self.multi_line && axis.is_vertical() {
+            self.text_height = content_rules.ideal_size();
+        }
 
         let rules = content_rules.surrounded_by(frame_rules, true);
         if axis.is_horizontal() {
@@ -205,21 +343,32 @@ impl<G: 'static> Layout for EditBox<G> {
         self.core.rect = rect;
         self.text_rect.pos = rect.pos + self.frame_offset;
         self.text_rect.size = rect.size - self.frame_size;
+        self.view_offset.1 = self.view_offset.1.min(self.max_view_offset());
     }
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
-        let class = if self.multi_line {
-            TextClass::EditMulti
-        } else {
-            TextClass::Edit
-        };
+        let class = self.text_class();
         let mut input_state = self.input_state(mgr, disabled);
         input_state.error = self.error_state;
         draw_handle.edit_box(self.core.rect, input_state);
         let align = (Align::Begin, Align::Begin);
-        draw_handle.text(self.text_rect, &self.text, class, align);
-        if input_state.char_focus {
-            draw_handle.edit_marker(self.text_rect, &self.text, class, align, self.edit_pos);
+        let (rect, scale) = (self.layout_rect(), self.text_scale);
+        let mut draw_text = |draw_handle: &mut dyn DrawHandle| {
+            if !self.selection.is_empty() {
+                let range = self.selection.range();
+                draw_handle.text_selection_scaled(rect, &self.text, class, scale, align, range);
+            }
+            draw_handle.text_scaled(rect, &self.text, class, scale, align);
+            if input_state.char_focus {
+                let pos = self.selection.edit_pos();
+                draw_handle.edit_marker_scaled(rect, &self.text, class, scale, align, pos);
+            }
+        };
+        if self.multi_line {
+            let offset = self.view_offset;
+            draw_handle.clip_region(self.text_rect, offset, ClipRegion::Scroll, &mut draw_text);
+        } else {
+            draw_text(draw_handle);
         }
     }
 }
@@ -234,13 +383,18 @@ impl EditBox<EditVoid> {
             frame_offset: Default::default(),
             frame_size: Default::default(),
             text_rect: Default::default(),
+            view_offset: Coord::ZERO,
+            text_height: 0,
+            edit_x: None,
             editable: true,
             multi_line: false,
             text,
-            edit_pos,
+            selection: SelectionHelper::new(edit_pos, edit_pos),
             old_state: None,
             last_edit: LastEdit::None,
             error_state: false,
+            text_scale: 1.0,
+            zoom: false,
             guard: EditVoid,
         }
     }
@@ -258,13 +412,18 @@ impl EditBox<EditVoid> {
             frame_offset: self.frame_offset,
             frame_size: self.frame_size,
             text_rect: self.text_rect,
+            view_offset: self.view_offset,
+            text_height: self.text_height,
+            edit_x: self.edit_x,
             editable: self.editable,
             multi_line: self.multi_line,
             text: self.text,
-            edit_pos: self.edit_pos,
+            selection: self.selection,
             old_state: self.old_state,
             last_edit: self.last_edit,
             error_state: self.error_state,
+            text_scale: self.text_scale,
+            zoom: self.zoom,
             guard,
         };
         let _ = G::edit(&mut edit);
@@ -309,6 +468,19 @@ impl EditBox<EditVoid> {
     pub fn on_edit<F: Fn(&str) -> Option<M>, M>(self, f: F) -> EditBox<EditEdit<F, M>> {
         self.with_guard(EditEdit(f))
     }
+
+    /// Set notification granularity, emitting [`Edit`] messages
+    ///
+    /// See [`EditNotify`] for when [`Edit::Changed`] is emitted;
+    /// [`Edit::Submitted`] is emitted on Enter/Return.
+    ///
+    /// This method is a parametisation of [`EditBox::with_guard`] using
+    /// [`EditNotifier`]. Any guard previously assigned to the `EditBox` will
+    /// be replaced.
+    pub fn with_notify(self, notify: EditNotify) -> EditBox<EditNotifier> {
+        let reported = self.text.clone();
+        self.with_guard(EditNotifier { notify, reported })
+    }
 }
 
 impl<G> EditBox<G> {
@@ -319,11 +491,44 @@ impl<G> EditBox<G> {
     }
 
     /// Set whether this `EditBox` shows multiple text lines
+    ///
+    /// See [`EditBox`] for a description of multi-line mode.
     pub fn multi_line(mut self, multi_line: bool) -> Self {
         self.multi_line = multi_line;
         self
     }
 
+    /// Set the initial text scale
+    ///
+    /// Text is drawn at the theme's font size multiplied by `scale`.
+    pub fn with_text_scale(mut self, scale: f32) -> Self {
+        self.text_scale = scale;
+        self
+    }
+
+    /// Enable zooming text via Ctrl+scroll
+    pub fn with_zoom(mut self, zoom: bool) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Get the text scale
+    pub fn text_scale(&self) -> f32 {
+        self.text_scale
+    }
+
+    /// Set the text scale
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if the scale
+    /// changes.
+    pub fn set_text_scale(&mut self, scale: f32) -> TkAction {
+        if scale == self.text_scale {
+            return TkAction::empty();
+        }
+        self.text_scale = scale;
+        TkAction::RECONFIGURE
+    }
+
     /// Get whether the input state is erroneous
     pub fn has_error(&self) -> bool {
         self.error_state
@@ -337,53 +542,181 @@ impl<G> EditBox<G> {
         self.error_state = error_state;
     }
 
+    /// Get the selected text
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection.range()]
+    }
+
+    /// Get the selection range
+    ///
+    /// This is a range of byte indices into the text. It is empty (and starts
+    /// at the edit position) when nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.selection.range()
+    }
+
+    /// Set the selection range
+    ///
+    /// The edit position is placed at the end of the range. An empty range
+    /// sets the edit position without selecting anything.
+    ///
+    /// Panics if either bound of `range` does not lie on a `char` boundary of
+    /// the text.
+    pub fn set_selection(&mut self, range: Range<usize>) -> TkAction {
+        assert!(self.text.is_char_boundary(range.start) && self.text.is_char_boundary(range.end));
+        self.selection = SelectionHelper::new(range.end, range.start);
+        TkAction::REDRAW
+    }
+
+    fn text_class(&self) -> TextClass {
+        if self.multi_line {
+            TextClass::EditMulti
+        } else {
+            TextClass::Edit
+        }
+    }
+
+    // The rect in which text is laid out: text_rect, extended to the height
+    // of the text (drawn with offset view_offset and clipped to text_rect)
+    fn layout_rect(&self) -> Rect {
+        let mut rect = self.text_rect;
+        rect.size.1 = rect.size.1.max(self.text_height);
+        rect
+    }
+
+    fn max_view_offset(&self) -> i32 {
+        self.text_height.saturating_sub(self.text_rect.size.1) as i32
+    }
+
     fn received_char(&mut self, mgr: &mut Manager, c: char) -> EditAction {
+        self.edit_x = None;
         if !self.editable {
             return EditAction::None;
         }
 
-        let pos = self.edit_pos;
-        if self.last_edit != LastEdit::Insert {
-            self.old_state = Some((self.text.clone(), pos));
-            self.last_edit = LastEdit::Insert;
-        }
-        self.text.insert(pos, c);
-        self.edit_pos = pos + c.len_utf8();
+        let mut buf = [0u8; 4];
+        self.replace_selection(c.encode_utf8(&mut buf), LastEdit::Insert);
 
         mgr.redraw(self.id());
         EditAction::Edit
     }
 
     fn control_key(&mut self, mgr: &mut Manager, key: ControlKey) -> EditAction {
-        if !self.editable {
-            return EditAction::None;
-        }
-
         mgr.redraw(self.id());
-        let pos = self.edit_pos;
-        match key {
-            ControlKey::Return => EditAction::Activate,
+        let pos = self.selection.edit_pos();
+        let extend = mgr.modifiers().shift();
+        let edit_x = self.edit_x.take();
+        let new_pos = match key {
+            ControlKey::Up
+            | ControlKey::Down
+            | ControlKey::Home
+            | ControlKey::End
+            | ControlKey::PageUp
+            | ControlKey::PageDown
+                if self.multi_line && !mgr.modifiers().ctrl() =>
+            {
+                self.line_nav(mgr, key, edit_x)
+            }
             ControlKey::Left => {
                 let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
-                if let Some(prev) = cursor.prev_boundary(&self.text, 0).unwrap() {
-                    self.edit_pos = prev;
-                }
-                EditAction::None
+                cursor.prev_boundary(&self.text, 0).unwrap().unwrap_or(pos)
             }
             ControlKey::Right => {
                 let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
-                if let Some(next) = cursor.next_boundary(&self.text, 0).unwrap() {
-                    self.edit_pos = next;
+                cursor.next_boundary(&self.text, 0).unwrap().unwrap_or(pos)
+            }
+            ControlKey::Up | ControlKey::Home | ControlKey::PageUp => 0,
+            ControlKey::Down | ControlKey::End | ControlKey::PageDown => self.text.len(),
+            ControlKey::Copy => {
+                if !self.selection.is_empty() {
+                    mgr.set_clipboard(self.selected_text().into());
                 }
-                EditAction::None
+                return EditAction::None;
             }
-            ControlKey::Up | ControlKey::Home | ControlKey::PageUp => {
-                self.edit_pos = 0;
-                EditAction::None
+            key if self.editable => return self.edit_key(mgr, key),
+            _ => return EditAction::None,
+        };
+
+        if extend {
+            self.selection.set_edit_pos(new_pos);
+            if !self.selection.is_empty() {
+                mgr.set_primary(self.selected_text().into());
             }
-            ControlKey::Down | ControlKey::End | ControlKey::PageDown => {
-                self.edit_pos = self.text.len();
-                EditAction::None
+        } else {
+            self.selection.set_pos(new_pos);
+        }
+        EditAction::None
+    }
+
+    /// Find the edit position after navigating via `key` in multi-line mode
+    ///
+    /// Where the position moves between lines, the horizontal position
+    /// `edit_x` (if given, otherwise that of the current position) is kept.
+    fn line_nav(&mut self, mgr: &mut Manager, key: ControlKey, edit_x: Option<f32>) -> usize {
+        let class = self.text_class();
+        let align = (Align::Begin, Align::Begin);
+        let (rect, scale) = (self.layout_rect(), self.text_scale);
+        let page = self.text_rect.size.1 as f32;
+        let (text, pos) = (&self.text, self.selection.edit_pos());
+        let (new_pos, edit_x) = mgr.size_handle(|h| {
+            let lines = h.text_lines_scaled(rect, text, class, scale, align);
+            let index = lines
+                .iter()
+                .rposition(|line| line.range.start <= pos)
+                .unwrap_or(0);
+            let line = &lines[index];
+            let target = match key {
+                ControlKey::Home => return (line.range.start, None),
+                ControlKey::End => return (line.range.end, None),
+                ControlKey::Up if index == 0 => return (0, None),
+                ControlKey::Down if index + 1 == lines.len() => return (text.len(), None),
+                ControlKey::Up => index - 1,
+                ControlKey::Down => index + 1,
+                ControlKey::PageUp => {
+                    let y = line.top - page;
+                    lines.iter().position(|line| line.bottom > y).unwrap_or(0)
+                }
+                _ => {
+                    let y = line.bottom + page;
+                    let last = lines.len() - 1;
+                    lines.iter().rposition(|line| line.top < y).unwrap_or(last)
+                }
+            };
+
+            let mut marker_x = |byte| {
+                h.edit_marker_rect_scaled(rect, text, class, scale, align, byte)
+                    .pos
+                    .0
+            };
+            let x = edit_x.unwrap_or_else(|| marker_x(pos) as f32);
+            let range = lines[target].range.clone();
+            // text_index_nearest may not find the position after the last glyph
+            let new_pos = if x >= marker_x(range.end) as f32 {
+                range.end
+            } else {
+                let y = 0.5 * (lines[target].top + lines[target].bottom);
+                let index =
+                    h.text_index_nearest_scaled(rect, text, class, scale, align, Vec2(x, y));
+                index.max(range.start).min(range.end)
+            };
+            (new_pos, Some(x))
+        });
+        self.edit_x = edit_x;
+        new_pos
+    }
+
+    // Handle control keys which may edit the text
+    fn edit_key(&mut self, mgr: &mut Manager, key: ControlKey) -> EditAction {
+        let pos = self.selection.edit_pos();
+        match key {
+            ControlKey::Return if self.multi_line => {
+                self.replace_selection("\n", LastEdit::Insert);
+                EditAction::Edit
+            }
+            ControlKey::Return => EditAction::Activate,
+            ControlKey::Delete | ControlKey::Backspace if !self.selection.is_empty() => {
+                self.replace_selection("", LastEdit::Delete);
+                EditAction::Edit
             }
             ControlKey::Delete => {
                 let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
@@ -408,58 +741,30 @@ impl<G> EditBox<G> {
                     }
 
                     self.text.replace_range(prev..pos, "");
-                    self.edit_pos = prev;
+                    self.selection.set_pos(prev);
                     EditAction::Edit
                 } else {
                     EditAction::None
                 }
             }
             ControlKey::Cut => {
-                mgr.set_clipboard((&self.text).into());
-
-                if self.last_edit != LastEdit::Clear {
-                    self.old_state = Some((self.text.clone(), pos));
-                    self.last_edit = LastEdit::Clear;
+                if self.selection.is_empty() {
+                    return EditAction::None;
                 }
-                self.text.clear();
-                self.edit_pos = 0;
+                mgr.set_clipboard(self.selected_text().into());
+                self.replace_selection("", LastEdit::Clear);
                 EditAction::Edit
             }
-            ControlKey::Copy => {
-                // we don't yet have selection support, so just copy everything
-                mgr.set_clipboard((&self.text).into());
-                EditAction::None
-            }
-            ControlKey::Paste => {
-                if let Some(content) = mgr.get_clipboard() {
-                    if self.last_edit != LastEdit::Paste {
-                        self.old_state = Some((self.text.clone(), pos));
-                        self.last_edit = LastEdit::Paste;
-                    }
-
-                    // We cut the content short on control characters and
-                    // ignore them (preventing line-breaks and ignoring any
-                    // actions such as recursive-paste).
-                    let mut end = content.len();
-                    for (i, c) in content.char_indices() {
-                        if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') {
-                            end = i;
-                            break;
-                        }
-                    }
-                    self.text.insert_str(pos, &content[0..end]);
-                    self.edit_pos = pos + end;
-                    EditAction::Edit
-                } else {
-                    EditAction::None
-                }
-            }
+            ControlKey::Paste => match mgr.get_clipboard() {
+                Some(content) => self.paste(&content),
+                None => EditAction::None,
+            },
             ControlKey::Undo | ControlKey::Redo => {
                 // TODO: maintain full edit history (externally?)
                 // NOTE: undo *and* redo shortcuts map to this control char
                 if let Some((state, pos2)) = self.old_state.as_mut() {
                     std::mem::swap(state, &mut self.text);
-                    self.edit_pos = *pos2;
+                    self.selection.set_pos(*pos2);
                     *pos2 = pos;
                     self.last_edit = LastEdit::None;
                 }
@@ -469,17 +774,118 @@ impl<G> EditBox<G> {
         }
     }
 
-    fn set_edit_pos_from_coord(&mut self, mgr: &mut Manager, coord: Coord) {
-        let class = if self.multi_line {
-            TextClass::EditMulti
-        } else {
-            TextClass::Edit
+    /// Replace the selection (or insert at the edit position) with `content`
+    fn paste(&mut self, content: &str) -> EditAction {
+        let content = match self.multi_line {
+            true => content.replace("\r\n", "\n"),
+            false => content.to_string(),
         };
+        // We cut the content short on control characters and
+        // ignore them (preventing line-breaks in single-line mode and
+        // ignoring any actions such as recursive-paste).
+        let mut end = content.len();
+        for (i, c) in content.char_indices() {
+            if c == '\n' && self.multi_line {
+                continue;
+            }
+            if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') {
+                end = i;
+                break;
+            }
+        }
+        self.replace_selection(&content[0..end], LastEdit::Paste);
+        EditAction::Edit
+    }
+
+    /// Replace the selected text with `s`, placing the edit position after it
+    ///
+    /// The prior state is saved for undo unless this continues an edit of
+    /// the same kind.
+    fn replace_selection(&mut self, s: &str, edit: LastEdit) {
+        if self.last_edit != edit || !self.selection.is_empty() {
+            self.old_state = Some((self.text.clone(), self.selection.edit_pos()));
+            self.last_edit = edit;
+        }
+        let range = self.selection.range();
+        self.text.replace_range(range.clone(), s);
+        self.selection.set_pos(range.start + s.len());
+    }
+
+    /// Set the edit position from a coordinate
+    ///
+    /// If `extend`, the selection is extended, otherwise it is cleared.
+    fn set_edit_pos_from_coord(&mut self, mgr: &mut Manager, coord: Coord, extend: bool) {
+        self.edit_x = None;
+        let class = self.text_class();
         let align = (Align::Begin, Align::Begin);
-        self.edit_pos = mgr.size_handle(|h| {
-            h.text_index_nearest(self.text_rect, &self.text, class, align, coord.into())
+        let (rect, scale) = (self.layout_rect(), self.text_scale);
+        let coord = coord + self.view_offset;
+        let pos = mgr.size_handle(|h| {
+            h.text_index_nearest_scaled(rect, &self.text, class, scale, align, coord.into())
         });
+        if extend {
+            self.selection.set_edit_pos(pos);
+        } else {
+            self.selection.set_pos(pos);
+        }
         mgr.redraw(self.id());
+        self.report_caret(mgr);
+    }
+
+    /// Report the edit marker position for focus tracking
+    ///
+    /// In multi-line mode, this first updates the text height and scrolls to
+    /// keep the edit marker visible.
+    fn report_caret(&mut self, mgr: &mut Manager) {
+        let class = self.text_class();
+        let align = (Align::Begin, Align::Begin);
+        let scale = self.text_scale;
+        let pos = self.selection.edit_pos();
+        if self.multi_line {
+            let (rect, text) = (self.layout_rect(), &self.text);
+            let lines = mgr.size_handle(|h| h.text_lines_scaled(rect, text, class, scale, align));
+            let (top, bottom) = (lines[0].top, lines[lines.len() - 1].bottom);
+            self.text_height = (bottom - top).ceil() as u32;
+        }
+
+        let rect = self.layout_rect();
+        let caret = mgr
+            .size_handle(|h| h.edit_marker_rect_scaled(rect, &self.text, class, scale, align, pos));
+
+        if self.multi_line {
+            let view = self.text_rect;
+            let top = caret.pos.1 - view.pos.1;
+            let bottom = top + caret.size.1 as i32;
+            let mut offset = self.view_offset.1;
+            offset = offset.max(bottom - view.size.1 as i32).min(top);
+            offset = offset.max(0).min(self.max_view_offset());
+            if offset != self.view_offset.1 {
+                self.view_offset.1 = offset;
+                mgr.redraw(self.id());
+            }
+        }
+        mgr.set_caret_rect(self.id(), caret - self.view_offset);
+    }
+}
+
+impl<G: EditGuard> EditBox<G> {
+    /// Replace the text as if edited by the user
+    ///
+    /// The edit position is moved to the end (clearing any selection) and the
+    /// change may be undone. Returns the result of [`EditGuard::edit`].
+    pub(crate) fn replace_text(&mut self, text: String) -> Option<G::Msg> {
+        let old_text = std::mem::replace(&mut self.text, text);
+        self.old_state = Some((old_text, self.selection.edit_pos()));
+        self.last_edit = LastEdit::None;
+        self.selection.set_pos(self.text.len());
+        G::edit(self)
+    }
+
+    /// Add auto-completion
+    ///
+    /// See [`AutoComplete`].
+    pub fn with_completer<C: Completer + 'static>(self, completer: C) -> AutoComplete<G> {
+        AutoComplete::new(self, completer)
     }
 }
 
@@ -490,8 +896,9 @@ impl<G: EditGuard> HasText for EditBox<G> {
 
     fn set_cow_string(&mut self, text: CowString) -> TkAction {
         self.text = text.to_string();
+        self.selection.set_pos(self.text.len());
         let _ = G::edit(self);
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
 
@@ -512,34 +919,92 @@ impl<G: EditGuard + 'static> event::Handler for EditBox<G> {
         match event {
             Event::Activate => {
                 mgr.request_char_focus(self.id());
+                self.report_caret(mgr);
                 Response::None
             }
             Event::LostCharFocus => {
+                self.selection.set_empty();
+                mgr.redraw(self.id());
                 let r = G::focus_lost(self);
                 r.map(|msg| msg.into()).unwrap_or(Response::None)
             }
-            Event::Control(key) => match self.control_key(mgr, key) {
-                EditAction::None => Response::None,
-                EditAction::Activate => G::activate(self).into(),
-                EditAction::Edit => G::edit(self).into(),
-            },
-            Event::ReceivedCharacter(c) => match self.received_char(mgr, c) {
-                EditAction::None => Response::None,
-                EditAction::Activate => G::activate(self).into(),
-                EditAction::Edit => G::edit(self).into(),
-            },
+            Event::Control(key) => {
+                let action = self.control_key(mgr, key);
+                self.report_caret(mgr);
+                match action {
+                    EditAction::None => Response::None,
+                    EditAction::Activate => G::activate(self).into(),
+                    EditAction::Edit => G::edit(self).into(),
+                }
+            }
+            Event::ReceivedCharacter(c) => {
+                let action = self.received_char(mgr, c);
+                self.report_caret(mgr);
+                match action {
+                    EditAction::None => Response::None,
+                    EditAction::Activate => G::activate(self).into(),
+                    EditAction::Edit => G::edit(self).into(),
+                }
+            }
             Event::PressStart { source, coord, .. } if source.is_primary() => {
-                self.set_edit_pos_from_coord(mgr, coord);
+                let extend = mgr.modifiers().shift();
+                self.set_edit_pos_from_coord(mgr, coord, extend);
                 mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
                 mgr.request_char_focus(self.id());
                 Response::None
             }
+            Event::PressStart {
+                source: PressSource::Mouse(MouseButton::Middle),
+                coord,
+                ..
+            } if self.editable => {
+                // Paste the primary selection
+                match mgr.get_primary() {
+                    Some(content) => {
+                        self.set_edit_pos_from_coord(mgr, coord, false);
+                        mgr.request_char_focus(self.id());
+                        match self.paste(&content) {
+                            EditAction::Edit => G::edit(self).into(),
+                            _ => Response::None,
+                        }
+                    }
+                    None => Response::Unhandled(event),
+                }
+            }
             Event::PressMove { coord, .. } => {
-                self.set_edit_pos_from_coord(mgr, coord);
-                // TODO: text selection
+                self.set_edit_pos_from_coord(mgr, coord, true);
+                Response::None
+            }
+            Event::PressEnd { .. } => {
+                if !self.selection.is_empty() {
+                    mgr.set_primary(self.selected_text().into());
+                }
+                Response::None
+            }
+            Event::Scroll(delta) if self.zoom && mgr.modifiers().ctrl() => {
+                let lines = match delta {
+                    ScrollDelta::LineDelta(_, y) => y,
+                    ScrollDelta::PixelDelta(coord) => coord.1 as f32 / ZOOM_PIXELS_PER_STEP,
+                };
+                let scale = self.text_scale * ZOOM_STEP.powf(lines);
+                Response::action(self.set_text_scale(scale.max(ZOOM_MIN).min(ZOOM_MAX)))
+            }
+            Event::Scroll(delta) if self.multi_line => {
+                let d = match mgr.scroll_delta(delta) {
+                    ScrollDelta::LineDelta(_, y) => {
+                        let step = mgr.size_handle(|h| h.scroll_step());
+                        (step as f32 * y) as i32
+                    }
+                    ScrollDelta::PixelDelta(coord) => coord.1,
+                };
+                let offset = (self.view_offset.1 - d).max(0).min(self.max_view_offset());
+                if offset == self.view_offset.1 {
+                    return Response::Unhandled(Event::Scroll(delta));
+                }
+                self.view_offset.1 = offset;
+                mgr.redraw(self.id());
                 Response::None
             }
-            Event::PressEnd { .. } => Response::None,
             event => Response::Unhandled(event),
         }
     }
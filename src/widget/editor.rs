@@ -0,0 +1,633 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Multi-line text editor
+
+use std::cell::RefCell;
+use unicode_segmentation::GraphemeCursor;
+
+use kas::document::TextDocument;
+use kas::draw::{ClipRegion, TextClass};
+use kas::event::{ControlKey, GrabMode, MouseButton, PressSource, ScrollDelta};
+use kas::prelude::*;
+
+/// Number of lines scrolled per mouse-wheel step
+const LINES_PER_STEP: f32 = 3.0;
+
+/// A multi-line text editor
+///
+/// This widget is a view over a [`TextDocument`]. Multiple views may share a
+/// document; each has its own edit cursor and scroll position. Edits made via
+/// any view are reported to other views through the document's update handle.
+///
+/// Only visible lines are laid out and drawn, hence large documents may be
+/// edited efficiently. Line layout is cached: after an edit, only the edited
+/// line and visible lines following it are re-laid out. Lines are not wrapped.
+/// The horizontal scroll range is limited by the widest visible line.
+///
+/// Selection is not yet supported: Copy and Cut act on the whole line
+/// containing the edit cursor.
+#[widget(config=noauto)]
+#[handler(handle=noauto)]
+#[derive(Clone, Debug, Widget)]
+pub struct TextEditor {
+    #[widget_core]
+    core: CoreData,
+    frame_offset: Coord,
+    frame_size: Size,
+    text_rect: Rect,
+    line_height: u32,
+    ideal_size: (usize, usize),
+    offset: Coord,
+    editable: bool,
+    doc: TextDocument,
+    revision: u64,
+    edit_pos: usize,
+    // Lines are laid out when drawn, thus the cache is mutable from draw
+    cache: RefCell<LineCache>,
+}
+
+/// Text and layout of visible lines
+///
+/// Line `first + i` is `lines[i]`, as of document revision `revision`.
+/// Text is fetched by [`LineCache::update`], while lines are laid out (using
+/// the theme) by [`LineCache::layout`]; each is only done for lines not
+/// already cached.
+#[derive(Clone, Debug, Default)]
+struct LineCache {
+    revision: u64,
+    first: usize,
+    lines: Vec<CachedLine>,
+}
+
+#[derive(Clone, Debug)]
+struct CachedLine {
+    text: String,
+    // Width as laid out, if laid out
+    width: Option<u32>,
+}
+
+impl LineCache {
+    fn get(&self, line: usize) -> Option<&str> {
+        let i = line.checked_sub(self.first)?;
+        self.lines.get(i).map(|line| line.text.as_str())
+    }
+
+    /// Width of the widest line laid out
+    fn width(&self) -> u32 {
+        self.lines
+            .iter()
+            .filter_map(|line| line.width)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Invalidate lines from the first edited since `self.revision`
+    fn invalidate_edits(&mut self, doc: &TextDocument) {
+        if self.revision == doc.revision() {
+            return;
+        }
+        let edits = doc.edits_since(self.revision);
+        self.revision = doc.revision();
+        let edits = match edits {
+            Some(edits) => edits,
+            None => return self.lines.clear(),
+        };
+        // Text before an edit is not moved by any subsequent edit, thus the
+        // lowest start position (mapped forward) marks the first change.
+        let mut start = None;
+        for edit in edits {
+            start = Some(match start {
+                Some(pos) => edit.map_pos(pos).min(edit.pos),
+                None => edit.pos,
+            });
+        }
+        if let Some(pos) = start {
+            self.invalidate_from(doc.line_of(pos));
+        }
+    }
+
+    fn invalidate_from(&mut self, line: usize) {
+        let len = line.saturating_sub(self.first).min(self.lines.len());
+        self.lines.truncate(len);
+    }
+
+    /// Update to cover lines `first..last`
+    fn update(&mut self, doc: &TextDocument, first: usize, last: usize) {
+        self.invalidate_edits(doc);
+        let last = last.max(first);
+        if first < self.first || first >= self.first + self.lines.len() {
+            self.lines.clear();
+            self.first = first;
+        } else {
+            self.lines.drain(..first - self.first);
+            self.first = first;
+        }
+        self.lines.truncate(last - first);
+        for line in first + self.lines.len()..last {
+            let text = doc.line(line);
+            self.lines.push(CachedLine { text, width: None });
+        }
+    }
+
+    /// Lay out lines not already laid out
+    fn layout(&mut self, size_handle: &mut dyn SizeHandle, line_height: u32) {
+        let (class, align) = (TextClass::Edit, (Align::Begin, Align::Begin));
+        let rect = Rect::new(Coord::ZERO, Size(0, line_height));
+        for line in self.lines.iter_mut().filter(|line| line.width.is_none()) {
+            // The edit marker after the last glyph marks the line's extent
+            let end = line.text.len();
+            let marker = size_handle.edit_marker_rect(rect, &line.text, class, align, end);
+            line.width = Some(marker.pos.0.max(0) as u32);
+        }
+    }
+}
+
+impl WidgetConfig for TextEditor {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.update_on_handle(self.doc.update_handle(), self.id());
+    }
+
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> event::CursorIcon {
+        event::CursorIcon::Text
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        match self.editable {
+            true => vec![(event::WidgetAction::Edit, Event::Activate)],
+            false => vec![],
+        }
+    }
+
+    fn scroll_offset(&self) -> Option<Coord> {
+        Some(self.offset)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Coord) -> TkAction {
+        let offset = self.clamp_offset(offset);
+        if offset == self.offset {
+            return TkAction::empty();
+        }
+        self.offset = offset;
+        TkAction::REDRAW
+    }
+}
+
+impl Layout for TextEditor {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let frame_sides = size_handle.edit_surround();
+        let inner = size_handle.inner_margin();
+        let frame_offset = frame_sides.0 + inner;
+        let frame_size = frame_offset + frame_sides.1 + inner;
+
+        let margins = size_handle.outer_margins();
+        let frame_rules = SizeRules::extract_fixed(axis.is_vertical(), frame_size, margins);
+
+        self.line_height = size_handle.line_height(TextClass::Edit).max(1);
+        let (min, ideal) = if axis.is_horizontal() {
+            let sample = "0".repeat(self.ideal_size.0);
+            let ideal = size_handle
+                .text_bound(&sample, TextClass::Edit, axis)
+                .ideal_size();
+            (ideal / 4, ideal)
+        } else {
+            let rows = self.ideal_size.1 as u32;
+            (self.line_height, self.line_height * rows)
+        };
+        let content_rules = SizeRules::new(min, ideal, (0, 0), StretchPolicy::Maximise);
+
+        if axis.is_horizontal() {
+            self.frame_offset.0 = frame_offset.0 as i32;
+            self.frame_size.0 = frame_size.0;
+        } else {
+            self.frame_offset.1 = frame_offset.1 as i32;
+            self.frame_size.1 = frame_size.1;
+        }
+        content_rules.surrounded_by(frame_rules, true)
+    }
+
+    fn set_rect(&mut self, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        self.text_rect.pos = rect.pos + self.frame_offset;
+        self.text_rect.size = rect.size - self.frame_size;
+        self.offset = self.clamp_offset(self.offset);
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let input_state = self.input_state(mgr, disabled);
+        draw_handle.edit_box(self.core.rect, input_state);
+
+        let (class, align) = (TextClass::Edit, (Align::Begin, Align::Begin));
+        let edit_pos = self.edit_pos();
+        let cursor_line = self.doc.line_of(edit_pos);
+        let (first, last) = self.visible_lines();
+        let mut cache = self.cache.borrow_mut();
+        cache.update(&self.doc, first, last);
+        let line_height = self.line_height;
+        draw_handle.size_handle_dyn(&mut |size_handle| cache.layout(size_handle, line_height));
+        let rect = self.text_rect;
+        draw_handle.clip_region(rect, self.offset, ClipRegion::Scroll, &mut |draw_handle| {
+            for line in first..last {
+                let rect = self.line_rect(line);
+                let text = cache.get(line).unwrap_or_default();
+                draw_handle.text(rect, text, class, align);
+                if input_state.char_focus && line == cursor_line {
+                    let byte = edit_pos - self.doc.line_start(line);
+                    draw_handle.edit_marker(rect, text, class, align, byte);
+                }
+            }
+        });
+    }
+}
+
+impl TextEditor {
+    /// Construct a view over `doc`
+    pub fn new(doc: TextDocument) -> Self {
+        let revision = doc.revision();
+        TextEditor {
+            core: Default::default(),
+            frame_offset: Coord::ZERO,
+            frame_size: Size::ZERO,
+            text_rect: Default::default(),
+            line_height: 1,
+            ideal_size: (40, 10),
+            offset: Coord::ZERO,
+            editable: true,
+            doc,
+            revision,
+            edit_pos: 0,
+            cache: RefCell::new(LineCache {
+                revision,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Set the ideal size, in characters and lines
+    ///
+    /// By default this is 40 × 10.
+    pub fn with_ideal_size(mut self, cols: usize, rows: usize) -> Self {
+        self.ideal_size = (cols, rows);
+        self
+    }
+
+    /// Set whether the document may be edited via this view
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    /// Access the document
+    pub fn document(&self) -> &TextDocument {
+        &self.doc
+    }
+
+    /// Get the edit cursor position (byte index)
+    pub fn edit_pos(&self) -> usize {
+        self.doc.map_pos(self.edit_pos, self.revision)
+    }
+
+    /// Set the edit cursor position (byte index)
+    ///
+    /// The position is clamped to the document length.
+    pub fn set_edit_pos(&mut self, pos: usize) -> TkAction {
+        self.sync();
+        self.edit_pos = self.doc.clamp_pos(pos);
+        TkAction::REDRAW
+    }
+
+    /// Apply edits made via other views
+    fn sync(&mut self) {
+        self.edit_pos = self.doc.map_pos(self.edit_pos, self.revision);
+        self.revision = self.doc.revision();
+    }
+
+    /// Lay out visible lines, where not already cached
+    ///
+    /// Lines are otherwise laid out when drawn.
+    fn update_layout(&mut self, mgr: &mut Manager) {
+        let (first, last) = self.visible_lines();
+        let cache = self.cache.get_mut();
+        cache.update(&self.doc, first, last);
+        let line_height = self.line_height;
+        mgr.size_handle(|size_handle| cache.layout(size_handle, line_height));
+    }
+
+    fn line_rect(&self, line: usize) -> Rect {
+        let lh = self.line_height;
+        let pos = self.text_rect.pos + Coord(0, (line as u32 * lh) as i32);
+        // Text beyond the visible region is clipped
+        let size = Size(self.text_rect.size.0 + self.offset.0 as u32, lh);
+        Rect::new(pos, size)
+    }
+
+    /// Range of visible lines
+    fn visible_lines(&self) -> (usize, usize) {
+        let lh = self.line_height as i32;
+        let first = (self.offset.1 / lh) as usize;
+        let last = (self.offset.1 + self.text_rect.size.1 as i32 + lh - 1) / lh;
+        (first, (last as usize).min(self.doc.len_lines()))
+    }
+
+    fn clamp_offset(&self, offset: Coord) -> Coord {
+        let height = self.doc.len_lines() as u32 * self.line_height;
+        let max_y = height.saturating_sub(self.text_rect.size.1) as i32;
+        // Allow space for the edit marker after the end of the widest line
+        let width = self.cache.borrow().width() + self.line_height;
+        let max_x = width.saturating_sub(self.text_rect.size.0) as i32;
+        Coord(offset.0.min(max_x).max(0), offset.1.min(max_y).max(0))
+    }
+
+    fn set_offset(&mut self, mgr: &mut Manager, offset: Coord) -> bool {
+        let offset = self.clamp_offset(offset);
+        if offset != self.offset {
+            self.offset = offset;
+            self.update_layout(mgr);
+            mgr.redraw(self.id());
+            return true;
+        }
+        false
+    }
+
+    /// Scroll such that the edit cursor is visible
+    fn ensure_visible(&mut self, mgr: &mut Manager) {
+        // Lay out the edit cursor's line before clamping the offset to width
+        self.update_layout(mgr);
+        let caret = self.caret_rect(mgr);
+        let x = caret.pos.0 - self.text_rect.pos.0;
+        let y = caret.pos.1 - self.text_rect.pos.1;
+
+        let lh = self.line_height as i32;
+        let size = self.text_rect.size;
+        let mut offset = self.offset;
+        if x < offset.0 {
+            offset.0 = x;
+        } else if x + lh > offset.0 + size.0 as i32 {
+            offset.0 = x + lh - size.0 as i32;
+        }
+        if y < offset.1 {
+            offset.1 = y;
+        } else if y + lh > offset.1 + size.1 as i32 {
+            offset.1 = y + lh - size.1 as i32;
+        }
+        self.set_offset(mgr, offset);
+        self.report_caret(mgr);
+    }
+
+    /// Get the edit marker's rect, before scrolling
+    fn caret_rect(&self, mgr: &mut Manager) -> Rect {
+        let line = self.doc.line_of(self.edit_pos);
+        let rect = self.line_rect(line);
+        let text = self.doc.line(line);
+        let byte = self.edit_pos - self.doc.line_start(line);
+        let (class, align) = (TextClass::Edit, (Align::Begin, Align::Begin));
+        mgr.size_handle(|h| h.edit_marker_rect(rect, &text, class, align, byte))
+    }
+
+    /// Report the edit marker position for focus tracking
+    fn report_caret(&self, mgr: &mut Manager) {
+        let caret = self.caret_rect(mgr);
+        mgr.set_caret_rect(self.id(), caret - self.offset);
+    }
+
+    /// Replace `range` and move the edit cursor to the end of `text`
+    fn edit(&mut self, mgr: &mut Manager, range: std::ops::Range<usize>, text: &str) {
+        let pos = range.start + text.len();
+        self.doc.replace(range, text);
+        self.edit_pos = pos;
+        self.revision = self.doc.revision();
+        self.doc.notify(mgr);
+        self.ensure_visible(mgr);
+        mgr.redraw(self.id());
+    }
+
+    fn line_end(&self, line: usize) -> usize {
+        self.doc.line_start(line) + self.doc.line(line).len()
+    }
+
+    fn control_key(&mut self, mgr: &mut Manager, key: ControlKey) -> Response<VoidMsg> {
+        let pos = self.edit_pos;
+        let line = self.doc.line_of(pos);
+        let start = self.doc.line_start(line);
+        let text = self.doc.line(line);
+        let col = pos - start;
+        let last_line = self.doc.len_lines() - 1;
+        let rows = (self.text_rect.size.1 / self.line_height).max(1) as usize;
+
+        // Move to line `target`, keeping the column where possible
+        let to_line = |doc: &TextDocument, target: usize| {
+            let text = doc.line(target);
+            let mut col = col.min(text.len());
+            while !text.is_char_boundary(col) {
+                col -= 1;
+            }
+            doc.line_start(target) + col
+        };
+
+        let new_pos = match key {
+            ControlKey::Return if self.editable => {
+                self.edit(mgr, pos..pos, "\n");
+                return Response::None;
+            }
+            ControlKey::Left => {
+                let mut cursor = GraphemeCursor::new(col, text.len(), true);
+                match cursor.prev_boundary(&text, 0).unwrap() {
+                    Some(prev) => start + prev,
+                    None if line > 0 => self.line_end(line - 1),
+                    None => pos,
+                }
+            }
+            ControlKey::Right => {
+                let mut cursor = GraphemeCursor::new(col, text.len(), true);
+                match cursor.next_boundary(&text, 0).unwrap() {
+                    Some(next) => start + next,
+                    None if line < last_line => self.doc.line_start(line + 1),
+                    None => pos,
+                }
+            }
+            ControlKey::Up => match line {
+                0 => 0,
+                line => to_line(&self.doc, line - 1),
+            },
+            ControlKey::Down => match line < last_line {
+                true => to_line(&self.doc, line + 1),
+                false => self.doc.len(),
+            },
+            ControlKey::PageUp => to_line(&self.doc, line.saturating_sub(rows)),
+            ControlKey::PageDown => to_line(&self.doc, (line + rows).min(last_line)),
+            ControlKey::Home => start,
+            ControlKey::End => start + text.len(),
+            ControlKey::Delete if self.editable => {
+                let mut cursor = GraphemeCursor::new(col, text.len(), true);
+                let end = match cursor.next_boundary(&text, 0).unwrap() {
+                    Some(next) => start + next,
+                    None if line < last_line => self.doc.line_start(line + 1),
+                    None => return Response::None,
+                };
+                self.edit(mgr, pos..end, "");
+                return Response::None;
+            }
+            ControlKey::Backspace if self.editable => {
+                let mut cursor = GraphemeCursor::new(col, text.len(), true);
+                let prev = match cursor.prev_boundary(&text, 0).unwrap() {
+                    Some(prev) => start + prev,
+                    None if line > 0 => self.line_end(line - 1),
+                    None => return Response::None,
+                };
+                self.edit(mgr, prev..pos, "");
+                return Response::None;
+            }
+            ControlKey::Copy | ControlKey::Cut => {
+                let end = match line < last_line {
+                    true => self.doc.line_start(line + 1),
+                    false => start + text.len(),
+                };
+                mgr.set_clipboard(self.doc.slice(start..end).into());
+                if key == ControlKey::Cut && self.editable {
+                    self.edit(mgr, start..end, "");
+                }
+                return Response::None;
+            }
+            ControlKey::Paste if self.editable => {
+                if let Some(content) = mgr.get_clipboard() {
+                    // Filter out control characters other than line breaks and tabs
+                    let content: String = content
+                        .chars()
+                        .filter(|c| *c == '\n' || *c == '\t' || !c.is_control())
+                        .collect();
+                    self.edit(mgr, pos..pos, &content);
+                }
+                return Response::None;
+            }
+            key => return Response::Unhandled(Event::Control(key)),
+        };
+
+        if new_pos != pos {
+            self.edit_pos = new_pos;
+            self.ensure_visible(mgr);
+            mgr.redraw(self.id());
+        }
+        Response::None
+    }
+
+    fn set_edit_pos_from_coord(&mut self, mgr: &mut Manager, coord: Coord) {
+        let lh = self.line_height as i32;
+        let y = coord.1 - self.text_rect.pos.1 + self.offset.1;
+        let line = ((y.max(0) / lh) as usize).min(self.doc.len_lines() - 1);
+        let rect = self.line_rect(line);
+        let text = self.doc.line(line);
+        let (class, align) = (TextClass::Edit, (Align::Begin, Align::Begin));
+        let pos = (coord + self.offset).into();
+        let index = mgr.size_handle(|h| h.text_index_nearest(rect, &text, class, align, pos));
+        self.edit_pos = self.doc.line_start(line) + index;
+        self.ensure_visible(mgr);
+        mgr.redraw(self.id());
+    }
+}
+
+impl event::Handler for TextEditor {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        self.sync();
+        match event {
+            Event::Activate => {
+                mgr.request_char_focus(self.id());
+                self.report_caret(mgr);
+                Response::None
+            }
+            Event::Control(key) => self.control_key(mgr, key),
+            Event::ReceivedCharacter(c) if self.editable => {
+                let pos = self.edit_pos;
+                let mut buf = [0; 4];
+                self.edit(mgr, pos..pos, c.encode_utf8(&mut buf));
+                Response::None
+            }
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                self.set_edit_pos_from_coord(mgr, coord);
+                mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
+                mgr.request_char_focus(self.id());
+                Response::None
+            }
+            Event::PressStart {
+                source: PressSource::Mouse(MouseButton::Middle),
+                coord,
+                ..
+            } if self.editable => {
+                // Paste the primary selection
+                match mgr.get_primary() {
+                    Some(content) => {
+                        self.set_edit_pos_from_coord(mgr, coord);
+                        mgr.request_char_focus(self.id());
+                        let content: String = content
+                            .chars()
+                            .filter(|c| *c == '\n' || *c == '\t' || !c.is_control())
+                            .collect();
+                        let pos = self.edit_pos;
+                        self.edit(mgr, pos..pos, &content);
+                        Response::None
+                    }
+                    None => Response::Unhandled(event),
+                }
+            }
+            Event::PressMove { coord, .. } => {
+                self.set_edit_pos_from_coord(mgr, coord);
+                Response::None
+            }
+            Event::PressEnd { .. } => Response::None,
+            Event::Scroll(delta) => {
+                let d = match mgr.scroll_delta(delta) {
+                    ScrollDelta::LineDelta(x, y) => {
+                        let lh = self.line_height as f32 * LINES_PER_STEP;
+                        Coord((-x * lh) as i32, (y * lh) as i32)
+                    }
+                    ScrollDelta::PixelDelta(d) => d,
+                };
+                match self.set_offset(mgr, self.offset - d) {
+                    true => {
+                        self.report_caret(mgr);
+                        Response::None
+                    }
+                    false => Response::Unhandled(Event::Scroll(delta)),
+                }
+            }
+            Event::HandleUpdate { handle, .. } if handle == self.doc.update_handle() => {
+                // Cursor was adjusted by sync(); the document length may have
+                // changed, thus we re-clamp the offset.
+                self.update_layout(mgr);
+                self.offset = self.clamp_offset(self.offset);
+                mgr.redraw(self.id());
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+#[test]
+fn line_cache() {
+    let doc = TextDocument::new("0\n1\n2\n3\n4\n5\n");
+    let mut cache = LineCache::default();
+    cache.update(&doc, 1, 5);
+    assert_eq!(cache.get(4), Some("4"));
+
+    // Edit within line 3: lines 1 and 2 remain cached
+    doc.insert(doc.line_start(3), "x");
+    cache.invalidate_edits(&doc);
+    assert_eq!(cache.lines.len(), 2);
+    cache.update(&doc, 2, 6);
+    assert_eq!(cache.get(2), Some("2"));
+    assert_eq!(cache.get(3), Some("x3"));
+    assert_eq!(cache.get(5), Some("5"));
+
+    // Insert a line before the cached region
+    doc.insert(0, "\n");
+    cache.update(&doc, 2, 6);
+    assert_eq!(cache.get(2), Some("1"));
+}
@@ -0,0 +1,120 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A region reserved for an external renderer
+
+use raw_window_handle::RawWindowHandle;
+use std::fmt::{self, Debug};
+
+use kas::prelude::*;
+
+/// A region reserved for an external renderer
+///
+/// This widget occupies space in the layout but draws nothing. It allows an
+/// external renderer (e.g. libmpv or an OpenGL context) to draw into a
+/// sub-region of a KAS window, usually via a child window created with the
+/// native window handle and positioned over the widget's rect.
+///
+/// The handler set via [`ExternalSurface::on_change`] is called with the raw
+/// window handle (if the toolkit provides one) and the widget's rect (in
+/// physical pixels, relative to the window) whenever either changes.
+///
+/// Note that the toolkit continues to draw the window background behind this
+/// region, thus the external renderer must draw over it.
+#[widget(config=noauto)]
+#[derive(Widget)]
+pub struct ExternalSurface {
+    #[widget_core]
+    core: CoreData,
+    min_size: Size,
+    ideal_size: Size,
+    handle: Option<RawWindowHandle>,
+    reported: Option<(Coord, Size)>,
+    on_change: Option<Box<dyn FnMut(Option<RawWindowHandle>, Rect)>>,
+}
+
+impl Debug for ExternalSurface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ExternalSurface {{ core: {:?}, min_size: {:?}, ideal_size: {:?}, handle: {:?}, ... }}",
+            self.core, self.min_size, self.ideal_size, self.handle,
+        )
+    }
+}
+
+impl WidgetConfig for ExternalSurface {
+    fn configure(&mut self, mgr: &mut Manager) {
+        // The handle may have changed; we report on set_rect, which always
+        // follows configuration.
+        self.handle = mgr.raw_window_handle();
+        self.reported = None;
+    }
+}
+
+impl Layout for ExternalSurface {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let scale = size_handle.scale_factor();
+        let (min, ideal) = match axis.is_horizontal() {
+            true => (self.min_size.0, self.ideal_size.0),
+            false => (self.min_size.1, self.ideal_size.1),
+        };
+        let min = (min as f32 * scale).round() as u32;
+        let ideal = (ideal as f32 * scale).round() as u32;
+        SizeRules::new(min, ideal, (0, 0), StretchPolicy::Maximise)
+    }
+
+    fn set_rect(&mut self, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        self.report();
+    }
+
+    fn draw(&self, _: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {}
+}
+
+impl ExternalSurface {
+    /// Construct with the given minimum and ideal sizes
+    ///
+    /// Sizes are in logical pixels (i.e. are multiplied by the scale factor).
+    /// The widget may stretch beyond its ideal size.
+    pub fn new(min_size: Size, ideal_size: Size) -> Self {
+        ExternalSurface {
+            core: Default::default(),
+            min_size,
+            ideal_size,
+            handle: None,
+            reported: None,
+            on_change: None,
+        }
+    }
+
+    /// Set a handler called when the window handle or rect changes
+    ///
+    /// The handler is called with the raw window handle (`None` if the
+    /// toolkit does not provide one) and the widget's rect.
+    pub fn on_change<F: FnMut(Option<RawWindowHandle>, Rect) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Get the raw window handle, if known
+    ///
+    /// This is available after the widget is configured, if the toolkit
+    /// provides a handle.
+    pub fn raw_window_handle(&self) -> Option<RawWindowHandle> {
+        self.handle
+    }
+
+    fn report(&mut self) {
+        let rect = self.core.rect;
+        if self.reported == Some((rect.pos, rect.size)) {
+            return;
+        }
+        self.reported = Some((rect.pos, rect.size));
+        if let Some(f) = self.on_change.as_mut() {
+            f(self.handle, rect);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A list with a filter bar
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::{EditBox, EditGuard, List};
+use kas::class::HasText;
+use kas::prelude::*;
+
+#[derive(Clone, Debug, VoidMsg)]
+enum FilterMsg {
+    Edit(String),
+    Activate,
+}
+
+#[derive(Clone, Debug)]
+struct FilterGuard;
+impl EditGuard for FilterGuard {
+    type Msg = FilterMsg;
+
+    fn activate(_: &mut EditBox<Self>) -> Option<Self::Msg> {
+        Some(FilterMsg::Activate)
+    }
+
+    fn edit(edit: &mut EditBox<Self>) -> Option<Self::Msg> {
+        Some(FilterMsg::Edit(edit.get_text().to_string()))
+    }
+}
+
+/// A row/column with a filter bar
+///
+/// This widget consists of an [`EditBox`] (the *filter bar*) above a [`List`]
+/// of items. On each edit of the filter bar, items are tested against the
+/// filter text using a user-provided predicate; only those matching are shown.
+/// An empty filter shows all items. Pressing Enter in the filter bar activates
+/// the first visible item.
+///
+/// Filtering preserves item order. Hidden items are removed from the widget
+/// tree, hence showing or hiding items triggers a reconfigure.
+///
+/// Matches are not currently highlighted within item labels.
+#[layout(column)]
+#[handler(msg = <W as event::Handler>::Msg)]
+#[derive(Widget)]
+pub struct FilterList<D: Directional, W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(handler = handle_filter)]
+    edit: EditBox<FilterGuard>,
+    #[widget]
+    list: List<D, W>,
+    /// Original index of each element of `list`
+    indices: Vec<usize>,
+    /// Hidden elements with their original index
+    hidden: Vec<(usize, W)>,
+    filter: Rc<dyn Fn(&W, &str) -> bool>,
+}
+
+impl<D: Directional, W: Widget> Debug for FilterList<D, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FilterList {{ core: {:?}, edit: {:?}, list: {:?}, indices: {:?}, hidden: {:?}, ... }}",
+            self.core, self.edit, self.list, self.indices, self.hidden,
+        )
+    }
+}
+
+impl<D: Directional + Default, W: Widget> FilterList<D, W> {
+    /// Construct from a list of items and a predicate
+    ///
+    /// The predicate `filter(item, text)` is called with the current filter
+    /// text (never empty) and should return true if `item` matches.
+    pub fn new<F: Fn(&W, &str) -> bool + 'static>(widgets: Vec<W>, filter: F) -> Self {
+        FilterList {
+            core: Default::default(),
+            layout_data: Default::default(),
+            edit: EditBox::new("").with_guard(FilterGuard),
+            indices: (0..widgets.len()).collect(),
+            list: List::new(widgets),
+            hidden: vec![],
+            filter: Rc::new(filter),
+        }
+    }
+}
+
+impl<D: Directional + Default, W: Widget + HasText> FilterList<D, W> {
+    /// Construct from a list of text items
+    ///
+    /// Items match when their text contains the filter text, ignoring case.
+    pub fn new_text(widgets: Vec<W>) -> Self {
+        Self::new(widgets, |item, text| {
+            let item = item.get_text().to_lowercase();
+            item.contains(&text.to_lowercase())
+        })
+    }
+}
+
+impl<D: Directional, W: Widget> FilterList<D, W> {
+    /// Get the filter text
+    pub fn filter_text(&self) -> &str {
+        self.edit.get_text()
+    }
+
+    /// Set the filter text, updating visible items
+    pub fn set_filter_text<T: ToString>(&mut self, text: T) -> TkAction {
+        let text = text.to_string();
+        let action = self.edit.set_text(text.clone());
+        action + self.refilter(&text)
+    }
+
+    /// Access the list of visible items
+    pub fn visible(&self) -> &List<D, W> {
+        &self.list
+    }
+
+    /// Number of items, including hidden items
+    pub fn len(&self) -> usize {
+        self.list.len() + self.hidden.len()
+    }
+
+    /// True if there are no items
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty() && self.hidden.is_empty()
+    }
+
+    fn refilter(&mut self, text: &str) -> TkAction {
+        let old_indices = self.indices.clone();
+        let mut items = Vec::with_capacity(self.len());
+        while let (Some(w), _) = self.list.pop() {
+            items.push((self.indices.pop().unwrap(), w));
+        }
+        items.append(&mut self.hidden);
+        items.sort_by_key(|item| item.0);
+
+        for (index, w) in items {
+            if text.is_empty() || (self.filter)(&w, text) {
+                self.indices.push(index);
+                let _ = self.list.push(w);
+            } else {
+                self.hidden.push((index, w));
+            }
+        }
+
+        // Widgets retain their identifiers; we only need to reconfigure if the
+        // set of visible widgets changed.
+        match old_indices == self.indices {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        }
+    }
+
+    fn handle_filter(&mut self, mgr: &mut Manager, msg: FilterMsg) -> Response<W::Msg> {
+        match msg {
+            FilterMsg::Edit(text) => Response::action(self.refilter(&text)),
+            FilterMsg::Activate => {
+                if self.list.is_empty() {
+                    return Response::None;
+                }
+                let id = self.list[0].id();
+                match self.list.send(mgr, id, Event::Activate) {
+                    Response::Unhandled(_) => Response::None,
+                    r => r,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A flow layout: items wrapped into rows
+
+use std::ops::{Index, IndexMut};
+
+use kas::prelude::*;
+
+/// A flow (wrapping) layout of equally-sized children
+///
+/// Children are placed left-to-right in rows, wrapping to a new row when the
+/// available width is exhausted, like a photo grid. All children are allotted
+/// a cell of the same size: the maximum of the children's ideal sizes.
+///
+/// The number of columns is determined by the available width and updated
+/// whenever the widget is resized; the required height depends on this (thus
+/// a `Flow` is usually placed within a [`ScrollRegion`](super::ScrollRegion)).
+/// When width is not constrained, the ideal width fits
+/// [`Flow::with_ideal_columns`] columns (default: 4).
+///
+/// Configuring and resizing elements is O(n) in the number of children.
+/// Since cell positions are calculated, drawing and finding children by
+/// coordinate is O(v) in the number of visible children. Note that all
+/// children must still be constructed; see also the "view widgets" item of the
+/// roadmap.
+#[handler(send=noauto, msg=<W as event::Handler>::Msg)]
+#[widget(children=noauto)]
+#[derive(Clone, Debug, Widget)]
+pub struct Flow<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    widgets: Vec<W>,
+    ideal_cols: usize,
+    cols: usize,
+    cell: Size,
+    gap: Size,
+}
+
+impl<W: Widget> WidgetChildren for Flow<W> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.widgets.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.widgets.get_mut(index).map(|w| w.as_widget_mut())
+    }
+}
+
+// Total size of n cells
+fn span(n: usize, cell: u32, gap: u32) -> u32 {
+    match n {
+        0 => 0,
+        n => n as u32 * (cell + gap) - gap,
+    }
+}
+
+impl<W: Widget> Layout for Flow<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let child_axis = match axis.is_vertical() {
+            false => AxisInfo::new(false, None),
+            true => AxisInfo::new(true, Some(self.cell.0)),
+        };
+        let mut rules = SizeRules::EMPTY;
+        for child in &mut self.widgets {
+            rules.max_with(child.size_rules(size_handle, child_axis));
+        }
+        let cell = rules.ideal_size();
+        let gap = rules.margin_between(rules);
+
+        let len = self.widgets.len();
+        let ideal_cols = len.min(self.ideal_cols);
+        if axis.is_horizontal() {
+            self.cell.0 = cell;
+            self.gap.0 = gap;
+            let min = span(len.min(1), cell, gap);
+            let ideal = span(ideal_cols, cell, gap);
+            SizeRules::new(min, ideal, rules.margins(), StretchPolicy::Filler)
+        } else {
+            self.cell.1 = cell;
+            self.gap.1 = gap;
+            let cols = match axis.other() {
+                Some(width) => self.columns_for(width),
+                None => ideal_cols.max(1),
+            };
+            let rows = (len + cols - 1) / cols;
+            let height = span(rows, cell, gap);
+            SizeRules::new(height, height, rules.margins(), StretchPolicy::Fixed)
+        }
+    }
+
+    fn set_rect(&mut self, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        self.cols = self.columns_for(rect.size.0);
+        for index in 0..self.widgets.len() {
+            let rect = self.cell_rect(index);
+            self.widgets[index].set_rect(rect, AlignHints::default());
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+
+        let rel = coord - self.core.rect.pos;
+        let col = rel.0 as usize / (self.cell.0 + self.gap.0).max(1) as usize;
+        let row = rel.1 as usize / (self.cell.1 + self.gap.1).max(1) as usize;
+        if col < self.cols {
+            if let Some(child) = self.widgets.get(row * self.cols + col) {
+                if child.rect().contains(coord) {
+                    return child.find_id(coord);
+                }
+            }
+        }
+
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        let target = draw_handle.target_rect();
+        let row_height = (self.cell.1 + self.gap.1).max(1) as i32;
+        let y0 = target.pos.1 - self.core.rect.pos.1;
+        let y1 = y0 + target.size.1 as i32;
+        let first = (y0.max(0) / row_height) as usize * self.cols;
+        let last = ((y1.max(0) / row_height) as usize + 1) * self.cols;
+        let last = last.min(self.widgets.len());
+        for child in self.widgets.get(first..last).unwrap_or(&[]) {
+            child.draw(draw_handle, mgr, disabled);
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for Flow<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            for child in &mut self.widgets {
+                if id <= child.id() {
+                    return child.send(mgr, id, event);
+                }
+            }
+        }
+
+        Response::Unhandled(event)
+    }
+}
+
+impl<W: Widget> Flow<W> {
+    /// Construct a new instance
+    pub fn new(widgets: Vec<W>) -> Self {
+        Flow {
+            core: Default::default(),
+            widgets,
+            ideal_cols: 4,
+            cols: 1,
+            cell: Size::ZERO,
+            gap: Size::ZERO,
+        }
+    }
+
+    /// Set the number of columns used to calculate the ideal width
+    ///
+    /// This only affects size requirements; when the width is known the
+    /// number of columns is calculated from that.
+    pub fn with_ideal_columns(mut self, cols: usize) -> Self {
+        self.ideal_cols = cols.max(1);
+        self
+    }
+
+    /// Get the number of columns in the current layout
+    pub fn columns(&self) -> usize {
+        self.cols
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Remove all child widgets
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn clear(&mut self) -> TkAction {
+        let action = match self.widgets.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        self.widgets.clear();
+        action
+    }
+
+    /// Append a child widget
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, widget: W) -> TkAction {
+        self.widgets.push(widget);
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the last child widget
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self) -> (Option<W>, TkAction) {
+        let action = match self.widgets.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        (self.widgets.pop(), action)
+    }
+
+    /// Inserts a child widget position `index`
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn insert(&mut self, index: usize, widget: W) -> TkAction {
+        self.widgets.insert(index, widget);
+        TkAction::RECONFIGURE
+    }
+
+    /// Removes the child widget at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> (W, TkAction) {
+        let r = self.widgets.remove(index);
+        (r, TkAction::RECONFIGURE)
+    }
+
+    /// Append child widgets from an iterator
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widgets
+    /// are added.
+    pub fn extend<T: IntoIterator<Item = W>>(&mut self, iter: T) -> TkAction {
+        let len = self.widgets.len();
+        self.widgets.extend(iter);
+        match len == self.widgets.len() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        }
+    }
+
+    /// Iterate over childern
+    pub fn iter(&self) -> std::slice::Iter<'_, W> {
+        self.widgets.iter()
+    }
+
+    // Number of columns fitting within width
+    fn columns_for(&self, width: u32) -> usize {
+        let pitch = self.cell.0 + self.gap.0;
+        if pitch == 0 {
+            return self.widgets.len().max(1);
+        }
+        ((width + self.gap.0) / pitch).max(1) as usize
+    }
+
+    fn cell_rect(&self, index: usize) -> Rect {
+        let (row, col) = (index / self.cols, index % self.cols);
+        let x = col as u32 * (self.cell.0 + self.gap.0);
+        let y = row as u32 * (self.cell.1 + self.gap.1);
+        Rect::new(self.core.rect.pos + Coord(x as i32, y as i32), self.cell)
+    }
+}
+
+impl<W: Widget> Index<usize> for Flow<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.widgets[index]
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for Flow<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.widgets[index]
+    }
+}
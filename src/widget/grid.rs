@@ -0,0 +1,235 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A grid with run-time specified contents
+
+use kas::layout::{GridChildInfo, GridSetter, GridSolver, RulesSetter, RulesSolver};
+use kas::prelude::*;
+
+/// A grid of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Grid`] type.
+pub type BoxGrid<M> = Grid<Box<dyn Widget<Msg = M>>>;
+
+/// A generic grid widget
+///
+/// Each child is placed in a cell, or in a span of cells, of the grid. Layout
+/// is solved exactly as for `#[layout(grid)]` via [`make_widget`], but the
+/// set of children is determined at run-time; see [`GridBuilder`].
+///
+/// Configuring, resizing, drawing and event handling are O(n) in the number
+/// of children.
+///
+/// [`make_widget`]: ../macros/index.html#the-make_widget-macro
+#[handler(send=noauto, msg=<W as event::Handler>::Msg)]
+#[widget(children=noauto)]
+#[derive(Clone, Default, Debug, Widget)]
+pub struct Grid<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    widgets: Vec<(GridChildInfo, W)>,
+    data: layout::DynGridStorage,
+    dim: (usize, usize),
+    spans: (usize, usize),
+}
+
+impl<W: Widget> WidgetChildren for Grid<W> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.widgets.get(index).map(|w| w.1.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.widgets.get_mut(index).map(|w| w.1.as_widget_mut())
+    }
+}
+
+impl<W: Widget> Layout for Grid<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let spans = (
+            vec![(SizeRules::EMPTY, 0, 0); self.spans.0],
+            vec![(SizeRules::EMPTY, 0, 0); self.spans.1],
+        );
+        let mut solver = GridSolver::new_with_spans(axis, self.dim, spans, &mut self.data);
+        for (info, child) in self.widgets.iter_mut() {
+            solver.for_child(&mut self.data, *info, |axis| {
+                child.size_rules(size_handle, axis)
+            });
+        }
+        solver.finish(&mut self.data)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let mut setter =
+            GridSetter::<Vec<u32>, Vec<u32>, _>::new(rect, self.dim, align, &mut self.data);
+
+        for (info, child) in self.widgets.iter_mut() {
+            let align = AlignHints::default();
+            child.set_rect(setter.child_rect(&mut self.data, *info), align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+
+        for (_, child) in &self.widgets {
+            if child.rect().contains(coord) {
+                return child.find_id(coord);
+            }
+        }
+
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        for (_, child) in &self.widgets {
+            child.draw(draw_handle, mgr, disabled);
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for Grid<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            for (_, child) in &mut self.widgets {
+                if id <= child.id() {
+                    return child.send(mgr, id, event);
+                }
+            }
+        }
+
+        Response::Unhandled(event)
+    }
+}
+
+impl<W: Widget> Grid<W> {
+    /// Construct a new instance
+    ///
+    /// Each child is paired with the cell (or span of cells) it occupies.
+    /// The grid's dimensions are inferred from the children. Cells may be
+    /// empty, but children should not overlap.
+    pub fn new(widgets: Vec<(GridChildInfo, W)>) -> Self {
+        let mut dim = (0, 0);
+        let mut spans = (0, 0);
+        for (info, _) in &widgets {
+            dim.0 = dim.0.max(info.col_end as usize);
+            dim.1 = dim.1.max(info.row_end as usize);
+            if info.col_end > info.col + 1 {
+                spans.0 += 1;
+            }
+            if info.row_end > info.row + 1 {
+                spans.1 += 1;
+            }
+        }
+        Grid {
+            core: Default::default(),
+            widgets,
+            data: Default::default(),
+            dim,
+            spans,
+        }
+    }
+
+    /// Get the number of columns and rows
+    pub fn dim(&self) -> (usize, usize) {
+        self.dim
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Iterate over children and their cell positions
+    pub fn iter(&self) -> impl Iterator<Item = (GridChildInfo, &W)> {
+        self.widgets.iter().map(|(info, w)| (*info, w))
+    }
+
+    /// Access the child at the given index
+    pub fn get(&self, index: usize) -> Option<&W> {
+        self.widgets.get(index).map(|w| &w.1)
+    }
+
+    /// Mutably access the child at the given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut W> {
+        self.widgets.get_mut(index).map(|w| &mut w.1)
+    }
+}
+
+/// A builder for a [`BoxGrid`]
+///
+/// Example:
+/// ```ignore
+/// let grid = GridBuilder::new()
+///     .cell(0, 0, Label::new("Name"))
+///     .cell(1, 0, EditBox::new(""))
+///     .cell_span(0, 1, 2, 1, TextButton::new("Save", Msg::Save))
+///     .build();
+/// ```
+pub struct GridBuilder<M> {
+    widgets: Vec<(GridChildInfo, Box<dyn Widget<Msg = M>>)>,
+}
+
+impl<M: 'static> Default for GridBuilder<M> {
+    fn default() -> Self {
+        GridBuilder::new()
+    }
+}
+
+impl<M: 'static> GridBuilder<M> {
+    /// Construct an empty builder
+    pub fn new() -> Self {
+        GridBuilder {
+            widgets: Vec::new(),
+        }
+    }
+
+    /// Add a widget in cell `(col, row)`
+    pub fn cell<W: Widget<Msg = M> + 'static>(self, col: u32, row: u32, widget: W) -> Self {
+        self.cell_span(col, row, 1, 1, widget)
+    }
+
+    /// Add a widget spanning `col_span` columns and `row_span` rows
+    ///
+    /// The top-left cell is `(col, row)`. Spans must be at least 1.
+    pub fn cell_span<W: Widget<Msg = M> + 'static>(
+        mut self,
+        col: u32,
+        row: u32,
+        col_span: u32,
+        row_span: u32,
+        widget: W,
+    ) -> Self {
+        assert!(col_span >= 1 && row_span >= 1);
+        let info = GridChildInfo {
+            col,
+            col_end: col + col_span,
+            row,
+            row_end: row + row_span,
+        };
+        self.widgets.push((info, Box::new(widget)));
+        self
+    }
+
+    /// Build the grid
+    pub fn build(self) -> BoxGrid<M> {
+        Grid::new(self.widgets)
+    }
+}
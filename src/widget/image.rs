@@ -0,0 +1,355 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Image widget
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+use std::time::Duration;
+
+use kas::draw::ImageId;
+use kas::prelude::*;
+
+/// Frames of an animated image: RGBA pixel data and display duration
+type Frames = Rc<Vec<(Vec<u8>, Duration)>>;
+
+/// A raster image
+///
+/// The image is drawn at its natural size (in logical pixels, i.e. multiplied
+/// by the scale factor) and aligned within the available space.
+///
+/// An image constructed via [`Image::new`] is loaded (via
+/// [`Manager::load_image`]) when first configured and freed when the widget is
+/// destroyed (see [`WidgetConfig::destroy`]), thus each instance holds its own
+/// copy on the GPU. Where an image is displayed repeatedly, load it once via
+/// [`Manager::load_image`] and use [`Image::from_id`]; such images are never
+/// freed by this widget.
+///
+/// An animated image may be constructed from frames via
+/// [`Image::new_animated`], or decoded from a GIF via [`Image::from_gif`]
+/// (requires the `gif` feature). Only the frame shown is loaded: on each
+/// advance (scheduled via [`Manager::update_after`]) the previous frame is
+/// freed and the next loaded, thus GPU memory usage does not depend on the
+/// number of frames.
+#[widget(config=noauto)]
+#[handler(handle=noauto)]
+#[derive(Widget)]
+pub struct Image {
+    #[widget_core]
+    core: CoreData,
+    size: Size,
+    ideal: Size,
+    frames: Option<Frames>,
+    id: Option<ImageId>,
+    current: usize,
+    playing: bool,
+}
+
+impl Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Image {{ core: {:?}, size: {:?}, id: {:?}, current: {}, playing: {}, ... }}",
+            self.core, self.size, self.id, self.current, self.playing
+        )
+    }
+}
+
+impl Clone for Image {
+    fn clone(&self) -> Self {
+        Image {
+            core: self.core.clone(),
+            size: self.size,
+            ideal: self.ideal,
+            frames: self.frames.clone(),
+            // Images loaded from data belong to this instance
+            id: match self.frames.is_some() {
+                true => None,
+                false => self.id,
+            },
+            current: self.current,
+            playing: self.playing,
+        }
+    }
+}
+
+impl WidgetConfig for Image {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if self.id.is_none() {
+            self.load_frame(mgr);
+        }
+        self.schedule_frame(mgr);
+    }
+
+    fn destroy(&mut self, mgr: &mut Manager) {
+        if self.frames.is_some() {
+            if let Some(id) = self.id.take() {
+                mgr.remove_image(id);
+            }
+        }
+    }
+}
+
+impl Layout for Image {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let scale = size_handle.scale_factor();
+        self.ideal = Size(
+            (self.size.0 as f32 * scale).round() as u32,
+            (self.size.1 as f32 * scale).round() as u32,
+        );
+        let margins = size_handle.outer_margins();
+        SizeRules::extract_fixed(axis.is_vertical(), self.ideal, margins)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        let rect = align
+            .complete(Align::Centre, Align::Centre, self.ideal)
+            .apply(rect);
+        self.core.rect = rect;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {
+        if let Some(id) = self.id {
+            draw_handle.image(self.core.rect, id);
+        }
+    }
+}
+
+impl event::Handler for Image {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<VoidMsg> {
+        match event {
+            Event::TimerUpdate if self.is_playing() => {
+                if let Some(id) = self.id.take() {
+                    mgr.remove_image(id);
+                }
+                self.current = (self.current + 1) % self.frame_count();
+                self.load_frame(mgr);
+                mgr.redraw(self.id());
+                self.schedule_frame(mgr);
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl Image {
+    /// Construct from RGBA pixel data
+    ///
+    /// The image is specified as 8-bit RGBA pixel data (sRGB colour space,
+    /// four bytes per pixel) in row-major order, top row first.
+    ///
+    /// Panics if `data.len() != 4 * size.0 * size.1`.
+    pub fn new(size: Size, data: Vec<u8>) -> Self {
+        Image::new_animated(size, vec![(data, Duration::default())])
+    }
+
+    /// Construct an animated image from frames
+    ///
+    /// Each frame is specified as RGBA pixel data (as for [`Image::new`]) and
+    /// the duration for which it is shown. The animation loops, and plays
+    /// unless paused (see [`Image::pause`]).
+    ///
+    /// Panics if `frames` is empty or the data length of any frame is not
+    /// `4 * size.0 * size.1`.
+    pub fn new_animated(size: Size, frames: Vec<(Vec<u8>, Duration)>) -> Self {
+        assert!(!frames.is_empty(), "Image: expected at least one frame");
+        for (data, _) in &frames {
+            assert_eq!(data.len(), 4 * size.0 as usize * size.1 as usize);
+        }
+        Image {
+            core: Default::default(),
+            size,
+            ideal: Size::ZERO,
+            frames: Some(Rc::new(frames)),
+            id: None,
+            current: 0,
+            playing: true,
+        }
+    }
+
+    /// Construct from an image already loaded
+    ///
+    /// The `size` is the natural size of the image, in logical pixels.
+    pub fn from_id(id: ImageId, size: Size) -> Self {
+        Image {
+            core: Default::default(),
+            size,
+            ideal: Size::ZERO,
+            frames: None,
+            id: Some(id),
+            current: 0,
+            playing: false,
+        }
+    }
+
+    /// Decode a GIF image
+    ///
+    /// Frames of an animated GIF are composited (observing each frame's
+    /// disposal method) into full RGBA images when decoded. As in common web
+    /// browsers, a frame delay of zero is treated as 100ms.
+    ///
+    /// Requires the `gif` feature.
+    #[cfg(feature = "gif")]
+    pub fn from_gif(data: &[u8]) -> Result<Self, gif::DecodingError> {
+        use gif::DisposalMethod;
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(data)?;
+        let (w, h) = (decoder.width() as usize, decoder.height() as usize);
+        let mut canvas = vec![0; 4 * w * h];
+        let mut frames = vec![];
+        while let Some(frame) = decoder.read_next_frame()? {
+            let previous = match frame.dispose {
+                DisposalMethod::Previous => Some(canvas.clone()),
+                _ => None,
+            };
+            let (left, top) = (frame.left as usize, frame.top as usize);
+            let (fw, fh) = (frame.width as usize, frame.height as usize);
+            // Frames may not exceed the canvas; transparent pixels are skipped
+            for (y, row) in frame.buffer.chunks(4 * fw).enumerate() {
+                for (x, pixel) in row.chunks(4).enumerate() {
+                    let (x, y) = (left + x, top + y);
+                    if pixel[3] != 0 && x < w && y < h {
+                        let i = 4 * (y * w + x);
+                        canvas[i..i + 4].copy_from_slice(pixel);
+                    }
+                }
+            }
+
+            let delay = match frame.delay {
+                0 => 10,
+                delay => delay,
+            };
+            frames.push((canvas.clone(), Duration::from_millis(10 * delay as u64)));
+
+            match frame.dispose {
+                DisposalMethod::Background => {
+                    for y in top..(top + fh).min(h) {
+                        let x0 = left.min(w);
+                        let x1 = (left + fw).min(w);
+                        canvas[4 * (y * w + x0)..4 * (y * w + x1)]
+                            .iter_mut()
+                            .for_each(|c| *c = 0);
+                    }
+                }
+                DisposalMethod::Previous => canvas = previous.unwrap(),
+                _ => (),
+            }
+        }
+
+        if frames.is_empty() {
+            let kind = std::io::ErrorKind::InvalidData;
+            let msg = "GIF contains no frames";
+            return Err(gif::DecodingError::Io(std::io::Error::new(kind, msg)));
+        }
+        Ok(Image::new_animated(Size(w as u32, h as u32), frames))
+    }
+
+    /// Get the image identifier, if loaded
+    ///
+    /// For an animated image, this is the identifier of the current frame.
+    /// Frames are loaded only when shown, thus this changes as the animation
+    /// plays.
+    pub fn image_id(&self) -> Option<ImageId> {
+        self.id
+    }
+
+    /// The number of frames
+    ///
+    /// This is one except for animated images.
+    pub fn frame_count(&self) -> usize {
+        self.frames.as_ref().map(|frames| frames.len()).unwrap_or(1)
+    }
+
+    /// Index of the current frame
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// True if the animation is playing
+    ///
+    /// This is false for images with only a single frame.
+    pub fn is_playing(&self) -> bool {
+        self.playing && self.frame_count() > 1
+    }
+
+    /// Start or resume playing the animation
+    ///
+    /// This does nothing for images with only a single frame.
+    pub fn play(&mut self, mgr: &mut Manager) {
+        if !self.playing {
+            self.playing = true;
+            self.schedule_frame(mgr);
+        }
+    }
+
+    /// Pause the animation on the current frame
+    pub fn pause(&mut self, mgr: &mut Manager) {
+        if self.playing {
+            self.playing = false;
+            mgr.cancel_updates(self.id());
+        }
+    }
+
+    // Load the current frame, if constructed from data
+    //
+    // On failure (e.g. due to the memory limit) nothing is drawn until the
+    // next frame is loaded.
+    fn load_frame(&mut self, mgr: &mut Manager) {
+        if let Some(ref frames) = self.frames {
+            self.id = mgr.load_image(self.size, &frames[self.current].0);
+        }
+    }
+
+    // Schedule the next frame, if playing
+    fn schedule_frame(&mut self, mgr: &mut Manager) {
+        if self.is_playing() {
+            let frames = self.frames.as_ref().unwrap();
+            mgr.update_after(frames[self.current].1, self.id());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "gif"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gif_decoding() {
+        use gif::{DisposalMethod, Encoder, Frame};
+
+        // A 2×2 GIF: a red frame, then a green pixel drawn over it and disposed
+        let red = [255, 0, 0, 255];
+        let green = [0, 255, 0, 255];
+        let mut data = vec![];
+        {
+            let mut encoder = Encoder::new(&mut data, 2, 2, &[]).unwrap();
+            let mut frame = Frame::from_rgba(2, 2, &mut red.repeat(4));
+            frame.delay = 5;
+            encoder.write_frame(&frame).unwrap();
+            let mut frame = Frame::from_rgba(1, 1, &mut green.to_vec());
+            frame.left = 1;
+            frame.dispose = DisposalMethod::Previous;
+            encoder.write_frame(&frame).unwrap();
+            encoder
+                .write_frame(&Frame::from_rgba(1, 1, &mut red.to_vec()))
+                .unwrap();
+        }
+
+        let image = Image::from_gif(&data).unwrap();
+        assert_eq!(image.frame_count(), 3);
+        let frames = image.frames.as_ref().unwrap();
+        assert_eq!(frames[0], (red.repeat(4), Duration::from_millis(50)));
+        let second = [red, green, red, red].concat();
+        assert_eq!(frames[1], (second, Duration::from_millis(100)));
+        assert_eq!(frames[2].0, red.repeat(4));
+
+        assert!(Image::from_gif(&[]).is_err());
+    }
+}
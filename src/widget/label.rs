@@ -5,16 +5,23 @@
 
 //! Text widgets
 
+use unicode_segmentation::GraphemeCursor;
+
+use super::SelectionHelper;
 use kas::class::HasText;
-use kas::draw::TextClass;
+use kas::draw::{TextClass, TextOrientation};
+use kas::event::{ControlKey, GrabMode};
 use kas::prelude::*;
 
 /// A simple text label
+///
+/// Text may be drawn vertically or rotated; see [`Label::with_orientation`].
 #[derive(Clone, Default, Debug, Widget)]
 pub struct Label {
     #[widget_core]
     core: CoreData,
     align: (Align, Align),
+    orientation: TextOrientation,
     reserve: Option<&'static str>,
     text: LabelString,
 }
@@ -22,7 +29,8 @@ pub struct Label {
 impl Layout for Label {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let text = self.reserve.unwrap_or(&self.text);
-        let rules = size_handle.text_bound(text, TextClass::Label, axis);
+        let class = TextClass::Label;
+        let rules = size_handle.text_bound_oriented(text, class, self.orientation, axis);
         if axis.is_horizontal() {
             self.core.rect.size.0 = rules.ideal_size();
         } else {
@@ -32,15 +40,36 @@ impl Layout for Label {
     }
 
     fn set_rect(&mut self, rect: Rect, align: AlignHints) {
-        self.align = (
-            align.horiz.unwrap_or(Align::Begin),
-            align.vert.unwrap_or(Align::Centre),
-        );
+        self.align = match self.orientation {
+            TextOrientation::Horizontal => (
+                align.horiz.unwrap_or(Align::Begin),
+                align.vert.unwrap_or(Align::Centre),
+            ),
+            TextOrientation::Vertical => (Align::Centre, align.vert.unwrap_or(Align::Begin)),
+            // Text alignment is relative to the direction of reading
+            TextOrientation::RotatedCw => (
+                align.vert.unwrap_or(Align::Centre),
+                reverse(align.horiz.unwrap_or(Align::Centre)),
+            ),
+            TextOrientation::RotatedCcw => (
+                reverse(align.vert.unwrap_or(Align::Centre)),
+                align.horiz.unwrap_or(Align::Centre),
+            ),
+        };
         self.core.rect = rect;
     }
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &ManagerState, _: bool) {
-        draw_handle.text(self.core.rect, &self.text, TextClass::Label, self.align);
+        let (class, orientation) = (TextClass::Label, self.orientation);
+        draw_handle.text_oriented(self.core.rect, &self.text, class, orientation, self.align);
+    }
+}
+
+fn reverse(align: Align) -> Align {
+    match align {
+        Align::Begin => Align::End,
+        Align::End => Align::Begin,
+        align => align,
     }
 }
 
@@ -50,11 +79,21 @@ impl Label {
         Label {
             core: Default::default(),
             align: Default::default(),
+            orientation: TextOrientation::Horizontal,
             reserve: None,
             text: text.into(),
         }
     }
 
+    /// Set the text orientation (chain style)
+    ///
+    /// For example, [`TextOrientation::RotatedCcw`] is suitable for the label
+    /// of a vertical chart axis.
+    pub fn with_orientation(mut self, orientation: TextOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
     /// Reserve sufficient room for the given text
     ///
     /// If this option is used, the label will be sized to fit this text, not
@@ -72,7 +111,177 @@ impl HasText for Label {
 
     fn set_cow_string(&mut self, text: CowString) -> TkAction {
         self.text = text.into();
-        TkAction::Redraw
+        TkAction::REDRAW
+    }
+}
+
+/// A text label supporting selection
+///
+/// This is like [`Label`], except that the user may select text with the
+/// mouse or via the keyboard (Shift with arrow keys, Home and End) and copy
+/// the selection to the clipboard. The text may not be edited. Only
+/// horizontal text is supported.
+#[widget(config=noauto)]
+#[handler(handle=noauto)]
+#[derive(Clone, Default, Debug, Widget)]
+pub struct SelectableLabel {
+    #[widget_core]
+    core: CoreData,
+    align: (Align, Align),
+    text: LabelString,
+    selection: SelectionHelper,
+}
+
+impl WidgetConfig for SelectableLabel {
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> event::CursorIcon {
+        event::CursorIcon::Text
+    }
+}
+
+impl Layout for SelectableLabel {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        size_handle.text_bound(&self.text, TextClass::Label, axis)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.align = (
+            align.horiz.unwrap_or(Align::Begin),
+            align.vert.unwrap_or(Align::Centre),
+        );
+        self.core.rect = rect;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, _: bool) {
+        let (rect, class, align) = (self.core.rect, TextClass::Label, self.align);
+        if !self.selection.is_empty() {
+            let range = self.selection.range();
+            draw_handle.text_selection(rect, &self.text, class, align, range);
+        }
+        draw_handle.text(rect, &self.text, class, align);
+        if mgr.char_focus(self.id()) {
+            let pos = self.selection.edit_pos();
+            draw_handle.edit_marker(rect, &self.text, class, align, pos);
+        }
+    }
+}
+
+impl SelectableLabel {
+    /// Construct a new instance
+    pub fn new<T: Into<LabelString>>(text: T) -> Self {
+        SelectableLabel {
+            core: Default::default(),
+            align: Default::default(),
+            text: text.into(),
+            selection: Default::default(),
+        }
+    }
+
+    /// Get the selected text
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection.range()]
+    }
+
+    /// Set the edit position from a coordinate
+    ///
+    /// If `extend`, the selection is extended, otherwise it is cleared.
+    fn set_pos_from_coord(&mut self, mgr: &mut Manager, coord: Coord, extend: bool) {
+        let (rect, align) = (self.core.rect, self.align);
+        let text = &self.text;
+        let pos = mgr.size_handle(|h| {
+            h.text_index_nearest(rect, text, TextClass::Label, align, coord.into())
+        });
+        self.set_pos(mgr, pos, extend);
+    }
+
+    fn set_pos(&mut self, mgr: &mut Manager, pos: usize, extend: bool) {
+        if extend {
+            self.selection.set_edit_pos(pos);
+        } else {
+            self.selection.set_pos(pos);
+        }
+        mgr.redraw(self.id());
+    }
+
+    fn control_key(&mut self, mgr: &mut Manager, key: ControlKey) -> Response<VoidMsg> {
+        let pos = self.selection.edit_pos();
+        let extend = mgr.modifiers().shift();
+        let new_pos = match key {
+            ControlKey::Left => {
+                let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
+                cursor.prev_boundary(&self.text, 0).unwrap().unwrap_or(pos)
+            }
+            ControlKey::Right => {
+                let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
+                cursor.next_boundary(&self.text, 0).unwrap().unwrap_or(pos)
+            }
+            ControlKey::Home => 0,
+            ControlKey::End => self.text.len(),
+            ControlKey::Copy => {
+                if !self.selection.is_empty() {
+                    mgr.set_clipboard(self.selected_text().into());
+                }
+                return Response::None;
+            }
+            key => return Response::Unhandled(Event::Control(key)),
+        };
+        self.set_pos(mgr, new_pos, extend);
+        if extend && !self.selection.is_empty() {
+            mgr.set_primary(self.selected_text().into());
+        }
+        Response::None
+    }
+}
+
+impl HasText for SelectableLabel {
+    fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    fn set_cow_string(&mut self, text: CowString) -> TkAction {
+        self.text = text.into();
+        self.selection.clear();
+        TkAction::REDRAW
+    }
+}
+
+impl event::Handler for SelectableLabel {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::Activate => {
+                mgr.request_char_focus(self.id());
+                Response::None
+            }
+            Event::LostCharFocus => {
+                self.selection.set_empty();
+                mgr.redraw(self.id());
+                Response::None
+            }
+            Event::Control(key) => self.control_key(mgr, key),
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                let extend = mgr.modifiers().shift();
+                self.set_pos_from_coord(mgr, coord, extend);
+                mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
+                mgr.request_char_focus(self.id());
+                Response::None
+            }
+            Event::PressMove { coord, .. } => {
+                self.set_pos_from_coord(mgr, coord, true);
+                Response::None
+            }
+            Event::PressEnd { .. } => {
+                if !self.selection.is_empty() {
+                    mgr.set_primary(self.selected_text().into());
+                }
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
     }
 }
 
@@ -136,6 +345,6 @@ impl HasText for AccelLabel {
 
     fn set_cow_string(&mut self, text: CowString) -> TkAction {
         self.text = text.into();
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
@@ -0,0 +1,274 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Overlapping layers
+
+use std::ops::{Index, IndexMut};
+
+use kas::draw::ClipRegion;
+use kas::prelude::*;
+
+/// Placement of one layer
+#[derive(Clone, Debug)]
+struct LayerData {
+    z: i32,
+    align: (Align, Align),
+    ideal: Size,
+}
+
+impl Default for LayerData {
+    fn default() -> Self {
+        LayerData {
+            z: 0,
+            align: (Align::Stretch, Align::Stretch),
+            ideal: Size::ZERO,
+        }
+    }
+}
+
+/// A set of overlapping widgets with explicit draw order
+///
+/// All children are placed within the same rect, each aligned within that
+/// rect according to [`Layers::with_alignment`] (by default, stretched to fill
+/// it). For example, a badge may be placed over the top-right corner of an
+/// icon.
+///
+/// Children are drawn in order of increasing z-index (see
+/// [`Layers::with_z_index`]), thus children with higher z-index appear on top.
+/// Children with equal z-index are drawn in the order given. Each layer above
+/// the first is drawn in a new clip region (of class [`ClipRegion::Layer`]),
+/// thus draw order is well defined even where primitives overlap.
+///
+/// Hit-testing (e.g. to determine which widget is under the mouse) uses the
+/// reverse order: the top-most layer which claims a coordinate receives input.
+/// Note that a stretched child usually claims its whole rect, hiding any
+/// layers beneath it from input.
+///
+/// Configuring and resizing elements is O(n) in the number of children.
+#[handler(send=noauto, msg=<W as event::Handler>::Msg)]
+#[widget(children=noauto)]
+#[derive(Clone, Default, Debug, Widget)]
+pub struct Layers<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    widgets: Vec<W>,
+    data: Vec<LayerData>,
+    // indices of widgets, ordered by z-index
+    order: Vec<usize>,
+}
+
+impl<W: Widget> WidgetChildren for Layers<W> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.widgets.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.widgets.get_mut(index).map(|w| w.as_widget_mut())
+    }
+}
+
+impl<W: Widget> Layout for Layers<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let mut rules = SizeRules::EMPTY;
+        for (child, data) in self.widgets.iter_mut().zip(self.data.iter_mut()) {
+            let child_rules = child.size_rules(size_handle, axis);
+            if axis.is_horizontal() {
+                data.ideal.0 = child_rules.ideal_size();
+            } else {
+                data.ideal.1 = child_rules.ideal_size();
+            }
+            rules = rules.max(child_rules);
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        for (child, data) in self.widgets.iter_mut().zip(self.data.iter()) {
+            let hints = AlignHints::new(Some(data.align.0), Some(data.align.1));
+            let child_rect = hints
+                .complete(Align::Stretch, Align::Stretch, data.ideal)
+                .apply(rect);
+            child.set_rect(child_rect, hints);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+
+        for index in self.order.iter().rev() {
+            if let Some(id) = self.widgets[*index].find_id(coord) {
+                return Some(id);
+            }
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.draw_from(draw_handle, mgr, disabled, 0);
+    }
+}
+
+impl<W: Widget> event::SendEvent for Layers<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            for child in &mut self.widgets {
+                if id <= child.id() {
+                    return child.send(mgr, id, event);
+                }
+            }
+        }
+
+        Response::Unhandled(event)
+    }
+}
+
+impl<W: Widget> Layers<W> {
+    /// Construct a new instance
+    ///
+    /// All children initially have z-index 0 and are stretched to fill the
+    /// available space.
+    pub fn new(widgets: Vec<W>) -> Self {
+        let data = vec![LayerData::default(); widgets.len()];
+        let order = (0..widgets.len()).collect();
+        Layers {
+            core: Default::default(),
+            widgets,
+            data,
+            order,
+        }
+    }
+
+    /// Set the z-index of the child at `index` (chain style)
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn with_z_index(mut self, index: usize, z: i32) -> Self {
+        let _ = self.set_z_index(index, z);
+        self
+    }
+
+    /// Set the alignment of the child at `index` (chain style)
+    ///
+    /// Unless [`Align::Stretch`] is used, the child is sized to its ideal size
+    /// on that axis and positioned within the available space.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn with_alignment(mut self, index: usize, horiz: Align, vert: Align) -> Self {
+        self.data[index].align = (horiz, vert);
+        self
+    }
+
+    /// Get the z-index of the child at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn z_index(&self, index: usize) -> i32 {
+        self.data[index].z
+    }
+
+    /// Set the z-index of the child at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Returns [`TkAction::REGION_MOVED`] if the draw order changed.
+    pub fn set_z_index(&mut self, index: usize, z: i32) -> TkAction {
+        if self.data[index].z == z {
+            return TkAction::empty();
+        }
+        self.data[index].z = z;
+        self.update_order();
+        TkAction::REGION_MOVED
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Append a child widget with z-index `z`
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, widget: W, z: i32) -> TkAction {
+        self.widgets.push(widget);
+        self.data.push(LayerData {
+            z,
+            ..Default::default()
+        });
+        self.update_order();
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the child widget at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> (W, TkAction) {
+        let r = self.widgets.remove(index);
+        self.data.remove(index);
+        self.update_order();
+        (r, TkAction::RECONFIGURE)
+    }
+
+    /// Iterate over children
+    pub fn iter(&self) -> std::slice::Iter<'_, W> {
+        self.widgets.iter()
+    }
+
+    fn update_order(&mut self) {
+        let data = &self.data;
+        self.order = (0..data.len()).collect();
+        // Stable sort: equal z-index retains the given order
+        self.order.sort_by_key(|index| data[*index].z);
+    }
+
+    // Draw layers from position n in self.order, each above the last
+    fn draw_from(
+        &self,
+        draw_handle: &mut dyn DrawHandle,
+        mgr: &event::ManagerState,
+        disabled: bool,
+        n: usize,
+    ) {
+        if let Some(index) = self.order.get(n) {
+            self.widgets[*index].draw(draw_handle, mgr, disabled);
+            if n + 1 < self.order.len() {
+                // Clip to both our rect and any parent clip region
+                let target = draw_handle.target_rect();
+                if let Some(rect) = self.core.rect.intersection(&target) {
+                    draw_handle.clip_region(rect, Coord::ZERO, ClipRegion::Layer, &mut |handle| {
+                        self.draw_from(handle, mgr, disabled, n + 1)
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<W: Widget> Index<usize> for Layers<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.widgets[index]
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for Layers<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.widgets[index]
+    }
+}
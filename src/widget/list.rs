@@ -7,6 +7,7 @@
 
 use std::ops::{Index, IndexMut};
 
+use super::Stretch;
 use kas::layout::{RulesSetter, RulesSolver};
 use kas::prelude::*;
 
@@ -41,6 +42,16 @@ pub type BoxColumn<M> = BoxList<kas::Down, M>;
 /// See documentation of [`List`] type.
 pub type BoxList<D, M> = List<D, Box<dyn Widget<Msg = M>>>;
 
+/// A builder for a [`BoxRow`]
+///
+/// See documentation of [`ListBuilder`] type.
+pub type RowBuilder<M> = ListBuilder<kas::Right, M>;
+
+/// A builder for a [`BoxColumn`]
+///
+/// See documentation of [`ListBuilder`] type.
+pub type ColumnBuilder<M> = ListBuilder<kas::Down, M>;
+
 /// A row of widget references
 ///
 /// This is parameterised over handler message type.
@@ -237,8 +248,8 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// removed.
     pub fn clear(&mut self) -> TkAction {
         let action = match self.widgets.is_empty() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         };
         self.widgets.clear();
         action
@@ -249,7 +260,7 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn push(&mut self, widget: W) -> TkAction {
         self.widgets.push(widget);
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Remove the last child widget
@@ -261,8 +272,8 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// removed.
     pub fn pop(&mut self) -> (Option<W>, TkAction) {
         let action = match self.widgets.is_empty() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         };
         (self.widgets.pop(), action)
     }
@@ -274,7 +285,7 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn insert(&mut self, index: usize, widget: W) -> TkAction {
         self.widgets.insert(index, widget);
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Removes the child widget at position `index`
@@ -284,7 +295,7 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn remove(&mut self, index: usize) -> (W, TkAction) {
         let r = self.widgets.remove(index);
-        (r, TkAction::Reconfigure)
+        (r, TkAction::RECONFIGURE)
     }
 
     /// Replace the child at `index`
@@ -297,7 +308,7 @@ impl<D: Directional, W: Widget> List<D, W> {
     // we somehow test "has compatible size"?
     pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
         std::mem::swap(&mut widget, &mut self.widgets[index]);
-        (widget, TkAction::Reconfigure)
+        (widget, TkAction::RECONFIGURE)
     }
 
     /// Append child widgets from an iterator
@@ -308,8 +319,8 @@ impl<D: Directional, W: Widget> List<D, W> {
         let len = self.widgets.len();
         self.widgets.extend(iter);
         match len == self.widgets.len() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         }
     }
 
@@ -319,7 +330,7 @@ impl<D: Directional, W: Widget> List<D, W> {
     pub fn resize_with<F: Fn(usize) -> W>(&mut self, len: usize, f: F) -> TkAction {
         let l0 = self.widgets.len();
         if l0 == len {
-            return TkAction::None;
+            return TkAction::empty();
         } else if l0 > len {
             self.widgets.truncate(len);
         } else {
@@ -328,7 +339,7 @@ impl<D: Directional, W: Widget> List<D, W> {
                 self.widgets.push(f(i));
             }
         }
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Retain only widgets satisfying predicate `f`
@@ -341,8 +352,8 @@ impl<D: Directional, W: Widget> List<D, W> {
         let len = self.widgets.len();
         self.widgets.retain(f);
         match len == self.widgets.len() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         }
     }
 
@@ -394,3 +405,60 @@ impl<'a, D: Directional, W: Widget> ExactSizeIterator for ListIter<'a, D, W> {
         self.list.widgets.len() - self.index
     }
 }
+
+/// A builder for a [`BoxList`]
+///
+/// This allows construction of a row or column of widgets of differing types
+/// without [`make_widget`](../macros/index.html#the-make_widget-macro):
+/// ```ignore
+/// let row = RowBuilder::new()
+///     .push(Label::new("Search:"))
+///     .push_stretch(EditBox::new(""))
+///     .build();
+/// ```
+pub struct ListBuilder<D: Directional, M> {
+    widgets: Vec<Box<dyn Widget<Msg = M>>>,
+    direction: D,
+}
+
+impl<D: Directional + Default, M: 'static> Default for ListBuilder<D, M> {
+    fn default() -> Self {
+        ListBuilder::new()
+    }
+}
+
+impl<D: Directional + Default, M: 'static> ListBuilder<D, M> {
+    /// Construct an empty builder
+    pub fn new() -> Self {
+        ListBuilder::new_with_direction(Default::default())
+    }
+}
+
+impl<D: Directional, M: 'static> ListBuilder<D, M> {
+    /// Construct an empty builder with explicit direction
+    pub fn new_with_direction(direction: D) -> Self {
+        ListBuilder {
+            widgets: Vec::new(),
+            direction,
+        }
+    }
+
+    /// Append a widget
+    pub fn push<W: Widget<Msg = M> + 'static>(mut self, widget: W) -> Self {
+        self.widgets.push(Box::new(widget));
+        self
+    }
+
+    /// Append a widget which takes all available extra space
+    ///
+    /// The widget is wrapped with [`StretchPolicy::Maximise`]; see
+    /// [`Stretch`].
+    pub fn push_stretch<W: Widget<Msg = M> + 'static>(self, widget: W) -> Self {
+        self.push(Stretch::new(widget, StretchPolicy::Maximise))
+    }
+
+    /// Build the list
+    pub fn build(self) -> BoxList<D, M> {
+        List::new_with_direction(self.direction, self.widgets)
+    }
+}
@@ -6,6 +6,7 @@
 //! Menus
 
 use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
 
 mod menu_entry;
 mod menu_frame;
@@ -36,6 +37,54 @@ pub trait Menu: Widget {
     ///
     /// `target == None` implies that all menus should close.
     fn menu_path(&mut self, _mgr: &mut Manager, _target: Option<WidgetId>) {}
+
+    /// Get the entry's label, if any
+    ///
+    /// This is used for type-ahead selection: while a menu is open, typing
+    /// moves the navigation focus to the first entry whose label starts with
+    /// the typed text.
+    ///
+    /// By default, this is `None` (never matched).
+    fn menu_label(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Type-ahead state for menus
+///
+/// Characters are accumulated into a prefix, which is reset when no character
+/// is received for [`TypeAhead::TIMEOUT`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TypeAhead {
+    prefix: String,
+    last: Option<Instant>,
+}
+
+impl TypeAhead {
+    const TIMEOUT: Duration = Duration::from_millis(1000);
+
+    /// Push a character, returning the new (lower-case) prefix
+    pub fn push(&mut self, c: char) -> &str {
+        let now = Instant::now();
+        if self.last.map(|t| now - t > Self::TIMEOUT).unwrap_or(true) {
+            self.prefix.clear();
+        }
+        self.last = Some(now);
+        self.prefix.extend(c.to_lowercase());
+        &self.prefix
+    }
+
+    /// Find the index of the first label matching `prefix` (case-insensitive)
+    pub fn find<'a, I: IntoIterator<Item = Option<&'a str>>>(
+        prefix: &str,
+        labels: I,
+    ) -> Option<usize> {
+        labels.into_iter().position(|label| {
+            label
+                .map(|label| label.to_lowercase().starts_with(prefix))
+                .unwrap_or(false)
+        })
+    }
 }
 
 impl<M: 'static> WidgetCore for Box<dyn Menu<Msg = M>> {
@@ -102,6 +151,9 @@ impl<M: 'static> WidgetConfig for Box<dyn Menu<Msg = M>> {
     fn cursor_icon(&self) -> event::CursorIcon {
         self.as_ref().cursor_icon()
     }
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        self.as_ref().actions()
+    }
 }
 
 impl<M: 'static> Layout for Box<dyn Menu<Msg = M>> {
@@ -149,6 +201,9 @@ impl<M: 'static> Menu for Box<dyn Menu<Msg = M>> {
     fn menu_path(&mut self, mgr: &mut Manager, target: Option<WidgetId>) {
         self.deref_mut().menu_path(mgr, target)
     }
+    fn menu_label(&self) -> Option<&str> {
+        self.deref().menu_label()
+    }
 }
 
 impl<M: 'static> Clone for Box<dyn Menu<Msg = M>> {
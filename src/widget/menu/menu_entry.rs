@@ -15,6 +15,8 @@ use kas::prelude::*;
 use kas::widget::{AccelLabel, CheckBoxBare};
 
 /// A standard menu entry
+///
+/// Replies to [`Event::Select`] with [`Response::Select`].
 #[widget(config=noauto)]
 #[handler(handle=noauto)]
 #[derive(Clone, Debug, Default, Widget)]
@@ -34,6 +36,10 @@ impl<M: Clone + Debug + 'static> WidgetConfig for MenuEntry<M> {
     fn key_nav(&self) -> bool {
         true
     }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        vec![(event::WidgetAction::Activate, Event::Activate)]
+    }
 }
 
 impl<M: Clone + Debug + 'static> Layout for MenuEntry<M> {
@@ -85,7 +91,7 @@ impl<M: Clone + Debug + 'static> HasText for MenuEntry<M> {
 
     fn set_cow_string(&mut self, text: CowString) -> TkAction {
         self.label = text.into();
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
 
@@ -95,12 +101,17 @@ impl<M: Clone + Debug + 'static> event::Handler for MenuEntry<M> {
     fn handle(&mut self, _: &mut Manager, event: Event) -> Response<M> {
         match event {
             Event::Activate => self.msg.clone().into(),
+            Event::Select => Response::Select,
             event => Response::Unhandled(event),
         }
     }
 }
 
-impl<M: Clone + Debug> Menu for MenuEntry<M> {}
+impl<M: Clone + Debug> Menu for MenuEntry<M> {
+    fn menu_label(&self) -> Option<&str> {
+        Some(self.label.get(false))
+    }
+}
 
 /// A menu entry which can be toggled
 #[handler(msg = M, generics = <> where M: From<VoidMsg>)]
@@ -241,7 +252,11 @@ impl<M: 'static> Layout for MenuToggle<M> {
     }
 }
 
-impl<M: From<VoidMsg>> Menu for MenuToggle<M> {}
+impl<M: From<VoidMsg>> Menu for MenuToggle<M> {
+    fn menu_label(&self) -> Option<&str> {
+        Some(self.label.get_text())
+    }
+}
 
 impl<M: 'static> HasBool for MenuToggle<M> {
     #[inline]
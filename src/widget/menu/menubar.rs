@@ -91,6 +91,7 @@ impl<D: Directional, W: Menu<Msg = M>, M> event::Handler for MenuBar<D, W> {
                 source,
                 start_id,
                 coord,
+                ..
             } => {
                 if self.is_ancestor_of(start_id) {
                     if source.is_primary()
@@ -109,14 +110,14 @@ impl<D: Directional, W: Menu<Msg = M>, M> event::Handler for MenuBar<D, W> {
                                     if !w.menu_is_open() {
                                         self.opening = true;
                                         self.delayed_open = Some(id);
-                                        mgr.update_on_timer(DELAY, self.id());
+                                        mgr.update_after(DELAY, self.id());
                                     }
                                     break;
                                 }
                             }
                         } else {
                             self.delayed_open = Some(start_id);
-                            mgr.update_on_timer(DELAY, self.id());
+                            mgr.update_after(DELAY, self.id());
                         }
                     }
                 } else {
@@ -131,7 +132,7 @@ impl<D: Directional, W: Menu<Msg = M>, M> event::Handler for MenuBar<D, W> {
                         mgr.set_grab_depress(source, Some(id));
                         mgr.set_nav_focus(id);
                         self.delayed_open = Some(id);
-                        mgr.update_on_timer(DELAY, self.id());
+                        mgr.update_after(DELAY, self.id());
                     }
                 } else {
                     mgr.set_grab_depress(source, None);
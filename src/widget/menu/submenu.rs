@@ -5,7 +5,7 @@
 
 //! Sub-menu
 
-use super::{Menu, MenuFrame};
+use super::{Menu, MenuFrame, TypeAhead};
 use kas::class::HasText;
 use kas::draw::TextClass;
 use kas::event::{ConfigureManager, ControlKey};
@@ -26,6 +26,7 @@ pub struct SubMenu<D: Directional, W: Menu> {
     #[widget]
     pub list: MenuFrame<Column<W>>,
     popup_id: Option<WindowId>,
+    type_ahead: TypeAhead,
 }
 
 impl<D: Directional + Default, W: Menu> SubMenu<D, W> {
@@ -66,6 +67,7 @@ impl<D: Directional, W: Menu> SubMenu<D, W> {
             label_off: Coord::ZERO,
             list: MenuFrame::new(Column::new(list)),
             popup_id: None,
+            type_ahead: Default::default(),
         }
     }
 
@@ -100,6 +102,13 @@ impl<D: Directional, W: Menu> WidgetConfig for SubMenu<D, W> {
     fn key_nav(&self) -> bool {
         true
     }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        match self.popup_id {
+            None => vec![(event::WidgetAction::OpenPopup, Event::Activate)],
+            Some(_) => vec![],
+        }
+    }
 }
 
 impl<D: Directional, W: Menu> kas::Layout for SubMenu<D, W> {
@@ -149,6 +158,15 @@ impl<D: Directional, M, W: Menu<Msg = M>> event::Handler for SubMenu<D, W> {
                 debug_assert_eq!(Some(id), self.popup_id);
                 self.popup_id = None;
             }
+            Event::ReceivedCharacter(c) if self.popup_id.is_some() => {
+                let prefix = self.type_ahead.push(c);
+                let list = &self.list.inner;
+                let labels = (0..list.len()).map(|i| list[i].menu_label());
+                if let Some(i) = TypeAhead::find(prefix, labels) {
+                    mgr.clear_nav_focus();
+                    mgr.next_nav_focus(self.list.inner[i].as_widget(), false);
+                }
+            }
             Event::Control(key) => match (self.direction.as_direction(), key) {
                 (Direction::Left, ControlKey::Left) => self.open_menu(mgr),
                 (Direction::Right, ControlKey::Right) => self.open_menu(mgr),
@@ -173,14 +191,13 @@ impl<D: Directional, W: Menu> event::SendEvent for SubMenu<D, W> {
 
             // The pop-up API expects us to check actions here
             // But NOTE: we don't actually use this. Should we remove from API?
-            match mgr.pop_action() {
-                TkAction::Close => {
-                    if let Some(id) = self.popup_id {
-                        mgr.close_window(id);
-                    }
+            let action = mgr.pop_action();
+            if action.contains(TkAction::CLOSE) {
+                if let Some(id) = self.popup_id {
+                    mgr.close_window(id);
                 }
-                other => mgr.send_action(other),
             }
+            mgr.send_action(action - TkAction::CLOSE);
 
             match r {
                 Response::Unhandled(ev) => match ev {
@@ -231,6 +248,10 @@ impl<D: Directional, W: Menu> Menu for SubMenu<D, W> {
         self.popup_id.is_some()
     }
 
+    fn menu_label(&self) -> Option<&str> {
+        Some(self.label.get(false))
+    }
+
     fn menu_path(&mut self, mgr: &mut Manager, target: Option<WidgetId>) {
         match target {
             Some(id) if self.is_ancestor_of(id) => {
@@ -275,6 +296,6 @@ impl<D: Directional, W: Menu> HasText for SubMenu<D, W> {
 
     fn set_cow_string(&mut self, text: CowString) -> TkAction {
         self.label = text.into();
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
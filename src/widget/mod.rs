@@ -17,8 +17,16 @@
 //!
 //! -   [`Frame`]: a simple frame around a single child
 //! -   [`ScrollRegion`]: may be larger on the inside than the outside
-//! -   [`Stack`]: a stack of widgets in the same rect (TODO: `TabbedStack`)
+//! -   [`Stack`]: a stack of widgets in the same rect
+//! -   [`TabbedStack`]: a [`Stack`] with a tab bar to select the visible page
+//! -   [`Navigator`]: a stack of pages with push / pop navigation
+//! -   [`Layers`]: overlapping widgets in the same rect, ordered by z-index
 //! -   [`List`]: a dynamic row / column of children
+//! -   [`Grid`]: a grid of children, each in a cell or span of cells
+//! -   [`ListBuilder`], [`GridBuilder`]: construct a [`List`] or [`Grid`] of
+//!     boxed children without macros
+//! -   [`FilterList`]: a [`List`] with a filter bar
+//! -   [`Flow`]: equally-sized children wrapped into rows, like a photo grid
 //! -   [`Splitter`]: similar to [`List`] but with resizing handles
 //! -   [`Window`] is usually the root widget and has special handling for
 //!     pop-ups and callbacks
@@ -30,20 +38,36 @@
 //! -   [`MenuEntry`], [`MenuToggle`], [`Separator`]: menu entries
 //! -   [`MenuFrame`]: edges of a pop-up menu
 //!
+//! ## Actions
+//!
+//! Widgets bound to a shared [`Action`](crate::action::Action):
+//!
+//! -   [`ActionButton`]: a button, e.g. for a toolbar
+//! -   [`MenuAction`]: a menu entry displaying the action's shortcut
+//!
 //! ## Controls
 //!
 //! -   [`TextButton`]: a simple button
 //! -   [`CheckBox`]: a checkable box
 //! -   [`RadioBox`]: a checkable box bound to a group
 //! -   [`EditBox`]: a text-editing box
+//! -   [`AutoComplete`]: an [`EditBox`] with a pop-up list of suggestions
+//! -   [`TextEditor`]: a multi-line editor over a shared
+//!     [`TextDocument`](crate::document::TextDocument) (feature `editor`)
 //! -   [`ScrollBar`]: a scrollbar
 //! -   [`Slider`]: a slider
+//! -   [`Rating`]: a row of stars, settable by mouse or keyboard
+//! -   [`TagInput`]: an [`EditBox`] for a list of tags, each removable
 //!
 //! ## Static widgets
 //!
 //! -   [`Filler`]: an empty widget, sometimes used to fill space
 //! -   [`Separator`]: a visible bar to separate things
 //! -   [`Label`]: a simple text label
+//! -   [`Image`]: a raster image, optionally animated
+//! -   [`ProgressBar`]: a bar showing the progress of some operation
+//! -   [`ExternalSurface`]: a region reserved for an external renderer
+//!     (feature `raw-window-handle`)
 //!
 //! ## Components
 //!
@@ -51,43 +75,92 @@
 //! -   [`CheckBoxBare`]: `CheckBox` without its label
 //! -   [`RadioBoxBare`]: `RadioBox` without its label
 //! -   [`DragHandle`]: a handle (e.g. for a slider, splitter or scrollbar)
+//!
+//! ## Adapters
+//!
+//! -   [`WidgetExt`]: provides fluent wrapping methods (e.g. `framed`,
+//!     `with_tooltip`, `map_msg`) on all widgets
+//! -   [`MapResponse`]: a wrapper mapping messages from its child
+//! -   [`Aligned`]: a wrapper aligning its child
+//! -   [`Stretch`]: a wrapper adjusting the stretch policy of its child
+//! -   [`WithTooltip`]: a wrapper adding a tooltip to its child
+//! -   [`Tour`]: a wrapper presenting a guided tour of its content
 
+mod action;
+mod adapter;
 mod button;
 mod checkbox;
 mod combobox;
+mod complete;
 mod dialog;
 mod drag;
 mod editbox;
+#[cfg(feature = "editor")]
+mod editor;
+#[cfg(feature = "raw-window-handle")]
+mod external;
 mod filler;
+mod filter;
+mod flow;
 mod frame;
+mod grid;
+mod image;
 mod label;
+mod layers;
 mod list;
 mod menu;
+mod navigator;
+mod progress;
 mod radiobox;
+mod rating;
 mod scroll;
 mod scrollbar;
+mod selection;
 mod separator;
 mod slider;
 mod splitter;
 mod stack;
+mod tabs;
+mod taginput;
+mod tour;
 mod window;
 
+pub use action::{ActionButton, MenuAction};
+pub use adapter::{Aligned, MapResponse, Stretch, WidgetExt, WithTooltip};
 pub use button::TextButton;
 pub use checkbox::{CheckBox, CheckBoxBare};
 pub use combobox::ComboBox;
+pub use complete::{AutoComplete, Completer};
 pub use dialog::MessageBox;
 pub use drag::DragHandle;
-pub use editbox::{EditBox, EditBoxVoid, EditGuard};
+pub use editbox::{Edit, EditBox, EditBoxVoid, EditGuard, EditNotifier, EditNotify};
+#[cfg(feature = "editor")]
+pub use editor::TextEditor;
+#[cfg(feature = "raw-window-handle")]
+pub use external::ExternalSurface;
 pub use filler::Filler;
+pub use filter::FilterList;
+pub use flow::Flow;
 pub use frame::Frame;
-pub use label::{AccelLabel, Label};
+pub use grid::{BoxGrid, Grid, GridBuilder};
+pub use image::Image;
+pub use label::{AccelLabel, Label, SelectableLabel};
+pub use layers::Layers;
 pub use list::*;
 pub use menu::*;
+pub use navigator::Navigator;
+pub use progress::ProgressBar;
 pub use radiobox::{RadioBox, RadioBoxBare};
+pub use rating::Rating;
 pub use scroll::ScrollRegion;
 pub use scrollbar::ScrollBar;
+pub use selection::SelectionHelper;
 pub use separator::Separator;
 pub use slider::{Slider, SliderType};
 pub use splitter::*;
 pub use stack::{BoxStack, RefStack, Stack};
+pub use tabs::TabbedStack;
+pub use taginput::{TagGuard, TagInput};
+pub use tour::Tour;
+pub(crate) use window::find_rect;
 pub use window::Window;
@@ -0,0 +1,304 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A stack of pages with push / pop navigation
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use kas::draw::ClipRegion;
+use kas::event::{ModifiersState, Shortcut, VirtualKeyCode};
+use kas::prelude::*;
+
+/// Time taken to slide between pages, in seconds
+const SLIDE_DURATION: f32 = 0.25;
+
+// A page transition in progress
+#[derive(Clone, Debug)]
+struct Slide<W> {
+    // True when a page was pushed, false when popped
+    push: bool,
+    // Fraction of the transition remaining, from 1 down to 0
+    remaining: f32,
+    // The popped page, drawn until the transition completes
+    leaving: Option<W>,
+}
+
+/// A stack of pages with push / pop navigation
+///
+/// A navigator shows a single page: the top of a stack. Each page is
+/// constructed from parameters of type `P` by the closure passed to
+/// [`Navigator::new`], thus typed data (e.g. a route enum, or the item to be
+/// shown) is passed to pages. [`Navigator::push`] opens a new page over the
+/// current one while [`Navigator::pop`] returns to the previous page. Pages
+/// beneath the top keep their state; the bottom (root) page is never popped.
+///
+/// Messages from pages are passed through; usually the parent handles these,
+/// calling [`Navigator::push`] or [`Navigator::pop`] as appropriate.
+///
+/// The Escape key and the "back" key ([`VirtualKeyCode::NavigateBackward`],
+/// e.g. the hardware back button on mobile devices) return to the previous
+/// page. Escape is only used thus when no pop-up is open and no widget has
+/// keyboard focus. These keys are registered as shortcuts (see
+/// [`Manager::add_shortcut`]); where navigators are nested, only the innermost
+/// responds.
+///
+/// Optionally, page changes may be animated (see [`Navigator::with_animation`]),
+/// sliding the new page in from the right and the popped page out to the right.
+#[handler(noauto)]
+#[widget(config=noauto, children=noauto)]
+#[derive(Clone, Widget)]
+pub struct Navigator<P: Debug + 'static, W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    pages: Vec<(P, W)>,
+    build: Rc<dyn Fn(&P) -> W>,
+    animate: bool,
+    slide: Option<Slide<W>>,
+}
+
+impl<P: Debug + 'static, W: Widget> Debug for Navigator<P, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Navigator {{ core: {:?}, pages: {:?}, animate: {:?}, slide: {:?}, ... }}",
+            self.core, self.pages, self.animate, self.slide,
+        )
+    }
+}
+
+impl<P: Debug + 'static, W: Widget> WidgetChildren for Navigator<P, W> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.pages.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.pages.get(index).map(|(_, w)| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.pages.get_mut(index).map(|(_, w)| w.as_widget_mut())
+    }
+}
+
+impl<P: Debug + 'static, W: Widget> WidgetConfig for Navigator<P, W> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        let none = ModifiersState::empty();
+        mgr.add_shortcut(self.id(), Shortcut::new(none, VirtualKeyCode::Escape));
+        mgr.add_shortcut(
+            self.id(),
+            Shortcut::new(none, VirtualKeyCode::NavigateBackward),
+        );
+    }
+}
+
+impl<P: Debug + 'static, W: Widget> Layout for Navigator<P, W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let mut rules = SizeRules::EMPTY;
+        for (_, page) in &mut self.pages {
+            rules = rules.max(page.size_rules(size_handle, axis));
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        for (_, page) in &mut self.pages {
+            page.set_rect(rect, align.clone());
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.pages.last().and_then(|(_, page)| page.find_id(coord))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        let rect = self.core.rect;
+        let len = self.pages.len();
+        let slide = match self.slide.as_ref() {
+            Some(slide) => slide,
+            None => {
+                if let Some((_, page)) = self.pages.last() {
+                    page.draw(draw_handle, mgr, disabled);
+                }
+                return;
+            }
+        };
+
+        // Pages are drawn side-by-side, the front page to the right
+        let width = rect.size.0 as f32;
+        let (back, front, x) = if slide.push {
+            let back = len.checked_sub(2).map(|i| &self.pages[i].1);
+            (back, self.pages.last().map(|(_, w)| w), slide.remaining)
+        } else {
+            let back = self.pages.last().map(|(_, w)| w);
+            (back, slide.leaving.as_ref(), 1.0 - slide.remaining)
+        };
+        let x = (x * width).round() as i32;
+        let mut draw_at = |page: &W, dx: i32| {
+            let offset = Coord(-dx, 0);
+            draw_handle.clip_region(rect, offset, ClipRegion::Scroll, &mut |handle| {
+                page.draw(handle, mgr, disabled)
+            });
+        };
+        if let Some(page) = back {
+            draw_at(page, x - rect.size.0 as i32);
+        }
+        if let Some(page) = front {
+            draw_at(page, x);
+        }
+    }
+}
+
+impl<P: Debug + 'static, W: Widget> event::Handler for Navigator<P, W> {
+    type Msg = <W as event::Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::Activate => {
+                // Received via shortcut: Escape or the back key
+                match self.pop(mgr) {
+                    Some(_) => Response::None,
+                    None => Response::Unhandled(event),
+                }
+            }
+            Event::Frame(info) => {
+                if let Some(slide) = self.slide.as_mut() {
+                    slide.remaining -= info.interval.as_secs_f32() / SLIDE_DURATION;
+                    if slide.remaining > 0.0 {
+                        mgr.request_frame(self.id());
+                    } else {
+                        self.slide = None;
+                    }
+                    mgr.redraw(self.id());
+                }
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl<P: Debug + 'static, W: Widget> event::SendEvent for Navigator<P, W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        for (_, page) in &mut self.pages {
+            if id <= page.id() {
+                return page.send(mgr, id, event);
+            }
+        }
+        Manager::handle_generic(self, mgr, event)
+    }
+}
+
+impl<P: Debug + 'static, W: Widget> Navigator<P, W> {
+    /// Construct, with a root page
+    ///
+    /// Pages are constructed from their parameters by `build`; the root page
+    /// is constructed immediately from `root`.
+    pub fn new<F: Fn(&P) -> W + 'static>(root: P, build: F) -> Self {
+        let page = build(&root);
+        Navigator {
+            core: Default::default(),
+            pages: vec![(root, page)],
+            build: Rc::new(build),
+            animate: false,
+            slide: None,
+        }
+    }
+
+    /// Set whether page changes are animated (chain style)
+    ///
+    /// By default, pages are not animated.
+    pub fn with_animation(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
+
+    /// Get the number of pages, including the root page
+    ///
+    /// This is always at least 1.
+    pub fn depth(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Get the parameters of the current page
+    pub fn params(&self) -> &P {
+        &self.pages.last().unwrap().0
+    }
+
+    /// Get a direct reference to the current page
+    pub fn page(&self) -> &W {
+        &self.pages.last().unwrap().1
+    }
+
+    /// Get a direct mutable reference to the current page
+    pub fn page_mut(&mut self) -> &mut W {
+        &mut self.pages.last_mut().unwrap().1
+    }
+
+    /// Open a new page, constructed from `params`
+    ///
+    /// The current page is hidden but retains its state.
+    pub fn push(&mut self, mgr: &mut Manager, params: P) {
+        let page = (self.build)(&params);
+        if let Some((_, w)) = self.pages.last_mut() {
+            mgr.notify_visibility(w, false);
+        }
+        self.pages.push((params, page));
+        self.start_slide(mgr, true, None);
+        *mgr += TkAction::RECONFIGURE;
+    }
+
+    /// Return to the previous page
+    ///
+    /// The current page is destroyed (see [`Manager::destroy`]) and its
+    /// parameters returned. Does nothing and returns `None` when the current
+    /// page is the root.
+    pub fn pop(&mut self, mgr: &mut Manager) -> Option<P> {
+        if self.pages.len() < 2 {
+            return None;
+        }
+        let (params, mut page) = self.pages.pop().unwrap();
+        mgr.destroy(&mut page);
+        mgr.notify_visibility(&mut self.pages.last_mut().unwrap().1, true);
+        self.start_slide(mgr, false, Some(page));
+        *mgr += TkAction::RECONFIGURE;
+        Some(params)
+    }
+
+    /// Return to the root page
+    ///
+    /// All other pages are destroyed. Returns the number of pages removed.
+    pub fn pop_to_root(&mut self, mgr: &mut Manager) -> usize {
+        let n = self.pages.len() - 1;
+        if n > 0 {
+            let mut pages = self.pages.split_off(1);
+            for (_, page) in &mut pages {
+                mgr.destroy(page);
+            }
+            mgr.notify_visibility(&mut self.pages[0].1, true);
+            self.start_slide(mgr, false, pages.pop().map(|(_, w)| w));
+            *mgr += TkAction::RECONFIGURE;
+        }
+        n
+    }
+
+    fn start_slide(&mut self, mgr: &mut Manager, push: bool, leaving: Option<W>) {
+        if self.animate {
+            self.slide = Some(Slide {
+                push,
+                remaining: 1.0,
+                leaving,
+            });
+            mgr.request_frame(self.id());
+        }
+    }
+}
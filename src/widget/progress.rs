@@ -0,0 +1,160 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Progress bar
+
+use kas::prelude::*;
+
+/// Time taken by an indeterminate bar to sweep the length of its track, in seconds
+const INDETERMINATE_PERIOD: f32 = 1.5;
+
+/// A progress bar
+///
+/// Displays a value between 0 and 1 as the filled fraction of a bar. Where
+/// progress cannot be measured, the bar may instead be put in indeterminate
+/// mode (see [`ProgressBar::set_indeterminate`]), in which a short segment
+/// sweeps repeatedly along the track; this is animated via [`Event::Frame`].
+///
+/// Drawing is via [`DrawHandle::progress_bar`] and
+/// [`DrawHandle::progress_bar_indeterminate`], using metrics from
+/// [`SizeHandle::scrollbar`].
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+#[derive(Clone, Debug, Default, Widget)]
+pub struct ProgressBar<D: Directional> {
+    #[widget_core]
+    core: CoreData,
+    direction: D,
+    value: f32,
+    indeterminate: bool,
+    phase: f32,
+}
+
+impl<D: Directional + Default> ProgressBar<D> {
+    /// Construct a progress bar
+    ///
+    /// The initial value is `0.0`; use [`ProgressBar::with_value`] to override.
+    #[inline]
+    pub fn new() -> Self {
+        ProgressBar::new_with_direction(D::default())
+    }
+}
+
+impl<D: Directional> ProgressBar<D> {
+    /// Construct a progress bar with the given `direction`
+    ///
+    /// The initial value is `0.0`; use [`ProgressBar::with_value`] to override.
+    #[inline]
+    pub fn new_with_direction(direction: D) -> Self {
+        ProgressBar {
+            core: Default::default(),
+            direction,
+            value: 0.0,
+            indeterminate: false,
+            phase: 0.0,
+        }
+    }
+
+    /// Set the initial value (chain style)
+    ///
+    /// The value is clamped to the range `0.0..=1.0`.
+    #[inline]
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value.max(0.0).min(1.0);
+        self
+    }
+
+    /// Set whether the bar is initially indeterminate (chain style)
+    #[inline]
+    pub fn with_indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Get the current value
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Set the value
+    ///
+    /// The value is clamped to the range `0.0..=1.0`. This also ends
+    /// indeterminate mode.
+    pub fn set_value(&mut self, value: f32) -> TkAction {
+        let value = value.max(0.0).min(1.0);
+        if value == self.value && !self.indeterminate {
+            return TkAction::empty();
+        }
+        self.value = value;
+        self.indeterminate = false;
+        TkAction::REDRAW
+    }
+
+    /// True if the bar is in indeterminate mode
+    #[inline]
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    /// Put the bar in indeterminate mode
+    ///
+    /// The bar is animated until a value is set via [`ProgressBar::set_value`].
+    pub fn set_indeterminate(&mut self, mgr: &mut Manager) {
+        if !self.indeterminate {
+            self.indeterminate = true;
+            self.phase = 0.0;
+            mgr.request_frame(self.id());
+        }
+    }
+}
+
+impl<D: Directional> WidgetConfig for ProgressBar<D> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if self.indeterminate {
+            mgr.request_frame(self.id());
+        }
+    }
+}
+
+impl<D: Directional> Layout for ProgressBar<D> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let (size, min_len) = size_handle.scrollbar();
+        let margins = (0, 0);
+        if self.direction.is_vertical() == axis.is_vertical() {
+            SizeRules::new(min_len, 4 * min_len, margins, StretchPolicy::HighUtility)
+        } else {
+            SizeRules::fixed(size.1, margins)
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {
+        let dir = self.direction.as_direction();
+        if self.indeterminate {
+            draw_handle.progress_bar_indeterminate(self.core.rect, dir, self.phase);
+        } else {
+            draw_handle.progress_bar(self.core.rect, dir, self.value);
+        }
+    }
+}
+
+impl<D: Directional> event::Handler for ProgressBar<D> {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<VoidMsg> {
+        match event {
+            Event::Frame(info) => {
+                if self.indeterminate {
+                    let t = info.interval.as_secs_f32() / INDETERMINATE_PERIOD;
+                    self.phase = (self.phase + t).fract();
+                    mgr.redraw(self.id());
+                    mgr.request_frame(self.id());
+                }
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
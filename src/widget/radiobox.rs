@@ -44,6 +44,10 @@ impl<M: 'static> WidgetConfig for RadioBoxBare<M> {
     fn key_nav(&self) -> bool {
         true
     }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        vec![(event::WidgetAction::Select, Event::Activate)]
+    }
 }
 
 impl<M: 'static> event::Handler for RadioBoxBare<M> {
@@ -174,7 +178,7 @@ impl<M: 'static> HasBool for RadioBoxBare<M> {
 
     fn set_bool(&mut self, state: bool) -> TkAction {
         self.state = state;
-        TkAction::Redraw
+        TkAction::REDRAW
     }
 }
 
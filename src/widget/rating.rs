@@ -0,0 +1,222 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Star rating widget
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use kas::draw::TextClass;
+use kas::event::{ControlKey, GrabMode};
+use kas::prelude::*;
+
+/// A star rating
+///
+/// Displays `max` stars, of which the first `value` are filled. The value may
+/// be set by clicking or dragging over the stars, or via the arrow keys
+/// (Home and End select the minimum and maximum).
+#[widget(config=noauto)]
+#[handler(handle=noauto)]
+#[derive(Clone, Default, Widget)]
+pub struct Rating<M: 'static> {
+    #[widget_core]
+    core: CoreData,
+    max: u32,
+    value: u32,
+    star_size: Size,
+    on_change: Option<Rc<dyn Fn(u32) -> M>>,
+}
+
+impl<M: 'static> Debug for Rating<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Rating {{ core: {:?}, max: {:?}, value: {:?}, ... }}",
+            self.core, self.max, self.value
+        )
+    }
+}
+
+impl<M: 'static> WidgetConfig for Rating<M> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        let mut actions = vec![];
+        if self.value < self.max {
+            actions.push((
+                event::WidgetAction::Increment,
+                Event::Control(ControlKey::Up),
+            ));
+        }
+        if self.value > 0 {
+            actions.push((
+                event::WidgetAction::Decrement,
+                Event::Control(ControlKey::Down),
+            ));
+        }
+        actions
+    }
+}
+
+impl<M: 'static> Layout for Rating<M> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let side = size_handle.line_height(TextClass::Label);
+        self.star_size = Size::uniform(side);
+        let size = Size(side * self.max, side);
+        let margins = size_handle.outer_margins();
+        SizeRules::extract_fixed(axis.is_vertical(), size, margins)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        let size = Size(self.star_size.0 * self.max, self.star_size.1);
+        let rect = align
+            .complete(Align::Begin, Align::Centre, size)
+            .apply(rect);
+        self.core.rect = rect;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {
+        let mut rect = Rect::new(self.core.rect.pos, self.star_size);
+        for i in 0..self.max {
+            let star = if i < self.value { "★" } else { "☆" };
+            let align = (Align::Centre, Align::Centre);
+            draw_handle.text(rect, star, TextClass::Label, align);
+            rect.pos.0 += self.star_size.0 as i32;
+        }
+    }
+}
+
+impl<M: 'static> Rating<M> {
+    /// Construct a rating of `max` stars which calls `f` when changed
+    ///
+    /// This is a shortcut for `Rating::new(max).on_change(f)`.
+    ///
+    /// The closure `f` is called with the new value when changed by the user,
+    /// and the result of `f` is returned from the event handler.
+    #[inline]
+    pub fn new_on<F: Fn(u32) -> M + 'static>(max: u32, f: F) -> Self {
+        Rating {
+            core: Default::default(),
+            max,
+            value: 0,
+            star_size: Size::ZERO,
+            on_change: Some(Rc::new(f)),
+        }
+    }
+
+    /// Set the initial value
+    #[inline]
+    pub fn with_value(mut self, value: u32) -> Self {
+        self.value = value.min(self.max);
+        self
+    }
+
+    /// Get the maximum value (number of stars)
+    #[inline]
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Get the current value
+    #[inline]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Set the value
+    ///
+    /// The value is clamped to `0..=max`.
+    /// Returns [`TkAction::REDRAW`] if a redraw is required.
+    pub fn set_value(&mut self, value: u32) -> TkAction {
+        let value = value.min(self.max);
+        if value == self.value {
+            TkAction::empty()
+        } else {
+            self.value = value;
+            TkAction::REDRAW
+        }
+    }
+
+    // Value selected by a press at coord
+    fn value_at(&self, coord: Coord) -> u32 {
+        let x = coord.0 - self.core.rect.pos.0;
+        if x < 0 || self.star_size.0 == 0 {
+            0
+        } else {
+            (x as u32 / self.star_size.0 + 1).min(self.max)
+        }
+    }
+
+    fn change(&mut self, mgr: &mut Manager, value: u32) -> Response<M> {
+        let action = self.set_value(value);
+        if action.is_empty() {
+            return Response::None;
+        }
+        mgr.send_action(action);
+        if let Some(ref f) = self.on_change {
+            f(self.value).into()
+        } else {
+            Response::None
+        }
+    }
+}
+
+impl Rating<VoidMsg> {
+    /// Construct a rating of `max` stars
+    #[inline]
+    pub fn new(max: u32) -> Self {
+        Rating {
+            core: Default::default(),
+            max,
+            value: 0,
+            star_size: Size::ZERO,
+            on_change: None,
+        }
+    }
+
+    /// Set the event handler to be called on change.
+    ///
+    /// The closure `f` is called with the new value when changed by the user,
+    /// and the result of `f` is returned from the event handler.
+    #[inline]
+    pub fn on_change<M, F>(self, f: F) -> Rating<M>
+    where
+        F: Fn(u32) -> M + 'static,
+    {
+        Rating {
+            core: self.core,
+            max: self.max,
+            value: self.value,
+            star_size: self.star_size,
+            on_change: Some(Rc::new(f)),
+        }
+    }
+}
+
+impl<M: 'static> event::Handler for Rating<M> {
+    type Msg = M;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<M> {
+        let value = match event {
+            Event::Control(key) => match key {
+                ControlKey::Left | ControlKey::Down => self.value.saturating_sub(1),
+                ControlKey::Right | ControlKey::Up => self.value + 1,
+                ControlKey::Home => 0,
+                ControlKey::End => self.max,
+                key => return Response::Unhandled(Event::Control(key)),
+            },
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
+                self.value_at(coord)
+            }
+            Event::PressMove { coord, .. } => self.value_at(coord),
+            Event::PressEnd { .. } => return Response::None,
+            event => return Response::Unhandled(event),
+        };
+        self.change(mgr, value)
+    }
+}
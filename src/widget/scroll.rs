@@ -9,19 +9,37 @@ use std::fmt::Debug;
 
 use super::ScrollBar;
 use kas::draw::{ClipRegion, TextClass};
-use kas::event::ControlKey;
 use kas::event::ScrollDelta::{LineDelta, PixelDelta};
+use kas::event::{ControlKey, PressSource, UpdateHandle};
+use kas::geom::Vec2;
 use kas::prelude::*;
 
+/// Maximum overscroll, relative to the size of the view
+const OVERSCROLL_LIMIT: f32 = 0.25;
+/// Time (in seconds) for the overscroll indicator to relax by half
+const OVERSCROLL_HALF_LIFE: f32 = 0.05;
+
 /// A scrollable region
 ///
 /// This region supports scrolling via mouse wheel and drag.
-/// Optionally, it can have scroll bars (see [`ScrollRegion::show_bars`] and
-/// [`ScrollRegion::with_bars`]).
+/// Optionally, it can have scroll bars, either shown only where the content
+/// does not fit (see [`ScrollRegion::with_auto_bars`]) or always (see
+/// [`ScrollRegion::show_bars`] and [`ScrollRegion::with_bars`]).
+///
+/// When a touch drag scrolls past the end of content, an overscroll indicator
+/// is drawn ([`DrawHandle::overscroll`]), relaxing once the touch ends.
+///
+/// The region may also be scrolled via the keyboard (arrow keys, Page Up/Down,
+/// Home and End) when it has nav focus (it accepts focus when scrollable),
+/// when a child with nav focus does not handle these keys, or when nothing
+/// has nav focus. The step size is given by the theme
+/// ([`SizeHandle::scroll_step`]).
 ///
 /// Scroll regions translate their contents by an `offset`, which has a
 /// minimum value of [`Coord::ZERO`] and a maximum value of
-/// [`ScrollRegion::max_offset`].
+/// [`ScrollRegion::max_offset`]. Scroll-linked widgets (e.g. a minimap) may
+/// follow changes to the offset via [`ScrollRegion::with_update_handle`].
+/// The offset is saved in a [`kas::session::Session`].
 #[widget(config=noauto)]
 #[handler(send=noauto, msg = <W as event::Handler>::Msg)]
 #[derive(Clone, Debug, Default, Widget)]
@@ -32,10 +50,13 @@ pub struct ScrollRegion<W: Widget> {
     inner_size: Size,
     max_offset: Coord,
     offset: Coord,
+    // Touch drag distance past the offset limits (signed)
+    overscroll: Vec2,
     scroll_rate: f32,
     bar_width: u32,
     auto_bars: bool,
     show_bars: (bool, bool),
+    update_handle: Option<UpdateHandle>,
     #[widget]
     horiz_bar: ScrollBar<kas::Right>,
     #[widget]
@@ -54,10 +75,12 @@ impl<W: Widget> ScrollRegion<W> {
             inner_size: Size::ZERO,
             max_offset: Coord::ZERO,
             offset: Coord::ZERO,
+            overscroll: Vec2::ZERO,
             scroll_rate: 30.0,
             bar_width: 0,
             auto_bars: false,
             show_bars: (false, false),
+            update_handle: None,
             horiz_bar: ScrollBar::new(),
             vert_bar: ScrollBar::new(),
             inner,
@@ -67,10 +90,12 @@ impl<W: Widget> ScrollRegion<W> {
     /// Auto-enable bars
     ///
     /// If enabled, this automatically enables/disables scroll bars when
-    /// resized.
+    /// resized: a bar is shown on each axis where the inner widget's minimum
+    /// size exceeds the space available (after making room for the other bar,
+    /// if shown). No space is reserved for bars, thus at its ideal size the
+    /// region shows none.
     ///
-    /// This has the side-effect of reserving enough space for scroll bars even
-    /// when not required.
+    /// While enabled, [`ScrollRegion::show_bars`] has no effect.
     #[inline]
     pub fn with_auto_bars(mut self, enable: bool) -> Self {
         self.auto_bars = enable;
@@ -90,6 +115,18 @@ impl<W: Widget> ScrollRegion<W> {
         self.show_bars = (horiz, vert);
     }
 
+    /// Notify on scroll (chain style)
+    ///
+    /// When the offset is changed by user input,
+    /// [`Manager::trigger_update_data`] is called on `handle`, passing the new
+    /// offset as a [`Coord`] (see [`Event::HandleUpdate`]). Changes via [`ScrollRegion::set_offset`] are
+    /// not notified.
+    #[inline]
+    pub fn with_update_handle(mut self, handle: UpdateHandle) -> Self {
+        self.update_handle = Some(handle);
+        self
+    }
+
     /// Access inner widget directly
     #[inline]
     pub fn inner(&self) -> &W {
@@ -116,17 +153,34 @@ impl<W: Widget> ScrollRegion<W> {
 
     /// Set the scroll offset
     ///
-    /// Returns [`TkAction::None`] if the offset is identical to the old offset,
+    /// The offset is clamped to the range `Coord::ZERO..=self.max_offset()`
+    /// and scroll bars are updated.
+    ///
+    /// Returns [`TkAction::empty`] if the offset is identical to the old offset,
     /// or a greater action if not identical.
-    #[inline]
     pub fn set_offset(&mut self, offset: Coord) -> TkAction {
         let offset = offset.clamp(Coord::ZERO, self.max_offset);
         if offset == self.offset {
-            TkAction::None
+            TkAction::empty()
         } else {
             self.offset = offset;
-            TkAction::RegionMoved
+            TkAction::REGION_MOVED
+                + self.horiz_bar.set_value(offset.0 as u32)
+                + self.vert_bar.set_value(offset.1 as u32)
+        }
+    }
+
+    // Set the offset in response to user input, notifying if changed
+    fn scroll_to(&mut self, mgr: &mut Manager, offset: Coord) -> bool {
+        let action = self.set_offset(offset);
+        if action.is_empty() {
+            return false;
+        }
+        *mgr += action;
+        if let Some(handle) = self.update_handle {
+            mgr.trigger_update_data(handle, 0, self.offset);
         }
+        true
     }
 }
 
@@ -134,6 +188,18 @@ impl<W: Widget> WidgetConfig for ScrollRegion<W> {
     fn configure(&mut self, mgr: &mut Manager) {
         mgr.register_nav_fallback(self.id());
     }
+
+    fn key_nav(&self) -> bool {
+        self.max_offset != Coord::ZERO
+    }
+
+    fn scroll_offset(&self) -> Option<Coord> {
+        Some(self.offset)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Coord) -> TkAction {
+        self.set_offset(offset)
+    }
 }
 
 impl<W: Widget> Layout for ScrollRegion<W> {
@@ -145,13 +211,17 @@ impl<W: Widget> Layout for ScrollRegion<W> {
             self.min_child_size.1 = rules.min_size();
         }
         let line_height = size_handle.line_height(TextClass::Label);
-        self.scroll_rate = 3.0 * line_height as f32;
+        self.scroll_rate = size_handle.scroll_step() as f32;
         rules.reduce_min_to(line_height);
         self.bar_width = (size_handle.scrollbar().0).1;
 
-        if axis.is_horizontal() && (self.auto_bars || self.show_bars.1) {
+        if self.auto_bars {
+            // Bars are not reserved, but the region must be large enough to
+            // show one (which may be needed on the other axis)
+            rules = rules.max(SizeRules::fixed(self.bar_width, (0, 0)));
+        } else if axis.is_horizontal() && self.show_bars.1 {
             rules.append(self.vert_bar.size_rules(size_handle, axis));
-        } else if axis.is_vertical() && (self.auto_bars || self.show_bars.0) {
+        } else if axis.is_vertical() && self.show_bars.0 {
             rules.append(self.horiz_bar.size_rules(size_handle, axis));
         }
         rules
@@ -164,16 +234,20 @@ impl<W: Widget> Layout for ScrollRegion<W> {
         self.inner_size = rect.size;
 
         if self.auto_bars {
-            self.show_bars = (
-                self.min_child_size.0 + self.bar_width > rect.size.0,
-                self.min_child_size.1 + self.bar_width > rect.size.1,
-            );
+            // Showing one bar reduces space on the other axis
+            let (min, w) = (self.min_child_size, self.bar_width);
+            let mut horiz = min.0 > rect.size.0;
+            let vert = min.1 > rect.size.1.saturating_sub(if horiz { w } else { 0 });
+            if vert && !horiz {
+                horiz = min.0 > rect.size.0.saturating_sub(w);
+            }
+            self.show_bars = (horiz, vert);
         }
         if self.show_bars.0 {
-            self.inner_size.1 -= self.bar_width;
+            self.inner_size.1 = self.inner_size.1.saturating_sub(self.bar_width);
         }
         if self.show_bars.1 {
-            self.inner_size.0 -= self.bar_width;
+            self.inner_size.0 = self.inner_size.0.saturating_sub(self.bar_width);
         }
 
         let child_size = self.inner_size.max(self.min_child_size);
@@ -189,15 +263,15 @@ impl<W: Widget> Layout for ScrollRegion<W> {
                 .set_rect(Rect { pos, size }, AlignHints::NONE);
             let _ = self
                 .horiz_bar
-                .set_limits(self.max_offset.0 as u32, rect.size.0);
+                .set_limits(self.max_offset.0 as u32, self.inner_size.0);
         }
         if self.show_bars.1 {
             let pos = Coord(pos.0 + self.inner_size.0 as i32, pos.1);
-            let size = Size(self.bar_width, self.core.rect.size.1);
+            let size = Size(self.bar_width, self.inner_size.1);
             self.vert_bar.set_rect(Rect { pos, size }, AlignHints::NONE);
             let _ = self
                 .vert_bar
-                .set_limits(self.max_offset.1 as u32, rect.size.1);
+                .set_limits(self.max_offset.1 as u32, self.inner_size.1);
         }
     }
 
@@ -214,20 +288,30 @@ impl<W: Widget> Layout for ScrollRegion<W> {
             return None;
         }
 
-        self.horiz_bar
-            .find_id(coord)
-            .or_else(|| self.vert_bar.find_id(coord))
-            .or_else(|| self.inner.find_id(coord + self.offset))
-            .or(Some(self.id()))
+        // Hidden bars may retain a stale rect
+        if self.show_bars.0 {
+            if let Some(id) = self.horiz_bar.find_id(coord) {
+                return Some(id);
+            }
+        }
+        if self.show_bars.1 {
+            if let Some(id) = self.vert_bar.find_id(coord) {
+                return Some(id);
+            }
+        }
+        self.inner.find_id(coord + self.offset).or(Some(self.id()))
     }
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let disabled = disabled || self.is_disabled();
+        let nav_focus = mgr.nav_focus(self.id());
         if self.show_bars.0 {
-            self.horiz_bar.draw(draw_handle, mgr, disabled);
+            self.horiz_bar
+                .draw_with_focus(draw_handle, mgr, disabled, nav_focus);
         }
         if self.show_bars.1 {
-            self.vert_bar.draw(draw_handle, mgr, disabled);
+            self.vert_bar
+                .draw_with_focus(draw_handle, mgr, disabled, nav_focus);
         }
         let rect = Rect {
             pos: self.core.rect.pos,
@@ -236,6 +320,25 @@ impl<W: Widget> Layout for ScrollRegion<W> {
         draw_handle.clip_region(rect, self.offset, ClipRegion::Scroll, &mut |handle| {
             self.inner.draw(handle, mgr, disabled)
         });
+
+        let limit = Vec2::from(self.inner_size) * OVERSCROLL_LIMIT;
+        let o = self.overscroll;
+        if o.0 != 0.0 && limit.0 > 0.0 {
+            let dir = if o.0 < 0.0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            };
+            draw_handle.overscroll(rect, dir, o.0.abs() / limit.0);
+        }
+        if o.1 != 0.0 && limit.1 > 0.0 {
+            let dir = if o.1 < 0.0 {
+                Direction::Up
+            } else {
+                Direction::Down
+            };
+            draw_handle.overscroll(rect, dir, o.1.abs() / limit.1);
+        }
     }
 }
 
@@ -249,8 +352,9 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
             match Response::<Self::Msg>::try_from(self.horiz_bar.send(mgr, id, event)) {
                 Ok(Response::Unhandled(event)) => event,
                 Ok(r) => return r,
-                Err(msg) => {
-                    *mgr += self.set_offset(Coord(msg as i32, self.offset.1));
+                Err((msg, action)) => {
+                    *mgr += action;
+                    self.scroll_to(mgr, Coord(msg as i32, self.offset.1));
                     return Response::None;
                 }
             }
@@ -258,8 +362,9 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
             match Response::<Self::Msg>::try_from(self.vert_bar.send(mgr, id, event)) {
                 Ok(Response::Unhandled(event)) => event,
                 Ok(r) => return r,
-                Err(msg) => {
-                    *mgr += self.set_offset(Coord(self.offset.0, msg as i32));
+                Err((msg, action)) => {
+                    *mgr += action;
+                    self.scroll_to(mgr, Coord(self.offset.0, msg as i32));
                     return Response::None;
                 }
             }
@@ -269,30 +374,36 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                     source,
                     start_id,
                     coord,
+                    pos,
                 } => Event::PressStart {
                     source,
                     start_id,
                     coord: coord + self.offset,
+                    pos: pos + Vec2::from(self.offset),
                 },
                 Event::PressMove {
                     source,
                     cur_id,
                     coord,
+                    pos,
                     delta,
                 } => Event::PressMove {
                     source,
                     cur_id,
                     coord: coord + self.offset,
+                    pos: pos + Vec2::from(self.offset),
                     delta,
                 },
                 Event::PressEnd {
                     source,
                     end_id,
                     coord,
+                    pos,
                 } => Event::PressEnd {
                     source,
                     end_id,
                     coord: coord + self.offset,
+                    pos: pos + Vec2::from(self.offset),
                 },
                 event => event,
             };
@@ -303,7 +414,7 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                     let mut offset = self.offset;
                     offset = offset.max(rect.pos_end() - self.core.rect.pos_end());
                     offset = offset.min(rect.pos - self.core.rect.pos);
-                    *mgr += self.set_offset(offset);
+                    self.scroll_to(mgr, offset);
                     return Response::Focus(rect - self.offset);
                 }
                 r => return r,
@@ -317,11 +428,7 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                 LineDelta(x, y) => Coord((-w.scroll_rate * x) as i32, (w.scroll_rate * y) as i32),
                 PixelDelta(d) => d,
             };
-            let action = w.set_offset(w.offset - d);
-            if action != TkAction::None {
-                *mgr += action
-                    + w.horiz_bar.set_value(w.offset.0 as u32)
-                    + w.vert_bar.set_value(w.offset.1 as u32);
+            if w.scroll_to(mgr, w.offset - d) {
                 Response::None
             } else {
                 Response::Unhandled(Event::Scroll(delta))
@@ -336,15 +443,11 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                     ControlKey::Up => LineDelta(0.0, 1.0),
                     ControlKey::Down => LineDelta(0.0, -1.0),
                     ControlKey::Home | ControlKey::End => {
-                        let action = self.set_offset(match key {
+                        let offset = match key {
                             ControlKey::Home => Coord::ZERO,
                             _ => self.max_offset,
-                        });
-                        if action != TkAction::None {
-                            *mgr += action
-                                + self.horiz_bar.set_value(self.offset.0 as u32)
-                                + self.vert_bar.set_value(self.offset.1 as u32);
-                        }
+                        };
+                        self.scroll_to(mgr, offset);
                         return Response::None;
                     }
                     ControlKey::PageUp => PixelDelta(Coord(0, self.core.rect.size.1 as i32 / 2)),
@@ -355,7 +458,10 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                 };
                 scroll(self, mgr, delta)
             }
-            Event::Scroll(delta) => scroll(self, mgr, delta),
+            Event::Scroll(delta) => {
+                let delta = mgr.scroll_delta(delta);
+                scroll(self, mgr, delta)
+            }
             Event::PressStart { source, coord, .. } if source.is_primary() => {
                 mgr.request_grab(
                     self.id(),
@@ -366,17 +472,46 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                 );
                 Response::None
             }
-            Event::PressMove { delta, .. } => {
-                let action = self.set_offset(self.offset - delta);
-                if action != TkAction::None {
-                    *mgr += action
-                        + self.horiz_bar.set_value(self.offset.0 as u32)
-                        + self.vert_bar.set_value(self.offset.1 as u32);
+            Event::PressMove { source, delta, .. } => {
+                let target = self.offset - delta;
+                self.scroll_to(mgr, target);
+                if let PressSource::Touch(_) = source {
+                    // Overscroll grows at half the rate of excess drag, and
+                    // resets on any axis dragged back within limits
+                    let excess = Vec2::from(target - self.offset) * 0.5;
+                    let limit = Vec2::from(self.inner_size) * OVERSCROLL_LIMIT;
+                    let grow = |o: f32, e: f32, l: f32| match e == 0.0 {
+                        true => 0.0,
+                        false => (o + e).max(-l).min(l),
+                    };
+                    let o = Vec2(
+                        grow(self.overscroll.0, excess.0, limit.0),
+                        grow(self.overscroll.1, excess.1, limit.1),
+                    );
+                    if o != self.overscroll {
+                        self.overscroll = o;
+                        mgr.redraw(self.id());
+                    }
                 }
                 Response::None
             }
             Event::PressEnd { .. } => {
-                // consume due to request
+                if self.overscroll != Vec2::ZERO {
+                    mgr.request_frame(self.id());
+                }
+                Response::None
+            }
+            Event::Frame(info) => {
+                // Relax towards zero, halving every OVERSCROLL_HALF_LIFE
+                let t = info.interval.as_secs_f32() / OVERSCROLL_HALF_LIFE;
+                self.overscroll = self.overscroll * 0.5f32.powf(t);
+                let o = self.overscroll.abs();
+                if o.0 > 0.5 || o.1 > 0.5 {
+                    mgr.request_frame(self.id());
+                } else {
+                    self.overscroll = Vec2::ZERO;
+                }
+                mgr.redraw(self.id());
                 Response::None
             }
             e @ _ => Response::Unhandled(e),
@@ -6,14 +6,26 @@
 //! `ScrollBar` control
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use super::DragHandle;
+use kas::event::{GrabMode, MouseButton, PressSource};
 use kas::prelude::*;
 
+/// Delay before paging repeats while the trough is held
+const PAGE_DELAY: Duration = Duration::from_millis(400);
+/// Interval between repeated pages
+const PAGE_REPEAT: Duration = Duration::from_millis(60);
+
 /// A scroll bar
 ///
 /// Scroll bars allow user-input of a value between 0 and a defined maximum,
 /// and allow the size of the handle to be specified.
+///
+/// Pressing the trough (outside the handle) pages towards the press point,
+/// repeating while held until the handle reaches that point. A middle-click
+/// or Shift+click on the trough instead moves the handle directly to the press
+/// point (then allows dragging).
 #[handler(send=noauto, msg = u32)]
 #[derive(Clone, Debug, Default, Widget)]
 pub struct ScrollBar<D: Directional> {
@@ -28,6 +40,8 @@ pub struct ScrollBar<D: Directional> {
     value: u32,
     #[widget]
     handle: DragHandle,
+    // Source and coordinate of a press paging the trough
+    page_press: Option<(PressSource, Coord)>,
 }
 
 impl<D: Directional + Default> ScrollBar<D> {
@@ -54,6 +68,7 @@ impl<D: Directional> ScrollBar<D> {
             max_value: 0,
             value: 0,
             handle: DragHandle::new(),
+            page_press: None,
         }
     }
 
@@ -88,7 +103,7 @@ impl<D: Directional> ScrollBar<D> {
     /// The choice of units is not important (e.g. can be pixels or lines),
     /// so long as both parameters use the same units.
     ///
-    /// Returns [`TkAction::Redraw`] if a redraw is required.
+    /// Returns [`TkAction::REDRAW`] if a redraw is required.
     pub fn set_limits(&mut self, max_value: u32, handle_value: u32) -> TkAction {
         // We should gracefully handle zero, though appearance may be wrong.
         self.handle_value = handle_value.max(1);
@@ -108,7 +123,7 @@ impl<D: Directional> ScrollBar<D> {
     pub fn set_value(&mut self, value: u32) -> TkAction {
         let value = value.min(self.max_value);
         if value == self.value {
-            TkAction::None
+            TkAction::empty()
         } else {
             self.value = value;
             self.handle.set_offset(self.offset()).1
@@ -156,6 +171,49 @@ impl<D: Directional> ScrollBar<D> {
         }
     }
 
+    /// Draw, optionally highlighting as if the handle had nav focus
+    ///
+    /// This is used by a parent (e.g. [`super::ScrollRegion`]) which handles
+    /// keyboard input on behalf of the scroll bar.
+    pub(crate) fn draw_with_focus(
+        &self,
+        draw_handle: &mut dyn DrawHandle,
+        mgr: &event::ManagerState,
+        disabled: bool,
+        nav_focus: bool,
+    ) {
+        let dir = self.direction.as_direction();
+        let mut state = self.handle.input_state(mgr, disabled);
+        state.nav_focus |= nav_focus;
+        draw_handle.scrollbar(self.core.rect, self.handle.rect(), dir, state);
+    }
+
+    // Move one page towards coord; true if the value changed
+    fn page_towards(&mut self, mgr: &mut Manager, coord: Coord) -> bool {
+        let (click, start) = match self.direction.is_vertical() {
+            false => (coord.0, self.handle.rect().pos.0),
+            true => (coord.1, self.handle.rect().pos.1),
+        };
+        let mut forward = if click < start {
+            false
+        } else if click >= start + self.handle_len as i32 {
+            true
+        } else {
+            return false;
+        };
+        if self.direction.is_reversed() {
+            forward = !forward;
+        }
+
+        let value = match forward {
+            false => self.value.saturating_sub(self.handle_value),
+            true => self.value.saturating_add(self.handle_value),
+        };
+        let action = self.set_value(value);
+        mgr.send_action(action);
+        !action.is_empty()
+    }
+
     // true if not equal to old value
     fn set_offset(&mut self, offset: Coord) -> bool {
         let len = self.len() - self.handle_len;
@@ -209,9 +267,7 @@ impl<D: Directional> Layout for ScrollBar<D> {
     }
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
-        let dir = self.direction.as_direction();
-        let state = self.handle.input_state(mgr, disabled);
-        draw_handle.scrollbar(self.core.rect, self.handle.rect(), dir, state);
+        self.draw_with_focus(draw_handle, mgr, disabled, false);
     }
 }
 
@@ -224,13 +280,53 @@ impl<D: Directional> event::SendEvent for ScrollBar<D> {
         let offset = if id <= self.handle.id() {
             match self.handle.send(mgr, id, event).try_into() {
                 Ok(res) => return res,
-                Err(offset) => offset,
+                Err((offset, action)) => {
+                    *mgr += action;
+                    offset
+                }
             }
         } else {
             match event {
-                Event::PressStart { source, coord, .. } => {
+                Event::PressStart { source, coord, .. }
+                    if source == PressSource::Mouse(MouseButton::Middle)
+                        || (source.is_primary() && mgr.modifiers().shift()) =>
+                {
                     self.handle.handle_press_on_track(mgr, source, coord)
                 }
+                Event::PressStart { source, coord, .. } if source.is_primary() => {
+                    if !mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None) {
+                        return Response::None;
+                    }
+                    self.page_press = Some((source, coord));
+                    mgr.update_after(PAGE_DELAY, self.id());
+                    return match self.page_towards(mgr, coord) {
+                        true => Response::Msg(self.value),
+                        false => Response::None,
+                    };
+                }
+                Event::PressMove { source, coord, .. } => {
+                    if let Some(press) = self.page_press.as_mut() {
+                        if press.0 == source {
+                            press.1 = coord;
+                        }
+                    }
+                    return Response::None;
+                }
+                Event::PressEnd { source, .. } => {
+                    if self.page_press.map(|press| press.0) == Some(source) {
+                        self.page_press = None;
+                    }
+                    return Response::None;
+                }
+                Event::TimerUpdate => {
+                    if let Some((_, coord)) = self.page_press {
+                        if self.page_towards(mgr, coord) {
+                            mgr.update_after(PAGE_REPEAT, self.id());
+                            return Response::Msg(self.value);
+                        }
+                    }
+                    return Response::None;
+                }
                 ev @ _ => return Response::Unhandled(ev),
             }
         };
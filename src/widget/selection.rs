@@ -0,0 +1,101 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Text selection
+
+use std::ops::Range;
+
+/// Text selection helper
+///
+/// Tracks the edit (caret) position and a selection anchor, both as byte
+/// indices into some text. The selection is the range between these. This
+/// is used by widgets supporting text selection, e.g. [`SelectableLabel`]
+/// and [`EditBox`].
+///
+/// [`SelectableLabel`]: super::SelectableLabel
+/// [`EditBox`]: super::EditBox
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectionHelper {
+    edit_pos: usize,
+    anchor_pos: usize,
+}
+
+impl SelectionHelper {
+    /// Construct from `(edit, anchor)` positions
+    pub fn new(edit_pos: usize, anchor_pos: usize) -> Self {
+        SelectionHelper {
+            edit_pos,
+            anchor_pos,
+        }
+    }
+
+    /// Reset to the default state (all positions are zero)
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// True if the selection range is empty
+    pub fn is_empty(&self) -> bool {
+        self.edit_pos == self.anchor_pos
+    }
+
+    /// Set the selection range to empty (at the edit position)
+    pub fn set_empty(&mut self) {
+        self.anchor_pos = self.edit_pos;
+    }
+
+    /// Set both edit and anchor positions, thus with empty selection
+    pub fn set_pos(&mut self, pos: usize) {
+        self.edit_pos = pos;
+        self.anchor_pos = pos;
+    }
+
+    /// Get the edit position
+    pub fn edit_pos(&self) -> usize {
+        self.edit_pos
+    }
+
+    /// Set the edit position without adjusting the anchor
+    ///
+    /// This extends (or reduces) the selection.
+    pub fn set_edit_pos(&mut self, pos: usize) {
+        self.edit_pos = pos;
+    }
+
+    /// Get the anchor position
+    pub fn anchor_pos(&self) -> usize {
+        self.anchor_pos
+    }
+
+    /// Select the whole of a text of length `len`
+    ///
+    /// The edit position is placed at the end.
+    pub fn select_all(&mut self, len: usize) {
+        self.anchor_pos = 0;
+        self.edit_pos = len;
+    }
+
+    /// Get the selection range
+    pub fn range(&self) -> Range<usize> {
+        let mut range = self.edit_pos..self.anchor_pos;
+        if range.start > range.end {
+            std::mem::swap(&mut range.start, &mut range.end);
+        }
+        range
+    }
+}
+
+#[test]
+fn selection_range() {
+    let mut sel = SelectionHelper::new(2, 2);
+    assert!(sel.is_empty());
+    sel.set_edit_pos(7);
+    assert_eq!(sel.range(), 2..7);
+    sel.set_edit_pos(0);
+    assert_eq!(sel.range(), 0..2);
+    sel.set_empty();
+    assert!(sel.is_empty());
+    assert_eq!(sel.edit_pos(), 0);
+}
@@ -72,9 +72,13 @@ impl SliderType for Duration {
 
 /// A slider
 ///
-/// Sliders allow user input of a value from a fixed range.
+/// Sliders allow user input of a value from a fixed range. The value may be
+/// adjusted by dragging the handle, by pressing on the track (moving the
+/// handle to the press) or via the keyboard (arrow keys, Page Up/Down, Home
+/// and End). Drawing is via [`DrawHandle::slider`], using metrics from
+/// [`SizeHandle::slider`].
 #[handler(send=noauto, msg = T)]
-#[widget(config(key_nav = true))]
+#[widget(config=noauto)]
 #[derive(Clone, Debug, Default, Widget)]
 pub struct Slider<T: SliderType, D: Directional> {
     #[widget_core]
@@ -128,9 +132,11 @@ impl<T: SliderType, D: Directional> Slider<T, D> {
     }
 
     /// Set the initial value
+    ///
+    /// The value is clamped to the slider's range.
     #[inline]
     pub fn with_value(mut self, value: T) -> Self {
-        self.value = value;
+        self.value = self.clamp(value);
         self
     }
 
@@ -142,23 +148,48 @@ impl<T: SliderType, D: Directional> Slider<T, D> {
 
     /// Set the value
     ///
-    /// Returns [`TkAction::Redraw`] if a redraw is required.
-    pub fn set_value(&mut self, mut value: T) -> TkAction {
-        if value < self.range.0 {
-            value = self.range.0;
-        } else if value > self.range.1 {
-            value = self.range.1;
-        }
+    /// Returns [`TkAction::REDRAW`] if a redraw is required.
+    pub fn set_value(&mut self, value: T) -> TkAction {
+        let value = self.clamp(value);
         if value == self.value {
-            TkAction::None
+            TkAction::empty()
         } else {
             self.value = value;
             self.handle.set_offset(self.offset()).1
         }
     }
 
+    fn clamp(&self, value: T) -> T {
+        if value < self.range.0 {
+            self.range.0
+        } else if value > self.range.1 {
+            self.range.1
+        } else {
+            value
+        }
+    }
+
+    // Step the value up (towards range.1) or down, without overflow
+    fn step_value(&self, up: bool, step: T) -> T {
+        if up {
+            match self.range.1 - self.value > step {
+                true => self.value + step,
+                false => self.range.1,
+            }
+        } else {
+            match self.value - self.range.0 > step {
+                true => self.value - step,
+                false => self.range.0,
+            }
+        }
+    }
+
     // translate value to offset in local coordinates
     fn offset(&self) -> Coord {
+        if !(self.range.0 < self.range.1) {
+            // Avoid division by zero on an empty range
+            return Coord::ZERO;
+        }
         let a = self.value - self.range.0;
         let b = self.range.1 - self.range.0;
         let max_offset = self.handle.max_offset();
@@ -176,11 +207,15 @@ impl<T: SliderType, D: Directional> Slider<T, D> {
     // true if not equal to old value
     fn set_offset(&mut self, offset: Coord) -> bool {
         let b = self.range.1 - self.range.0;
-        let max_offset = self.handle.max_offset();
-        let mut a = match self.direction.is_vertical() {
-            false => b.mul_f64(offset.0 as f64 / max_offset.0 as f64),
-            true => b.mul_f64(offset.1 as f64 / max_offset.1 as f64),
+        let (offset, max_offset) = match self.direction.is_vertical() {
+            false => (offset.0, self.handle.max_offset().0),
+            true => (offset.1, self.handle.max_offset().1),
         };
+        if max_offset <= 0 {
+            // The handle cannot move
+            return false;
+        }
+        let mut a = b.mul_f64(offset as f64 / max_offset as f64);
         if self.direction.is_reversed() {
             a = b - a;
         }
@@ -200,6 +235,28 @@ impl<T: SliderType, D: Directional> Slider<T, D> {
     }
 }
 
+impl<T: SliderType, D: Directional> WidgetConfig for Slider<T, D> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        // Right/Down steps towards range.1 unless reversed
+        let (inc, dec) = match self.direction.is_reversed() {
+            false => (ControlKey::Right, ControlKey::Left),
+            true => (ControlKey::Left, ControlKey::Right),
+        };
+        let mut actions = vec![];
+        if self.value < self.range.1 {
+            actions.push((event::WidgetAction::Increment, Event::Control(inc)));
+        }
+        if self.value > self.range.0 {
+            actions.push((event::WidgetAction::Decrement, Event::Control(dec)));
+        }
+        actions
+    }
+}
+
 impl<T: SliderType, D: Directional> Layout for Slider<T, D> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let (mut size, min_len) = size_handle.slider();
@@ -245,38 +302,38 @@ impl<T: SliderType, D: Directional> event::SendEvent for Slider<T, D> {
         let offset = if id <= self.handle.id() {
             match self.handle.send(mgr, id, event).try_into() {
                 Ok(res) => return res,
-                Err(offset) => offset,
+                Err((offset, action)) => {
+                    *mgr += action;
+                    offset
+                }
             }
         } else {
             match event {
                 Event::Control(key) => {
                     let rev = self.direction.is_reversed();
                     let v = match key {
-                        ControlKey::Left | ControlKey::Up => match rev {
-                            false => self.value - self.step,
-                            true => self.value + self.step,
-                        },
-                        ControlKey::Right | ControlKey::Down => match rev {
-                            false => self.value + self.step,
-                            true => self.value - self.step,
-                        },
+                        ControlKey::Left | ControlKey::Up => self.step_value(rev, self.step),
+                        ControlKey::Right | ControlKey::Down => self.step_value(!rev, self.step),
                         ControlKey::PageUp | ControlKey::PageDown => {
                             // Generics makes this easier than constructing a literal and multiplying!
-                            let mut x = self.step + self.step;
-                            x = x + x;
-                            x = x + x;
-                            x = x + x;
-                            match rev == (key == ControlKey::PageDown) {
-                                false => self.value + x,
-                                true => self.value - x,
+                            // We double the step three times, but never beyond
+                            // the span of the range, thus cannot overflow.
+                            let span = self.range.1 - self.range.0;
+                            let mut x = if self.step < span { self.step } else { span };
+                            for _ in 0..3 {
+                                x = match span - x > x {
+                                    true => x + x,
+                                    false => span,
+                                };
                             }
+                            self.step_value(rev != (key == ControlKey::PageDown), x)
                         }
                         ControlKey::Home => self.range.0,
                         ControlKey::End => self.range.1,
                         key => return Response::Unhandled(Event::Control(key)),
                     };
                     let action = self.set_value(v);
-                    return if action == TkAction::None {
+                    return if action.is_empty() {
                         Response::None
                     } else {
                         mgr.send_action(action);
@@ -234,9 +234,10 @@ impl<D: Directional, W: Widget> event::SendEvent for Splitter<D, W> {
                     return self.handles[n]
                         .send(mgr, id, event)
                         .try_into()
-                        .unwrap_or_else(|_| {
+                        .unwrap_or_else(|(_, action)| {
                             // Message is the new offset relative to the track;
                             // the handle has already adjusted its position
+                            *mgr += action;
                             self.adjust_size(n);
                             Response::None
                         });
@@ -343,8 +344,8 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     /// removed.
     pub fn clear(&mut self) -> TkAction {
         let action = match self.widgets.is_empty() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         };
         self.widgets.clear();
         self.handles.clear();
@@ -359,7 +360,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
             self.handles.push(DragHandle::new());
         }
         self.widgets.push(widget);
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Remove the last child widget
@@ -371,8 +372,8 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     /// removed.
     pub fn pop(&mut self) -> (Option<W>, TkAction) {
         let action = match self.widgets.is_empty() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         };
         let _ = self.handles.pop();
         (self.widgets.pop(), action)
@@ -388,7 +389,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
             self.handles.push(DragHandle::new());
         }
         self.widgets.insert(index, widget);
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Removes the child widget at position `index`
@@ -399,7 +400,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     pub fn remove(&mut self, index: usize) -> (W, TkAction) {
         let _ = self.handles.pop();
         let r = self.widgets.remove(index);
-        (r, TkAction::Reconfigure)
+        (r, TkAction::RECONFIGURE)
     }
 
     /// Replace the child at `index`
@@ -412,7 +413,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     // we somehow test "has compatible size"?
     pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
         std::mem::swap(&mut widget, &mut self.widgets[index]);
-        (widget, TkAction::Reconfigure)
+        (widget, TkAction::RECONFIGURE)
     }
 
     /// Append child widgets from an iterator
@@ -425,8 +426,8 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         self.handles
             .resize_with(self.widgets.len().saturating_sub(1), || DragHandle::new());
         match len == self.widgets.len() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         }
     }
 
@@ -436,7 +437,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     pub fn resize_with<F: Fn(usize) -> W>(&mut self, len: usize, f: F) -> TkAction {
         let l0 = self.widgets.len();
         if l0 == len {
-            return TkAction::None;
+            return TkAction::empty();
         } else if l0 > len {
             self.widgets.truncate(len);
         } else {
@@ -447,7 +448,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         }
         self.handles
             .resize_with(self.widgets.len().saturating_sub(1), || DragHandle::new());
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Retain only widgets satisfying predicate `f`
@@ -462,8 +463,8 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         self.handles
             .resize_with(self.widgets.len().saturating_sub(1), || DragHandle::new());
         match len == self.widgets.len() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         }
     }
 }
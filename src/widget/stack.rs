@@ -25,6 +25,9 @@ pub type RefStack<'a, M> = Stack<&'a mut dyn Widget<Msg = M>>;
 /// A stack consists a set of child widgets, all of equal size.
 /// Only a single member is visible at a time.
 ///
+/// When a descendant of a hidden member receives navigation focus or reports
+/// [`Response::Select`], that member is made visible.
+///
 /// This may only be parametrised with a single widget type; [`BoxStack`] is
 /// a parametrisation allowing run-time polymorphism of child widgets.
 ///
@@ -93,9 +96,13 @@ impl<W: Widget> event::SendEvent for Stack<W> {
                 if id <= child.id() {
                     return match child.send(mgr, id, event) {
                         Response::Focus(rect) => {
-                            *mgr += self.set_active(index);
+                            self.activate(mgr, index);
                             Response::Focus(rect)
                         }
+                        Response::Select => {
+                            self.activate(mgr, index);
+                            Response::Select
+                        }
                         r => r,
                     };
                 }
@@ -131,11 +138,22 @@ impl<W: Widget> Stack<W> {
     /// child widgets.
     pub fn set_active(&mut self, active: usize) -> TkAction {
         if self.active == active {
-            TkAction::None
+            TkAction::empty()
         } else {
             self.active = active;
-            TkAction::RegionMoved
+            TkAction::REGION_MOVED
+        }
+    }
+
+    // Set the active widget, notifying members shown or hidden
+    pub(crate) fn activate(&mut self, mgr: &mut Manager, index: usize) {
+        if index != self.active {
+            if let Some(w) = self.widgets.get_mut(self.active) {
+                mgr.notify_visibility(w, false);
+            }
+            mgr.notify_visibility(&mut self.widgets[index], true);
         }
+        *mgr += self.set_active(index);
     }
 
     /// Get a direct reference to the active widget, if any
@@ -183,8 +201,8 @@ impl<W: Widget> Stack<W> {
     /// removed.
     pub fn clear(&mut self) -> TkAction {
         let action = match self.widgets.is_empty() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         };
         self.widgets.clear();
         action
@@ -195,7 +213,7 @@ impl<W: Widget> Stack<W> {
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn push(&mut self, widget: W) -> TkAction {
         self.widgets.push(widget);
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Remove the last child widget
@@ -207,8 +225,8 @@ impl<W: Widget> Stack<W> {
     /// removed.
     pub fn pop(&mut self) -> (Option<W>, TkAction) {
         let action = match self.widgets.is_empty() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         };
         (self.widgets.pop(), action)
     }
@@ -220,7 +238,7 @@ impl<W: Widget> Stack<W> {
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn insert(&mut self, index: usize, widget: W) -> TkAction {
         self.widgets.insert(index, widget);
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Removes the child widget at position `index`
@@ -230,7 +248,7 @@ impl<W: Widget> Stack<W> {
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn remove(&mut self, index: usize) -> (W, TkAction) {
         let r = self.widgets.remove(index);
-        (r, TkAction::Reconfigure)
+        (r, TkAction::RECONFIGURE)
     }
 
     /// Replace the child at `index`
@@ -243,7 +261,7 @@ impl<W: Widget> Stack<W> {
     // we somehow test "has compatible size"?
     pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
         std::mem::swap(&mut widget, &mut self.widgets[index]);
-        (widget, TkAction::Reconfigure)
+        (widget, TkAction::RECONFIGURE)
     }
 
     /// Append child widgets from an iterator
@@ -254,8 +272,8 @@ impl<W: Widget> Stack<W> {
         let len = self.widgets.len();
         self.widgets.extend(iter);
         match len == self.widgets.len() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         }
     }
 
@@ -265,7 +283,7 @@ impl<W: Widget> Stack<W> {
     pub fn resize_with<F: Fn(usize) -> W>(&mut self, len: usize, f: F) -> TkAction {
         let l0 = self.widgets.len();
         if l0 == len {
-            return TkAction::None;
+            return TkAction::empty();
         } else if l0 > len {
             self.widgets.truncate(len);
         } else {
@@ -274,7 +292,7 @@ impl<W: Widget> Stack<W> {
                 self.widgets.push(f(i));
             }
         }
-        TkAction::Reconfigure
+        TkAction::RECONFIGURE
     }
 
     /// Retain only widgets satisfying predicate `f`
@@ -287,8 +305,8 @@ impl<W: Widget> Stack<W> {
         let len = self.widgets.len();
         self.widgets.retain(f);
         match len == self.widgets.len() {
-            true => TkAction::None,
-            false => TkAction::Reconfigure,
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
         }
     }
 }
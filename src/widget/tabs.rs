@@ -0,0 +1,466 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A stack with a tab bar
+
+use std::fmt::{self, Debug};
+use std::ops::{Index, IndexMut};
+use std::rc::Rc;
+
+use super::{Row, Stack, TextButton};
+use kas::draw::TextClass;
+use kas::event::{ModifiersState, Shortcut, VirtualKeyCode};
+use kas::prelude::*;
+
+#[derive(Clone, Debug, VoidMsg)]
+enum TabMsg {
+    Close(usize),
+}
+
+/// A tab of a [`TabbedStack`], with an optional close button
+#[handler(noauto)]
+#[widget(config=noauto, children=noauto)]
+#[derive(Clone, Debug, Widget)]
+struct Tab {
+    #[widget_core]
+    core: CoreData,
+    index: usize,
+    label: AccelString,
+    active: bool,
+    close: Option<TextButton<TabMsg>>,
+    frame_size: Size,
+    close_size: Size,
+}
+
+impl Tab {
+    fn new(index: usize, label: AccelString, closable: bool) -> Self {
+        let mut tab = Tab {
+            core: Default::default(),
+            index,
+            label,
+            active: false,
+            close: None,
+            frame_size: Size::ZERO,
+            close_size: Size::ZERO,
+        };
+        tab.set_closable(closable);
+        tab
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index;
+        if let Some(close) = self.close.as_mut() {
+            close.set_msg(TabMsg::Close(index));
+        }
+    }
+
+    fn set_closable(&mut self, closable: bool) {
+        self.close = match closable {
+            true => Some(TextButton::new("×", TabMsg::Close(self.index))),
+            false => None,
+        };
+    }
+}
+
+impl WidgetChildren for Tab {
+    #[inline]
+    fn len(&self) -> usize {
+        self.close.is_some() as usize
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        match index {
+            0 => self.close.as_ref().map(|w| w.as_widget()),
+            _ => None,
+        }
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        match index {
+            0 => self.close.as_mut().map(|w| w.as_widget_mut()),
+            _ => None,
+        }
+    }
+}
+
+impl WidgetConfig for Tab {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.add_accel_keys(self.id(), self.label.keys());
+    }
+
+    fn key_nav(&self) -> bool {
+        true
+    }
+
+    fn actions(&self) -> Vec<(event::WidgetAction, Event)> {
+        vec![(event::WidgetAction::Select, Event::Activate)]
+    }
+}
+
+impl Layout for Tab {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let sides = size_handle.button_surround();
+        self.frame_size = sides.1;
+        let margins = size_handle.outer_margins();
+        let frame_rules = SizeRules::extract_fixed(axis.is_vertical(), sides.0 + sides.1, margins);
+
+        let mut rules = size_handle.text_bound(self.label.get(false), TextClass::Button, axis);
+        if let Some(close) = self.close.as_mut() {
+            let close_rules = close.size_rules(size_handle, axis);
+            if axis.is_horizontal() {
+                self.close_size.0 = close_rules.ideal_size();
+                rules.append(close_rules);
+            } else {
+                self.close_size.1 = close_rules.ideal_size();
+                rules.max_with(close_rules);
+            }
+        }
+        rules.surrounded_by(frame_rules, true)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        if let Some(close) = self.close.as_mut() {
+            // Place the close button at the end, within the frame
+            let size = self.close_size.min(rect.size);
+            let x = rect.size.0.saturating_sub(size.0 + self.frame_size.0);
+            let y = (rect.size.1 - size.1) / 2;
+            let pos = rect.pos + Coord(x as i32, y as i32);
+            close.set_rect(Rect::new(pos, size), align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.close
+            .as_ref()
+            .and_then(|close| close.find_id(coord))
+            .or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let state = self.input_state(mgr, disabled);
+        draw_handle.tab(self.core.rect, self.active, state);
+
+        let mut rect = self.core.rect;
+        if let Some(close) = self.close.as_ref() {
+            rect.size.0 = (close.rect().pos.0 - rect.pos.0).max(0) as u32;
+        }
+        let text = self.label.get(mgr.show_accel_labels());
+        let class = match self.active {
+            true => TextClass::Button,
+            false => TextClass::Label,
+        };
+        draw_handle.text(rect, text, class, (Align::Centre, Align::Centre));
+
+        if let Some(close) = self.close.as_ref() {
+            close.draw(draw_handle, mgr, state.disabled);
+        }
+    }
+}
+
+impl event::Handler for Tab {
+    type Msg = TabMsg;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle(&mut self, _: &mut Manager, event: Event) -> Response<TabMsg> {
+        match event {
+            Event::Activate | Event::Select => Response::Select,
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl event::SendEvent for Tab {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<TabMsg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        match self.close.as_mut() {
+            Some(close) if id <= close.id() => close.send(mgr, id, event),
+            _ => Manager::handle_generic(self, mgr, event),
+        }
+    }
+}
+
+/// A stack of pages with a tab bar
+///
+/// This is a [`Stack`] of pages below a row of tabs, one per page. Clicking
+/// or otherwise activating a tab shows its page. Tabs support accelerator keys
+/// (as does [`TextButton`]), and Ctrl+Tab and Ctrl+Shift+Tab show the next and
+/// previous page respectively. (Like other [`Shortcut`]s, these are bound to
+/// only one widget per window; where multiple tabbed stacks are present, the
+/// first configured receives them.)
+///
+/// A tab reports [`Response::Select`] when activated or sent
+/// [`Event::Select`]; the stack shows its page and passes the response on.
+///
+/// Tabs may optionally include a close button (see
+/// [`TabbedStack::with_closable`]), which removes the page; the removed page
+/// is passed to the handler set via [`TabbedStack::on_close`], if any.
+///
+/// Tabs are drawn via [`DrawHandle::tab`].
+#[layout(column)]
+#[handler(noauto)]
+#[widget(config=noauto)]
+#[derive(Clone, Widget)]
+pub struct TabbedStack<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget]
+    bar: Row<Tab>,
+    #[widget]
+    stack: Stack<W>,
+    closable: bool,
+    on_close: Option<Rc<dyn Fn(&mut Manager, usize, W)>>,
+}
+
+impl<W: Widget> Debug for TabbedStack<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TabbedStack {{ core: {:?}, bar: {:?}, stack: {:?}, closable: {:?}, ... }}",
+            self.core, self.bar, self.stack, self.closable,
+        )
+    }
+}
+
+impl<W: Widget> WidgetConfig for TabbedStack<W> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        let modifiers = ModifiersState::CTRL | ModifiersState::SHIFT;
+        mgr.add_shortcut(self.id(), Shortcut::ctrl(VirtualKeyCode::Tab));
+        mgr.add_shortcut(self.id(), Shortcut::new(modifiers, VirtualKeyCode::Tab));
+    }
+}
+
+impl<W: Widget> event::Handler for TabbedStack<W> {
+    type Msg = <W as event::Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::Activate => {
+                // Received via shortcut: Ctrl+Tab or Ctrl+Shift+Tab
+                let len = self.stack.len();
+                if len > 0 {
+                    let active = self.stack.active_index().min(len - 1);
+                    let index = match mgr.modifiers().shift() {
+                        false => (active + 1) % len,
+                        true => (active + len - 1) % len,
+                    };
+                    self.select(mgr, index);
+                }
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for TabbedStack<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        if id <= self.bar.id() {
+            let r = self.bar.send(mgr, id, event).apply_action(mgr);
+            match r.try_into() {
+                Ok(Response::Select) => {
+                    let bar = &self.bar;
+                    if let Some(index) = (0..bar.len()).find(|i| id <= bar[*i].id()) {
+                        self.select(mgr, index);
+                    }
+                    Response::Select
+                }
+                Ok(r) => r,
+                Err((TabMsg::Close(index), _)) => {
+                    self.close(mgr, index);
+                    Response::None
+                }
+            }
+        } else if id <= self.stack.id() {
+            // The stack may show another page on navigation
+            let r = self.stack.send(mgr, id, event);
+            *mgr += self.update_tabs();
+            r
+        } else {
+            Manager::handle_generic(self, mgr, event)
+        }
+    }
+}
+
+impl<W: Widget> Default for TabbedStack<W> {
+    fn default() -> Self {
+        TabbedStack::new()
+    }
+}
+
+impl<W: Widget> TabbedStack<W> {
+    /// Construct, with no pages
+    pub fn new() -> Self {
+        TabbedStack {
+            core: Default::default(),
+            layout_data: Default::default(),
+            bar: Row::new(vec![]),
+            stack: Stack::new(vec![], 0),
+            closable: false,
+            on_close: None,
+        }
+    }
+
+    /// Append a page (chain style)
+    pub fn with_page<S: Into<AccelString>>(mut self, label: S, page: W) -> Self {
+        let _ = self.push(label, page);
+        self
+    }
+
+    /// Set whether tabs have a close button (chain style)
+    ///
+    /// By default, tabs cannot be closed.
+    pub fn with_closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        for index in 0..self.bar.len() {
+            self.bar[index].set_closable(closable);
+        }
+        self
+    }
+
+    /// Set a handler called when a page is closed via its tab (chain style)
+    ///
+    /// The handler is passed the page's former index and the page itself.
+    pub fn on_close<F: Fn(&mut Manager, usize, W) + 'static>(mut self, f: F) -> Self {
+        self.on_close = Some(Rc::new(f));
+        self
+    }
+
+    /// True if there are no pages
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Returns the number of pages
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Get the index of the active page
+    pub fn active_index(&self) -> usize {
+        self.stack.active_index()
+    }
+
+    /// Change the active page via index
+    ///
+    /// See [`Stack::set_active`].
+    pub fn set_active(&mut self, active: usize) -> TkAction {
+        self.stack.set_active(active) | self.update_tabs()
+    }
+
+    /// Get the label of the tab at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn label(&self, index: usize) -> &str {
+        self.bar[index].label.get(false)
+    }
+
+    /// Set the label of the tab at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_label<S: Into<AccelString>>(&mut self, index: usize, label: S) -> TkAction {
+        self.bar[index].label = label.into();
+        TkAction::RECONFIGURE
+    }
+
+    /// Append a page
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push<S: Into<AccelString>>(&mut self, label: S, page: W) -> TkAction {
+        let index = self.stack.len();
+        let mut tab = Tab::new(index, label.into(), self.closable);
+        tab.active = index == self.stack.active_index();
+        self.stack.push(page) | self.bar.push(tab)
+    }
+
+    /// Remove the page at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> (W, TkAction) {
+        let (page, action) = self.stack.remove(index);
+        let (_, bar_action) = self.bar.remove(index);
+        for i in index..self.bar.len() {
+            self.bar[i].set_index(i);
+        }
+
+        // Keep the active page where possible, else show its successor
+        let active = self.stack.active_index();
+        let len = self.stack.len();
+        if index < active || (index == active && active == len && len > 0) {
+            let _ = self.stack.set_active(active - 1);
+        }
+        (page, action | bar_action | self.update_tabs())
+    }
+
+    // Show the page at index
+    fn select(&mut self, mgr: &mut Manager, index: usize) {
+        if index < self.stack.len() {
+            self.stack.activate(mgr, index);
+            *mgr += self.update_tabs();
+        }
+    }
+
+    // Close the page at index, notifying the page shown in its place
+    fn close(&mut self, mgr: &mut Manager, index: usize) {
+        let was_active = index == self.stack.active_index();
+        let (page, action) = self.remove(index);
+        *mgr += action;
+        if was_active {
+            let active = self.stack.active_index();
+            if active < self.stack.len() {
+                mgr.notify_visibility(&mut self.stack[active], true);
+            }
+        }
+        if let Some(f) = self.on_close.as_ref() {
+            f(mgr, index, page);
+        }
+    }
+
+    // Set the active state of tabs to match the stack
+    fn update_tabs(&mut self) -> TkAction {
+        let active = self.stack.active_index();
+        let mut action = TkAction::empty();
+        for index in 0..self.bar.len() {
+            let tab = &mut self.bar[index];
+            if tab.active != (index == active) {
+                tab.active = index == active;
+                action = TkAction::REDRAW;
+            }
+        }
+        action
+    }
+}
+
+impl<W: Widget> Index<usize> for TabbedStack<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.stack[index]
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for TabbedStack<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.stack[index]
+    }
+}
@@ -0,0 +1,198 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Tag input widget
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::{AutoComplete, Completer, EditBox, EditGuard, Row, TextButton};
+use kas::class::HasText;
+use kas::prelude::*;
+
+/// [`EditGuard`] used by [`TagInput`]
+///
+/// Emits the text on activation or when a comma is entered.
+#[derive(Clone, Debug)]
+pub struct TagGuard;
+
+impl EditGuard for TagGuard {
+    type Msg = String;
+
+    fn activate(edit: &mut EditBox<Self>) -> Option<String> {
+        Some(edit.get_text().to_string())
+    }
+
+    fn edit(edit: &mut EditBox<Self>) -> Option<String> {
+        if edit.get_text().contains(',') {
+            Some(edit.get_text().to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// An input for a list of tags
+///
+/// Tags are entered into an edit box and added on pressing Return or entering
+/// a comma (thus multiple comma-separated tags may be pasted at once).
+/// Duplicate and empty tags are ignored. Each tag is displayed as a button;
+/// clicking a tag's button removes the tag. Suggestions may be displayed via
+/// [`TagInput::with_completer`].
+#[layout(row)]
+#[handler(msg = M)]
+#[derive(Clone, Widget)]
+pub struct TagInput<M: 'static> {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(handler = remove_tag)]
+    buttons: Row<TextButton<usize>>,
+    #[widget(handler = add_tags)]
+    edit: AutoComplete<TagGuard>,
+    tags: Vec<String>,
+    on_change: Option<Rc<dyn Fn(&[String]) -> M>>,
+}
+
+impl<M: 'static> Debug for TagInput<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TagInput {{ core: {:?}, buttons: {:?}, edit: {:?}, tags: {:?}, ... }}",
+            self.core, self.buttons, self.edit, self.tags
+        )
+    }
+}
+
+fn no_suggestions(_: &str) -> Vec<String> {
+    vec![]
+}
+
+fn tag_button(tag: &str, index: usize) -> TextButton<usize> {
+    // Labels treat '&' as an accelerator-key prefix
+    let label = format!("{} ×", tag.replace('&', "&&"));
+    TextButton::new(label, index)
+}
+
+impl Default for TagInput<VoidMsg> {
+    fn default() -> Self {
+        TagInput::new()
+    }
+}
+
+impl TagInput<VoidMsg> {
+    /// Construct an empty tag input
+    pub fn new() -> Self {
+        TagInput {
+            core: Default::default(),
+            layout_data: Default::default(),
+            buttons: Row::new(vec![]),
+            edit: EditBox::new("")
+                .with_guard(TagGuard)
+                .with_completer(no_suggestions),
+            tags: vec![],
+            on_change: None,
+        }
+    }
+
+    /// Set the event handler to be called on change.
+    ///
+    /// The closure `f` is called with the list of tags whenever tags are added
+    /// or removed by the user, and the result of `f` is returned from the
+    /// event handler.
+    pub fn on_change<M, F>(self, f: F) -> TagInput<M>
+    where
+        F: Fn(&[String]) -> M + 'static,
+    {
+        TagInput {
+            core: self.core,
+            layout_data: self.layout_data,
+            buttons: self.buttons,
+            edit: self.edit,
+            tags: self.tags,
+            on_change: Some(Rc::new(f)),
+        }
+    }
+}
+
+impl<M: 'static> TagInput<M> {
+    /// Set the initial tags (chain style)
+    pub fn with_tags<T: Into<String>, I: IntoIterator<Item = T>>(mut self, tags: I) -> Self {
+        for tag in tags {
+            let _ = self.push(tag.into());
+        }
+        self
+    }
+
+    /// Use a [`Completer`] to suggest tags (chain style)
+    ///
+    /// Suggestions are displayed while typing; see [`AutoComplete`].
+    pub fn with_completer<C: Completer + 'static>(mut self, completer: C) -> Self {
+        self.edit = EditBox::new("")
+            .with_guard(TagGuard)
+            .with_completer(completer);
+        self
+    }
+
+    /// Get the list of tags
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Set the list of tags
+    ///
+    /// This replaces all existing tags and triggers a reconfigure action.
+    pub fn set_tags<T: Into<String>, I: IntoIterator<Item = T>>(&mut self, tags: I) -> TkAction {
+        self.tags.clear();
+        let _ = self.buttons.clear();
+        for tag in tags {
+            let _ = self.push(tag.into());
+        }
+        TkAction::RECONFIGURE
+    }
+
+    // Add a tag, returning false if empty or already present
+    fn push(&mut self, tag: String) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.tags.iter().any(|t| t == tag) {
+            return false;
+        }
+        let _ = self.buttons.push(tag_button(tag, self.tags.len()));
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    fn changed(&self) -> Response<M> {
+        if let Some(ref f) = self.on_change {
+            f(&self.tags).into()
+        } else {
+            Response::None
+        }
+    }
+
+    fn add_tags(&mut self, mgr: &mut Manager, text: String) -> Response<M> {
+        let mut added = false;
+        for tag in text.split(',') {
+            added |= self.push(tag.to_string());
+        }
+        *mgr += self.edit.edit_mut().set_text("");
+        if added {
+            *mgr += TkAction::RECONFIGURE;
+            self.changed()
+        } else {
+            Response::None
+        }
+    }
+
+    fn remove_tag(&mut self, mgr: &mut Manager, index: usize) -> Response<M> {
+        self.tags.remove(index);
+        *mgr += self.buttons.remove(index).1;
+        for i in index..self.buttons.len() {
+            self.buttons[i].set_msg(i);
+        }
+        self.changed()
+    }
+}
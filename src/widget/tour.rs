@@ -0,0 +1,252 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Guided tours
+
+use log::warn;
+
+use super::{find_rect, Label, MenuFrame, TextButton};
+use kas::class::HasText;
+use kas::prelude::*;
+use kas::{Overlay, Placement};
+
+#[derive(Clone, Debug, VoidMsg)]
+enum TourMsg {
+    Next,
+    Skip,
+}
+
+#[layout(grid)]
+#[handler(msg = TourMsg)]
+#[derive(Clone, Debug, Widget)]
+struct TourPanel {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(row = 0, col = 0, cspan = 2)]
+    label: Label,
+    #[widget(row = 1, col = 0)]
+    skip: TextButton<TourMsg>,
+    #[widget(row = 1, col = 1)]
+    next: TextButton<TourMsg>,
+}
+
+// The overlay: dims the window (except the spotlight), then draws the panel
+#[handler(msg = TourMsg)]
+#[derive(Clone, Debug, Widget)]
+struct TourBubble {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    frame: MenuFrame<TourPanel>,
+    spotlight: Option<Rect>,
+}
+
+impl Layout for TourBubble {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.frame.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.frame.set_rect(rect, align);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.frame.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        // Overlays are drawn in a clip region covering the whole window
+        let window_rect = draw_handle.target_rect();
+        draw_handle.dim(window_rect, self.spotlight);
+        self.frame.draw(draw_handle, mgr, disabled);
+    }
+}
+
+/// A guided tour over the inner widget
+///
+/// A tour is a sequence of steps, each highlighting a widget (addressed by
+/// name; see [`WidgetChildren::find_name_path`]) and describing it in a
+/// bubble with "Next" and "Skip" buttons. While a step is shown, the rest of
+/// the window is dimmed (see [`DrawHandle::dim`]) and the bubble is drawn as
+/// an [`Overlay`] next to the highlighted widget. Input to the rest of the
+/// window is not blocked.
+///
+/// Steps whose widget cannot be found are skipped. The tour does not start by
+/// itself; call [`Tour::start`], for example from a
+/// [`Window`](super::Window) callback on [`Callback::Start`].
+///
+/// The spotlight is calculated in the coordinates of this widget, thus a
+/// `Tour` should not be placed within a [`ScrollRegion`](super::ScrollRegion);
+/// usually it directly wraps the window's content.
+///
+/// [`Callback::Start`]: kas::event::Callback::Start
+#[handler(send=noauto, msg = <W as event::Handler>::Msg)]
+#[derive(Clone, Debug, Widget)]
+pub struct Tour<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: W,
+    #[widget]
+    bubble: TourBubble,
+    steps: Vec<(CowString, CowString)>,
+    // Index of the current step and its widget
+    current: Option<(usize, WidgetId)>,
+}
+
+impl<W: Widget> Layout for Tour<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.inner.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.inner.set_rect(rect, align);
+        if let Some((_, id)) = self.current {
+            self.bubble.spotlight = find_rect(self.inner.as_widget(), id);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.inner.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.inner.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl<W: Widget> event::SendEvent for Tour<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        if id <= self.inner.id() {
+            self.inner.send(mgr, id, event)
+        } else if id <= self.bubble.id() {
+            let r = self.bubble.send(mgr, id, event).apply_action(mgr);
+            match r.try_into() {
+                Ok(r) => r,
+                Err((TourMsg::Next, _)) => {
+                    let next = self.current.map(|(index, _)| index + 1).unwrap_or(0);
+                    self.show_step(mgr, next);
+                    Response::None
+                }
+                Err((TourMsg::Skip, _)) => {
+                    self.stop(mgr);
+                    Response::None
+                }
+            }
+        } else {
+            Response::Unhandled(event)
+        }
+    }
+}
+
+impl<W: Widget> Tour<W> {
+    /// Construct, with no steps
+    pub fn new(inner: W) -> Self {
+        let panel = TourPanel {
+            core: Default::default(),
+            layout_data: Default::default(),
+            label: Label::new(""),
+            skip: TextButton::new("&Skip", TourMsg::Skip),
+            next: TextButton::new("&Next", TourMsg::Next),
+        };
+        Tour {
+            core: Default::default(),
+            inner,
+            bubble: TourBubble {
+                core: Default::default(),
+                frame: MenuFrame::new(panel),
+                spotlight: None,
+            },
+            steps: vec![],
+            current: None,
+        }
+    }
+
+    /// Add a step (chain style)
+    ///
+    /// The step highlights the widget found via the path of names `name` (see
+    /// [`WidgetChildren::find_name_path`]), describing it with `text`.
+    pub fn with_step<N: Into<CowString>, T: Into<CowString>>(mut self, name: N, text: T) -> Self {
+        self.push_step(name, text);
+        self
+    }
+
+    /// Add a step
+    ///
+    /// See [`Tour::with_step`].
+    pub fn push_step<N: Into<CowString>, T: Into<CowString>>(&mut self, name: N, text: T) {
+        self.steps.push((name.into(), text.into()));
+    }
+
+    /// Access the inner widget
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+
+    /// Access the inner widget mutably
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Get the index of the current step, if the tour is running
+    pub fn step(&self) -> Option<usize> {
+        self.current.map(|(index, _)| index)
+    }
+
+    /// Start (or restart) the tour from the first step
+    pub fn start(&mut self, mgr: &mut Manager) {
+        self.show_step(mgr, 0);
+    }
+
+    /// Stop the tour, if running
+    pub fn stop(&mut self, mgr: &mut Manager) {
+        if self.current.take().is_some() {
+            self.bubble.spotlight = None;
+            mgr.remove_overlay(self.bubble.id());
+        }
+    }
+
+    // Show the first step from index whose widget exists, or stop
+    fn show_step(&mut self, mgr: &mut Manager, mut index: usize) {
+        while let Some((name, text)) = self.steps.get(index) {
+            let id = match self.inner.find_name_path(name) {
+                Some(w) => w.id(),
+                None => {
+                    warn!("Tour: no widget found for step {} ({})", index, name);
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let panel = &mut self.bubble.frame.inner;
+            *mgr += panel.label.set_text(text.clone());
+            let next = if index + 1 < self.steps.len() {
+                "&Next"
+            } else {
+                "&Done"
+            };
+            *mgr += panel.next.set_text(next);
+
+            self.current = Some((index, id));
+            self.bubble.spotlight = find_rect(self.inner.as_widget(), id);
+            mgr.add_overlay(Overlay {
+                id: self.bubble.id(),
+                anchor: id,
+                placement: Placement::Beside(Direction::Down),
+            });
+            return;
+        }
+        self.stop(mgr);
+    }
+}
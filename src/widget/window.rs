@@ -7,11 +7,17 @@
 
 use smallvec::SmallVec;
 use std::fmt::{self, Debug};
+use std::rc::Rc;
 
+use super::{Label, MenuFrame};
+use kas::class::HasText;
 use kas::draw::ClipRegion;
-use kas::event::Callback;
+use kas::event::{Callback, Help};
+use kas::layout::Margins;
 use kas::prelude::*;
-use kas::WindowId;
+use kas::{Placement, WindowId};
+
+type HelpHandler = Rc<dyn Fn(&mut Manager, WidgetId, &Help)>;
 
 /// The main instantiation of the [`Window`] trait.
 #[handler(send=noauto, generics = <> where W: Widget<Msg = VoidMsg>)]
@@ -24,7 +30,15 @@ pub struct Window<W: Widget + 'static> {
     title: CowString,
     #[widget]
     w: W,
+    #[widget]
+    help: MenuFrame<Label>,
     popups: SmallVec<[(WindowId, kas::Popup); 16]>,
+    overlays: SmallVec<[kas::Overlay; 4]>,
+    // Pop-up window and anchor widget of the help pop-up
+    help_popup: Option<(WindowId, WidgetId)>,
+    // True if the help pop-up currently shows a tooltip
+    is_tooltip: bool,
+    help_handler: Option<HelpHandler>,
     fns: Vec<(Callback, &'static dyn Fn(&mut W, &mut Manager))>,
 }
 
@@ -53,7 +67,12 @@ impl<W: Widget + Clone> Clone for Window<W> {
             restrict_dimensions: self.restrict_dimensions.clone(),
             title: self.title.clone(),
             w: self.w.clone(),
+            help: self.help.clone(),
             popups: Default::default(), // these are temporary; don't clone
+            overlays: Default::default(),
+            help_popup: None,
+            is_tooltip: false,
+            help_handler: self.help_handler.clone(),
             fns: self.fns.clone(),
         }
     }
@@ -67,7 +86,12 @@ impl<W: Widget> Window<W> {
             restrict_dimensions: (true, false),
             title: title.into(),
             w,
+            help: MenuFrame::new(Label::new("")),
             popups: Default::default(),
+            overlays: Default::default(),
+            help_popup: None,
+            is_tooltip: false,
+            help_handler: None,
             fns: Vec::new(),
         }
     }
@@ -86,6 +110,71 @@ impl<W: Widget> Window<W> {
     pub fn add_callback(&mut self, condition: Callback, f: &'static dyn Fn(&mut W, &mut Manager)) {
         self.fns.push((condition, f));
     }
+
+    /// Set the help handler
+    ///
+    /// This closure is called when help is requested via F1 (see [`Help`]),
+    /// with the identifier of the widget whose help is passed. Typically it
+    /// opens a help browser or the documentation at [`Help::url`].
+    pub fn set_help_handler<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Manager, WidgetId, &Help) + 'static,
+    {
+        self.help_handler = Some(Rc::new(f));
+    }
+
+    fn show_help(&mut self, mgr: &mut Manager, id: WidgetId) {
+        if let Some(text) = mgr.help(id).map(|help| help.text.clone()) {
+            self.show_popup_text(mgr, id, text, false);
+        }
+    }
+
+    /// Show `text` in the help pop-up, next to widget `id`
+    fn show_popup_text(&mut self, mgr: &mut Manager, id: WidgetId, text: CowString, tip: bool) {
+        if let Some((window, _)) = self.help_popup.take() {
+            mgr.close_window(window);
+        }
+        let action = self.help.set_text(text);
+        *mgr += action;
+        // The help pop-up belongs to the window, so that any press closes
+        // it, but is placed next to the widget.
+        let window = mgr.add_popup(kas::Popup {
+            id: self.help.id(),
+            parent: self.id(),
+            direction: Direction::Down,
+        });
+        self.help_popup = Some((window, id));
+        self.is_tooltip = tip;
+    }
+
+    fn show_overlay(&mut self, mgr: &mut Manager, overlay: kas::Overlay) {
+        let index = match self.overlays.iter().position(|o| o.id == overlay.id) {
+            Some(index) => {
+                self.overlays[index] = overlay;
+                index
+            }
+            None => match self.w.find_mut(overlay.id) {
+                Some(w) => {
+                    mgr.notify_visibility(w, true);
+                    self.overlays.push(overlay);
+                    self.overlays.len() - 1
+                }
+                None => return,
+            },
+        };
+        mgr.size_handle(|size_handle| self.resize_overlay(size_handle, index));
+        mgr.send_action(TkAction::REGION_MOVED);
+    }
+
+    fn hide_overlay(&mut self, mgr: &mut Manager, id: WidgetId) {
+        if let Some(index) = self.overlays.iter().position(|o| o.id == id) {
+            self.overlays.remove(index);
+            if let Some(w) = self.w.find_mut(id) {
+                mgr.notify_visibility(w, false);
+            }
+            mgr.send_action(TkAction::REGION_MOVED);
+        }
+    }
 }
 
 impl<W: Widget> WidgetConfig for Window<W> {
@@ -118,7 +207,12 @@ impl<W: Widget> Layout for Window<W> {
             return None;
         }
         for popup in self.popups.iter().rev() {
-            if let Some(id) = self.w.find(popup.1.id).and_then(|w| w.find_id(coord)) {
+            if let Some(id) = self.find(popup.1.id).and_then(|w| w.find_id(coord)) {
+                return Some(id);
+            }
+        }
+        for overlay in self.overlays.iter().rev() {
+            if let Some(id) = self.find(overlay.id).and_then(|w| w.find_id(coord)) {
                 return Some(id);
             }
         }
@@ -129,11 +223,20 @@ impl<W: Widget> Layout for Window<W> {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let disabled = disabled || self.is_disabled();
         self.w.draw(draw_handle, mgr, disabled);
+        for overlay in &self.overlays {
+            let class = ClipRegion::Overlay;
+            draw_handle.clip_region(self.core.rect, Coord::ZERO, class, &mut |draw_handle| {
+                if let Some(w) = self.find(overlay.id) {
+                    w.draw(draw_handle, mgr, disabled);
+                }
+            });
+        }
         for popup in &self.popups {
             let class = ClipRegion::Popup;
             draw_handle.clip_region(self.core.rect, Coord::ZERO, class, &mut |draw_handle| {
-                self.find(popup.1.id)
-                    .map(|w| w.draw(draw_handle, mgr, disabled));
+                if let Some(w) = self.find(popup.1.id) {
+                    w.draw(draw_handle, mgr, disabled);
+                }
             });
         }
     }
@@ -141,10 +244,48 @@ impl<W: Widget> Layout for Window<W> {
 
 impl<W: Widget<Msg = VoidMsg> + 'static> event::SendEvent for Window<W> {
     fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
-        if !self.is_disabled() && id <= self.w.id() {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+        if id <= self.w.id() {
             return self.w.send(mgr, id, event);
+        } else if id <= self.help.id() {
+            return self.help.send(mgr, id, event);
         }
-        Response::Unhandled(event)
+
+        match event {
+            Event::ShowHelp(id) => self.show_help(mgr, id),
+            Event::HelpRequested(id) => {
+                let help = mgr.help(id).cloned();
+                match (self.help_handler.clone(), help) {
+                    (Some(handler), Some(help)) => handler(mgr, id, &help),
+                    _ => return Response::Unhandled(event),
+                }
+            }
+            Event::ShowTooltip(id) => {
+                // Do not replace help with a tooltip
+                if self.help_popup.is_none() || self.is_tooltip {
+                    if let Some(text) = mgr.tooltip(id).cloned() {
+                        self.show_popup_text(mgr, id, text, true);
+                    }
+                }
+            }
+            Event::HideTooltip => {
+                if let Some((window, _)) = self.help_popup.filter(|_| self.is_tooltip) {
+                    mgr.close_window(window);
+                    self.help_popup = None;
+                }
+            }
+            Event::PopupRemoved(window) => {
+                if self.help_popup.map(|(w, _)| w) == Some(window) {
+                    self.help_popup = None;
+                }
+            }
+            Event::ShowOverlay(overlay) => self.show_overlay(mgr, overlay),
+            Event::HideOverlay(id) => self.hide_overlay(mgr, id),
+            event => return Response::Unhandled(event),
+        }
+        Response::None
     }
 }
 
@@ -159,16 +300,22 @@ impl<W: Widget<Msg = VoidMsg> + 'static> kas::Window for Window<W> {
 
     fn add_popup(&mut self, mgr: &mut Manager, id: WindowId, popup: kas::Popup) {
         let index = self.popups.len();
+        if let Some(w) = self.w.find_mut(popup.id) {
+            mgr.notify_visibility(w, true);
+        }
         self.popups.push((id, popup));
         mgr.size_handle(|size_handle| self.resize_popup(size_handle, index));
-        mgr.send_action(TkAction::Redraw);
+        mgr.send_action(TkAction::REDRAW);
     }
 
     fn remove_popup(&mut self, mgr: &mut Manager, id: WindowId) {
         for i in 0..self.popups.len() {
             if id == self.popups[i].0 {
-                self.popups.remove(i);
-                mgr.send_action(TkAction::RegionMoved);
+                let (_, popup) = self.popups.remove(i);
+                if let Some(w) = self.w.find_mut(popup.id) {
+                    mgr.notify_visibility(w, false);
+                }
+                mgr.send_action(TkAction::REGION_MOVED);
                 return;
             }
         }
@@ -178,6 +325,9 @@ impl<W: Widget<Msg = VoidMsg> + 'static> kas::Window for Window<W> {
         for i in 0..self.popups.len() {
             self.resize_popup(size_handle, i);
         }
+        for i in 0..self.overlays.len() {
+            self.resize_overlay(size_handle, i);
+        }
     }
 
     fn handle_closure(&mut self, mgr: &mut Manager) {
@@ -191,7 +341,7 @@ impl<W: Widget<Msg = VoidMsg> + 'static> kas::Window for Window<W> {
 }
 
 // This is like WidgetChildren::find, but returns a translated Rect.
-fn find_rect(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Rect> {
+pub(crate) fn find_rect(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Rect> {
     if id == widget.id() {
         return Some(widget.rect());
     } else if id > widget.id() {
@@ -215,48 +365,105 @@ impl<W: Widget> Window<W> {
         // Notation: p=point/coord, s=size, m=margin
         // r=window/root rect, c=anchor rect
         let r = self.core.rect;
-        let popup = &mut self.popups[index].1;
+        let (window, popup) = self.popups[index].clone();
 
-        let c = find_rect(self.w.as_widget(), popup.parent).unwrap();
-        let widget = self.w.find_mut(popup.id).unwrap();
+        let is_help = self.help_popup.map(|(w, _)| w) == Some(window);
+        let anchor = match self.help_popup {
+            Some((w, anchor)) if w == window => anchor,
+            _ => popup.parent,
+        };
+        let c = find_rect(self.w.as_widget(), anchor).unwrap();
+        let widget = match is_help {
+            true => self.help.as_widget_mut(),
+            false => self.w.find_mut(popup.id).unwrap(),
+        };
         let mut cache = layout::SolveCache::find_constraints(widget, size_handle);
         let ideal = cache.ideal(false);
         let m = cache.margins();
 
-        let is_reversed = popup.direction.is_reversed();
-        let place_in = |rp, rs: u32, cp: i32, cs, ideal, m: (u16, u16)| -> (i32, u32) {
-            let before: i32 = cp - (rp + m.1 as i32);
-            let before = before.max(0) as u32;
-            let after = rs.saturating_sub(cs + before + m.0 as u32);
-            if after >= ideal {
-                if is_reversed && before >= ideal {
-                    (cp - ideal as i32 - m.1 as i32, ideal)
-                } else {
-                    (cp + cs as i32 + m.0 as i32, ideal)
-                }
-            } else if before >= ideal {
-                (cp - ideal as i32 - m.1 as i32, ideal)
-            } else if before > after {
-                (rp, before)
-            } else {
-                (cp + cs as i32 + m.0 as i32, after)
-            }
+        let rect = place_beside(r, c, ideal, m, popup.direction);
+        cache.apply_rect(widget, size_handle, rect, false);
+    }
+
+    fn resize_overlay(&mut self, size_handle: &mut dyn SizeHandle, index: usize) {
+        // Notation as in resize_popup
+        let r = self.core.rect;
+        let overlay = self.overlays[index].clone();
+
+        let c = match find_rect(self.w.as_widget(), overlay.anchor) {
+            Some(rect) => rect,
+            None => return,
         };
-        let place_out = |rp, rs, cp: i32, cs, ideal: u32| -> (i32, u32) {
-            let pos = cp.min(rp + rs as i32 - ideal as i32).max(rp);
-            let size = ideal.max(cs).min(rs);
-            (pos, size)
+        let widget = match self.w.find_mut(overlay.id) {
+            Some(w) => w,
+            None => return,
         };
-        let rect = if popup.direction.is_horizontal() {
-            let (x, w) = place_in(r.pos.0, r.size.0, c.pos.0, c.size.0, ideal.0, m.horiz);
-            let (y, h) = place_out(r.pos.1, r.size.1, c.pos.1, c.size.1, ideal.1);
-            Rect::new(Coord(x, y), Size(w, h))
-        } else {
-            let (x, w) = place_out(r.pos.0, r.size.0, c.pos.0, c.size.0, ideal.0);
-            let (y, h) = place_in(r.pos.1, r.size.1, c.pos.1, c.size.1, ideal.1, m.vert);
-            Rect::new(Coord(x, y), Size(w, h))
+        let mut cache = layout::SolveCache::find_constraints(widget, size_handle);
+        let ideal = cache.ideal(false);
+        let m = cache.margins();
+
+        let rect = match overlay.placement {
+            Placement::Over(horiz, vert) => {
+                let place_over = |rp, rs: u32, cp: i32, cs: u32, ideal, align| -> (i32, u32) {
+                    let size = match align {
+                        Align::Stretch => cs,
+                        _ => ideal,
+                    }
+                    .min(rs);
+                    let pos = match align {
+                        Align::Begin | Align::Stretch => cp,
+                        Align::Centre => cp + (cs as i32 - size as i32) / 2,
+                        Align::End => cp + cs as i32 - size as i32,
+                    };
+                    let pos = pos.min(rp + (rs - size) as i32).max(rp);
+                    (pos, size)
+                };
+                let (x, w) = place_over(r.pos.0, r.size.0, c.pos.0, c.size.0, ideal.0, horiz);
+                let (y, h) = place_over(r.pos.1, r.size.1, c.pos.1, c.size.1, ideal.1, vert);
+                Rect::new(Coord(x, y), Size(w, h))
+            }
+            Placement::Beside(direction) => place_beside(r, c, ideal, m, direction),
         };
 
         cache.apply_rect(widget, size_handle, rect, false);
     }
 }
+
+// Place a widget of the given ideal size and margins next to anchor rect c,
+// in direction dir (or, if not possible, in the opposite direction), within
+// window rect r.
+fn place_beside(r: Rect, c: Rect, ideal: Size, m: Margins, dir: Direction) -> Rect {
+    let is_reversed = dir.is_reversed();
+    let place_in = |rp, rs: u32, cp: i32, cs, ideal, m: (u16, u16)| -> (i32, u32) {
+        let before: i32 = cp - (rp + m.1 as i32);
+        let before = before.max(0) as u32;
+        let after = rs.saturating_sub(cs + before + m.0 as u32);
+        if after >= ideal {
+            if is_reversed && before >= ideal {
+                (cp - ideal as i32 - m.1 as i32, ideal)
+            } else {
+                (cp + cs as i32 + m.0 as i32, ideal)
+            }
+        } else if before >= ideal {
+            (cp - ideal as i32 - m.1 as i32, ideal)
+        } else if before > after {
+            (rp, before)
+        } else {
+            (cp + cs as i32 + m.0 as i32, after)
+        }
+    };
+    let place_out = |rp, rs, cp: i32, cs, ideal: u32| -> (i32, u32) {
+        let pos = cp.min(rp + rs as i32 - ideal as i32).max(rp);
+        let size = ideal.max(cs).min(rs);
+        (pos, size)
+    };
+    if dir.is_horizontal() {
+        let (x, w) = place_in(r.pos.0, r.size.0, c.pos.0, c.size.0, ideal.0, m.horiz);
+        let (y, h) = place_out(r.pos.1, r.size.1, c.pos.1, c.size.1, ideal.1);
+        Rect::new(Coord(x, y), Size(w, h))
+    } else {
+        let (x, w) = place_out(r.pos.0, r.size.0, c.pos.0, c.size.0, ideal.0);
+        let (y, h) = place_in(r.pos.1, r.size.1, c.pos.1, c.size.1, ideal.1, m.vert);
+        Rect::new(Coord(x, y), Size(w, h))
+    }
+}